@@ -0,0 +1,106 @@
+//! Compares sequential and parallel decoding of a block range's worth of
+//! logs and calls.
+//!
+//! Run with `cargo bench --features parallel`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ethereum_abi::{standards::ERC20, AbiRegistry, Log, Value};
+use ethereum_types::{H160, H256, U256};
+
+const BATCH_SIZES: [usize; 2] = [1_000, 50_000];
+
+fn transfer_logs(count: usize) -> Vec<Log> {
+    let transfer = ERC20
+        .events
+        .iter()
+        .find(|e| e.name() == "Transfer")
+        .unwrap();
+
+    (0..count)
+        .map(|i| Log {
+            address: H160::repeat_byte(9),
+            topics: vec![
+                transfer.topic(),
+                H256::from(H160::from_low_u64_be(i as u64)),
+                H256::from(H160::from_low_u64_be(i as u64 + 1)),
+            ],
+            data: Value::encode(&[Value::Uint(U256::from(i), 256)]),
+            block_number: Some(i as u64 / 100),
+            block_hash: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            removed: false,
+        })
+        .collect()
+}
+
+fn transfer_calls(count: usize) -> Vec<(H160, Vec<u8>)> {
+    (0..count)
+        .map(|i| {
+            let input = ERC20
+                .encode_call(
+                    "transferFrom",
+                    &[
+                        Value::Address(H160::from_low_u64_be(i as u64)),
+                        Value::Address(H160::from_low_u64_be(i as u64 + 1)),
+                        Value::Uint(U256::from(i), 256),
+                    ],
+                )
+                .unwrap();
+
+            (H160::repeat_byte(9), input)
+        })
+        .collect()
+}
+
+fn decode_logs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_logs");
+
+    for size in BATCH_SIZES {
+        let logs = transfer_logs(size);
+        group.throughput(Throughput::Elements(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("sequential", size), &logs, |b, logs| {
+            b.iter(|| {
+                logs.iter()
+                    .map(|log| ERC20.decode_log(log))
+                    .collect::<Vec<_>>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", size), &logs, |b, logs| {
+            b.iter(|| ERC20.decode_logs_par(logs))
+        });
+    }
+
+    group.finish();
+}
+
+fn decode_calls(c: &mut Criterion) {
+    let mut registry = AbiRegistry::new();
+    registry.register(H160::repeat_byte(9), ERC20.clone());
+
+    let mut group = c.benchmark_group("decode_calls");
+
+    for size in BATCH_SIZES {
+        let calls = transfer_calls(size);
+        group.throughput(Throughput::Elements(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("sequential", size), &calls, |b, calls| {
+            b.iter(|| {
+                calls
+                    .iter()
+                    .map(|(to, input)| registry.decode_call(to, input))
+                    .collect::<Vec<_>>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", size), &calls, |b, calls| {
+            b.iter(|| registry.decode_calls_par(calls))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, decode_logs, decode_calls);
+criterion_main!(benches);
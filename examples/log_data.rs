@@ -28,5 +28,5 @@ fn main() {
         .decode_log_from_slice(&topics, data)
         .expect("failed decoding log");
 
-    println!("event: {}\ndata: {:?}", evt.name, decoded_data);
+    println!("event: {}\ndata: {:?}", evt.name(), decoded_data);
 }
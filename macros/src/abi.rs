@@ -0,0 +1,296 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Error, LitStr};
+
+use crate::{keccak256, types::canonical_param};
+
+/// An ABI item that gets a constant.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Item {
+    pub(crate) kind: ItemKind,
+    pub(crate) name: String,
+    pub(crate) signature: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ItemKind {
+    Function,
+    Event,
+    Error,
+}
+
+pub(crate) fn expand(path: &LitStr) -> Result<TokenStream, Error> {
+    let error = |msg: String| Error::new(path.span(), msg);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| error("CARGO_MANIFEST_DIR is not set".to_string()))?;
+    let full_path = PathBuf::from(manifest_dir).join(path.value());
+
+    let json = std::fs::read_to_string(&full_path)
+        .map_err(|err| error(format!("failed to read {}: {}", full_path.display(), err)))?;
+
+    let items = parse_items(&json)
+        .map_err(|err| error(format!("invalid ABI {}: {}", full_path.display(), err)))?;
+
+    let full_path = full_path.to_string_lossy();
+    let constants = constants(&items);
+
+    Ok(quote! {
+        /// The embedded ABI, parsed on first use.
+        pub static ABI: ::std::sync::LazyLock<::ethereum_abi::Abi> =
+            ::std::sync::LazyLock::new(|| {
+                ::ethereum_abi::__private::serde_json::from_str(include_str!(#full_path))
+                    .expect("embedded ABI was validated at compile time")
+            });
+
+        #(#constants)*
+    })
+}
+
+/// Validates a JSON ABI and collects its functions, events and errors.
+pub(crate) fn parse_items(json: &str) -> Result<Vec<Item>, String> {
+    let entries: serde_json::Value = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    let entries = entries
+        .as_array()
+        .ok_or_else(|| "expected an array of ABI entries".to_string())?;
+
+    let mut items = vec![];
+
+    for entry in entries {
+        let ty = match entry.get("type") {
+            // Old ABIs may omit the type of functions.
+            None => "function",
+            Some(ty) => ty
+                .as_str()
+                .ok_or_else(|| format!("invalid entry type: {}", ty))?,
+        };
+
+        let kind = match ty {
+            "function" => ItemKind::Function,
+            "event" => ItemKind::Event,
+            "error" => ItemKind::Error,
+            "constructor" | "fallback" | "receive" => {
+                params(entry, "inputs")?;
+                continue;
+            }
+            _ => return Err(format!("unknown entry type: {}", ty)),
+        };
+
+        let name = entry
+            .get("name")
+            .and_then(|name| name.as_str())
+            .ok_or_else(|| format!("{} without a name: {}", ty, entry))?;
+
+        let signature = format!("{}({})", name, params(entry, "inputs")?.join(","));
+
+        if kind == ItemKind::Function {
+            params(entry, "outputs")?;
+        }
+
+        let anonymous = entry
+            .get("anonymous")
+            .and_then(|anonymous| anonymous.as_bool())
+            .unwrap_or(false);
+
+        if kind == ItemKind::Event && anonymous {
+            continue;
+        }
+
+        items.push(Item {
+            kind,
+            name: name.to_string(),
+            signature,
+        });
+    }
+
+    Ok(items)
+}
+
+// Canonical types of an entry's parameter list, which may be missing.
+fn params(entry: &serde_json::Value, field: &str) -> Result<Vec<String>, String> {
+    match entry.get(field) {
+        None => Ok(vec![]),
+        Some(params) => params
+            .as_array()
+            .ok_or_else(|| format!("invalid {}: {}", field, params))?
+            .iter()
+            .map(canonical_param)
+            .collect(),
+    }
+}
+
+/// Returns the upper snake case name of every item, suffixed with the
+/// item's selector when its name is overloaded.
+pub(crate) fn item_names(items: &[Item]) -> Vec<String> {
+    let mut counts = HashMap::new();
+    for item in items {
+        *counts.entry((item.kind, &item.name)).or_insert(0) += 1;
+    }
+
+    items
+        .iter()
+        .map(|item| {
+            let mut name = upper_snake_case(&item.name);
+
+            if counts[&(item.kind, &item.name)] > 1 {
+                let hash = keccak256(item.signature.as_bytes());
+                name.push_str(&format!("_{}", hex_upper(&hash[..4])));
+            }
+
+            name
+        })
+        .collect()
+}
+
+/// Selector and topic constants of the given items.
+pub(crate) fn constants(items: &[Item]) -> Vec<TokenStream> {
+    items
+        .iter()
+        .zip(item_names(items))
+        .map(|(item, name)| {
+            let hash = keccak256(item.signature.as_bytes());
+
+            match item.kind {
+                ItemKind::Function | ItemKind::Error => {
+                    let suffix = match item.kind {
+                        ItemKind::Error => "ERROR_SELECTOR",
+                        _ => "SELECTOR",
+                    };
+                    let ident = format_ident!("{}_{}", name, suffix, span = Span::call_site());
+                    let doc = format!("Selector of `{}`.", item.signature);
+                    let selector = &hash[..4];
+
+                    quote! {
+                        #[doc = #doc]
+                        pub const #ident: [u8; 4] = [#(#selector),*];
+                    }
+                }
+                ItemKind::Event => {
+                    let ident = format_ident!("{}_TOPIC", name, span = Span::call_site());
+                    let doc = format!("Topic of `{}`.", item.signature);
+
+                    quote! {
+                        #[doc = #doc]
+                        pub const #ident: ::ethereum_abi::__private::ethereum_types::H256 =
+                            ::ethereum_abi::__private::ethereum_types::H256([#(#hash),*]);
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+/// Converts a Solidity identifier such as `balanceOf` or `ERC20Transfer` to
+/// upper snake case.
+pub(crate) fn upper_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|c| c.is_ascii_lowercase());
+
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                out.push('_');
+            }
+        }
+
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_uppercase());
+        } else {
+            out.push('_');
+        }
+    }
+
+    out
+}
+
+fn hex_upper(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn parse_abi_items() {
+        let items = parse_items(
+            r#"[
+                {"type": "constructor", "inputs": [{"name": "supply", "type": "uint256"}]},
+                {"name": "transfer", "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}], "outputs": [{"name": "", "type": "bool"}]},
+                {"type": "event", "name": "Transfer", "inputs": [{"name": "from", "type": "address", "indexed": true}]},
+                {"type": "event", "name": "Hidden", "inputs": [], "anonymous": true},
+                {"type": "error", "name": "Unauthorized", "inputs": [{"name": "who", "type": "tuple", "components": [{"name": "a", "type": "address"}]}]}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            items,
+            vec![
+                Item {
+                    kind: ItemKind::Function,
+                    name: "transfer".to_string(),
+                    signature: "transfer(address,uint256)".to_string(),
+                },
+                Item {
+                    kind: ItemKind::Event,
+                    name: "Transfer".to_string(),
+                    signature: "Transfer(address)".to_string(),
+                },
+                Item {
+                    kind: ItemKind::Error,
+                    name: "Unauthorized".to_string(),
+                    signature: "Unauthorized((address))".to_string(),
+                },
+            ]
+        );
+
+        assert!(parse_items("{}").is_err());
+        assert!(parse_items("[{").is_err());
+        assert!(parse_items(r#"[{"type": "event", "inputs": []}]"#).is_err());
+        assert!(parse_items(r#"[{"type": "method", "name": "f"}]"#).is_err());
+        assert!(parse_items(
+            r#"[{"type": "function", "name": "f", "inputs": [], "outputs": [{"type": "uint7"}]}]"#
+        )
+        .is_err());
+        assert!(parse_items(r#"[{"type": "constructor", "inputs": [{"type": "int"}]}]"#).is_err());
+    }
+
+    #[test]
+    fn upper_snake_case_names() {
+        assert_eq!(upper_snake_case("balanceOf"), "BALANCE_OF");
+        assert_eq!(upper_snake_case("ERC20Transfer"), "ERC20_TRANSFER");
+        assert_eq!(upper_snake_case("DOMAIN_SEPARATOR"), "DOMAIN_SEPARATOR");
+        assert_eq!(upper_snake_case("_mint"), "_MINT");
+        assert_eq!(upper_snake_case("get$"), "GET_");
+    }
+
+    #[test]
+    fn overloaded_constants() {
+        let items = parse_items(
+            r#"[
+                {"type": "function", "name": "safeTransferFrom", "inputs": [{"type": "address"}, {"type": "address"}, {"type": "uint256"}]},
+                {"type": "function", "name": "safeTransferFrom", "inputs": [{"type": "address"}, {"type": "address"}, {"type": "uint256"}, {"type": "bytes"}]}
+            ]"#,
+        )
+        .unwrap();
+
+        let constants = constants(&items)
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+
+        assert!(constants[0].contains("SAFE_TRANSFER_FROM_42842E0E_SELECTOR"));
+        assert!(constants[1].contains("SAFE_TRANSFER_FROM_B88D4FDE_SELECTOR"));
+    }
+}
@@ -0,0 +1,147 @@
+//! Procedural macros for the `ethereum_abi` crate.
+//!
+//! These are re-exported by `ethereum_abi` behind its `macros` feature and
+//! should be used from there.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, LitStr};
+
+mod abi;
+mod signature;
+mod sol;
+mod types;
+
+/// Embeds a JSON ABI file at compile time.
+///
+/// The path is relative to the crate root (`CARGO_MANIFEST_DIR`). The file is
+/// checked while compiling, so malformed ABIs fail the build. The macro
+/// expands to:
+///
+/// - `ABI`, a lazily parsed `static` [`Abi`](../ethereum_abi/struct.Abi.html);
+/// - a `<NAME>_SELECTOR: [u8; 4]` constant for every function;
+/// - a `<NAME>_TOPIC: H256` constant for every non-anonymous event;
+/// - a `<NAME>_ERROR_SELECTOR: [u8; 4]` constant for every custom error.
+///
+/// Names are converted to upper snake case. Overloaded names are suffixed
+/// with their selector, e.g. `SAFE_TRANSFER_FROM_B88D4FDE_SELECTOR`.
+///
+/// Invoke it inside a module to keep the generated items apart:
+///
+/// ```ignore
+/// mod erc20 {
+///     ethereum_abi::abi!("abis/erc20.json");
+/// }
+///
+/// assert_eq!(erc20::TRANSFER_SELECTOR, [0xa9, 0x05, 0x9c, 0xbb]);
+/// assert_eq!(erc20::ABI.functions.len(), 6);
+/// ```
+#[proc_macro]
+pub fn abi(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+
+    abi::expand(&path)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Declares an ABI inline with Solidity-like syntax.
+///
+/// Accepts `constructor`, `function`, `event`, `error`, `receive` and
+/// `fallback` declarations, each terminated by `;`. Parameter names,
+/// visibility and data locations are optional. Types and declarations are
+/// checked while compiling. The macro expands to:
+///
+/// - `ABI`, a lazily built `static` [`Abi`](../ethereum_abi/struct.Abi.html);
+/// - the selector and topic constants of [`abi!`];
+/// - an `encode_<name>` function per function, taking typed arguments and
+///   returning its calldata;
+/// - a `decode_<name>_output` function per function with outputs, returning
+///   the typed output, or a tuple of outputs.
+///
+/// Helper names are converted to snake case, and suffixed with the selector
+/// when overloaded. Arguments and outputs map to Rust types as follows,
+/// other types being passed and returned as `Value`s:
+///
+/// | Solidity   | Argument     | Output       |
+/// |------------|--------------|--------------|
+/// | `address`  | `H160`       | `H160`       |
+/// | `bool`     | `bool`       | `bool`       |
+/// | `uint<M>`  | `U256`       | `U256`       |
+/// | `int<M>`   | `I256`       | `I256`       |
+/// | `string`   | `&str`       | `String`     |
+/// | `bytes`    | `&[u8]`      | `Vec<u8>`    |
+/// | `bytes<M>` | `[u8; M]`    | `Vec<u8>`    |
+/// | `T[]`      | `Vec<T>`     | `Value`      |
+///
+/// ```ignore
+/// mod erc20 {
+///     ethereum_abi::sol_abi! {
+///         function balanceOf(address owner) external view returns (uint256);
+///         function transfer(address to, uint256 amount) external returns (bool);
+///         event Transfer(address indexed from, address indexed to, uint256 value);
+///     }
+/// }
+///
+/// let calldata = erc20::encode_transfer(to, amount)?;
+/// assert_eq!(calldata[..4], erc20::TRANSFER_SELECTOR);
+/// ```
+#[proc_macro]
+pub fn sol_abi(input: TokenStream) -> TokenStream {
+    let abi = parse_macro_input!(input as sol::SolAbi);
+
+    sol::expand(&abi)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Computes a function or error selector at compile time.
+///
+/// Expands to a `[u8; 4]` array literal, so it can be used in constants and
+/// match patterns alike. The signature is checked and canonicalized first:
+/// whitespace is ignored and types such as `fixed` are expanded.
+///
+/// ```ignore
+/// match calldata[..4] {
+///     ethereum_abi::selector!("transfer(address,uint256)") => { /* ... */ }
+///     ethereum_abi::selector!("approve(address, uint256)") => { /* ... */ }
+///     _ => { /* ... */ }
+/// }
+/// ```
+#[proc_macro]
+pub fn selector(input: TokenStream) -> TokenStream {
+    let signature = parse_macro_input!(input as LitStr);
+
+    signature::expand_selector(&signature)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Computes an event topic at compile time.
+///
+/// Expands to an `H256` literal, usable in constants and match patterns. The
+/// signature is checked like [`selector!`]'s.
+///
+/// ```ignore
+/// const TRANSFER: H256 = ethereum_abi::topic!("Transfer(address,address,uint256)");
+/// ```
+#[proc_macro]
+pub fn topic(input: TokenStream) -> TokenStream {
+    let signature = parse_macro_input!(input as LitStr);
+
+    signature::expand_topic(&signature)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Keccak-256 hash of the given bytes.
+pub(crate) fn keccak256(data: &[u8]) -> [u8; 32] {
+    use tiny_keccak::{Hasher, Keccak};
+
+    let mut keccak = Keccak::v256();
+    let mut hash = [0u8; 32];
+
+    keccak.update(data);
+    keccak.finalize(&mut hash);
+
+    hash
+}
@@ -0,0 +1,85 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, LitStr};
+
+use crate::{keccak256, types::canonical_type};
+
+pub(crate) fn expand_selector(signature: &LitStr) -> Result<TokenStream, Error> {
+    let hash = hash(signature)?;
+    let selector = &hash[..4];
+
+    Ok(quote! { [#(#selector),*] })
+}
+
+pub(crate) fn expand_topic(signature: &LitStr) -> Result<TokenStream, Error> {
+    let hash = hash(signature)?;
+
+    Ok(quote! { ::ethereum_abi::__private::ethereum_types::H256([#(#hash),*]) })
+}
+
+fn hash(signature: &LitStr) -> Result<[u8; 32], Error> {
+    let canonical =
+        canonical_signature(&signature.value()).map_err(|err| Error::new(signature.span(), err))?;
+
+    Ok(keccak256(canonical.as_bytes()))
+}
+
+/// Returns the canonical form of a signature such as
+/// `transfer(address, uint256)`, ignoring whitespace.
+pub(crate) fn canonical_signature(signature: &str) -> Result<String, String> {
+    let signature: String = signature.chars().filter(|c| !c.is_whitespace()).collect();
+    let invalid = || format!("invalid signature: {}", signature);
+
+    let open = signature.find('(').ok_or_else(invalid)?;
+    let (name, params) = signature.split_at(open);
+
+    let valid_name = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    // The parameter list must be a plain tuple, without array suffixes.
+    if !valid_name || !params.ends_with(')') {
+        return Err(invalid());
+    }
+
+    let params = canonical_type(params).map_err(|_| invalid())?;
+
+    Ok(format!("{}{}", name, params))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn canonical_signatures() {
+        assert_eq!(
+            canonical_signature("transfer(address,uint256)").unwrap(),
+            "transfer(address,uint256)"
+        );
+        assert_eq!(
+            canonical_signature(" swap( (address, fixed)[] , bytes32 ) ").unwrap(),
+            "swap((address,fixed128x18)[],bytes32)"
+        );
+        assert_eq!(canonical_signature("f()").unwrap(), "f()");
+
+        for signature in &[
+            "",
+            "transfer",
+            "(uint256)",
+            "1f(uint256)",
+            "f(uint)",
+            "f(uint256)[]",
+            "f(uint256",
+            "f-g(uint256)",
+        ] {
+            assert!(canonical_signature(signature).is_err(), "{}", signature);
+        }
+    }
+}
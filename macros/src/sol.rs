@@ -0,0 +1,872 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    bracketed,
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token, Error, Ident, LitInt, Token,
+};
+
+use crate::{
+    abi::{constants, item_names, Item, ItemKind},
+    types::canonical_type,
+};
+
+/// Inline Solidity-ish ABI declarations.
+pub(crate) struct SolAbi {
+    declarations: Vec<Declaration>,
+}
+
+enum Declaration {
+    Constructor {
+        inputs: Vec<SolParam>,
+        payable: bool,
+    },
+    Function {
+        name: Ident,
+        inputs: Vec<SolParam>,
+        outputs: Vec<SolParam>,
+        mutability: Mutability,
+    },
+    Event {
+        name: Ident,
+        inputs: Vec<SolParam>,
+        anonymous: bool,
+    },
+    Error {
+        name: Ident,
+        inputs: Vec<SolParam>,
+    },
+    Receive,
+    Fallback {
+        payable: bool,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mutability {
+    Pure,
+    View,
+    NonPayable,
+    Payable,
+}
+
+struct SolParam {
+    ty: SolType,
+    name: Option<Ident>,
+    indexed: Option<Ident>,
+}
+
+#[derive(Debug, PartialEq)]
+enum SolType {
+    /// Canonical elementary type, e.g. `uint256`.
+    Elementary(String),
+    Array(Box<SolType>, Option<usize>),
+    Tuple(Vec<SolType>),
+}
+
+impl std::fmt::Display for SolType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolType::Elementary(ty) => write!(f, "{}", ty),
+            SolType::Array(ty, None) => write!(f, "{}[]", ty),
+            SolType::Array(ty, Some(size)) => write!(f, "{}[{}]", ty, size),
+            SolType::Tuple(tys) => write!(
+                f,
+                "({})",
+                tys.iter()
+                    .map(|ty| ty.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+impl Parse for SolAbi {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut declarations = vec![];
+
+        while !input.is_empty() {
+            declarations.push(input.parse()?);
+        }
+
+        Ok(Self { declarations })
+    }
+}
+
+impl Parse for Declaration {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let keyword = input.call(Ident::parse_any)?;
+
+        let declaration =
+            match keyword.to_string().as_str() {
+                "constructor" => {
+                    let inputs = parse_params(input)?;
+                    let mutability = parse_modifiers(input, &mut None)?;
+
+                    Declaration::Constructor {
+                        inputs,
+                        payable: mutability == Mutability::Payable,
+                    }
+                }
+                "function" => {
+                    let name = input.call(Ident::parse_any)?;
+                    let inputs = parse_params(input)?;
+                    let mut outputs = Some(vec![]);
+                    let mutability = parse_modifiers(input, &mut outputs)?;
+
+                    Declaration::Function {
+                        name,
+                        inputs,
+                        outputs: outputs.unwrap_or_default(),
+                        mutability,
+                    }
+                }
+                "event" => {
+                    let name = input.call(Ident::parse_any)?;
+                    let inputs = parse_params(input)?;
+                    let anonymous = parse_flag(input, "anonymous")?;
+
+                    Declaration::Event {
+                        name,
+                        inputs,
+                        anonymous,
+                    }
+                }
+                "error" => Declaration::Error {
+                    name: input.call(Ident::parse_any)?,
+                    inputs: parse_params(input)?,
+                },
+                "receive" | "fallback" => {
+                    let params = parse_params(input)?;
+                    if let Some(param) = params.first() {
+                        return Err(Error::new(
+                            param.name.as_ref().map_or(keyword.span(), Ident::span),
+                            format!("{} takes no parameters", keyword),
+                        ));
+                    }
+
+                    let mutability = parse_modifiers(input, &mut None)?;
+
+                    if keyword == "receive" {
+                        Declaration::Receive
+                    } else {
+                        Declaration::Fallback {
+                            payable: mutability == Mutability::Payable,
+                        }
+                    }
+                }
+                _ => return Err(Error::new(
+                    keyword.span(),
+                    "expected `constructor`, `function`, `event`, `error`, `receive` or `fallback`",
+                )),
+            };
+
+        input.parse::<Token![;]>()?;
+
+        Ok(declaration)
+    }
+}
+
+// Parses visibility and mutability keywords up to the closing `;`, and the
+// `returns (...)` clause if `outputs` can take one.
+fn parse_modifiers(
+    input: ParseStream,
+    outputs: &mut Option<Vec<SolParam>>,
+) -> syn::Result<Mutability> {
+    let mut mutability = Mutability::NonPayable;
+
+    while !input.peek(Token![;]) && !input.is_empty() {
+        let modifier = input.call(Ident::parse_any)?;
+
+        match modifier.to_string().as_str() {
+            "returns" if outputs.is_some() => *outputs = Some(parse_params(input)?),
+            "pure" => mutability = Mutability::Pure,
+            "view" => mutability = Mutability::View,
+            "payable" => mutability = Mutability::Payable,
+            "nonpayable" => mutability = Mutability::NonPayable,
+            "external" | "public" | "virtual" | "override" => {}
+            _ => return Err(Error::new(modifier.span(), "unexpected modifier")),
+        }
+    }
+
+    Ok(mutability)
+}
+
+fn parse_flag(input: ParseStream, flag: &str) -> syn::Result<bool> {
+    if input.peek(Token![;]) {
+        return Ok(false);
+    }
+
+    let ident = input.call(Ident::parse_any)?;
+    if ident != flag {
+        return Err(Error::new(
+            ident.span(),
+            format!("expected `{}` or `;`", flag),
+        ));
+    }
+
+    Ok(true)
+}
+
+fn parse_params(input: ParseStream) -> syn::Result<Vec<SolParam>> {
+    let content;
+    parenthesized!(content in input);
+
+    Ok(
+        Punctuated::<SolParam, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect(),
+    )
+}
+
+impl Parse for SolParam {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse()?;
+
+        let mut name = None;
+        let mut indexed = None;
+
+        while name.is_none() && input.peek(Ident::peek_any) {
+            let ident = input.call(Ident::parse_any)?;
+
+            match ident.to_string().as_str() {
+                "indexed" => indexed = Some(ident),
+                "memory" | "calldata" | "storage" | "payable" => {}
+                _ => name = Some(ident),
+            }
+        }
+
+        Ok(Self { ty, name, indexed })
+    }
+}
+
+impl Parse for SolType {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident::peek_any) && !input.peek2(token::Paren) {
+            let ident = input.call(Ident::parse_any)?;
+            let ty =
+                canonical_type(&ident.to_string()).map_err(|err| Error::new(ident.span(), err))?;
+
+            return parse_array_suffix(input, SolType::Elementary(ty));
+        }
+
+        // Tuples, optionally spelled `tuple(...)`.
+        if input.peek(Ident::peek_any) {
+            let ident = input.call(Ident::parse_any)?;
+            if ident != "tuple" {
+                return Err(Error::new(ident.span(), "expected a type"));
+            }
+        }
+
+        let tys = parse_params(input)?
+            .into_iter()
+            .map(|param| param.ty)
+            .collect();
+
+        parse_array_suffix(input, SolType::Tuple(tys))
+    }
+}
+
+fn parse_array_suffix(input: ParseStream, mut ty: SolType) -> syn::Result<SolType> {
+    while input.peek(token::Bracket) {
+        let content;
+        bracketed!(content in input);
+
+        let size = if content.is_empty() {
+            None
+        } else {
+            Some(content.parse::<LitInt>()?.base10_parse()?)
+        };
+
+        ty = SolType::Array(Box::new(ty), size);
+    }
+
+    Ok(ty)
+}
+
+impl SolAbi {
+    /// Checks the declarations the way `AbiBuilder::build` would, so that
+    /// mistakes are reported at compile time.
+    fn validate(&self) -> syn::Result<()> {
+        let mut seen = HashSet::new();
+        let mut singletons = HashSet::new();
+
+        for declaration in &self.declarations {
+            let (kind, name, inputs) = match declaration {
+                Declaration::Function { name, inputs, .. } => ("function", name, inputs),
+                Declaration::Event { name, inputs, .. } => ("event", name, inputs),
+                Declaration::Error { name, inputs } => ("error", name, inputs),
+                Declaration::Constructor { inputs, .. } => {
+                    check_not_indexed(inputs)?;
+
+                    if !singletons.insert("constructor") {
+                        return Err(Error::new(
+                            Span::call_site(),
+                            "constructor defined more than once",
+                        ));
+                    }
+
+                    continue;
+                }
+                Declaration::Receive | Declaration::Fallback { .. } => continue,
+            };
+
+            let signature = signature(name, inputs);
+
+            if !seen.insert((kind, signature.clone())) {
+                return Err(Error::new(
+                    name.span(),
+                    format!("{} {} defined more than once", kind, signature),
+                ));
+            }
+
+            if let Declaration::Event {
+                inputs, anonymous, ..
+            } = declaration
+            {
+                let indexed = inputs.iter().filter(|p| p.indexed.is_some()).count();
+                let max_indexed = if *anonymous { 4 } else { 3 };
+
+                if indexed > max_indexed {
+                    return Err(Error::new(
+                        name.span(),
+                        format!(
+                            "event {} has {} indexed inputs, at most {} allowed",
+                            signature, indexed, max_indexed
+                        ),
+                    ));
+                }
+            } else {
+                check_not_indexed(inputs)?;
+            }
+
+            if let Declaration::Function { outputs, .. } = declaration {
+                check_not_indexed(outputs)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn items(&self) -> Vec<Item> {
+        self.declarations
+            .iter()
+            .filter_map(|declaration| {
+                let (kind, name, inputs) = match declaration {
+                    Declaration::Function { name, inputs, .. } => {
+                        (ItemKind::Function, name, inputs)
+                    }
+                    Declaration::Event {
+                        name,
+                        inputs,
+                        anonymous: false,
+                    } => (ItemKind::Event, name, inputs),
+                    Declaration::Error { name, inputs } => (ItemKind::Error, name, inputs),
+                    _ => return None,
+                };
+
+                Some(Item {
+                    kind,
+                    name: name.to_string(),
+                    signature: signature(name, inputs),
+                })
+            })
+            .collect()
+    }
+}
+
+fn check_not_indexed(params: &[SolParam]) -> syn::Result<()> {
+    match params.iter().find_map(|param| param.indexed.as_ref()) {
+        Some(indexed) => Err(Error::new(
+            indexed.span(),
+            "only event inputs can be indexed",
+        )),
+        None => Ok(()),
+    }
+}
+
+fn signature(name: &Ident, params: &[SolParam]) -> String {
+    format!(
+        "{}({})",
+        name,
+        params
+            .iter()
+            .map(|param| param.ty.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+pub(crate) fn expand(abi: &SolAbi) -> syn::Result<TokenStream> {
+    abi.validate()?;
+
+    let builder_calls = abi.declarations.iter().map(builder_call);
+
+    let items = abi.items();
+    let constants = constants(&items);
+
+    let functions = abi
+        .declarations
+        .iter()
+        .filter_map(|declaration| match declaration {
+            Declaration::Function {
+                inputs, outputs, ..
+            } => Some((inputs, outputs)),
+            _ => None,
+        });
+
+    let helpers = items
+        .iter()
+        .zip(item_names(&items))
+        .filter(|(item, _)| item.kind == ItemKind::Function)
+        .zip(functions)
+        .enumerate()
+        .map(|(index, ((item, name), (inputs, outputs)))| {
+            function_helpers(index, item, &name.to_lowercase(), inputs, outputs)
+        });
+
+    Ok(quote! {
+        /// The declared ABI, built on first use.
+        pub static ABI: ::std::sync::LazyLock<::ethereum_abi::Abi> =
+            ::std::sync::LazyLock::new(|| {
+                ::ethereum_abi::Abi::builder()
+                    #(#builder_calls)*
+                    .build()
+                    .expect("inline ABI was validated at compile time")
+            });
+
+        #(#constants)*
+
+        #(#helpers)*
+    })
+}
+
+fn builder_call(declaration: &Declaration) -> TokenStream {
+    let params = |params: &[SolParam]| {
+        let params = params.iter().map(|param| {
+            let name = param_name(param);
+            let ty = param.ty.to_string();
+
+            quote! { (#name, #ty) }
+        });
+
+        quote! { &[#(#params),*] }
+    };
+
+    match declaration {
+        Declaration::Constructor { inputs, payable } => {
+            let inputs = params(inputs);
+            let mutability = state_mutability(if *payable {
+                Mutability::Payable
+            } else {
+                Mutability::NonPayable
+            });
+
+            quote! { .constructor(#inputs, #mutability) }
+        }
+        Declaration::Function {
+            name,
+            inputs,
+            outputs,
+            mutability,
+        } => {
+            let name = name.to_string();
+            let inputs = params(inputs);
+            let outputs = params(outputs);
+            let mutability = state_mutability(*mutability);
+
+            quote! { .function(#name, #inputs, #outputs, #mutability) }
+        }
+        Declaration::Event {
+            name,
+            inputs,
+            anonymous,
+        } => {
+            let name = name.to_string();
+            let inputs = inputs.iter().map(|param| {
+                let param_name = param_name(param);
+                let ty = param.ty.to_string();
+                let indexed = param.indexed.is_some();
+
+                quote! { (#param_name, #ty, #indexed) }
+            });
+
+            if *anonymous {
+                quote! { .anonymous_event(#name, &[#(#inputs),*]) }
+            } else {
+                quote! { .event(#name, &[#(#inputs),*]) }
+            }
+        }
+        Declaration::Error { name, inputs } => {
+            let name = name.to_string();
+            let inputs = params(inputs);
+
+            quote! { .error(#name, #inputs) }
+        }
+        Declaration::Receive => quote! { .receive() },
+        Declaration::Fallback { payable } => {
+            let mutability = state_mutability(if *payable {
+                Mutability::Payable
+            } else {
+                Mutability::NonPayable
+            });
+
+            quote! { .fallback(#mutability) }
+        }
+    }
+}
+
+fn param_name(param: &SolParam) -> String {
+    param
+        .name
+        .as_ref()
+        .map(|name| name.unraw().to_string())
+        .unwrap_or_default()
+}
+
+fn state_mutability(mutability: Mutability) -> TokenStream {
+    match mutability {
+        Mutability::Pure => quote! { ::ethereum_abi::StateMutability::Pure },
+        Mutability::View => quote! { ::ethereum_abi::StateMutability::View },
+        Mutability::NonPayable => quote! { ::ethereum_abi::StateMutability::NonPayable },
+        Mutability::Payable => quote! { ::ethereum_abi::StateMutability::Payable },
+    }
+}
+
+// `encode_<name>` and, for functions with outputs, `decode_<name>_output`.
+fn function_helpers(
+    index: usize,
+    item: &Item,
+    name: &str,
+    inputs: &[SolParam],
+    outputs: &[SolParam],
+) -> TokenStream {
+    let encode = format_ident!("encode_{}", name);
+    let encode_doc = format!("Encodes a call to `{}`.", item.signature);
+
+    let arg_names = arg_idents(inputs);
+    let arg_types = inputs.iter().map(|param| rust_type(&param.ty));
+    let arg_values = inputs
+        .iter()
+        .zip(&arg_names)
+        .map(|(param, arg)| to_value(&param.ty, quote! { #arg }));
+
+    let encode = quote! {
+        #[doc = #encode_doc]
+        pub fn #encode(
+            #(#arg_names: #arg_types),*
+        ) -> ::ethereum_abi::__private::anyhow::Result<::std::vec::Vec<u8>> {
+            ABI.functions[#index].encode_input(&[#(#arg_values),*])
+        }
+    };
+
+    if outputs.is_empty() {
+        return encode;
+    }
+
+    let decode = format_ident!("decode_{}_output", name);
+    let decode_doc = format!("Decodes the data returned by `{}`.", item.signature);
+
+    let (output_type, output_value) = match outputs {
+        [output] => (
+            output_type(&output.ty),
+            from_value(&output.ty, quote! { params[0].clone() }),
+        ),
+        _ => {
+            let output_types = outputs.iter().map(|param| output_type(&param.ty));
+            let output_values = outputs.iter().enumerate().map(|(i, param)| {
+                let value = quote! { params[#i].clone() };
+
+                if is_typed(&param.ty) {
+                    let value = from_value(&param.ty, value);
+                    quote! { #value? }
+                } else {
+                    value
+                }
+            });
+
+            (
+                quote! { (#(#output_types),*) },
+                quote! { Ok((#(#output_values),*)) },
+            )
+        }
+    };
+
+    quote! {
+        #encode
+
+        #[doc = #decode_doc]
+        pub fn #decode(
+            output: &[u8],
+        ) -> ::ethereum_abi::__private::anyhow::Result<#output_type> {
+            let params = ABI.functions[#index].decode_output_from_slice(output)?;
+
+            #output_value
+        }
+    }
+}
+
+// Snake case argument names, falling back to `argN` for unnamed, duplicate
+// or reserved ones.
+fn arg_idents(params: &[SolParam]) -> Vec<Ident> {
+    let mut seen = HashSet::new();
+
+    params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let name = param
+                .name
+                .as_ref()
+                .map(|name| crate::abi::upper_snake_case(&name.unraw().to_string()).to_lowercase())
+                .filter(|name| syn::parse_str::<Ident>(name).is_ok())
+                .filter(|name| seen.insert(name.clone()))
+                .unwrap_or_else(|| format!("arg{}", i));
+
+            format_ident!("{}", name)
+        })
+        .collect()
+}
+
+/// The argument type of an input, see [`to_value`].
+fn rust_type(ty: &SolType) -> TokenStream {
+    match ty {
+        SolType::Elementary(ty) => match ty.as_str() {
+            "address" => quote! { ::ethereum_abi::__private::ethereum_types::H160 },
+            "bool" => quote! { bool },
+            "string" => quote! { &str },
+            "bytes" => quote! { &[u8] },
+            _ => match sized(ty) {
+                Some(("bytes", size)) => quote! { [u8; #size] },
+                Some(("uint", _)) => quote! { ::ethereum_abi::__private::ethereum_types::U256 },
+                Some(("int", _)) => quote! { ::ethereum_abi::I256 },
+                _ => quote! { ::ethereum_abi::Value },
+            },
+        },
+        SolType::Array(inner, None) if is_typed(inner) => {
+            let inner = rust_type(inner);
+
+            quote! { ::std::vec::Vec<#inner> }
+        }
+        _ => quote! { ::ethereum_abi::Value },
+    }
+}
+
+/// Converts an argument of the type given by [`rust_type`] to a `Value`.
+fn to_value(ty: &SolType, arg: TokenStream) -> TokenStream {
+    match ty {
+        SolType::Elementary(ty) => match ty.as_str() {
+            "address" => quote! { ::ethereum_abi::Value::Address(#arg) },
+            "bool" => quote! { ::ethereum_abi::Value::Bool(#arg) },
+            "string" => quote! { ::ethereum_abi::Value::String(#arg.to_string()) },
+            "bytes" => quote! { ::ethereum_abi::Value::Bytes(#arg.to_vec().into()) },
+            _ => match sized(ty) {
+                Some(("bytes", _)) => quote! { ::ethereum_abi::Value::FixedBytes(#arg.to_vec()) },
+                Some(("uint", size)) => quote! { ::ethereum_abi::Value::Uint(#arg, #size) },
+                Some(("int", size)) => {
+                    quote! { ::ethereum_abi::Value::Int(#arg.into_raw(), #size) }
+                }
+                _ => arg,
+            },
+        },
+        SolType::Array(inner, None) if is_typed(inner) => {
+            let element = to_value(inner, quote! { element });
+
+            // The array is retyped after the declared type when encoded.
+            quote! {
+                ::ethereum_abi::Value::from(
+                    ::std::iter::IntoIterator::into_iter(#arg)
+                        .map(|element| #element)
+                        .collect::<::std::vec::Vec<::ethereum_abi::Value>>()
+                )
+            }
+        }
+        _ => arg,
+    }
+}
+
+/// The type a decoded output converts to, see [`from_value`].
+fn output_type(ty: &SolType) -> TokenStream {
+    match ty {
+        SolType::Elementary(ty) => match ty.as_str() {
+            "address" => quote! { ::ethereum_abi::__private::ethereum_types::H160 },
+            "bool" => quote! { bool },
+            "string" => quote! { ::std::string::String },
+            "bytes" => quote! { ::std::vec::Vec<u8> },
+            _ => match sized(ty) {
+                Some(("bytes", _)) => quote! { ::std::vec::Vec<u8> },
+                Some(("uint", _)) => quote! { ::ethereum_abi::__private::ethereum_types::U256 },
+                Some(("int", _)) => quote! { ::ethereum_abi::I256 },
+                _ => quote! { ::ethereum_abi::Value },
+            },
+        },
+        _ => quote! { ::ethereum_abi::Value },
+    }
+}
+
+/// Converts a decoded `Value` to the type given by [`output_type`], as a
+/// `Result`.
+fn from_value(ty: &SolType, value: TokenStream) -> TokenStream {
+    if !is_typed(ty) {
+        return quote! { Ok(#value) };
+    }
+
+    let output_type = output_type(ty);
+
+    quote! {
+        <#output_type as ::std::convert::TryFrom<::ethereum_abi::Value>>::try_from(#value)
+    }
+}
+
+fn is_typed(ty: &SolType) -> bool {
+    match ty {
+        SolType::Elementary(ty) => is_typed_elementary(ty),
+        _ => false,
+    }
+}
+
+fn is_typed_elementary(ty: &str) -> bool {
+    matches!(ty, "address" | "bool" | "string" | "bytes")
+        || matches!(sized(ty), Some(("bytes" | "uint" | "int", _)))
+}
+
+// Splits `uint<M>`, `int<M>` and `bytes<M>` into their prefix and size.
+fn sized(ty: &str) -> Option<(&str, usize)> {
+    ["bytes", "uint", "int"].iter().find_map(|prefix| {
+        ty.strip_prefix(prefix)
+            .and_then(|size| size.parse().ok())
+            .map(|size| (*prefix, size))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn parse(tokens: TokenStream) -> syn::Result<SolAbi> {
+        syn::parse2(tokens)
+    }
+
+    #[test]
+    fn parse_declarations() {
+        let abi = parse(quote! {
+            constructor(uint256 supply) payable;
+            function transfer(address to, uint256 amount) external returns (bool);
+            function balanceOf(address) view returns (uint256 balance);
+            function swap(tuple(address, uint24)[2] memory legs, bytes32[] calldata data);
+            event Transfer(address indexed from, address indexed to, uint256 value);
+            event Hidden(uint) anonymous;
+            error Unauthorized(address who);
+            receive() external payable;
+        });
+
+        // `uint` is not accepted as an alias.
+        assert!(abi.is_err());
+
+        let abi = parse(quote! {
+            constructor(uint256 supply) payable;
+            function transfer(address to, uint256 amount) external returns (bool);
+            function balanceOf(address) view returns (uint256 balance);
+            function swap(tuple(address, uint24)[2] memory legs, bytes32[] calldata data);
+            event Transfer(address indexed from, address indexed to, uint256 value);
+            event Hidden(uint8) anonymous;
+            error Unauthorized(address who);
+            receive() external payable;
+        })
+        .unwrap();
+
+        abi.validate().unwrap();
+
+        assert_eq!(
+            abi.items()
+                .into_iter()
+                .map(|item| item.signature)
+                .collect::<Vec<_>>(),
+            vec![
+                "transfer(address,uint256)",
+                "balanceOf(address)",
+                "swap((address,uint24)[2],bytes32[])",
+                "Transfer(address,address,uint256)",
+                "Unauthorized(address)",
+            ]
+        );
+
+        match &abi.declarations[2] {
+            Declaration::Function {
+                outputs,
+                mutability,
+                ..
+            } => {
+                assert!(*mutability == Mutability::View);
+                assert_eq!(outputs[0].ty, SolType::Elementary("uint256".to_string()));
+                assert_eq!(outputs[0].name.as_ref().unwrap(), "balance");
+            }
+            _ => panic!("expected a function"),
+        }
+    }
+
+    #[test]
+    fn invalid_declarations() {
+        for tokens in [
+            quote! { function f(uint7); },
+            quote! { function f() returns (bool) },
+            quote! { func f(); },
+            quote! { function f() mutable; },
+            quote! { event E(uint256) returns (bool); },
+            quote! { receive(uint256); },
+            quote! { function f(uint256[x]); },
+        ] {
+            assert!(parse(tokens.clone()).is_err(), "{}", tokens);
+        }
+
+        for tokens in [
+            quote! { function f(uint256); function f(uint256 x); },
+            quote! { function f(uint256 indexed); },
+            quote! { event E(bool indexed, bool indexed, bool indexed, bool indexed); },
+            quote! { constructor(); constructor(); },
+        ] {
+            assert!(
+                parse(tokens.clone()).unwrap().validate().is_err(),
+                "{}",
+                tokens
+            );
+        }
+
+        parse(
+            quote! { event E(bool indexed, bool indexed, bool indexed, bool indexed) anonymous; },
+        )
+        .unwrap()
+        .validate()
+        .unwrap();
+    }
+
+    #[test]
+    fn helper_arg_names() {
+        let abi = parse(quote! {
+            function f(address _initialAmount, uint256, bool self, bool r#type, bytes output);
+        })
+        .unwrap();
+
+        let inputs = match &abi.declarations[0] {
+            Declaration::Function { inputs, .. } => inputs,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            arg_idents(inputs)
+                .iter()
+                .map(|ident| ident.to_string())
+                .collect::<Vec<_>>(),
+            vec!["_initial_amount", "arg1", "arg2", "arg3", "output"]
+        );
+        assert_eq!(param_name(&inputs[3]), "type");
+    }
+}
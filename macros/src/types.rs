@@ -0,0 +1,237 @@
+//! Type string validation, mirroring the runtime parser of `ethereum_abi`.
+
+/// Returns the canonical form of a JSON ABI parameter's type, expanding
+/// tuples from their components.
+pub(crate) fn canonical_param(param: &serde_json::Value) -> Result<String, String> {
+    let ty = param
+        .get("type")
+        .and_then(|ty| ty.as_str())
+        .ok_or_else(|| format!("parameter without a type: {}", param))?;
+
+    match ty.strip_prefix("tuple") {
+        Some(suffix) if !suffix.starts_with('(') => {
+            let components = param
+                .get("components")
+                .and_then(|cs| cs.as_array())
+                .ok_or_else(|| format!("tuple parameter without components: {}", param))?;
+
+            let components = components
+                .iter()
+                .map(canonical_param)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(format!(
+                "({}){}",
+                components.join(","),
+                array_suffix(suffix).ok_or_else(|| format!("invalid type: {}", ty))?
+            ))
+        }
+        _ => canonical_type(ty),
+    }
+}
+
+/// Returns the canonical form of a type string such as `uint256`,
+/// `fixed[2]` or `(address,bytes)[]`.
+pub(crate) fn canonical_type(ty: &str) -> Result<String, String> {
+    let invalid = || format!("invalid type: {}", ty);
+
+    let unprefixed = ty.strip_prefix("tuple").unwrap_or(ty);
+
+    let (base, suffix) = if unprefixed.starts_with('(') {
+        let end = matching_paren(unprefixed).ok_or_else(invalid)?;
+        let components = split_top_level(&unprefixed[1..end])
+            .into_iter()
+            .map(canonical_type)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        (
+            format!("({})", components.join(",")),
+            &unprefixed[end + 1..],
+        )
+    } else {
+        let split = ty.find('[').unwrap_or(ty.len());
+
+        (
+            elementary_type(&ty[..split]).ok_or_else(invalid)?,
+            &ty[split..],
+        )
+    };
+
+    Ok(format!(
+        "{}{}",
+        base,
+        array_suffix(suffix).ok_or_else(invalid)?
+    ))
+}
+
+fn elementary_type(ty: &str) -> Option<String> {
+    match ty {
+        "address" | "bool" | "string" | "bytes" | "function" => return Some(ty.to_string()),
+        "fixed" | "ufixed" => return Some(format!("{}128x18", ty)),
+        _ => {}
+    }
+
+    if let Some(size) = ty.strip_prefix("bytes") {
+        let size = parse_integer(size).filter(|size| (1..=32).contains(size))?;
+
+        return Some(format!("bytes{}", size));
+    }
+
+    for prefix in &["ufixed", "fixed"] {
+        if let Some(rest) = ty.strip_prefix(prefix) {
+            let (size, decimals) = rest.split_once('x')?;
+            let size = parse_integer(size).filter(check_int_size)?;
+            let decimals = parse_integer(decimals).filter(|decimals| *decimals <= 80)?;
+
+            return Some(format!("{}{}x{}", prefix, size, decimals));
+        }
+    }
+
+    for prefix in &["uint", "int"] {
+        if let Some(size) = ty.strip_prefix(prefix) {
+            let size = parse_integer(size).filter(check_int_size)?;
+
+            return Some(format!("{}{}", prefix, size));
+        }
+    }
+
+    None
+}
+
+// Checks a sequence of `[]` and `[N]` array suffixes.
+fn array_suffix(suffix: &str) -> Option<String> {
+    let mut canonical = String::new();
+    let mut rest = suffix;
+
+    while !rest.is_empty() {
+        let end = rest.find(']')?;
+        let size = rest.strip_prefix('[')?.get(..end - 1)?;
+
+        if size.is_empty() {
+            canonical.push_str("[]");
+        } else {
+            canonical.push_str(&format!("[{}]", parse_integer(size)?));
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    Some(canonical)
+}
+
+fn matching_paren(s: &str) -> Option<usize> {
+    let mut depth = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+// Splits a tuple's contents on the commas that are not nested in another
+// tuple.
+fn split_top_level(s: &str) -> Vec<&str> {
+    if s.is_empty() {
+        return vec![];
+    }
+
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(&s[start..]);
+
+    parts
+}
+
+fn parse_integer(s: &str) -> Option<usize> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    s.parse().ok()
+}
+
+fn check_int_size(size: &usize) -> bool {
+    let size = *size;
+
+    size > 0 && size <= 256 && size.is_multiple_of(8)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn canonical_types() {
+        assert_eq!(canonical_type("uint256").unwrap(), "uint256");
+        assert_eq!(canonical_type("bytes32[2][]").unwrap(), "bytes32[2][]");
+        assert_eq!(canonical_type("fixed").unwrap(), "fixed128x18");
+        assert_eq!(canonical_type("ufixed64x10[3]").unwrap(), "ufixed64x10[3]");
+        assert_eq!(
+            canonical_type("tuple(address,(uint8,fixed)[])[2]").unwrap(),
+            "(address,(uint8,fixed128x18)[])[2]"
+        );
+        assert_eq!(canonical_type("()").unwrap(), "()");
+
+        for ty in &[
+            "uint",
+            "uint7",
+            "int264",
+            "bytes0",
+            "bytes33",
+            "tuple",
+            "uint256 ",
+            "uint256[",
+            "uint256[x]",
+            "(uint256,string",
+            "fixed8x81",
+            "strings",
+        ] {
+            assert!(canonical_type(ty).is_err(), "{}", ty);
+        }
+    }
+
+    #[test]
+    fn canonical_params() {
+        let param = serde_json::json!({
+            "name": "order",
+            "type": "tuple[]",
+            "components": [
+                {"name": "maker", "type": "address"},
+                {"name": "legs", "type": "tuple[2]", "components": [{"name": "amount", "type": "uint256"}]},
+            ],
+        });
+
+        assert_eq!(canonical_param(&param).unwrap(), "(address,(uint256)[2])[]");
+
+        assert!(canonical_param(&serde_json::json!({"name": "x"})).is_err());
+        assert!(canonical_param(&serde_json::json!({"type": "tuple"})).is_err());
+        assert!(canonical_param(&serde_json::json!({"type": "tuple[", "components": []})).is_err());
+    }
+}
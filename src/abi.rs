@@ -1,8 +1,23 @@
 use anyhow::{anyhow, Result};
+use bytes::Bytes;
 use ethereum_types::H256;
-use serde::{de::Visitor, Deserialize, Serialize};
-
-use crate::{params::Param, DecodedParams, Error, Event, Value};
+use serde::{de::Visitor, ser::SerializeSeq, Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    convert::TryInto,
+    fmt,
+    io::{BufReader, Read},
+};
+
+use crate::{
+    decode_hex, encode_hex,
+    hex_encoding::decode_hex_to_slice,
+    layout::{AbiLayout, EntryKey, SourceEntry},
+    memo::SignatureHash,
+    natspec::DocsSlot,
+    params::{declarations, Param},
+    DecodedEvent, DecodedParams, DecodedParamsRef, Docs, Error, Event, Value,
+};
 
 /// Contract ABI (Abstract Binary Interface).
 ///
@@ -19,7 +34,21 @@ use crate::{params::Param, DecodedParams, Error, Event, Value};
 ///
 /// let abi: Abi = serde_json::from_str(abi_json).unwrap();
 /// ```
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// ABIs can't be written as struct literals, as they remember the JSON they
+/// were parsed from. Use [`Abi::builder`], or start from `Abi::default()`:
+///
+/// ```
+/// use ethereum_abi::{Abi, Function, StateMutability};
+///
+/// let mut abi = Abi::default();
+/// abi.functions
+///     .push(Function::new("f", vec![], vec![], StateMutability::NonPayable));
+/// abi.fallback = Some(StateMutability::Payable);
+///
+/// assert_eq!(abi.functions[0].signature(), "f()");
+/// ```
+#[derive(Clone, Default, Eq, PartialEq)]
 pub struct Abi {
     /// Contract constructor definition (if it defines one).
     pub constructor: Option<Constructor>,
@@ -29,37 +58,82 @@ pub struct Abi {
     pub events: Vec<Event>,
     /// Contract defined errors.
     pub errors: Vec<Error>,
-    /// Whether the contract has the receive method defined.
-    pub has_receive: bool,
-    /// Whether the contract has the fallback method defined.
-    pub has_fallback: bool,
+    /// Contract receive function state mutability (if it defines one),
+    /// always payable.
+    pub receive: Option<StateMutability>,
+    /// Contract fallback function state mutability (if it defines one).
+    pub fallback: Option<StateMutability>,
+    /// How the ABI was laid out when parsed from JSON.
+    pub(crate) layout: AbiLayout,
+}
+
+impl fmt::Debug for Abi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Abi")
+            .field("constructor", &self.constructor)
+            .field("functions", &self.functions)
+            .field("events", &self.events)
+            .field("errors", &self.errors)
+            .field("receive", &self.receive)
+            .field("fallback", &self.fallback)
+            .finish()
+    }
 }
 
 impl Abi {
+    /// Parses a JSON ABI from a reader, such as a file.
+    ///
+    /// The input is read through a buffer and parsed entry by entry, so
+    /// multi-megabyte ABIs are never held in memory as text or as a list of
+    /// raw entries. Errors name the index of the failing entry.
+    ///
+    /// ```
+    /// use ethereum_abi::Abi;
+    ///
+    /// let json = r#"[{"type": "event", "name": "E", "inputs": []}, {"type": "function"}]"#;
+    ///
+    /// let err = Abi::from_reader(json.as_bytes()).unwrap_err();
+    /// assert!(err.to_string().starts_with("ABI entry 1: missing function name"));
+    /// ```
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self> {
+        Ok(serde_json::from_reader(BufReader::new(reader))?)
+    }
+
     // Decode function input from slice.
     pub fn decode_input_from_slice<'a>(
         &'a self,
         input: &[u8],
     ) -> Result<(&'a Function, DecodedParams)> {
+        let (f, decoded_params) = self.decode_input_ref(input)?;
+
+        Ok((f, decoded_params.into_owned()))
+    }
+
+    /// Decodes function input from slice, borrowing the params from the
+    /// function rather than cloning them.
+    pub fn decode_input_ref<'a>(
+        &'a self,
+        input: &[u8],
+    ) -> Result<(&'a Function, DecodedParamsRef<'a>)> {
+        let selector = input
+            .get(0..4)
+            .ok_or_else(|| anyhow!("missing function selector"))?;
+
         let f = self
-            .functions
-            .iter()
-            .find(|f| f.method_id() == input[0..4])
+            .function_by_selector(selector.try_into()?)
             .ok_or_else(|| anyhow!("ABI function not found"))?;
 
-        let decoded_params = f.decode_input_from_slice(&input[4..])?;
+        let decoded_params = f.decode_input_ref(&input[4..])?;
 
         Ok((f, decoded_params))
     }
 
-    // Decode function input from hex string.
+    /// Decode function input from hex string, with or without `0x` prefix.
     pub fn decode_input_from_hex<'a>(
         &'a self,
         input: &str,
     ) -> Result<(&'a Function, DecodedParams)> {
-        let slice = hex::decode(input)?;
-
-        self.decode_input_from_slice(&slice)
+        self.decode_input_from_slice(&decode_hex(input)?)
     }
 
     /// Decode event data from slice.
@@ -68,96 +142,416 @@ impl Abi {
         topics: &[H256],
         data: &[u8],
     ) -> Result<(&'a Event, DecodedParams)> {
+        let (e, decoded_params) = self.decode_log_ref(topics, data)?;
+
+        Ok((e, decoded_params.into_owned()))
+    }
+
+    /// Decodes event data from slice, borrowing the params from the event
+    /// rather than cloning them.
+    pub fn decode_log_ref<'a>(
+        &'a self,
+        topics: &[H256],
+        data: &[u8],
+    ) -> Result<(&'a Event, DecodedParamsRef<'a>)> {
         if topics.is_empty() {
             return Err(anyhow!("missing event topic id"));
         }
 
         let e = self
-            .events
-            .iter()
-            .find(|e| e.topic() == topics[0])
+            .event_by_topic(topics[0])
             .ok_or_else(|| anyhow!("ABI event not found"))?;
 
-        let decoded_params = e.decode_data_from_slice(topics, data)?;
+        let decoded_params = e.decode_data_ref(topics, data)?;
 
         Ok((e, decoded_params))
     }
-}
 
-impl Serialize for Abi {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let mut entries = vec![];
-
-        if let Some(c) = &self.constructor {
-            entries.push(AbiEntry {
-                type_: String::from("constructor"),
-                name: None,
-                inputs: Some(c.inputs.clone()),
-                outputs: None,
-                state_mutability: Some(StateMutability::NonPayable),
-                anonymous: None,
-            });
+    /// Decode event data from slice, keeping indexed and non-indexed params
+    /// apart.
+    pub fn decode_event_from_slice<'a>(
+        &'a self,
+        topics: &[H256],
+        data: &[u8],
+    ) -> Result<(&'a Event, DecodedEvent)> {
+        let topic = topics
+            .first()
+            .ok_or_else(|| anyhow!("missing event topic id"))?;
+
+        let e = self
+            .event_by_topic(*topic)
+            .ok_or_else(|| anyhow!("ABI event not found"))?;
+
+        Ok((e, e.decode_event_from_slice(topics, data)?))
+    }
+
+    /// Encodes a call to the function named `name`: its selector followed by
+    /// the encoded arguments.
+    ///
+    /// Overloads are resolved as in [`Abi::resolve_function`]. Integer values
+    /// are encoded at the width of the declared type.
+    pub fn encode_call(&self, name: &str, values: &[Value]) -> Result<Vec<u8>> {
+        self.resolve_function(name, values)?.encode_input(values)
+    }
+
+    /// Encodes a call like [`Abi::encode_call`], as a `0x` prefixed hex
+    /// string.
+    pub fn encode_call_hex(&self, name: &str, values: &[Value]) -> Result<String> {
+        Ok(encode_hex(self.encode_call(name, values)?))
+    }
+
+    /// Returns the first function named `name`.
+    ///
+    /// Use [`Abi::functions_by_name`] to list all overloads.
+    pub fn function(&self, name: &str) -> Option<&Function> {
+        self.functions.iter().find(|f| f.name == name)
+    }
+
+    /// Returns all overloads of the function named `name`.
+    pub fn functions_by_name(&self, name: &str) -> Vec<&Function> {
+        self.functions.iter().filter(|f| f.name == name).collect()
+    }
+
+    /// Returns the function with the given selector.
+    pub fn function_by_selector(&self, selector: [u8; 4]) -> Option<&Function> {
+        self.functions.iter().find(|f| f.method_id() == selector)
+    }
+
+    /// Returns the function with the given hex encoded selector, with or
+    /// without `0x` prefix.
+    pub fn function_by_selector_hex(&self, selector: &str) -> Result<Option<&Function>> {
+        let mut bytes = [0u8; 4];
+        decode_hex_to_slice(selector, &mut bytes)
+            .map_err(|_| anyhow!("selector must be 4 hex encoded bytes: {}", selector))?;
+
+        Ok(self.function_by_selector(bytes))
+    }
+
+    /// Returns the event with the given topic. Anonymous events have no topic
+    /// and are never returned.
+    pub fn event_by_topic(&self, topic: H256) -> Option<&Event> {
+        self.events
+            .iter()
+            .find(|e| !e.anonymous && e.topic() == topic)
+    }
+
+    /// Returns the custom error with the given selector.
+    pub fn error_by_selector(&self, selector: [u8; 4]) -> Option<&Error> {
+        self.errors.iter().find(|e| e.selector() == selector)
+    }
+
+    /// Picks the overload of the function named `name` whose inputs the given
+    /// values can be encoded as.
+    ///
+    /// If several overloads accept the values, the one whose input types are
+    /// exactly the values' types wins; otherwise the call is ambiguous and an
+    /// error listing the candidates is returned.
+    pub fn resolve_function(&self, name: &str, values: &[Value]) -> Result<&Function> {
+        let overloads = self.functions_by_name(name);
+
+        if overloads.is_empty() {
+            return Err(anyhow!("ABI function not found: {}", name));
         }
 
-        for f in &self.functions {
-            entries.push(AbiEntry {
-                type_: String::from("function"),
-                name: Some(f.name.clone()),
-                inputs: Some(f.inputs.clone()),
-                outputs: Some(f.outputs.clone()),
-                state_mutability: Some(f.state_mutability),
-                anonymous: None,
-            });
+        let candidates: Vec<_> = overloads
+            .iter()
+            .copied()
+            .filter(|f| {
+                f.inputs.len() == values.len()
+                    && f.inputs
+                        .iter()
+                        .zip(values)
+                        .all(|(p, v)| v.matches(&p.type_))
+            })
+            .collect();
+
+        let exact: Vec<_> = candidates
+            .iter()
+            .copied()
+            .filter(|f| {
+                f.inputs
+                    .iter()
+                    .zip(values)
+                    .all(|(p, v)| p.type_.to_string() == v.type_of().to_string())
+            })
+            .collect();
+
+        match (candidates.as_slice(), exact.as_slice()) {
+            ([f], _) | (_, [f]) => Ok(f),
+            ([], _) => Err(anyhow!(
+                "no overload of {} matches the given values, candidates: {}",
+                name,
+                signatures(&overloads)
+            )),
+            _ => Err(anyhow!(
+                "ambiguous call to {}, candidates: {}",
+                name,
+                signatures(&candidates)
+            )),
         }
+    }
 
-        for e in &self.events {
-            entries.push(AbiEntry {
-                type_: String::from("event"),
-                name: Some(e.name.clone()),
-                inputs: Some(e.inputs.clone()),
-                outputs: None,
-                state_mutability: None,
-                anonymous: Some(e.anonymous),
-            });
+    /// Computes the ERC-165 interface identifier: the XOR of all function
+    /// selectors.
+    pub fn interface_id(&self) -> [u8; 4] {
+        xor_selectors(self.functions.iter())
+    }
+
+    /// Computes the ERC-165 interface identifier of the functions with the
+    /// given names, including all overloads of each.
+    ///
+    /// Useful when an ABI implements several interfaces at once.
+    pub fn interface_id_of(&self, names: &[&str]) -> Result<[u8; 4]> {
+        if let Some(name) = names
+            .iter()
+            .find(|name| !self.functions.iter().any(|f| f.name == **name))
+        {
+            return Err(anyhow!("ABI function not found: {}", name));
         }
 
-        for e in &self.errors {
-            entries.push(AbiEntry {
-                type_: String::from("error"),
-                name: Some(e.name.clone()),
-                inputs: Some(e.inputs.clone()),
-                outputs: None,
-                state_mutability: None,
-                anonymous: None,
-            });
+        Ok(xor_selectors(
+            self.functions
+                .iter()
+                .filter(|f| names.contains(&f.name.as_str())),
+        ))
+    }
+}
+
+fn signatures(functions: &[&Function]) -> String {
+    functions
+        .iter()
+        .map(|f| f.signature())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn xor_selectors<'a>(functions: impl Iterator<Item = &'a Function>) -> [u8; 4] {
+    functions.fold([0u8; 4], |mut id, f| {
+        id.iter_mut()
+            .zip(f.method_id())
+            .for_each(|(byte, selector_byte)| *byte ^= selector_byte);
+        id
+    })
+}
+
+/// Locates a definition within an [`Abi`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub(crate) enum Def {
+    Constructor,
+    Function(usize),
+    Event(usize),
+    Error(usize),
+    Receive,
+    Fallback,
+}
+
+impl Abi {
+    // Lists the definitions in the order ABIs built in code serialize them.
+    pub(crate) fn definitions(&self) -> impl Iterator<Item = Def> + '_ {
+        self.constructor
+            .iter()
+            .map(|_| Def::Constructor)
+            .chain((0..self.functions.len()).map(Def::Function))
+            .chain((0..self.events.len()).map(Def::Event))
+            .chain((0..self.errors.len()).map(Def::Error))
+            .chain(self.receive.map(|_| Def::Receive))
+            .chain(self.fallback.map(|_| Def::Fallback))
+    }
+
+    pub(crate) fn key_of(&self, def: Def) -> EntryKey {
+        match def {
+            Def::Constructor => EntryKey::Constructor,
+            Def::Function(i) => EntryKey::Function(self.functions[i].method_id()),
+            Def::Event(i) => EntryKey::Event(self.events[i].topic()),
+            Def::Error(i) => EntryKey::Error(self.errors[i].selector()),
+            Def::Receive => EntryKey::Receive,
+            Def::Fallback => EntryKey::Fallback,
         }
+    }
 
-        if self.has_receive {
-            entries.push(AbiEntry {
-                type_: String::from("receive"),
-                name: None,
-                inputs: None,
-                outputs: None,
-                state_mutability: Some(StateMutability::Payable),
-                anonymous: None,
-            });
+    fn same_definition(&self, def: Def, other: &Abi, other_def: Def) -> bool {
+        match (def, other_def) {
+            (Def::Constructor, Def::Constructor) => self.constructor == other.constructor,
+            (Def::Function(i), Def::Function(j)) => self.functions[i] == other.functions[j],
+            (Def::Event(i), Def::Event(j)) => self.events[i] == other.events[j],
+            (Def::Error(i), Def::Error(j)) => self.errors[i] == other.errors[j],
+            (Def::Receive, Def::Receive) => self.receive == other.receive,
+            (Def::Fallback, Def::Fallback) => self.fallback == other.fallback,
+            _ => false,
         }
+    }
+
+    // Serializes a definition anew, regardless of how it was parsed.
+    pub(crate) fn entry_json(&self, def: Def) -> serde_json::Value {
+        serde_json::to_value(self.entry(def)).unwrap_or_default()
+    }
+
+    fn entry(&self, def: Def) -> AbiEntry {
+        let entry = AbiEntry {
+            type_: String::new(),
+            name: None,
+            inputs: None,
+            outputs: None,
+            state_mutability: None,
+            anonymous: None,
+            constant: None,
+            gas: None,
+            payable: None,
+        };
+
+        match def {
+            Def::Constructor => {
+                let c = self.constructor.as_ref().expect("ABI has a constructor");
+
+                AbiEntry {
+                    type_: String::from("constructor"),
+                    inputs: Some(c.inputs.clone()),
+                    state_mutability: Some(c.state_mutability),
+                    ..entry
+                }
+            }
+
+            Def::Function(i) => {
+                let f = &self.functions[i];
+
+                AbiEntry {
+                    type_: String::from("function"),
+                    name: Some(f.name.clone()),
+                    inputs: Some(f.inputs.clone()),
+                    outputs: Some(f.outputs.clone()),
+                    state_mutability: Some(f.state_mutability),
+                    gas: f.gas,
+                    ..entry
+                }
+            }
 
-        if self.has_fallback {
-            entries.push(AbiEntry {
+            Def::Event(i) => {
+                let e = &self.events[i];
+
+                AbiEntry {
+                    type_: String::from("event"),
+                    name: Some(e.name.clone()),
+                    inputs: Some(e.inputs.clone()),
+                    anonymous: Some(e.anonymous),
+                    ..entry
+                }
+            }
+
+            Def::Error(i) => {
+                let e = &self.errors[i];
+
+                AbiEntry {
+                    type_: String::from("error"),
+                    name: Some(e.name.clone()),
+                    inputs: Some(e.inputs.clone()),
+                    ..entry
+                }
+            }
+
+            Def::Receive => AbiEntry {
+                type_: String::from("receive"),
+                state_mutability: self.receive,
+                ..entry
+            },
+
+            Def::Fallback => AbiEntry {
                 type_: String::from("fallback"),
-                name: None,
-                inputs: None,
-                outputs: None,
-                state_mutability: Some(StateMutability::Payable),
-                anonymous: None,
-            });
+                state_mutability: self.fallback,
+                ..entry
+            },
         }
+    }
+
+    // Serializes a definition that was parsed from `json`: as it was read if
+    // it is unchanged since, otherwise anew but keeping the unknown fields.
+    fn restore_entry(&self, def: Def, json: &str) -> serde_json::Value {
+        let entry = self.entry(def);
+
+        let original: serde_json::Value = match serde_json::from_str(json) {
+            Ok(original) => original,
+            Err(_) => return serde_json::to_value(entry).unwrap_or_default(),
+        };
+
+        let mut parsed = Abi::default();
+        let unchanged = AbiEntry::deserialize(&original)
+            .ok()
+            .and_then(|entry| parsed.add_entry(entry).ok())
+            .is_some_and(|parsed_def| self.same_definition(def, &parsed, parsed_def));
 
-        entries.serialize(serializer)
+        if unchanged {
+            return original;
+        }
+
+        let mut restored = serde_json::to_value(entry).unwrap_or_default();
+
+        if let (Some(restored), serde_json::Value::Object(original)) =
+            (restored.as_object_mut(), original)
+        {
+            for (key, value) in original {
+                if !AbiEntry::FIELDS.contains(&key.as_str()) {
+                    restored.insert(key, value);
+                }
+            }
+        }
+
+        restored
+    }
+}
+
+impl Abi {
+    // Lists the definitions in the order they are serialized in, with the
+    // JSON they were parsed from if it was kept.
+    pub(crate) fn entry_order(&self) -> Vec<(Def, Option<&str>)> {
+        let mut unmatched = HashMap::<EntryKey, VecDeque<Def>>::new();
+        for def in self.definitions() {
+            unmatched
+                .entry(self.key_of(def))
+                .or_default()
+                .push_back(def);
+        }
+
+        let mut order = vec![];
+        let mut listed = HashSet::new();
+
+        for source in self.layout.entries() {
+            // Entries whose definition was removed since are skipped.
+            if let Some(def) = unmatched.get_mut(&source.key).and_then(VecDeque::pop_front) {
+                order.push((def, source.json.as_deref()));
+                listed.insert(def);
+            }
+        }
+
+        for def in self.definitions() {
+            if !listed.contains(&def) {
+                order.push((def, None));
+            }
+        }
+
+        order
+    }
+}
+
+impl Serialize for Abi {
+    /// Serializes the ABI as a JSON ABI.
+    ///
+    /// ABIs parsed from JSON serialize back to the same entries in the same
+    /// order, unknown fields included, with keys in the order solc writes
+    /// them. Definitions changed since are serialized anew in place, and
+    /// added ones follow the parsed ones.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None)?;
+
+        for (def, json) in self.entry_order() {
+            match json {
+                Some(json) => seq.serialize_element(&self.restore_entry(def, json))?,
+                None => seq.serialize_element(&self.entry(def))?,
+            }
+        }
+
+        seq.end()
     }
 }
 
@@ -179,31 +573,115 @@ pub struct Constructor {
     pub state_mutability: StateMutability,
 }
 
+impl Constructor {
+    /// Returns the constructor's signature, e.g. `constructor(address,uint256)`.
+    pub fn signature(&self) -> String {
+        format!(
+            "constructor({})",
+            self.inputs
+                .iter()
+                .map(|param| param.type_.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+
+    /// Encodes the given constructor arguments, as appended to the creation
+    /// bytecode.
+    ///
+    /// Fails if the number of values or any value's type does not match the
+    /// constructor's inputs.
+    pub fn encode_input(&self, values: &[Value]) -> Result<Vec<u8>> {
+        encode_args(&self.signature(), &self.inputs, values)
+    }
+}
+
 /// Contract function definition.
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// The method id is computed on first use and then cached. The name and inputs
+/// are only reachable through methods so that changing them clears it.
+#[derive(Clone, Eq, PartialEq)]
 pub struct Function {
-    /// Function name.
-    pub name: String,
-    /// Function inputs.
-    pub inputs: Vec<Param>,
+    pub(crate) name: String,
+    pub(crate) inputs: Vec<Param>,
     /// Function outputs.
     pub outputs: Vec<Param>,
     /// Function state mutability kind.
     pub state_mutability: StateMutability,
+    /// Gas estimate listed by older Vyper ABIs.
+    pub gas: Option<u64>,
+    pub(crate) hash: SignatureHash,
+    docs: DocsSlot,
+}
+
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Function")
+            .field("name", &self.name)
+            .field("inputs", &self.inputs)
+            .field("outputs", &self.outputs)
+            .field("state_mutability", &self.state_mutability)
+            .field("gas", &self.gas)
+            .finish()
+    }
 }
 
 impl Function {
-    /// Computes the function's method id (function selector).
-    pub fn method_id(&self) -> [u8; 4] {
-        use tiny_keccak::{Hasher, Keccak};
+    /// Creates a function definition.
+    pub fn new(
+        name: impl Into<String>,
+        inputs: Vec<Param>,
+        outputs: Vec<Param>,
+        state_mutability: StateMutability,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            inputs,
+            outputs,
+            state_mutability,
+            gas: None,
+            hash: SignatureHash::default(),
+            docs: DocsSlot::default(),
+        }
+    }
 
-        let mut keccak_out = [0u8; 32];
-        let mut hasher = Keccak::v256();
-        hasher.update(self.signature().as_bytes());
-        hasher.finalize(&mut keccak_out);
+    /// Returns the function's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Renames the function, clearing its cached method id.
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+        self.hash = SignatureHash::default();
+    }
 
+    /// Returns the function's inputs.
+    pub fn inputs(&self) -> &[Param] {
+        &self.inputs
+    }
+
+    /// Returns the function's inputs for editing, clearing its cached method id.
+    pub fn inputs_mut(&mut self) -> &mut Vec<Param> {
+        self.hash = SignatureHash::default();
+        &mut self.inputs
+    }
+
+    /// Returns the NatSpec documentation attached to the function, see
+    /// [`Abi::attach_natspec`](crate::Abi::attach_natspec).
+    pub fn docs(&self) -> Option<&Docs> {
+        self.docs.get()
+    }
+
+    /// Attaches NatSpec documentation to the function, or removes it.
+    pub fn set_docs(&mut self, docs: Option<Docs>) {
+        self.docs.set(docs);
+    }
+
+    /// Computes the function's method id (function selector).
+    pub fn method_id(&self) -> [u8; 4] {
         let mut mid = [0u8; 4];
-        mid.copy_from_slice(&keccak_out[0..4]);
+        mid.copy_from_slice(&self.hash.get_or_init(|| self.signature())[0..4]);
 
         mid
     }
@@ -221,21 +699,79 @@ impl Function {
         )
     }
 
+    /// Returns the function's declaration with parameter names, state
+    /// mutability and outputs, e.g. `balanceOf(address owner) view returns
+    /// (uint256)`.
+    ///
+    /// This is meant for display; use [`Function::signature`] for hashing.
+    pub fn full_signature(&self) -> String {
+        let mut sig = format!("{}({})", self.name, declarations(&self.inputs));
+
+        match self.state_mutability {
+            StateMutability::Pure => sig.push_str(" pure"),
+            StateMutability::View => sig.push_str(" view"),
+            StateMutability::Payable => sig.push_str(" payable"),
+            StateMutability::NonPayable => {}
+        }
+
+        if !self.outputs.is_empty() {
+            sig.push_str(&format!(" returns ({})", declarations(&self.outputs)));
+        }
+
+        sig
+    }
+
+    /// Encodes the function's selector followed by the given arguments.
+    ///
+    /// Fails if the number of values or any value's type does not match the
+    /// function's inputs.
+    pub fn encode_input(&self, values: &[Value]) -> Result<Vec<u8>> {
+        let mut input = self.method_id().to_vec();
+        input.extend(encode_args(&self.signature(), &self.inputs, values)?);
+
+        Ok(input)
+    }
+
+    /// Encodes the function's input like [`Function::encode_input`], as a
+    /// `0x` prefixed hex string.
+    pub fn encode_input_hex(&self, values: &[Value]) -> Result<String> {
+        Ok(encode_hex(self.encode_input(values)?))
+    }
+
+    /// Decodes function input from a shared buffer, with `bytes` arguments
+    /// pointing into it rather than copied out.
+    pub fn decode_input_from_bytes(&self, input: &Bytes) -> Result<DecodedParams> {
+        let values = Value::decode_all(input, self.inputs.iter().map(|param| &param.type_))?;
+
+        Ok(DecodedParamsRef::new(&self.inputs, values).into_owned())
+    }
+
     // Decode function input from slice.
     pub fn decode_input_from_slice(&self, input: &[u8]) -> Result<DecodedParams> {
-        let inputs_types = self
-            .inputs
-            .iter()
-            .map(|f_input| f_input.type_.clone())
-            .collect::<Vec<_>>();
+        self.decode_input_ref(input)
+            .map(DecodedParamsRef::into_owned)
+    }
 
-        Ok(DecodedParams::from(
-            self.inputs
-                .iter()
-                .cloned()
-                .zip(Value::decode_from_slice(input, &inputs_types)?)
-                .collect::<Vec<_>>(),
-        ))
+    /// Decodes function input from slice, borrowing the params from the
+    /// function rather than cloning them.
+    pub fn decode_input_ref(&self, input: &[u8]) -> Result<DecodedParamsRef<'_>> {
+        let values = Value::decode_all(input, self.inputs.iter().map(|param| &param.type_))?;
+
+        Ok(DecodedParamsRef::new(&self.inputs, values))
+    }
+
+    /// Decode the data returned by a call to the function.
+    pub fn decode_output_from_slice(&self, output: &[u8]) -> Result<DecodedParams> {
+        self.decode_output_ref(output)
+            .map(DecodedParamsRef::into_owned)
+    }
+
+    /// Decodes function output from slice, borrowing the params from the
+    /// function rather than cloning them.
+    pub fn decode_output_ref(&self, output: &[u8]) -> Result<DecodedParamsRef<'_>> {
+        let values = Value::decode_all(output, self.outputs.iter().map(|param| &param.type_))?;
+
+        Ok(DecodedParamsRef::new(&self.outputs, values))
     }
 }
 
@@ -253,21 +789,62 @@ pub enum StateMutability {
     Payable,
 }
 
+// Fields are declared in alphabetical order, the order solc writes them in.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AbiEntry {
-    #[serde(rename = "type")]
-    type_: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    name: Option<String>,
+    anonymous: Option<bool>,
+    // Pre-0.5 Solidity (and early Vyper) ABIs describe state mutability with
+    // these flags instead of `stateMutability`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    constant: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     inputs: Option<Vec<Param>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     outputs: Option<Vec<Param>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    state_mutability: Option<StateMutability>,
+    payable: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    anonymous: Option<bool>,
+    state_mutability: Option<StateMutability>,
+    // Entries predating the field are functions.
+    #[serde(default = "function_type", rename = "type")]
+    type_: String,
+}
+
+fn function_type() -> String {
+    String::from("function")
+}
+
+impl AbiEntry {
+    const FIELDS: &'static [&'static str] = &[
+        "anonymous",
+        "constant",
+        "gas",
+        "inputs",
+        "name",
+        "outputs",
+        "payable",
+        "stateMutability",
+        "type",
+    ];
+
+    fn state_mutability(&self) -> Option<StateMutability> {
+        if self.state_mutability.is_some() {
+            return self.state_mutability;
+        }
+
+        match (self.constant, self.payable) {
+            (_, Some(true)) => Some(StateMutability::Payable),
+            (Some(true), _) => Some(StateMutability::View),
+            (Some(false), _) | (_, Some(false)) => Some(StateMutability::NonPayable),
+            (None, None) => None,
+        }
+    }
 }
 
 struct AbiVisitor;
@@ -283,104 +860,171 @@ impl<'de> Visitor<'de> for AbiVisitor {
     where
         A: serde::de::SeqAccess<'de>,
     {
-        let mut abi = Abi {
-            constructor: None,
-            functions: vec![],
-            events: vec![],
-            errors: vec![],
-            has_receive: false,
-            has_fallback: false,
-        };
+        let mut abi = Abi::default();
+        let mut layout = vec![];
+
+        // Entries are added one at a time as they are read, and errors name
+        // the offending entry, which helps with ABIs thousands of entries long.
+        for index in 0.. {
+            let json = match seq
+                .next_element::<serde_json::Value>()
+                .map_err(|err| entry_error(index, err))?
+            {
+                Some(json) => json,
+                None => break,
+            };
+
+            let entry = AbiEntry::deserialize(&json).map_err(|err| entry_error(index, err))?;
+            let def = abi
+                .add_entry(entry)
+                .map_err(|err| entry_error(index, err))?;
+
+            // Only entries that would not serialize back as they were read
+            // are kept, which is rare for compiler output.
+            let regenerated = serde_json::to_value(abi.entry(def)).ok();
+
+            layout.push(SourceEntry {
+                key: abi.key_of(def),
+                json: (regenerated.as_ref() != Some(&json)).then(|| json.to_string().into()),
+            });
+        }
 
-        loop {
-            let entry = seq.next_element::<AbiEntry>()?;
+        abi.layout = layout.into();
 
-            match entry {
-                None => return Ok(abi),
+        Ok(abi)
+    }
+}
 
-                Some(entry) => match entry.type_.as_str() {
-                    "receive" => abi.has_receive = true,
+fn entry_error<E: serde::de::Error>(index: usize, err: impl std::fmt::Display) -> E {
+    E::custom(format!("ABI entry {}: {}", index, err))
+}
 
-                    "fallback" => abi.has_fallback = true,
+impl Abi {
+    fn add_entry(&mut self, entry: AbiEntry) -> Result<Def, String> {
+        let def = match entry.type_.as_str() {
+            "receive" => {
+                self.receive = Some(entry.state_mutability().unwrap_or(StateMutability::Payable));
+                Def::Receive
+            }
 
-                    "constructor" => {
-                        let state_mutability = entry.state_mutability.ok_or_else(|| {
-                            serde::de::Error::custom(
-                                "missing constructor state mutability".to_string(),
-                            )
-                        })?;
+            // Fallbacks declared before Solidity 0.5 were payable only when
+            // flagged as such.
+            "fallback" => {
+                self.fallback = Some(
+                    entry
+                        .state_mutability()
+                        .unwrap_or(StateMutability::NonPayable),
+                );
+                Def::Fallback
+            }
 
-                        let inputs = entry.inputs.unwrap_or_default();
+            // Entries from before payable was flagged carry no mutability
+            // at all, and couldn't receive Ether.
+            "constructor" => {
+                let state_mutability = entry
+                    .state_mutability()
+                    .unwrap_or(StateMutability::NonPayable);
 
-                        abi.constructor = Some(Constructor {
-                            inputs,
-                            state_mutability,
-                        });
-                    }
+                let inputs = entry.inputs.unwrap_or_default();
 
-                    "function" => {
-                        let state_mutability = entry.state_mutability.ok_or_else(|| {
-                            serde::de::Error::custom(
-                                "missing function state mutability".to_string(),
-                            )
-                        })?;
+                self.constructor = Some(Constructor {
+                    inputs,
+                    state_mutability,
+                });
+                Def::Constructor
+            }
 
-                        let inputs = entry.inputs.unwrap_or_default();
+            // Older Vyper versions list the default function as a regular
+            // function.
+            "function" if entry.name.as_deref() == Some("__default__") => {
+                self.fallback = Some(
+                    entry
+                        .state_mutability()
+                        .unwrap_or(StateMutability::NonPayable),
+                );
+                Def::Fallback
+            }
 
-                        let outputs = entry.outputs.unwrap_or_default();
+            "function" => {
+                let state_mutability = entry
+                    .state_mutability()
+                    .unwrap_or(StateMutability::NonPayable);
 
-                        let name = entry.name.ok_or_else(|| {
-                            serde::de::Error::custom("missing function name".to_string())
-                        })?;
+                let inputs = entry.inputs.unwrap_or_default();
 
-                        abi.functions.push(Function {
-                            name,
-                            inputs,
-                            outputs,
-                            state_mutability,
-                        });
-                    }
+                let outputs = entry.outputs.unwrap_or_default();
 
-                    "event" => {
-                        let inputs = entry.inputs.unwrap_or_default();
+                let name = entry.name.ok_or("missing function name")?;
 
-                        let name = entry.name.ok_or_else(|| {
-                            serde::de::Error::custom("missing function name".to_string())
-                        })?;
+                let mut function = Function::new(name, inputs, outputs, state_mutability);
+                function.gas = entry.gas;
 
-                        let anonymous = entry.anonymous.ok_or_else(|| {
-                            serde::de::Error::custom("missing event anonymous field".to_string())
-                        })?;
+                self.functions.push(function);
+                Def::Function(self.functions.len() - 1)
+            }
 
-                        abi.events.push(Event {
-                            name,
-                            inputs,
-                            anonymous,
-                        });
-                    }
+            "event" => {
+                let inputs = entry.inputs.unwrap_or_default();
 
-                    "error" => {
-                        let inputs = entry.inputs.unwrap_or_default();
+                let name = entry.name.ok_or("missing event name")?;
 
-                        let name = entry.name.ok_or_else(|| {
-                            serde::de::Error::custom("missing error name".to_string())
-                        })?;
+                // Vyper may omit the field for regular events.
+                let anonymous = entry.anonymous.unwrap_or(false);
 
-                        abi.errors.push(Error { name, inputs });
-                    }
+                self.events.push(Event::new(name, inputs, anonymous));
+                Def::Event(self.events.len() - 1)
+            }
 
-                    _ => {
-                        return Err(serde::de::Error::custom(format!(
-                            "invalid ABI entry type: {}",
-                            entry.type_
-                        )))
-                    }
-                },
+            "error" => {
+                let inputs = entry.inputs.unwrap_or_default();
+
+                let name = entry.name.ok_or("missing error name")?;
+
+                self.errors.push(Error::new(name, inputs));
+                Def::Error(self.errors.len() - 1)
             }
-        }
+
+            _ => return Err(format!("invalid ABI entry type: {}", entry.type_)),
+        };
+
+        Ok(def)
     }
 }
 
+/// Encodes call arguments after checking them against the given params.
+///
+/// `signature` names the function or constructor in error messages.
+pub(crate) fn encode_args(signature: &str, inputs: &[Param], values: &[Value]) -> Result<Vec<u8>> {
+    if values.len() != inputs.len() {
+        return Err(anyhow!(
+            "{} expects {} arguments, got {}",
+            signature,
+            inputs.len(),
+            values.len()
+        ));
+    }
+
+    let values = inputs
+        .iter()
+        .zip(values)
+        .map(|(param, value)| {
+            if value.matches(&param.type_) {
+                Ok(value.clone().normalize(&param.type_))
+            } else {
+                Err(anyhow!(
+                    "argument {:?} of {} expects {}, got {}",
+                    param.name,
+                    signature,
+                    param.type_,
+                    value.type_of()
+                ))
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Value::encode(&values))
+}
+
 #[cfg(test)]
 mod test {
     use ethereum_types::{H160, U256};
@@ -393,23 +1037,25 @@ mod test {
     const TEST_ABI_V1: &str = r#"[{"inputs":[{"internalType":"address","name":"a","type":"address"}],"stateMutability":"nonpayable","type":"constructor"},{"anonymous":false,"inputs":[{"indexed":false,"internalType":"address","name":"x","type":"address"},{"indexed":false,"internalType":"uint256","name":"y","type":"uint256"}],"name":"E","type":"event"},{"inputs":[{"internalType":"uint256","name":"x","type":"uint256"}],"name":"f","outputs":[{"internalType":"uint256","name":"","type":"uint256"}],"stateMutability":"nonpayable","type":"function"},{"stateMutability":"payable","type":"receive"},{"type":"error","inputs": [{"name":"x","type":"uint256"},{"name":"y","type":"uint256"}],"name":"Err"}]"#;
 
     fn test_function() -> Function {
-        Function {
-            name: "funname".to_string(),
-            inputs: vec![
+        Function::new(
+            "funname".to_string(),
+            vec![
                 Param {
-                    name: "".to_string(),
+                    name: "".into(),
                     type_: Type::Address,
                     indexed: None,
+                    internal_type: None,
                 },
                 Param {
-                    name: "x".to_string(),
+                    name: "x".into(),
                     type_: Type::FixedArray(Box::new(Type::Uint(56)), 2),
                     indexed: None,
+                    internal_type: None,
                 },
             ],
-            outputs: vec![],
-            state_mutability: StateMutability::Pure,
-        }
+            vec![],
+            StateMutability::Pure,
+        )
     }
 
     #[test]
@@ -418,12 +1064,102 @@ mod test {
         assert_eq!(fun.signature(), "funname(address,uint56[2])");
     }
 
+    #[test]
+    fn function_full_signature() {
+        let mut fun = test_function();
+        assert_eq!(fun.full_signature(), "funname(address, uint56[2] x) pure");
+
+        fun.state_mutability = StateMutability::NonPayable;
+        fun.outputs = vec![Param {
+            name: "".into(),
+            type_: Type::Bool,
+            indexed: None,
+            internal_type: None,
+        }];
+        assert_eq!(
+            fun.full_signature(),
+            "funname(address, uint56[2] x) returns (bool)"
+        );
+    }
+
     #[test]
     fn function_method_id() {
         let fun = test_function();
         assert_eq!(fun.method_id(), [0x83, 0x1f, 0xc7, 0x20]);
     }
 
+    #[test]
+    fn edits_clear_the_method_id() {
+        let mut fun = Function::parse("transfer(address,uint256)").unwrap();
+        assert_eq!(fun.method_id(), [0xa9, 0x05, 0x9c, 0xbb]);
+
+        fun.set_name("approve");
+        assert_eq!(fun.method_id(), [0x09, 0x5e, 0xa7, 0xb3]);
+
+        fun.inputs_mut().pop();
+        assert_eq!(
+            fun.method_id(),
+            Function::parse("approve(address)").unwrap().method_id()
+        );
+    }
+
+    #[test]
+    fn struct_param_method_ids() {
+        // Selectors as reported by solc's `methodIdentifiers`.
+        let abi: Abi = serde_json::from_str(
+            r#"[
+                {"type": "function", "name": "exactInputSingle", "stateMutability": "payable", "outputs": [],
+                 "inputs": [{"name": "params", "type": "tuple", "internalType": "struct ISwapRouter.ExactInputSingleParams", "components": [
+                    {"name": "tokenIn", "type": "address"},
+                    {"name": "tokenOut", "type": "address"},
+                    {"name": "fee", "type": "uint24"},
+                    {"name": "recipient", "type": "address"},
+                    {"name": "deadline", "type": "uint256"},
+                    {"name": "amountIn", "type": "uint256"},
+                    {"name": "amountOutMinimum", "type": "uint256"},
+                    {"name": "sqrtPriceLimitX96", "type": "uint160"}
+                 ]}]},
+                {"type": "function", "name": "aggregate3", "stateMutability": "payable", "outputs": [],
+                 "inputs": [{"name": "calls", "type": "tuple[]", "internalType": "struct Multicall3.Call3[]", "components": [
+                    {"name": "target", "type": "address"},
+                    {"name": "allowFailure", "type": "bool"},
+                    {"name": "callData", "type": "bytes"}
+                 ]}]},
+                {"type": "function", "name": "aggregate", "stateMutability": "payable", "outputs": [],
+                 "inputs": [{"name": "calls", "type": "tuple[]", "components": [
+                    {"name": "target", "type": "address"},
+                    {"name": "callData", "type": "bytes"}
+                 ]}]}
+            ]"#,
+        )
+        .unwrap();
+
+        let ids: Vec<_> = abi
+            .functions
+            .iter()
+            .map(|f| (f.signature(), hex::encode(f.method_id())))
+            .collect();
+
+        assert_eq!(
+            ids,
+            vec![
+                (
+                    "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))"
+                        .to_string(),
+                    "414bf389".to_string()
+                ),
+                (
+                    "aggregate3((address,bool,bytes)[])".to_string(),
+                    "82ad56cb".to_string()
+                ),
+                (
+                    "aggregate((address,bytes)[])".to_string(),
+                    "252dba42".to_string()
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn abi_function_decode_input_from_slice() {
         let addr = H160::random();
@@ -434,7 +1170,7 @@ mod test {
             Value::Address(addr),
             Value::FixedArray(
                 vec![Value::Uint(uint1, 56), Value::Uint(uint2, 56)],
-                Type::Uint(56),
+                Type::Uint(56).into(),
             ),
         ];
 
@@ -444,8 +1180,9 @@ mod test {
             functions: vec![fun],
             events: vec![],
             errors: vec![],
-            has_receive: false,
-            has_fallback: false,
+            receive: None,
+            fallback: None,
+            ..Default::default()
         };
 
         let mut enc_input = abi.functions[0].method_id().to_vec();
@@ -476,59 +1213,67 @@ mod test {
             Abi {
                 constructor: Some(Constructor {
                     inputs: vec![Param {
-                        name: "a".to_string(),
+                        name: "a".into(),
                         type_: Type::Address,
-                        indexed: None
+                        indexed: None,
+                        internal_type: Some("address".into())
                     }],
                     state_mutability: StateMutability::NonPayable
                 }),
-                functions: vec![Function {
-                    name: "f".to_string(),
-                    inputs: vec![Param {
-                        name: "x".to_string(),
+                functions: vec![Function::new(
+                    "f".to_string(),
+                    vec![Param {
+                        name: "x".into(),
                         type_: Type::Uint(256),
-                        indexed: None
+                        indexed: None,
+                        internal_type: Some("uint256".into())
                     }],
-                    outputs: vec![Param {
-                        name: "".to_string(),
+                    vec![Param {
+                        name: "".into(),
                         type_: Type::Uint(256),
-                        indexed: None
+                        indexed: None,
+                        internal_type: Some("uint256".into())
                     }],
-                    state_mutability: StateMutability::NonPayable
-                }],
-                events: vec![Event {
-                    name: "E".to_string(),
-                    inputs: vec![
+                    StateMutability::NonPayable
+                )],
+                events: vec![Event::new(
+                    "E".to_string(),
+                    vec![
                         Param {
-                            name: "x".to_string(),
+                            name: "x".into(),
                             type_: Type::Address,
-                            indexed: Some(false)
+                            indexed: Some(false),
+                            internal_type: Some("address".into())
                         },
                         Param {
-                            name: "y".to_string(),
+                            name: "y".into(),
                             type_: Type::Uint(256),
-                            indexed: Some(false)
+                            indexed: Some(false),
+                            internal_type: Some("uint256".into())
                         }
                     ],
-                    anonymous: false
-                }],
-                errors: vec![Error {
-                    name: "Err".to_string(),
-                    inputs: vec![
+                    false
+                )],
+                errors: vec![Error::new(
+                    "Err".to_string(),
+                    vec![
                         Param {
-                            name: "x".to_string(),
+                            name: "x".into(),
                             type_: Type::Uint(256),
-                            indexed: None
+                            indexed: None,
+                            internal_type: None
                         },
                         Param {
-                            name: "y".to_string(),
+                            name: "y".into(),
                             type_: Type::Uint(256),
-                            indexed: None
+                            indexed: None,
+                            internal_type: None
                         },
                     ]
-                }],
-                has_receive: true,
-                has_fallback: false
+                )],
+                receive: Some(StateMutability::Payable),
+                fallback: None,
+                ..Default::default()
             }
         )
     }
@@ -574,30 +1319,33 @@ mod test {
             abi,
             Abi {
                 constructor: None,
-                functions: vec![Function {
-                    name: "f".to_string(),
-                    inputs: vec![
+                functions: vec![Function::new(
+                    "f".to_string(),
+                    vec![
                         Param {
-                            name: "n".to_string(),
+                            name: "n".into(),
                             type_: Type::Uint(256),
                             indexed: None,
+                            internal_type: Some("uint256".into()),
                         },
                         Param {
-                            name: "x".to_string(),
-                            type_: Type::Tuple(vec![
-                                ("a".to_string(), Type::Uint(256)),
-                                ("b".to_string(), Type::String)
-                            ]),
+                            name: "x".into(),
+                            type_: Type::Struct(
+                                "A.X".into(),
+                                vec![("a".into(), Type::Uint(256)), ("b".into(), Type::String)]
+                            ),
                             indexed: None,
+                            internal_type: Some("struct A.X".into()),
                         }
                     ],
-                    outputs: vec![],
-                    state_mutability: StateMutability::NonPayable,
-                }],
+                    vec![],
+                    StateMutability::NonPayable
+                )],
                 events: vec![],
                 errors: vec![],
-                has_receive: false,
-                has_fallback: false,
+                receive: None,
+                fallback: None,
+                ..Default::default()
             }
         );
     }
@@ -611,4 +1359,539 @@ mod test {
 
         assert_eq!(abi, de_abi);
     }
+
+    // As emitted by solc, except for the entry order, the Vyper style `gas`
+    // estimate and the `signature` web3.js adds.
+    const SOLC_ABI: &str = r#"[
+        {"inputs":[{"internalType":"address","name":"owner","type":"address"}],"stateMutability":"payable","type":"constructor"},
+        {"stateMutability":"nonpayable","type":"fallback"},
+        {"anonymous":false,"inputs":[{"components":[{"internalType":"uint256","name":"id","type":"uint256"},{"internalType":"address[]","name":"makers","type":"address[]"}],"indexed":false,"internalType":"struct Market.Order","name":"order","type":"tuple"},{"indexed":true,"internalType":"address","name":"taker","type":"address"}],"name":"Filled","type":"event"},
+        {"inputs":[{"components":[{"internalType":"uint256","name":"id","type":"uint256"},{"internalType":"address[]","name":"makers","type":"address[]"}],"internalType":"struct Market.Order[]","name":"orders","type":"tuple[]"}],"name":"fill","outputs":[{"internalType":"bool","name":"","type":"bool"}],"stateMutability":"nonpayable","type":"function"},
+        {"inputs":[{"internalType":"uint256","name":"id","type":"uint256"}],"name":"UnknownOrder","type":"error"},
+        {"gas":2300,"inputs":[],"name":"owner","outputs":[{"internalType":"address","name":"","type":"address"}],"signature":"0x8da5cb5b","stateMutability":"view","type":"function"},
+        {"stateMutability":"payable","type":"receive"}
+    ]"#;
+
+    fn compact(json: &str) -> String {
+        serde_json::from_str::<serde_json::Value>(json)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn round_trip() {
+        let abi: Abi = serde_json::from_str(SOLC_ABI).unwrap();
+
+        assert_eq!(serde_json::to_string(&abi).unwrap(), compact(SOLC_ABI));
+
+        // Keys are written in solc's (alphabetical) order.
+        let vyper = include_str!("../testdata/vyper_0_2_pool.json");
+        let abi: Abi = serde_json::from_str(vyper).unwrap();
+        assert_eq!(serde_json::to_string(&abi).unwrap(), compact(vyper));
+    }
+
+    #[test]
+    fn round_trip_after_edits() {
+        let mut abi: Abi = serde_json::from_str(SOLC_ABI).unwrap();
+
+        abi.functions[1].state_mutability = StateMutability::Pure;
+        abi.events.clear();
+        abi.functions.push(Function::new(
+            "renounce".to_string(),
+            vec![],
+            vec![],
+            StateMutability::NonPayable,
+        ));
+
+        let json: serde_json::Value = serde_json::to_value(&abi).unwrap();
+        let entries = json.as_array().unwrap();
+
+        assert_eq!(entries.len(), 7);
+        assert_eq!(entries[1]["type"], "fallback");
+        assert_eq!(entries[2]["name"], "fill");
+        // Edited entries keep their position and unknown fields.
+        assert_eq!(entries[4]["name"], "owner");
+        assert_eq!(entries[4]["stateMutability"], "pure");
+        assert_eq!(entries[4]["gas"], 2300);
+        assert_eq!(entries[4]["signature"], "0x8da5cb5b");
+        assert_eq!(entries[6]["name"], "renounce");
+
+        assert_eq!(serde_json::from_value::<Abi>(json).unwrap(), abi);
+    }
+
+    #[test]
+    fn receive_and_fallback_state_mutability() {
+        let json = r#"[{"stateMutability":"payable","type":"receive"},{"stateMutability":"nonpayable","type":"fallback"}]"#;
+        let abi: Abi = serde_json::from_str(json).unwrap();
+
+        assert_eq!(abi.receive, Some(StateMutability::Payable));
+        assert_eq!(abi.fallback, Some(StateMutability::NonPayable));
+
+        let fresh = Abi {
+            layout: Default::default(),
+            ..abi
+        };
+        assert_eq!(serde_json::to_string(&fresh).unwrap(), json);
+
+        // Legacy fallbacks are non-payable unless flagged otherwise.
+        let abi: Abi = serde_json::from_str(r#"[{"type":"fallback"}]"#).unwrap();
+        assert_eq!(abi.fallback, Some(StateMutability::NonPayable));
+        assert_eq!(abi.receive, None);
+    }
+
+    #[test]
+    fn payable_constructor() {
+        let abi = Abi::builder()
+            .constructor(&[("owner", "address")], StateMutability::Payable)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&abi).unwrap(),
+            serde_json::json!([{
+                "type": "constructor",
+                "inputs": [{"name": "owner", "type": "address"}],
+                "stateMutability": "payable"
+            }])
+        );
+
+        // Edited constructors are serialized anew, still payable.
+        let mut abi: Abi = serde_json::from_str(SOLC_ABI).unwrap();
+        abi.constructor.as_mut().unwrap().inputs.clear();
+
+        let json = serde_json::to_value(&abi).unwrap();
+        assert_eq!(json[0]["type"], "constructor");
+        assert_eq!(json[0]["stateMutability"], "payable");
+
+        // So are legacy constructors flagged payable.
+        let abi: Abi =
+            serde_json::from_str(r#"[{"type":"constructor","inputs":[],"payable":true}]"#).unwrap();
+        let fresh = Abi {
+            layout: Default::default(),
+            ..abi
+        };
+        assert_eq!(
+            serde_json::to_string(&fresh).unwrap(),
+            r#"[{"inputs":[],"stateMutability":"payable","type":"constructor"}]"#
+        );
+    }
+
+    #[test]
+    fn serialize_tuple_params() {
+        let fee = Type::Struct("Fee".into(), vec![("bps".into(), Type::Uint(16))]);
+        let order = Type::Struct(
+            "Order".into(),
+            vec![
+                ("maker".into(), Type::Address),
+                ("fees".into(), Type::FixedArray(Box::new(fee), 2)),
+            ],
+        );
+        let route = Type::Tuple(vec![
+            ("pool".into(), Type::Address),
+            (
+                "hops".into(),
+                Type::FixedArray(
+                    Box::new(Type::Array(Box::new(Type::Tuple(vec![(
+                        "fee".into(),
+                        Type::Uint(24),
+                    )])))),
+                    3,
+                ),
+            ),
+        ]);
+
+        let param = |name: &str, type_: Type| Param {
+            name: name.into(),
+            type_,
+            indexed: None,
+            internal_type: None,
+        };
+
+        let abi = Abi {
+            functions: vec![Function::new(
+                "fill".to_string(),
+                vec![
+                    param("orders", Type::Array(Box::new(order))),
+                    param("route", route),
+                ],
+                vec![],
+                StateMutability::NonPayable,
+            )],
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&abi).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "type": "function",
+                "name": "fill",
+                "inputs": [
+                    {
+                        "name": "orders",
+                        "type": "tuple[]",
+                        "internalType": "struct Order[]",
+                        "components": [
+                            {"name": "maker", "type": "address"},
+                            {
+                                "name": "fees",
+                                "type": "tuple[2]",
+                                "internalType": "struct Fee[2]",
+                                "components": [{"name": "bps", "type": "uint16"}]
+                            }
+                        ]
+                    },
+                    {
+                        "name": "route",
+                        "type": "tuple",
+                        "components": [
+                            {"name": "pool", "type": "address"},
+                            {
+                                "name": "hops",
+                                "type": "tuple[][3]",
+                                "components": [{"name": "fee", "type": "uint24"}]
+                            }
+                        ]
+                    }
+                ],
+                "outputs": [],
+                "stateMutability": "nonpayable"
+            }])
+        );
+
+        assert_eq!(
+            abi.functions[0].signature(),
+            "fill((address,(uint16)[2])[],(address,(uint24)[][3]))"
+        );
+
+        // Struct names survive the round trip, now in `internalType`s.
+        let de_abi: Abi = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            de_abi.functions[0].inputs[0].type_,
+            abi.functions[0].inputs[0].type_
+        );
+        assert_eq!(de_abi.functions[0].inputs[1], abi.functions[0].inputs[1]);
+        assert_eq!(serde_json::to_value(&de_abi).unwrap(), json);
+    }
+
+    #[test]
+    fn from_reader() {
+        let abi = Abi::from_reader(TEST_ABI_V1.as_bytes()).unwrap();
+        assert_eq!(abi, serde_json::from_str(TEST_ABI_V1).unwrap());
+
+        // A large ABI, as aggregated from the facets of a diamond.
+        let entry = r#"{"type":"function","name":"f","inputs":[{"name":"x","type":"uint256"}],"outputs":[],"stateMutability":"view"}"#;
+        let json = format!("[{}]", vec![entry; 10_000].join(","));
+        assert_eq!(
+            Abi::from_reader(json.as_bytes()).unwrap().functions.len(),
+            10_000
+        );
+    }
+
+    #[test]
+    fn entry_index_in_errors() {
+        let error = |json: &str| serde_json::from_str::<Abi>(json).unwrap_err().to_string();
+
+        assert_eq!(
+            error(r#"[{"type":"receive"},{"type":"event","inputs":[]}]"#),
+            "ABI entry 1: missing event name at line 1 column 49"
+        );
+        assert_eq!(
+            error(r#"[{"type":"receive"},{"type":"receive"},{"type":"bogus"}]"#),
+            "ABI entry 2: invalid ABI entry type: bogus at line 1 column 56"
+        );
+        assert!(
+            error(r#"[{"type":"function","name":"f","inputs":[{"name":"x","type":"uint"}]}]"#)
+                .starts_with("ABI entry 0: ")
+        );
+        assert!(Abi::from_reader(&b"[{\"type\":"[..])
+            .unwrap_err()
+            .to_string()
+            .starts_with("ABI entry 0: EOF"));
+    }
+
+    #[test]
+    fn vyper_abis() {
+        let abi: Abi = serde_json::from_str(include_str!("../testdata/vyper_0_2_pool.json"))
+            .expect("vyper 0.2 abi");
+
+        assert!(abi.constructor.is_some());
+        assert_eq!(abi.fallback, Some(StateMutability::Payable));
+        assert_eq!(abi.events.len(), 2);
+        assert_eq!(abi.functions.len(), 5);
+
+        let abi: Abi = serde_json::from_str(include_str!("../testdata/vyper_0_1_units.json"))
+            .expect("vyper 0.1 abi");
+
+        assert_eq!(abi.fallback, Some(StateMutability::Payable));
+        assert!(!abi.events[0].anonymous);
+        assert_eq!(
+            abi.functions
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["balanceOf", "transfer"]
+        );
+        assert_eq!(abi.functions[0].outputs[0].name, "");
+        assert_eq!(
+            abi.functions.iter().map(|f| f.gas).collect::<Vec<_>>(),
+            vec![Some(745), Some(74020)]
+        );
+
+        // Gas estimates are serialized even for ABIs built anew.
+        let fresh = Abi {
+            layout: Default::default(),
+            ..abi
+        };
+        let json = serde_json::to_value(&fresh).unwrap();
+        let transfer = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry["name"] == "transfer")
+            .unwrap();
+        assert_eq!(transfer["gas"], 74020);
+    }
+
+    #[test]
+    fn legacy_abi() {
+        let abi: Abi = serde_json::from_str(include_str!("../testdata/legacy_erc20.json"))
+            .expect("legacy abi");
+
+        assert_eq!(
+            abi.constructor.as_ref().map(|c| c.state_mutability),
+            Some(StateMutability::NonPayable)
+        );
+        assert_eq!(
+            abi.functions
+                .iter()
+                .map(|f| (f.name.as_str(), f.state_mutability))
+                .collect::<Vec<_>>(),
+            vec![
+                ("totalSupply", StateMutability::View),
+                ("transfer", StateMutability::NonPayable),
+                ("deposit", StateMutability::Payable),
+            ]
+        );
+        assert_eq!(abi.fallback, Some(StateMutability::Payable));
+        assert!(!abi.events[0].anonymous);
+    }
+
+    #[test]
+    fn oldest_abi_entries() {
+        let json = r#"[
+            {"name": "owner", "inputs": [], "outputs": [{"name": "", "type": "address"}]},
+            {"type": "function", "name": "kill", "inputs": [], "outputs": []},
+            {"type": "constructor", "inputs": []}
+        ]"#;
+        let abi: Abi = serde_json::from_str(json).expect("oldest abi");
+
+        assert_eq!(
+            abi.functions
+                .iter()
+                .map(|f| (f.signature(), f.state_mutability))
+                .collect::<Vec<_>>(),
+            vec![
+                ("owner()".to_string(), StateMutability::NonPayable),
+                ("kill()".to_string(), StateMutability::NonPayable),
+            ]
+        );
+        assert_eq!(
+            abi.constructor.as_ref().map(|c| c.state_mutability),
+            Some(StateMutability::NonPayable)
+        );
+
+        // Entries are written back as they were read.
+        let back: serde_json::Value = serde_json::to_value(&abi).unwrap();
+        assert_eq!(
+            back,
+            serde_json::from_str::<serde_json::Value>(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn interface_ids() {
+        let erc165 = Abi::builder()
+            .function(
+                "supportsInterface",
+                &[("interfaceId", "bytes4")],
+                &[("", "bool")],
+                StateMutability::View,
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(erc165.interface_id(), [0x01, 0xff, 0xc9, 0xa7]);
+
+        let erc721_metadata = Abi::builder()
+            .function("name", &[], &[("", "string")], StateMutability::View)
+            .function("symbol", &[], &[("", "string")], StateMutability::View)
+            .function(
+                "tokenURI",
+                &[("", "uint256")],
+                &[("", "string")],
+                StateMutability::View,
+            )
+            .function(
+                "totalSupply",
+                &[],
+                &[("", "uint256")],
+                StateMutability::View,
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            erc721_metadata
+                .interface_id_of(&["name", "symbol", "tokenURI"])
+                .unwrap(),
+            [0x5b, 0x5e, 0x13, 0x9f]
+        );
+        assert!(erc721_metadata.interface_id_of(&["ownerOf"]).is_err());
+    }
+
+    #[test]
+    fn overload_resolution() {
+        let abi = Abi::from_signatures(&[
+            "transfer(address,uint256)",
+            "transfer(address,uint8)",
+            "transfer(address,uint256,bytes)",
+            "approve(address,uint256)",
+        ])
+        .unwrap();
+
+        assert_eq!(abi.functions_by_name("transfer").len(), 3);
+        assert_eq!(
+            abi.function("approve").map(|f| f.signature()),
+            Some("approve(address,uint256)".to_string())
+        );
+        assert!(abi.function("burn").is_none());
+
+        let to = Value::Address(H160::zero());
+
+        let f = abi
+            .resolve_function("transfer", &[to.clone(), Value::Uint(U256::from(1), 256)])
+            .unwrap();
+        assert_eq!(f.signature(), "transfer(address,uint256)");
+
+        let f = abi
+            .resolve_function("transfer", &[to.clone(), Value::Uint(U256::from(1), 8)])
+            .unwrap();
+        assert_eq!(f.signature(), "transfer(address,uint8)");
+
+        // Only fits the uint256 overload.
+        let f = abi
+            .resolve_function("transfer", &[to.clone(), Value::Uint(U256::from(300), 16)])
+            .unwrap();
+        assert_eq!(f.signature(), "transfer(address,uint256)");
+
+        // Fits both, matches neither exactly.
+        let err = abi
+            .resolve_function("transfer", &[to.clone(), Value::Uint(U256::from(1), 16)])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ambiguous call to transfer, candidates: \
+             transfer(address,uint256), transfer(address,uint8)"
+        );
+
+        assert!(abi.resolve_function("transfer", &[to]).is_err());
+        assert!(abi.resolve_function("burn", &[]).is_err());
+    }
+
+    #[test]
+    fn lookup_by_selector_and_topic() {
+        let abi = Abi::from_signatures(&[
+            "transfer(address,uint256)",
+            "event Transfer(address,address,uint256)",
+            "error InsufficientBalance(uint256,uint256)",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            abi.function_by_selector([0xa9, 0x05, 0x9c, 0xbb]),
+            Some(&abi.functions[0])
+        );
+        assert_eq!(
+            abi.function_by_selector_hex("0xa9059cbb").unwrap(),
+            Some(&abi.functions[0])
+        );
+        assert_eq!(abi.function_by_selector_hex("095ea7b3").unwrap(), None);
+        assert!(abi.function_by_selector_hex("0xa9059c").is_err());
+        assert!(abi.function_by_selector_hex("0xzz059cbb").is_err());
+
+        assert_eq!(
+            abi.event_by_topic(abi.events[0].topic()),
+            Some(&abi.events[0])
+        );
+        assert_eq!(abi.event_by_topic(H256::zero()), None);
+
+        assert_eq!(abi.errors[0].selector(), [0xcf, 0x47, 0x91, 0x81]);
+        assert_eq!(
+            abi.error_by_selector([0xcf, 0x47, 0x91, 0x81]),
+            Some(&abi.errors[0])
+        );
+    }
+
+    #[test]
+    fn encode_call_by_name() {
+        let abi =
+            Abi::from_signatures(&["approve(address,uint256)", "setFee(uint24,int8[2])"]).unwrap();
+
+        let spender = H160::repeat_byte(0x11);
+        let input = abi
+            .encode_call(
+                "approve",
+                &[Value::Address(spender), Value::Uint(U256::from(1000), 64)],
+            )
+            .unwrap();
+
+        assert_eq!(
+            hex::encode(&input),
+            "095ea7b3\
+             0000000000000000000000001111111111111111111111111111111111111111\
+             00000000000000000000000000000000000000000000000000000000000003e8"
+        );
+
+        let (f, params) = abi.decode_input_from_slice(&input).unwrap();
+        assert_eq!(f.name, "approve");
+        assert_eq!(params[1], Value::Uint(U256::from(1000), 256));
+
+        let input_hex = abi
+            .encode_call_hex(
+                "approve",
+                &[Value::Address(spender), Value::Uint(U256::from(1000), 64)],
+            )
+            .unwrap();
+        assert_eq!(input_hex, format!("0x{}", hex::encode(&input)));
+        assert_eq!(
+            abi.decode_input_from_hex(&input_hex).unwrap(),
+            abi.decode_input_from_hex(&input_hex[2..]).unwrap()
+        );
+
+        let fees = Value::FixedArray(
+            vec![Value::Int(U256::MAX, 256), Value::Int(U256::from(1), 256)],
+            Type::Int(256).into(),
+        );
+        let input = abi
+            .encode_call("setFee", &[Value::Uint(U256::from(3000), 256), fees])
+            .unwrap();
+        let (_, params) = abi.decode_input_from_slice(&input).unwrap();
+
+        assert_eq!(
+            params[1],
+            Value::FixedArray(
+                vec![Value::Int(U256::MAX, 8), Value::Int(U256::from(1), 8)],
+                Type::Int(8).into(),
+            )
+        );
+
+        assert!(abi
+            .encode_call("approve", &[Value::Address(spender), Value::Bool(true)])
+            .is_err());
+        assert!(abi.functions[0]
+            .encode_input(&[Value::Address(spender)])
+            .is_err());
+    }
 }
@@ -0,0 +1,109 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::H160;
+
+use crate::{decode_hex, keccak::keccak256};
+
+/// Renders an address in the EIP-55 mixed-case checksum form.
+///
+/// This is the form used whenever the crate displays or exports an address.
+pub fn to_checksum(address: &H160) -> String {
+    let hex = faster_hex::hex_string(address.as_bytes());
+    let hash = keccak256(hex.as_bytes());
+
+    let checksummed: String = hex
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    format!("0x{}", checksummed)
+}
+
+/// Parses an address given in the EIP-55 checksum form, with or without
+/// `0x` prefix.
+///
+/// Fails if the letter casing does not match the checksum, including for all
+/// lowercase or all uppercase input, which carries no checksum at all.
+pub fn parse_checksummed(s: &str) -> Result<H160> {
+    let address = parse_hex_address(s)?;
+
+    if to_checksum(&address)[2..] != *s.strip_prefix("0x").unwrap_or(s) {
+        return Err(anyhow!("invalid address checksum: {}", s));
+    }
+
+    Ok(address)
+}
+
+/// Parses a hex address, checking its EIP-55 checksum only if it is given in
+/// mixed case.
+pub(crate) fn parse_address(s: &str) -> Result<H160> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+
+    let has_lower = digits.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = digits.chars().any(|c| c.is_ascii_uppercase());
+
+    if has_lower && has_upper {
+        parse_checksummed(s)
+    } else {
+        parse_hex_address(s)
+    }
+}
+
+fn parse_hex_address(s: &str) -> Result<H160> {
+    let bytes = decode_hex(s).map_err(|err| anyhow!("invalid address {:?}: {}", s, err))?;
+
+    if bytes.len() != 20 {
+        return Err(anyhow!("address must be 20 bytes, got {}", bytes.len()));
+    }
+
+    Ok(H160::from_slice(&bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn eip55_checksum() {
+        // Test vectors from EIP-55.
+        for expected in &[
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let address: H160 = expected[2..].parse().unwrap();
+
+            assert_eq!(to_checksum(&address), *expected);
+        }
+    }
+
+    #[test]
+    fn checksum_validation() {
+        let address: H160 = "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap();
+
+        assert_eq!(
+            parse_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").unwrap(),
+            address
+        );
+        assert!(parse_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").is_err());
+        assert!(parse_checksummed("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_err());
+        assert!(parse_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA").is_err());
+
+        assert_eq!(
+            parse_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap(),
+            address
+        );
+        assert!(parse_address("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+    }
+}
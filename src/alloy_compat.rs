@@ -0,0 +1,528 @@
+//! Conversions from and to the ABI and value types of the alloy crates.
+//!
+//! Fixed point types, which alloy does not support, convert to the integer
+//! types they are encoded as.
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_json_abi::{InternalType, JsonAbi};
+use alloy_primitives::{Address, B256, I256};
+use anyhow::{anyhow, Result};
+use ethereum_types::{H160, U256};
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+    sync::Arc,
+};
+
+use crate::{
+    params::ParamEntry, Abi, Constructor, Error, Event, Function, Param, StateMutability, Type,
+    Value,
+};
+
+impl From<&Type> for DynSolType {
+    fn from(ty: &Type) -> Self {
+        match ty {
+            Type::Uint(size) | Type::Ufixed(size, _) => DynSolType::Uint(*size),
+            Type::Int(size) | Type::Fixed(size, _) => DynSolType::Int(*size),
+            Type::Address => DynSolType::Address,
+            Type::Bool => DynSolType::Bool,
+            Type::Function => DynSolType::Function,
+            Type::FixedBytes(size) => DynSolType::FixedBytes(*size),
+            Type::FixedArray(ty, size) => {
+                DynSolType::FixedArray(Box::new(ty.as_ref().into()), *size)
+            }
+            Type::String => DynSolType::String,
+            Type::Bytes => DynSolType::Bytes,
+            Type::Array(ty) => DynSolType::Array(Box::new(ty.as_ref().into())),
+            Type::Tuple(tys) | Type::Struct(_, tys) => {
+                DynSolType::Tuple(tys.iter().map(|(_, ty)| ty.into()).collect())
+            }
+        }
+    }
+}
+
+impl TryFrom<DynSolType> for Type {
+    type Error = anyhow::Error;
+
+    fn try_from(ty: DynSolType) -> Result<Self> {
+        Ok(match ty {
+            DynSolType::Bool => Type::Bool,
+            DynSolType::Int(size) => Type::Int(size),
+            DynSolType::Uint(size) => Type::Uint(size),
+            DynSolType::FixedBytes(size) => Type::FixedBytes(size),
+            DynSolType::Address => Type::Address,
+            DynSolType::Function => Type::Function,
+            DynSolType::Bytes => Type::Bytes,
+            DynSolType::String => Type::String,
+            DynSolType::Array(ty) => Type::Array(Box::new((*ty).try_into()?)),
+            DynSolType::FixedArray(ty, size) => Type::FixedArray(Box::new((*ty).try_into()?), size),
+            DynSolType::Tuple(tys) => Type::Tuple(
+                tys.into_iter()
+                    .map(|ty| Ok((String::new(), ty.try_into()?)))
+                    .collect::<Result<_>>()?,
+            ),
+            #[allow(unreachable_patterns)]
+            ty => return Err(anyhow!("unsupported alloy type {}", ty)),
+        })
+    }
+}
+
+impl From<&Value> for DynSolValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Uint(v, size) | Value::Ufixed(v, size, _) => {
+                DynSolValue::Uint(to_alloy(*v), *size)
+            }
+            Value::Int(v, size) | Value::Fixed(v, size, _) => {
+                DynSolValue::Int(I256::from_raw(to_alloy(*v)), *size)
+            }
+            Value::Address(address) => DynSolValue::Address(Address::from(address.0)),
+            Value::Bool(b) => DynSolValue::Bool(*b),
+            Value::Function(address, selector) => {
+                DynSolValue::Function(alloy_primitives::Function::from_slice(
+                    &[address.as_bytes(), &selector[..]].concat(),
+                ))
+            }
+            Value::FixedBytes(bytes) => {
+                DynSolValue::FixedBytes(B256::right_padding_from(bytes), bytes.len())
+            }
+            Value::FixedArray(values, _) => {
+                DynSolValue::FixedArray(values.iter().map(Into::into).collect())
+            }
+            Value::String(s) => DynSolValue::String(s.clone()),
+            Value::Bytes(bytes) => DynSolValue::Bytes(bytes.to_vec()),
+            Value::Array(values, _) => DynSolValue::Array(values.iter().map(Into::into).collect()),
+            Value::Tuple(values) => {
+                DynSolValue::Tuple(values.iter().map(|(_, value)| value.into()).collect())
+            }
+            Value::Hash(hash) => DynSolValue::FixedBytes(B256::from(hash.0), 32),
+        }
+    }
+}
+
+impl Value {
+    /// Converts an alloy value to a value of the given type.
+    ///
+    /// Array element types and tuple component names are taken from the
+    /// type, as alloy values do not carry them. Fails if the value does not
+    /// match the type.
+    pub fn from_sol_value(value: DynSolValue, ty: &Type) -> Result<Value> {
+        let values = |values: Vec<DynSolValue>, ty: &Type| -> Result<Vec<Value>> {
+            values
+                .into_iter()
+                .map(|value| Value::from_sol_value(value, ty))
+                .collect()
+        };
+
+        Ok(match (value, ty) {
+            (DynSolValue::Uint(v, size), Type::Uint(expected)) if size == *expected => {
+                Value::Uint(from_alloy(v), size)
+            }
+            (DynSolValue::Uint(v, size), Type::Ufixed(expected, decimals)) if size == *expected => {
+                Value::Ufixed(from_alloy(v), size, *decimals)
+            }
+            (DynSolValue::Int(v, size), Type::Int(expected)) if size == *expected => {
+                Value::Int(from_alloy(v.into_raw()), size)
+            }
+            (DynSolValue::Int(v, size), Type::Fixed(expected, decimals)) if size == *expected => {
+                Value::Fixed(from_alloy(v.into_raw()), size, *decimals)
+            }
+            (DynSolValue::Address(address), Type::Address) => {
+                Value::Address(H160(address.into_array()))
+            }
+            (DynSolValue::Bool(b), Type::Bool) => Value::Bool(b),
+            (DynSolValue::Function(function), Type::Function) => Value::Function(
+                H160::from_slice(&function[..20]),
+                function[20..].try_into()?,
+            ),
+            (DynSolValue::FixedBytes(word, size), Type::FixedBytes(expected))
+                if size == *expected =>
+            {
+                Value::FixedBytes(word[..size].to_vec())
+            }
+            (DynSolValue::FixedArray(items), Type::FixedArray(ty, size))
+                if items.len() == *size =>
+            {
+                Value::FixedArray(values(items, ty)?, Arc::new((**ty).clone()))
+            }
+            (DynSolValue::String(s), Type::String) => Value::String(s),
+            (DynSolValue::Bytes(bytes), Type::Bytes) => Value::Bytes(bytes.into()),
+            (DynSolValue::Array(items), Type::Array(ty)) => {
+                Value::Array(values(items, ty)?, Arc::new((**ty).clone()))
+            }
+            (DynSolValue::Tuple(items), Type::Tuple(tys))
+            | (DynSolValue::Tuple(items), Type::Struct(_, tys))
+                if items.len() == tys.len() =>
+            {
+                Value::Tuple(
+                    items
+                        .into_iter()
+                        .zip(tys)
+                        .map(|(item, (name, ty))| {
+                            Ok((name.clone(), Value::from_sol_value(item, ty)?))
+                        })
+                        .collect::<Result<_>>()?,
+                )
+            }
+            (value, ty) => return Err(anyhow!("alloy value {:?} is not a {}", value, ty)),
+        })
+    }
+}
+
+fn to_alloy(v: U256) -> alloy_primitives::U256 {
+    alloy_primitives::U256::from_limbs(v.0)
+}
+
+fn from_alloy(v: alloy_primitives::U256) -> U256 {
+    U256(v.into_limbs())
+}
+
+impl From<StateMutability> for alloy_json_abi::StateMutability {
+    fn from(state_mutability: StateMutability) -> Self {
+        match state_mutability {
+            StateMutability::Pure => alloy_json_abi::StateMutability::Pure,
+            StateMutability::View => alloy_json_abi::StateMutability::View,
+            StateMutability::NonPayable => alloy_json_abi::StateMutability::NonPayable,
+            StateMutability::Payable => alloy_json_abi::StateMutability::Payable,
+        }
+    }
+}
+
+impl From<alloy_json_abi::StateMutability> for StateMutability {
+    fn from(state_mutability: alloy_json_abi::StateMutability) -> Self {
+        match state_mutability {
+            alloy_json_abi::StateMutability::Pure => StateMutability::Pure,
+            alloy_json_abi::StateMutability::View => StateMutability::View,
+            alloy_json_abi::StateMutability::NonPayable => StateMutability::NonPayable,
+            alloy_json_abi::StateMutability::Payable => StateMutability::Payable,
+        }
+    }
+}
+
+fn to_alloy_param(entry: ParamEntry) -> alloy_json_abi::Param {
+    alloy_json_abi::Param {
+        ty: entry.type_,
+        name: entry.name,
+        components: entry
+            .components
+            .unwrap_or_default()
+            .into_iter()
+            .map(to_alloy_param)
+            .collect(),
+        internal_type: entry.internal_type.as_deref().and_then(InternalType::parse),
+    }
+}
+
+fn from_alloy_param(param: alloy_json_abi::Param, indexed: Option<bool>) -> ParamEntry {
+    ParamEntry {
+        name: param.name,
+        type_: param.ty,
+        indexed,
+        internal_type: param.internal_type.map(|it| it.to_string()),
+        components: Some(param.components)
+            .filter(|components| !components.is_empty())
+            .map(|components| {
+                components
+                    .into_iter()
+                    .map(|param| from_alloy_param(param, None))
+                    .collect()
+            }),
+    }
+}
+
+impl From<&Param> for alloy_json_abi::Param {
+    fn from(param: &Param) -> Self {
+        to_alloy_param(param.build_param_entry())
+    }
+}
+
+impl TryFrom<alloy_json_abi::Param> for Param {
+    type Error = anyhow::Error;
+
+    fn try_from(param: alloy_json_abi::Param) -> Result<Self> {
+        Param::from_param_entry(from_alloy_param(param, None))
+    }
+}
+
+impl From<&Param> for alloy_json_abi::EventParam {
+    fn from(param: &Param) -> Self {
+        let param_entry = param.build_param_entry();
+        let indexed = param_entry.indexed.unwrap_or(false);
+        let param = to_alloy_param(param_entry);
+
+        Self {
+            ty: param.ty,
+            name: param.name,
+            indexed,
+            components: param.components,
+            internal_type: param.internal_type,
+        }
+    }
+}
+
+impl TryFrom<alloy_json_abi::EventParam> for Param {
+    type Error = anyhow::Error;
+
+    fn try_from(param: alloy_json_abi::EventParam) -> Result<Self> {
+        let indexed = Some(param.indexed);
+        let param = alloy_json_abi::Param {
+            ty: param.ty,
+            name: param.name,
+            components: param.components,
+            internal_type: param.internal_type,
+        };
+
+        Param::from_param_entry(from_alloy_param(param, indexed))
+    }
+}
+
+fn try_map<T, U: TryFrom<T, Error = anyhow::Error>>(items: Vec<T>) -> Result<Vec<U>> {
+    items.into_iter().map(U::try_from).collect()
+}
+
+impl From<&Function> for alloy_json_abi::Function {
+    fn from(function: &Function) -> Self {
+        Self {
+            name: function.name.clone(),
+            inputs: function.inputs.iter().map(Into::into).collect(),
+            outputs: function.outputs.iter().map(Into::into).collect(),
+            state_mutability: function.state_mutability.into(),
+        }
+    }
+}
+
+impl TryFrom<alloy_json_abi::Function> for Function {
+    type Error = anyhow::Error;
+
+    fn try_from(function: alloy_json_abi::Function) -> Result<Self> {
+        Ok(Self::new(
+            function.name,
+            try_map(function.inputs)?,
+            try_map(function.outputs)?,
+            function.state_mutability.into(),
+        ))
+    }
+}
+
+impl From<&Event> for alloy_json_abi::Event {
+    fn from(event: &Event) -> Self {
+        Self {
+            name: event.name.clone(),
+            inputs: event.inputs.iter().map(Into::into).collect(),
+            anonymous: event.anonymous,
+        }
+    }
+}
+
+impl TryFrom<alloy_json_abi::Event> for Event {
+    type Error = anyhow::Error;
+
+    fn try_from(event: alloy_json_abi::Event) -> Result<Self> {
+        Ok(Self::new(
+            event.name,
+            try_map(event.inputs)?,
+            event.anonymous,
+        ))
+    }
+}
+
+impl From<&Error> for alloy_json_abi::Error {
+    fn from(error: &Error) -> Self {
+        Self {
+            name: error.name.clone(),
+            inputs: error.inputs.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<alloy_json_abi::Error> for Error {
+    type Error = anyhow::Error;
+
+    fn try_from(error: alloy_json_abi::Error) -> Result<Self> {
+        Ok(Self::new(error.name, try_map(error.inputs)?))
+    }
+}
+
+impl From<&Abi> for JsonAbi {
+    /// Converts an ABI, giving its fallback function, if any, the
+    /// `nonpayable` state mutability.
+    fn from(abi: &Abi) -> Self {
+        fn by_name<'a, T: 'a, U: From<&'a T>>(
+            items: &'a [T],
+            name: impl Fn(&T) -> &str,
+        ) -> BTreeMap<String, Vec<U>> {
+            let mut map: BTreeMap<String, Vec<U>> = BTreeMap::new();
+
+            for item in items {
+                map.entry(name(item).to_string())
+                    .or_default()
+                    .push(item.into());
+            }
+
+            map
+        }
+
+        Self {
+            constructor: abi
+                .constructor
+                .as_ref()
+                .map(|constructor| alloy_json_abi::Constructor {
+                    inputs: constructor.inputs.iter().map(Into::into).collect(),
+                    state_mutability: constructor.state_mutability.into(),
+                }),
+            fallback: abi
+                .fallback
+                .map(|state_mutability| alloy_json_abi::Fallback {
+                    state_mutability: state_mutability.into(),
+                }),
+            receive: abi.receive.map(|state_mutability| alloy_json_abi::Receive {
+                state_mutability: state_mutability.into(),
+            }),
+            functions: by_name(&abi.functions, |f| &f.name),
+            events: by_name(&abi.events, |e| &e.name),
+            errors: by_name(&abi.errors, |e| &e.name),
+        }
+    }
+}
+
+impl TryFrom<JsonAbi> for Abi {
+    type Error = anyhow::Error;
+
+    fn try_from(abi: JsonAbi) -> Result<Self> {
+        Ok(Self {
+            constructor: abi
+                .constructor
+                .map(|constructor| -> Result<_> {
+                    Ok(Constructor {
+                        inputs: try_map(constructor.inputs)?,
+                        state_mutability: constructor.state_mutability.into(),
+                    })
+                })
+                .transpose()?,
+            functions: try_map(abi.functions.into_values().flatten().collect())?,
+            events: try_map(abi.events.into_values().flatten().collect())?,
+            errors: try_map(abi.errors.into_values().flatten().collect())?,
+            receive: abi.receive.map(|receive| receive.state_mutability.into()),
+            fallback: abi
+                .fallback
+                .map(|fallback| fallback.state_mutability.into()),
+            layout: Default::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::H256;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn alloy_values() {
+        let ty = Type::Tuple(vec![
+            ("amount".into(), Type::Int(64)),
+            ("to".into(), Type::Array(Box::new(Type::Address))),
+            ("callback".into(), Type::Function),
+            ("tag".into(), Type::FixedBytes(4)),
+            ("data".into(), Type::FixedArray(Box::new(Type::Bytes), 2)),
+        ]);
+        let value = Value::Tuple(vec![
+            ("amount".into(), Value::Int(U256::MAX, 64)),
+            (
+                "to".into(),
+                Value::Array(
+                    vec![Value::Address(H160::repeat_byte(1))],
+                    Type::Address.into(),
+                ),
+            ),
+            (
+                "callback".into(),
+                Value::Function(H160::repeat_byte(2), [1, 2, 3, 4]),
+            ),
+            ("tag".into(), Value::FixedBytes(vec![5, 6, 7, 8])),
+            (
+                "data".into(),
+                Value::FixedArray(
+                    vec![Value::Bytes(vec![1].into()), Value::Bytes(vec![].into())],
+                    Type::Bytes.into(),
+                ),
+            ),
+        ]);
+
+        let sol_value = DynSolValue::from(&value);
+        let sol_type = DynSolType::from(&ty);
+
+        // Both encoders agree, and values survive the round trip.
+        let encoded = Value::encode(std::slice::from_ref(&value));
+        assert_eq!(sol_value.abi_encode(), encoded);
+        assert_eq!(sol_value.as_type(), Some(sol_type.clone()));
+
+        let decoded = sol_type.abi_decode(&encoded).unwrap();
+        assert_eq!(Value::from_sol_value(decoded, &ty).unwrap(), value);
+
+        let word = DynSolValue::FixedBytes(B256::from(H256::repeat_byte(1).0), 32);
+        assert_eq!(
+            Value::from_sol_value(word, &Type::FixedBytes(32)).unwrap(),
+            Value::FixedBytes(vec![1; 32])
+        );
+
+        let uint8 = DynSolValue::Uint(alloy_primitives::U256::ZERO, 8);
+        assert!(Value::from_sol_value(uint8, &Type::Uint(16)).is_err());
+
+        // Component names are lost going through alloy types.
+        assert_eq!(
+            Type::try_from(DynSolType::Tuple(vec![DynSolType::Bool])).unwrap(),
+            Type::Tuple(vec![(String::new(), Type::Bool)])
+        );
+    }
+
+    #[test]
+    fn alloy_json_abi() {
+        let json = r#"[
+            {"type": "constructor", "inputs": [{"name": "owner", "type": "address", "internalType": "address"}], "stateMutability": "payable"},
+            {"type": "function", "name": "f", "inputs": [{"name": "x", "type": "uint256", "internalType": "uint256"}], "outputs": [], "stateMutability": "view"},
+            {"type": "function", "name": "f", "inputs": [{"name": "x", "type": "bytes", "internalType": "bytes"}], "outputs": [{"name": "", "type": "bool", "internalType": "bool"}], "stateMutability": "payable"},
+            {
+                "type": "function",
+                "name": "fill",
+                "inputs": [{
+                    "name": "orders",
+                    "type": "tuple[]",
+                    "internalType": "struct Exchange.Order[]",
+                    "components": [{"name": "maker", "type": "address"}]
+                }],
+                "outputs": [],
+                "stateMutability": "nonpayable"
+            },
+            {"type": "event", "name": "E", "inputs": [{"name": "from", "type": "address", "indexed": true, "internalType": "address"}, {"name": "v", "type": "uint256", "indexed": false, "internalType": "uint256"}], "anonymous": false},
+            {"type": "error", "name": "Unauthorized", "inputs": [{"name": "caller", "type": "address", "internalType": "address"}]},
+            {"type": "receive", "stateMutability": "payable"}
+        ]"#;
+
+        let abi: Abi = serde_json::from_str(json).unwrap();
+        let json_abi: JsonAbi = serde_json::from_str(json).unwrap();
+
+        assert_eq!(JsonAbi::from(&abi), json_abi);
+
+        // ABIs serialized anew, rather than as they were parsed, are read
+        // back the same.
+        let fresh = Abi {
+            layout: Default::default(),
+            ..abi.clone()
+        };
+        assert_eq!(
+            serde_json::from_value::<JsonAbi>(serde_json::to_value(&fresh).unwrap()).unwrap(),
+            json_abi
+        );
+
+        let back = Abi::try_from(json_abi).unwrap();
+
+        assert_eq!(back.constructor, abi.constructor);
+        assert_eq!(back.events, abi.events);
+        assert_eq!(back.errors, abi.errors);
+        assert_eq!(back.function("fill"), abi.function("fill"));
+        assert_eq!(back.functions_by_name("f").len(), 2);
+        assert_eq!(back.receive, Some(StateMutability::Payable));
+    }
+}
@@ -0,0 +1,221 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::U256;
+
+use crate::{types::Type, Value};
+
+/// Lazy iterator over the elements of an encoded array, decoding each one
+/// only when it is reached.
+///
+/// Created by [`Value::decode_array_iter`] and [`CalldataView::param_iter`].
+/// The iterator stops after the first error, as the elements following a
+/// malformed one can't be located reliably.
+///
+/// [`CalldataView::param_iter`]: crate::CalldataView::param_iter
+#[derive(Debug, Clone)]
+pub struct ArrayIter<'a> {
+    bs: &'a [u8],
+    ty: &'a Type,
+    base_addr: usize,
+    at: usize,
+    remaining: usize,
+}
+
+impl<'a> ArrayIter<'a> {
+    /// Creates an iterator over the array of type `ty` whose head is at `at`
+    /// in `bs`.
+    pub(crate) fn new(bs: &'a [u8], ty: &'a Type, at: usize) -> Result<Self> {
+        let (ty, base_addr, len) = match ty {
+            Type::Array(ty) => {
+                let offset = read_usize(bs, at, "array offset")?;
+                let len = read_usize(bs, offset, "array length")?;
+
+                (&**ty, offset + 32, len)
+            }
+
+            Type::FixedArray(ty, size) if ty.is_dynamic() => {
+                (&**ty, read_usize(bs, at, "array offset")?, *size)
+            }
+
+            Type::FixedArray(ty, size) => (&**ty, at, *size),
+
+            _ => return Err(anyhow!("expected an array type, got {}", ty)),
+        };
+
+        if base_addr > bs.len() {
+            return Err(anyhow!("invalid array offset {}", base_addr));
+        }
+
+        Ok(Self {
+            bs,
+            ty,
+            base_addr,
+            at: 0,
+            remaining: len,
+        })
+    }
+
+    /// Returns the type of the array's elements.
+    pub fn element_type(&self) -> &'a Type {
+        self.ty
+    }
+}
+
+impl Iterator for ArrayIter<'_> {
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        match Value::decode(self.bs, self.ty, self.base_addr, self.at) {
+            Ok((value, consumed)) => {
+                self.remaining -= 1;
+                self.at += consumed;
+
+                Some(Ok(value))
+            }
+            Err(err) => {
+                self.remaining = 0;
+
+                Some(Err(err))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for ArrayIter<'_> {}
+
+impl Value {
+    /// Decodes an encoded array of type `ty` lazily, yielding its elements
+    /// one at a time.
+    ///
+    /// `data` holds the array as a lone value, as [`Value::encode`] lays it
+    /// out. Elements are only decoded as the iterator reaches them, so a
+    /// huge array can be processed, or given up on, without building the
+    /// whole [`Value::Array`].
+    ///
+    /// ```
+    /// use ethereum_abi::{Type, Value};
+    ///
+    /// let ty = Type::Array(Box::new(Type::Uint(256)));
+    /// let data = Value::encode(&[Value::Array(
+    ///     (0..1000u64).map(|i| Value::Uint(i.into(), 256)).collect(),
+    ///     Type::Uint(256).into(),
+    /// )]);
+    ///
+    /// let mut elements = Value::decode_array_iter(&data, &ty).unwrap();
+    /// assert_eq!(elements.len(), 1000);
+    ///
+    /// let first_big = elements.find(|v| matches!(v, Ok(Value::Uint(n, _)) if *n > 500.into()));
+    /// assert_eq!(first_big.unwrap().unwrap(), Value::Uint(501.into(), 256));
+    /// ```
+    pub fn decode_array_iter<'a>(data: &'a [u8], ty: &'a Type) -> Result<ArrayIter<'a>> {
+        ArrayIter::new(data, ty, 0)
+    }
+}
+
+fn read_usize(bs: &[u8], at: usize, what: &str) -> Result<usize> {
+    let slice = at
+        .checked_add(32)
+        .and_then(|end| bs.get(at..end))
+        .ok_or_else(|| anyhow!("reached end of input while decoding {}", what))?;
+    let n = U256::from_big_endian(slice);
+
+    if n > U256::from(usize::MAX) {
+        return Err(anyhow!("invalid {} {}", what, n));
+    }
+
+    Ok(n.as_usize())
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn uints(n: u64) -> Vec<Value> {
+        (0..n).map(|i| Value::Uint(i.into(), 256)).collect()
+    }
+
+    #[test]
+    fn iterate_dynamic_array() {
+        let ty = Type::Array(Box::new(Type::Uint(256)));
+        let data = Value::encode(&[Value::Array(uints(5), Type::Uint(256).into())]);
+
+        let elements = Value::decode_array_iter(&data, &ty)
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(elements, uints(5));
+    }
+
+    #[test]
+    fn iterate_arrays_of_dynamic_elements() {
+        let strings = vec![
+            Value::String("foo".to_string()),
+            Value::String("a much longer string, spanning two words".to_string()),
+        ];
+
+        let ty = Type::Array(Box::new(Type::String));
+        let data = Value::encode(&[Value::Array(strings.clone(), Type::String.into())]);
+        let elements = Value::decode_array_iter(&data, &ty).unwrap();
+        assert_eq!(elements.collect::<Result<Vec<_>>>().unwrap(), strings);
+
+        let ty = Type::FixedArray(Box::new(Type::String), 2);
+        let data = Value::encode(&[Value::FixedArray(strings.clone(), Type::String.into())]);
+        let elements = Value::decode_array_iter(&data, &ty).unwrap();
+        assert_eq!(elements.collect::<Result<Vec<_>>>().unwrap(), strings);
+    }
+
+    #[test]
+    fn iterate_fixed_array() {
+        let ty = Type::FixedArray(Box::new(Type::Uint(256)), 3);
+        let data = Value::encode(&[Value::FixedArray(uints(3), Type::Uint(256).into())]);
+
+        let mut elements = Value::decode_array_iter(&data, &ty).unwrap();
+
+        assert_eq!(elements.element_type(), &Type::Uint(256));
+        assert_eq!(elements.len(), 3);
+        assert_eq!(
+            elements.nth(2).unwrap().unwrap(),
+            Value::Uint(2.into(), 256)
+        );
+        assert!(elements.next().is_none());
+    }
+
+    #[test]
+    fn stop_at_truncated_element() {
+        let ty = Type::Array(Box::new(Type::Uint(256)));
+        let mut data = Value::encode(&[Value::Array(uints(4), Type::Uint(256).into())]);
+        data.truncate(data.len() - 16);
+
+        let mut elements = Value::decode_array_iter(&data, &ty).unwrap();
+
+        assert_eq!(elements.by_ref().take(3).count(), 3);
+        assert!(elements.next().unwrap().is_err());
+        assert!(elements.next().is_none());
+    }
+
+    #[test]
+    fn reject_invalid_arrays() {
+        let ty = Type::Array(Box::new(Type::Uint(256)));
+
+        assert!(Value::decode_array_iter(&[], &ty).is_err());
+        assert!(Value::decode_array_iter(&[0xff; 32], &ty).is_err());
+        assert!(Value::decode_array_iter(&[0; 64], &Type::Uint(256)).is_err());
+
+        // A bogus length is only noticed once the input runs out.
+        let mut data = [0u8; 64];
+        data[31] = 32;
+        data[60] = 0xff;
+        let mut elements = Value::decode_array_iter(&data, &ty).unwrap();
+        assert!(elements.next().unwrap().is_err());
+    }
+}
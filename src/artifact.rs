@@ -0,0 +1,607 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::H160;
+use serde::Deserialize;
+use std::{collections::BTreeMap, io::Read};
+
+use crate::{decode_hex, encode_hex, hex_encoding::decode_hex_to_slice, Abi};
+
+/// Compiled contract artifact: the contract ABI plus the auxiliary data build
+/// tools store alongside it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Artifact {
+    /// Contract name.
+    pub contract_name: Option<String>,
+    /// Path of the source file defining the contract.
+    pub source_name: Option<String>,
+    /// Contract ABI, with the NatSpec documentation of `userdoc` and
+    /// `devdoc` attached.
+    pub abi: Abi,
+    /// Hex encoded creation bytecode (without `0x` prefix).
+    ///
+    /// Bytecode of contracts using external libraries may hold unlinked
+    /// placeholders, which is why it is kept as text.
+    pub bytecode: Option<String>,
+    /// Hex encoded runtime bytecode (without `0x` prefix).
+    pub deployed_bytecode: Option<String>,
+    /// Function selectors by signature, as reported by the compiler.
+    pub method_identifiers: BTreeMap<String, [u8; 4]>,
+    /// Deployed contract addresses by chain id.
+    pub networks: BTreeMap<u64, H160>,
+    /// Storage layout, if requested from the compiler.
+    pub storage_layout: Option<serde_json::Value>,
+    /// User documentation (NatSpec `@notice`), if requested from the compiler.
+    pub userdoc: Option<serde_json::Value>,
+    /// Developer documentation (NatSpec `@dev`, `@param`, ...), if requested
+    /// from the compiler.
+    pub devdoc: Option<serde_json::Value>,
+    /// Full compiler version, e.g. `0.8.19+commit.7dd6d404`, if recorded.
+    pub compiler_version: Option<String>,
+    /// Compiler settings (optimizer, EVM version, remappings, ...) as found
+    /// in the compiler metadata, if recorded.
+    pub compiler_settings: Option<serde_json::Value>,
+}
+
+impl Artifact {
+    /// Parses a Hardhat artifact (`artifacts/**/<Contract>.json`).
+    pub fn from_hardhat_json(json: &str) -> Result<Self> {
+        Self::from_hardhat(serde_json::from_str(json)?)
+    }
+
+    /// Parses a Hardhat artifact from a reader.
+    pub fn from_hardhat_reader<R: Read>(reader: R) -> Result<Self> {
+        Self::from_hardhat(serde_json::from_reader(reader)?)
+    }
+
+    /// Parses a Foundry artifact (`out/<File>.sol/<Contract>.json`).
+    ///
+    /// The `methodIdentifiers` reported by the compiler are cross-checked
+    /// against the selectors computed from the parsed ABI.
+    pub fn from_foundry_json(json: &str) -> Result<Self> {
+        Self::from_foundry(serde_json::from_str(json)?)
+    }
+
+    /// Parses a Foundry artifact from a reader.
+    pub fn from_foundry_reader<R: Read>(reader: R) -> Result<Self> {
+        Self::from_foundry(serde_json::from_reader(reader)?)
+    }
+
+    /// Parses a Truffle artifact (`build/contracts/<Contract>.json`).
+    pub fn from_truffle_json(json: &str) -> Result<Self> {
+        Self::from_truffle(serde_json::from_str(json)?)
+    }
+
+    /// Parses a Truffle artifact from a reader.
+    pub fn from_truffle_reader<R: Read>(reader: R) -> Result<Self> {
+        Self::from_truffle(serde_json::from_reader(reader)?)
+    }
+
+    /// Parses a compiler metadata file (`metadata.json`), as published
+    /// alongside verified contracts.
+    ///
+    /// The metadata holds no bytecode, so the returned artifact has none.
+    pub fn from_solc_metadata_json(json: &str) -> Result<Self> {
+        Self::from_solc_metadata(serde_json::from_str(json)?)
+    }
+
+    /// Returns the address the contract is deployed at on the given chain.
+    pub fn address(&self, chain_id: u64) -> Option<H160> {
+        self.networks.get(&chain_id).copied()
+    }
+
+    /// Checks that the compiler reported method identifiers match the
+    /// selectors computed from the ABI.
+    ///
+    /// Artifacts without method identifiers always pass.
+    pub fn check_method_identifiers(&self) -> Result<()> {
+        if self.method_identifiers.is_empty() {
+            return Ok(());
+        }
+
+        for f in &self.abi.functions {
+            let signature = f.signature();
+
+            match self.method_identifiers.get(&signature) {
+                Some(selector) if *selector == f.method_id() => {}
+                Some(selector) => {
+                    return Err(anyhow!(
+                        "selector mismatch for {}: computed {}, compiler reported {}",
+                        signature,
+                        encode_hex(f.method_id()),
+                        encode_hex(selector)
+                    ))
+                }
+                None => {
+                    return Err(anyhow!(
+                        "function {} missing from method identifiers",
+                        signature
+                    ))
+                }
+            }
+        }
+
+        if self.method_identifiers.len() != self.abi.functions.len() {
+            return Err(anyhow!(
+                "{} method identifiers reported for {} ABI functions",
+                self.method_identifiers.len(),
+                self.abi.functions.len()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the creation bytecode.
+    ///
+    /// Fails if the bytecode still holds unlinked library placeholders.
+    pub fn decode_bytecode(&self) -> Result<Option<Vec<u8>>> {
+        decode_bytecode(self.bytecode.as_deref())
+    }
+
+    /// Decodes the runtime bytecode.
+    ///
+    /// Fails if the bytecode still holds unlinked library placeholders.
+    pub fn decode_deployed_bytecode(&self) -> Result<Option<Vec<u8>>> {
+        decode_bytecode(self.deployed_bytecode.as_deref())
+    }
+
+    pub(crate) fn from_solc_metadata(metadata: SolcMetadata) -> Result<Self> {
+        let (source_name, contract_name) = metadata.compilation_target().unzip();
+        let output = metadata
+            .output
+            .ok_or_else(|| anyhow!("compiler metadata has no output"))?;
+
+        Ok(Self {
+            contract_name,
+            source_name,
+            abi: output.abi,
+            bytecode: None,
+            deployed_bytecode: None,
+            method_identifiers: BTreeMap::new(),
+            networks: BTreeMap::new(),
+            storage_layout: None,
+            userdoc: output.userdoc,
+            devdoc: output.devdoc,
+            compiler_version: metadata.compiler.map(|compiler| compiler.version),
+            compiler_settings: Some(metadata.settings),
+        }
+        .with_natspec())
+    }
+
+    pub(crate) fn with_natspec(mut self) -> Self {
+        self.abi
+            .attach_natspec(self.userdoc.as_ref(), self.devdoc.as_ref());
+        self
+    }
+
+    fn from_hardhat(artifact: HardhatArtifact) -> Result<Self> {
+        Ok(Self {
+            contract_name: artifact.contract_name,
+            source_name: artifact.source_name,
+            abi: artifact.abi,
+            bytecode: normalize_bytecode(artifact.bytecode),
+            deployed_bytecode: normalize_bytecode(artifact.deployed_bytecode),
+            method_identifiers: BTreeMap::new(),
+            networks: BTreeMap::new(),
+            storage_layout: None,
+            userdoc: None,
+            devdoc: None,
+            compiler_version: None,
+            compiler_settings: None,
+        })
+    }
+
+    fn from_truffle(artifact: TruffleArtifact) -> Result<Self> {
+        let mut networks = BTreeMap::new();
+
+        for (chain_id, network) in artifact.networks {
+            let chain_id = chain_id
+                .parse()
+                .map_err(|_| anyhow!("invalid network id: {}", chain_id))?;
+
+            // Networks the contract was only linked on have no address.
+            if let Some(address) = network.address {
+                let address = address.trim_start_matches("0x").parse().map_err(|_| {
+                    anyhow!("invalid address for network {}: {}", chain_id, address)
+                })?;
+
+                networks.insert(chain_id, address);
+            }
+        }
+
+        Ok(Self {
+            contract_name: artifact.contract_name,
+            source_name: artifact.source_path,
+            abi: artifact.abi,
+            bytecode: normalize_bytecode(artifact.bytecode),
+            deployed_bytecode: normalize_bytecode(artifact.deployed_bytecode),
+            method_identifiers: BTreeMap::new(),
+            networks,
+            storage_layout: None,
+            userdoc: None,
+            devdoc: None,
+            compiler_version: None,
+            compiler_settings: None,
+        })
+    }
+
+    fn from_foundry(artifact: FoundryArtifact) -> Result<Self> {
+        // Foundry only records the contract name in the compiler metadata.
+        let (source_name, contract_name) = artifact
+            .metadata
+            .as_ref()
+            .and_then(SolcMetadata::compilation_target)
+            .unzip();
+        let (compiler_version, compiler_settings) = artifact
+            .metadata
+            .map(|metadata| (metadata.compiler.map(|c| c.version), metadata.settings))
+            .unzip();
+
+        let artifact = Self {
+            contract_name,
+            source_name,
+            abi: artifact.abi,
+            bytecode: normalize_bytecode(artifact.bytecode.map(|code| code.object)),
+            deployed_bytecode: normalize_bytecode(
+                artifact.deployed_bytecode.map(|code| code.object),
+            ),
+            method_identifiers: parse_method_identifiers(artifact.method_identifiers)?,
+            networks: BTreeMap::new(),
+            storage_layout: None,
+            userdoc: None,
+            devdoc: None,
+            compiler_version: compiler_version.flatten(),
+            compiler_settings,
+        };
+
+        artifact.check_method_identifiers()?;
+
+        Ok(artifact)
+    }
+}
+
+impl Abi {
+    /// Parses a contract artifact, returning the ABI along with the
+    /// artifact's auxiliary fields.
+    ///
+    /// Unlike deserializing an [`Abi`] directly, which expects the bare JSON
+    /// array, this accepts the artifact object written by build tools.
+    /// Hardhat, Foundry and Truffle artifacts are supported.
+    pub fn from_artifact_json(json: &str) -> Result<Artifact> {
+        Self::from_artifact_value(serde_json::from_str(json)?)
+    }
+
+    /// Parses a contract artifact from a reader.
+    ///
+    /// See [`Abi::from_artifact_json`].
+    pub fn from_artifact_reader<R: Read>(reader: R) -> Result<Artifact> {
+        Self::from_artifact_value(serde_json::from_reader(reader)?)
+    }
+
+    pub(crate) fn from_artifact_value(value: serde_json::Value) -> Result<Artifact> {
+        // Foundry nests the bytecode in an object alongside its source map.
+        if value.get("bytecode").is_some_and(|code| code.is_object()) {
+            Artifact::from_foundry(serde_json::from_value(value)?)
+        } else if value.get("networks").is_some() {
+            Artifact::from_truffle(serde_json::from_value(value)?)
+        } else {
+            Artifact::from_hardhat(serde_json::from_value(value)?)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HardhatArtifact {
+    #[serde(default)]
+    contract_name: Option<String>,
+    #[serde(default)]
+    source_name: Option<String>,
+    abi: Abi,
+    #[serde(default)]
+    bytecode: Option<String>,
+    #[serde(default)]
+    deployed_bytecode: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TruffleArtifact {
+    #[serde(default)]
+    contract_name: Option<String>,
+    #[serde(default)]
+    source_path: Option<String>,
+    abi: Abi,
+    #[serde(default)]
+    bytecode: Option<String>,
+    #[serde(default)]
+    deployed_bytecode: Option<String>,
+    #[serde(default)]
+    networks: BTreeMap<String, TruffleNetwork>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TruffleNetwork {
+    #[serde(default)]
+    address: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FoundryArtifact {
+    abi: Abi,
+    #[serde(default)]
+    bytecode: Option<FoundryBytecode>,
+    #[serde(default)]
+    deployed_bytecode: Option<FoundryBytecode>,
+    #[serde(default)]
+    method_identifiers: BTreeMap<String, String>,
+    #[serde(default)]
+    metadata: Option<SolcMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FoundryBytecode {
+    object: String,
+}
+
+/// Compiler metadata file, as embedded in Foundry artifacts and published
+/// by Sourcify.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SolcMetadata {
+    #[serde(default)]
+    compiler: Option<SolcMetadataCompiler>,
+    #[serde(default)]
+    output: Option<SolcMetadataOutput>,
+    settings: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct SolcMetadataCompiler {
+    version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SolcMetadataOutput {
+    abi: Abi,
+    #[serde(default)]
+    userdoc: Option<serde_json::Value>,
+    #[serde(default)]
+    devdoc: Option<serde_json::Value>,
+}
+
+impl SolcMetadata {
+    // Returns the (source name, contract name) the metadata is for.
+    fn compilation_target(&self) -> Option<(String, String)> {
+        let (source, contract) = self
+            .settings
+            .get("compilationTarget")?
+            .as_object()?
+            .iter()
+            .next()?;
+
+        Some((source.clone(), contract.as_str()?.to_string()))
+    }
+}
+
+pub(crate) fn parse_method_identifiers(
+    identifiers: BTreeMap<String, String>,
+) -> Result<BTreeMap<String, [u8; 4]>> {
+    identifiers
+        .into_iter()
+        .map(|(signature, selector)| {
+            let mut bytes = [0u8; 4];
+            decode_hex_to_slice(&selector, &mut bytes).map_err(|_| {
+                anyhow!("invalid method identifier for {}: {}", signature, selector)
+            })?;
+
+            Ok((signature, bytes))
+        })
+        .collect()
+}
+
+// Strips the 0x prefix and maps empty bytecode (interfaces, abstract
+// contracts) to None.
+pub(crate) fn normalize_bytecode(bytecode: Option<String>) -> Option<String> {
+    bytecode
+        .map(|code| code.trim_start_matches("0x").to_string())
+        .filter(|code| !code.is_empty())
+}
+
+fn decode_bytecode(bytecode: Option<&str>) -> Result<Option<Vec<u8>>> {
+    bytecode
+        .map(|code| {
+            if code.contains("__") {
+                return Err(anyhow!("bytecode has unlinked library references"));
+            }
+
+            decode_hex(code)
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    const HARDHAT_ARTIFACT: &str = r#"{
+        "_format": "hh-sol-artifact-1",
+        "contractName": "Counter",
+        "sourceName": "contracts/Counter.sol",
+        "abi": [
+            {"inputs": [], "name": "increment", "outputs": [], "stateMutability": "nonpayable", "type": "function"}
+        ],
+        "bytecode": "0x6080604052",
+        "deployedBytecode": "0x60806040",
+        "linkReferences": {},
+        "deployedLinkReferences": {}
+    }"#;
+
+    #[test]
+    fn hardhat_artifact() {
+        let artifact = Abi::from_artifact_json(HARDHAT_ARTIFACT).unwrap();
+
+        assert_eq!(artifact.contract_name.as_deref(), Some("Counter"));
+        assert_eq!(
+            artifact.source_name.as_deref(),
+            Some("contracts/Counter.sol")
+        );
+        assert_eq!(artifact.abi.functions[0].name, "increment");
+        assert_eq!(
+            artifact.decode_bytecode().unwrap(),
+            Some(vec![0x60, 0x80, 0x60, 0x40, 0x52])
+        );
+        assert_eq!(
+            artifact.decode_deployed_bytecode().unwrap(),
+            Some(vec![0x60, 0x80, 0x60, 0x40])
+        );
+    }
+
+    #[test]
+    fn hardhat_artifact_from_reader() {
+        let artifact = Abi::from_artifact_reader(HARDHAT_ARTIFACT.as_bytes()).unwrap();
+
+        assert_eq!(artifact, Abi::from_artifact_json(HARDHAT_ARTIFACT).unwrap());
+    }
+
+    #[test]
+    fn interface_artifact() {
+        let artifact = Artifact::from_hardhat_json(
+            r#"{"contractName": "IERC20", "abi": [], "bytecode": "0x", "deployedBytecode": "0x"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(artifact.bytecode, None);
+        assert_eq!(artifact.decode_bytecode().unwrap(), None);
+    }
+
+    #[test]
+    fn unlinked_bytecode() {
+        let artifact = Artifact::from_hardhat_json(
+            r#"{"abi": [], "bytecode": "0x73__$2d9ab2b4c8b1d2e0f1a9e8f7c6b5a4d3e2$__6000"}"#,
+        )
+        .unwrap();
+
+        assert!(artifact.decode_bytecode().is_err());
+    }
+
+    const FOUNDRY_ARTIFACT: &str = r#"{
+        "abi": [
+            {"type": "function", "name": "increment", "inputs": [], "outputs": [], "stateMutability": "nonpayable"},
+            {"type": "function", "name": "setNumber", "inputs": [{"name": "newNumber", "type": "uint256", "internalType": "uint256"}], "outputs": [], "stateMutability": "nonpayable"}
+        ],
+        "bytecode": {"object": "0x6080604052", "sourceMap": "65:", "linkReferences": {}},
+        "deployedBytecode": {"object": "0x60806040", "sourceMap": "65:", "linkReferences": {}},
+        "methodIdentifiers": {"increment()": "d09de08a", "setNumber(uint256)": "3fb5c1cb"},
+        "metadata": {
+            "compiler": {"version": "0.8.19+commit.7dd6d404"},
+            "settings": {"compilationTarget": {"src/Counter.sol": "Counter"}, "optimizer": {"enabled": true, "runs": 200}}
+        },
+        "id": 20
+    }"#;
+
+    #[test]
+    fn foundry_artifact() {
+        let artifact = Artifact::from_foundry_json(FOUNDRY_ARTIFACT).unwrap();
+
+        assert_eq!(artifact.contract_name.as_deref(), Some("Counter"));
+        assert_eq!(artifact.source_name.as_deref(), Some("src/Counter.sol"));
+        assert_eq!(artifact.abi.functions.len(), 2);
+        assert_eq!(artifact.bytecode.as_deref(), Some("6080604052"));
+        assert_eq!(artifact.deployed_bytecode.as_deref(), Some("60806040"));
+        assert_eq!(
+            artifact.compiler_version.as_deref(),
+            Some("0.8.19+commit.7dd6d404")
+        );
+        assert_eq!(
+            artifact.method_identifiers.get("setNumber(uint256)"),
+            Some(&[0x3f, 0xb5, 0xc1, 0xcb])
+        );
+
+        // Artifact format is detected automatically.
+        assert_eq!(Abi::from_artifact_json(FOUNDRY_ARTIFACT).unwrap(), artifact);
+    }
+
+    #[test]
+    fn foundry_selector_mismatch() {
+        let json = FOUNDRY_ARTIFACT.replace("3fb5c1cb", "deadbeef");
+
+        assert!(Artifact::from_foundry_json(&json).is_err());
+
+        let json = FOUNDRY_ARTIFACT.replace(r#""increment()": "d09de08a", "#, "");
+
+        assert!(Artifact::from_foundry_json(&json).is_err());
+    }
+
+    const TRUFFLE_ARTIFACT: &str = r#"{
+        "contractName": "MetaCoin",
+        "abi": [
+            {"constant": true, "inputs": [{"name": "addr", "type": "address"}], "name": "getBalance", "outputs": [{"name": "", "type": "uint256"}], "payable": false, "stateMutability": "view", "type": "function"}
+        ],
+        "bytecode": "0x6080604052",
+        "deployedBytecode": "0x60806040",
+        "sourcePath": "/home/user/metacoin/contracts/MetaCoin.sol",
+        "networks": {
+            "1": {"events": {}, "links": {}, "address": "0x5FbDB2315678afecb367f032d93F642f64180aa3", "transactionHash": "0x00"},
+            "5777": {"events": {}, "links": {}}
+        },
+        "schemaVersion": "3.4.16"
+    }"#;
+
+    #[test]
+    fn truffle_artifact() {
+        let artifact = Artifact::from_truffle_json(TRUFFLE_ARTIFACT).unwrap();
+
+        let address: H160 = "5FbDB2315678afecb367f032d93F642f64180aa3".parse().unwrap();
+
+        assert_eq!(artifact.contract_name.as_deref(), Some("MetaCoin"));
+        assert_eq!(
+            artifact.source_name.as_deref(),
+            Some("/home/user/metacoin/contracts/MetaCoin.sol")
+        );
+        assert_eq!(artifact.abi.functions[0].name, "getBalance");
+        assert_eq!(artifact.address(1), Some(address));
+        assert_eq!(artifact.address(5777), None);
+        assert_eq!(artifact.networks.len(), 1);
+
+        assert_eq!(Abi::from_artifact_json(TRUFFLE_ARTIFACT).unwrap(), artifact);
+    }
+
+    #[test]
+    fn missing_abi() {
+        assert!(Abi::from_artifact_json(r#"{"contractName": "X"}"#).is_err());
+    }
+
+    #[test]
+    fn solc_metadata() {
+        let json = r#"{
+            "compiler": {"version": "0.8.19+commit.7dd6d404"},
+            "language": "Solidity",
+            "output": {
+                "abi": [{"type": "function", "name": "increment", "inputs": [], "outputs": [], "stateMutability": "nonpayable"}],
+                "devdoc": {"kind": "dev", "methods": {}, "version": 1},
+                "userdoc": {"kind": "user", "methods": {}, "version": 1}
+            },
+            "settings": {
+                "compilationTarget": {"src/Counter.sol": "Counter"},
+                "evmVersion": "paris",
+                "optimizer": {"enabled": true, "runs": 200}
+            },
+            "sources": {},
+            "version": 1
+        }"#;
+
+        let artifact = Artifact::from_solc_metadata_json(json).unwrap();
+
+        assert_eq!(artifact.contract_name.as_deref(), Some("Counter"));
+        assert_eq!(artifact.source_name.as_deref(), Some("src/Counter.sol"));
+        assert_eq!(artifact.abi.functions[0].name, "increment");
+        assert_eq!(artifact.bytecode, None);
+        assert_eq!(
+            artifact.compiler_settings.unwrap()["evmVersion"],
+            serde_json::json!("paris")
+        );
+        assert!(artifact.devdoc.is_some());
+    }
+}
@@ -0,0 +1,155 @@
+use anyhow::{anyhow, Result};
+use ethereum_abi::{
+    encode_hex, to_checksum, Abi, AbiRegistry, Log, Receipt, SignatureLookup, Transaction,
+};
+use ethereum_types::{H160, H256};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::convert::TryInto;
+
+/// Fetches a transaction and its receipt, and decodes its input and logs
+/// against the registered ABIs, falling back to the signature directories
+/// when `lookup` is given.
+///
+/// `abi` is registered for the called contract.
+pub fn decode_tx(
+    rpc_url: &str,
+    hash: H256,
+    mut registry: AbiRegistry,
+    abi: Option<Abi>,
+    lookup: Option<&SignatureLookup>,
+) -> Result<serde_json::Value> {
+    let agent = &ureq::Agent::new_with_defaults();
+
+    let tx: Transaction = rpc(
+        agent,
+        rpc_url,
+        "eth_getTransactionByHash",
+        json!([format!("{:?}", hash)]),
+    )?;
+    let receipt: Receipt = rpc(
+        agent,
+        rpc_url,
+        "eth_getTransactionReceipt",
+        json!([format!("{:?}", hash)]),
+    )?;
+
+    if let (Some(to), Some(abi)) = (tx.to, abi) {
+        registry.register(to, abi);
+    }
+    let registry = &registry;
+
+    let call = match tx.to {
+        Some(to) if tx.input.len() >= 4 => decode_call(registry, lookup, &to, &tx.input),
+        _ => serde_json::Value::Null,
+    };
+
+    Ok(json!({
+        "hash": format!("{:?}", tx.hash),
+        "from": to_checksum(&tx.from),
+        "to": tx.to.as_ref().map(to_checksum),
+        "value": tx.value.to_string(),
+        "status": receipt.status,
+        "call": call,
+        "logs": receipt
+            .logs
+            .iter()
+            .map(|log| decode_log(registry, lookup, log))
+            .collect::<Vec<_>>(),
+    }))
+}
+
+fn decode_call(
+    registry: &AbiRegistry,
+    lookup: Option<&SignatureLookup>,
+    to: &H160,
+    input: &[u8],
+) -> serde_json::Value {
+    if let Ok((function, params)) = registry.decode_call(to, input) {
+        return json!({ "function": function.signature(), "params": params });
+    }
+
+    let candidates = lookup
+        .and_then(|lookup| lookup.lookup_function(input[..4].try_into().ok()?).ok())
+        .unwrap_or_default();
+
+    for function in candidates {
+        if let Ok(params) = function.decode_input_from_slice(&input[4..]) {
+            return json!({ "function": function.signature(), "params": params });
+        }
+    }
+
+    json!({ "selector": encode_hex(&input[..4]) })
+}
+
+fn decode_log(
+    registry: &AbiRegistry,
+    lookup: Option<&SignatureLookup>,
+    log: &Log,
+) -> serde_json::Value {
+    if let Ok((event, params)) = registry.decode_log(&log.address, &log.topics, &log.data) {
+        return json!({
+            "address": to_checksum(&log.address),
+            "event": event.signature(),
+            "params": params,
+        });
+    }
+
+    let candidates = match (lookup, log.topics.first()) {
+        (Some(lookup), Some(topic)) => lookup.lookup_event(*topic).unwrap_or_default(),
+        _ => vec![],
+    };
+
+    for mut event in candidates {
+        // Text signatures carry no indexed markers: assume the leading
+        // inputs fill the topics.
+        for (i, input) in event.inputs_mut().iter_mut().enumerate() {
+            input.indexed = Some(i + 1 < log.topics.len());
+        }
+
+        if let Ok(params) = event.decode_data_from_slice(&log.topics, &log.data) {
+            return json!({
+                "address": to_checksum(&log.address),
+                "event": event.signature(),
+                "params": params,
+            });
+        }
+    }
+
+    json!({
+        "address": to_checksum(&log.address),
+        "topics": log
+            .topics
+            .iter()
+            .map(|topic| format!("{:?}", topic))
+            .collect::<Vec<_>>(),
+        "data": encode_hex(&log.data),
+    })
+}
+
+fn rpc<T: DeserializeOwned>(
+    agent: &ureq::Agent,
+    url: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<T> {
+    let response: serde_json::Value = agent
+        .post(url)
+        .send_json(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }))?
+        .body_mut()
+        .read_json()?;
+
+    if let Some(error) = response.get("error") {
+        return Err(anyhow!("{} failed: {}", method, error));
+    }
+
+    match response.get("result") {
+        None | Some(serde_json::Value::Null) => Err(anyhow!("{} returned no result", method)),
+        Some(result) => Ok(serde_json::from_value(result.clone())?),
+    }
+}
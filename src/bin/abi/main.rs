@@ -0,0 +1,256 @@
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand};
+use ethereum_abi::{decode_hex, encode_hex, Abi, AbiRegistry, SignatureLookup};
+use ethereum_types::{H160, H256};
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+mod decode_tx;
+
+/// Encodes and decodes Ethereum ABI data.
+#[derive(Parser)]
+#[command(name = "abi", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Decodes function calldata into JSON.
+    DecodeCalldata {
+        /// ABI JSON file, or an artifact holding an `abi` field.
+        #[arg(long)]
+        abi: PathBuf,
+        /// Hex encoded calldata, read from stdin if omitted.
+        calldata: Option<String>,
+    },
+
+    /// Decodes an event log into JSON.
+    DecodeLog {
+        /// ABI JSON file, or an artifact holding an `abi` field.
+        #[arg(long)]
+        abi: PathBuf,
+        /// Log topic, in order; repeat for each topic.
+        #[arg(long = "topic")]
+        topics: Vec<String>,
+        /// Hex encoded log data, read from stdin if omitted.
+        data: Option<String>,
+    },
+
+    /// Encodes a function call from JSON arguments.
+    Encode {
+        /// ABI JSON file, or an artifact holding an `abi` field.
+        #[arg(long)]
+        abi: PathBuf,
+        /// Function name.
+        function: String,
+        /// Arguments as a JSON array or object, read from stdin if omitted.
+        args: Option<String>,
+    },
+
+    /// Fetches a transaction over JSON-RPC and decodes its input and logs
+    /// into JSON.
+    DecodeTx {
+        /// JSON-RPC endpoint URL.
+        #[arg(long)]
+        rpc: String,
+        /// ABI of the called contract.
+        #[arg(long)]
+        abi: Option<PathBuf>,
+        /// ABI of another contract, as `<address>=<file>`; repeat for each
+        /// contract.
+        #[arg(long = "contract")]
+        contracts: Vec<String>,
+        /// Resolve unknown selectors and topics against the public
+        /// signature directories.
+        #[arg(long)]
+        lookup: bool,
+        /// Transaction hash.
+        hash: String,
+    },
+
+    /// Lists the selectors of the ABI's functions and errors.
+    Selectors {
+        /// ABI JSON file, or an artifact holding an `abi` field.
+        #[arg(long)]
+        abi: PathBuf,
+    },
+
+    /// Lists the topics of the ABI's events.
+    Events {
+        /// ABI JSON file, or an artifact holding an `abi` field.
+        #[arg(long)]
+        abi: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::DecodeCalldata { abi, calldata } => {
+            let abi = read_abi(&abi)?;
+            let input = parse_hex(&arg_or_stdin(calldata)?)?;
+
+            let (function, params) = abi.decode_input_from_slice(&input)?;
+
+            print_json(&serde_json::json!({
+                "function": function.signature(),
+                "params": params,
+            }))
+        }
+
+        Command::DecodeLog { abi, topics, data } => {
+            let abi = read_abi(&abi)?;
+            let data = parse_hex(&arg_or_stdin(data)?)?;
+
+            let topics = topics
+                .iter()
+                .map(|topic| parse_hash(topic))
+                .collect::<Result<Vec<_>>>()?;
+
+            let (event, params) = abi.decode_log_from_slice(&topics, &data)?;
+
+            print_json(&serde_json::json!({
+                "event": event.signature(),
+                "params": params,
+            }))
+        }
+
+        Command::Encode {
+            abi,
+            function,
+            args,
+        } => {
+            let abi = read_abi(&abi)?;
+            let args = serde_json::from_str(&arg_or_stdin(args)?)
+                .context("arguments must be a JSON array or object")?;
+
+            let input = abi.encode_call_from_json(&function, &args)?;
+
+            println!("{}", encode_hex(input));
+
+            Ok(())
+        }
+
+        Command::DecodeTx {
+            rpc,
+            abi,
+            contracts,
+            lookup,
+            hash,
+        } => {
+            let hash = parse_hash(&hash)?;
+            let mut registry = AbiRegistry::new();
+
+            for contract in &contracts {
+                let (address, path) = contract
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("expected <address>=<file>, got {:?}", contract))?;
+
+                registry.register(parse_address(address)?, read_abi(Path::new(path))?);
+            }
+
+            let lookup = if lookup {
+                Some(SignatureLookup::new())
+            } else {
+                None
+            };
+
+            let abi = abi.map(|abi| read_abi(&abi)).transpose()?;
+
+            print_json(&decode_tx::decode_tx(
+                &rpc,
+                hash,
+                registry,
+                abi,
+                lookup.as_ref(),
+            )?)
+        }
+
+        Command::Selectors { abi } => {
+            let abi = read_abi(&abi)?;
+
+            for function in &abi.functions {
+                println!(
+                    "{} {}",
+                    encode_hex(function.method_id()),
+                    function.signature()
+                );
+            }
+
+            for error in &abi.errors {
+                println!("{} {}", encode_hex(error.selector()), error.signature());
+            }
+
+            Ok(())
+        }
+
+        Command::Events { abi } => {
+            let abi = read_abi(&abi)?;
+
+            for event in &abi.events {
+                println!("{:?} {}", event.topic(), event.signature());
+            }
+
+            Ok(())
+        }
+    }
+}
+
+// Reads an ABI JSON file, or the ABI embedded in a build artifact.
+fn read_abi(path: &Path) -> Result<Abi> {
+    let json = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+    let value: serde_json::Value = serde_json::from_str(&json)?;
+
+    let abi = match value.get("abi") {
+        Some(abi) => abi.clone(),
+        None => value,
+    };
+
+    serde_json::from_value(abi).with_context(|| format!("parsing ABI from {:?}", path))
+}
+
+fn arg_or_stdin(arg: Option<String>) -> Result<String> {
+    match arg {
+        Some(arg) if arg != "-" => Ok(arg),
+        _ => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+
+            Ok(input)
+        }
+    }
+}
+
+fn parse_hex(s: &str) -> Result<Vec<u8>> {
+    decode_hex(s.trim())
+}
+
+fn parse_hash(s: &str) -> Result<H256> {
+    let bytes = parse_hex(s)?;
+
+    if bytes.len() != 32 {
+        return Err(anyhow!("hash must be 32 bytes, got {}", bytes.len()));
+    }
+
+    Ok(H256::from_slice(&bytes))
+}
+
+fn parse_address(s: &str) -> Result<H160> {
+    let bytes = parse_hex(s)?;
+
+    if bytes.len() != 20 {
+        return Err(anyhow!("address must be 20 bytes, got {}", bytes.len()));
+    }
+
+    Ok(H160::from_slice(&bytes))
+}
+
+fn print_json(value: &serde_json::Value) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+
+    Ok(())
+}
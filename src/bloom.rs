@@ -0,0 +1,65 @@
+use ethereum_types::{Bloom, BloomInput, H160};
+
+use crate::{Abi, Event};
+
+impl Event {
+    /// Returns whether a block or receipt with the given logs bloom may
+    /// contain this event.
+    ///
+    /// Blooms have false positives but no false negatives, so `false` means
+    /// the logs can be skipped. Anonymous events have no topic to look for
+    /// and always possibly match.
+    pub fn possibly_in_bloom(&self, bloom: &Bloom) -> bool {
+        self.anonymous || bloom.contains_input(BloomInput::Raw(self.topic().as_bytes()))
+    }
+
+    /// Like [`Event::possibly_in_bloom`], for logs emitted by the contract at
+    /// `address` only.
+    pub fn possibly_in_bloom_at(&self, bloom: &Bloom, address: &H160) -> bool {
+        bloom.contains_input(BloomInput::Raw(address.as_bytes())) && self.possibly_in_bloom(bloom)
+    }
+}
+
+impl Abi {
+    /// Returns the events that a block or receipt with the given logs bloom
+    /// may contain, see [`Event::possibly_in_bloom`].
+    pub fn events_possibly_in_bloom(&self, bloom: &Bloom) -> Vec<&Event> {
+        self.events
+            .iter()
+            .filter(|e| e.possibly_in_bloom(bloom))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::standards::ERC20;
+
+    use super::*;
+
+    #[test]
+    fn logs_bloom() {
+        let transfer = ERC20.events.iter().find(|e| e.name == "Transfer").unwrap();
+        let token = H160::repeat_byte(0xaa);
+
+        let mut bloom = Bloom::default();
+        bloom.accrue(BloomInput::Raw(token.as_bytes()));
+        bloom.accrue(BloomInput::Raw(transfer.topic().as_bytes()));
+
+        assert!(transfer.possibly_in_bloom(&bloom));
+        assert!(transfer.possibly_in_bloom_at(&bloom, &token));
+        assert!(!transfer.possibly_in_bloom_at(&bloom, &H160::repeat_byte(0xbb)));
+        assert!(!transfer.possibly_in_bloom(&Bloom::default()));
+
+        assert_eq!(
+            ERC20
+                .events_possibly_in_bloom(&bloom)
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Transfer"]
+        );
+    }
+}
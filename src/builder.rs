@@ -0,0 +1,264 @@
+use anyhow::{anyhow, Result};
+
+use crate::{
+    signature::{is_identifier, parse_named_type},
+    Abi, Constructor, Error, Event, Function, Param, StateMutability,
+};
+
+/// Fluent builder for [`Abi`] definitions.
+///
+/// Parameter types are given as canonical type strings (`uint256`,
+/// `(address,bytes)[]`) and checked when the ABI is built. Tuple components
+/// may be named, as in `(address target,bytes callData)[]`.
+///
+/// ```
+/// use ethereum_abi::{Abi, StateMutability};
+///
+/// let abi = Abi::builder()
+///     .function(
+///         "transfer",
+///         &[("to", "address"), ("amount", "uint256")],
+///         &[("", "bool")],
+///         StateMutability::NonPayable,
+///     )
+///     .event(
+///         "Transfer",
+///         &[("from", "address", true), ("to", "address", true), ("value", "uint256", false)],
+///     )
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(abi.functions[0].signature(), "transfer(address,uint256)");
+/// ```
+#[derive(Debug)]
+pub struct AbiBuilder {
+    abi: Abi,
+    error: Option<anyhow::Error>,
+}
+
+impl Abi {
+    /// Returns a builder for assembling an ABI in code.
+    pub fn builder() -> AbiBuilder {
+        AbiBuilder {
+            abi: Abi::default(),
+            error: None,
+        }
+    }
+}
+
+impl AbiBuilder {
+    /// Sets the constructor.
+    pub fn constructor(
+        mut self,
+        inputs: &[(&str, &str)],
+        state_mutability: StateMutability,
+    ) -> Self {
+        if self.abi.constructor.is_some() {
+            self.fail(anyhow!("constructor defined more than once"));
+        }
+
+        if let Some(inputs) = self.params(inputs) {
+            self.abi.constructor = Some(Constructor {
+                inputs,
+                state_mutability,
+            });
+        }
+
+        self
+    }
+
+    /// Adds a function given its (name, type) inputs and outputs.
+    pub fn function(
+        mut self,
+        name: &str,
+        inputs: &[(&str, &str)],
+        outputs: &[(&str, &str)],
+        state_mutability: StateMutability,
+    ) -> Self {
+        self.check_name(name);
+
+        if let (Some(inputs), Some(outputs)) = (self.params(inputs), self.params(outputs)) {
+            self.abi
+                .functions
+                .push(Function::new(name, inputs, outputs, state_mutability));
+        }
+
+        self
+    }
+
+    /// Adds an event given its (name, type, indexed) inputs.
+    pub fn event(self, name: &str, inputs: &[(&str, &str, bool)]) -> Self {
+        self.add_event(name, inputs, false)
+    }
+
+    /// Adds an anonymous event given its (name, type, indexed) inputs.
+    pub fn anonymous_event(self, name: &str, inputs: &[(&str, &str, bool)]) -> Self {
+        self.add_event(name, inputs, true)
+    }
+
+    /// Adds a custom error given its (name, type) inputs.
+    pub fn error(mut self, name: &str, inputs: &[(&str, &str)]) -> Self {
+        self.check_name(name);
+
+        if let Some(inputs) = self.params(inputs) {
+            self.abi.errors.push(Error::new(name, inputs));
+        }
+
+        self
+    }
+
+    /// Declares the receive function.
+    pub fn receive(mut self) -> Self {
+        self.abi.receive = Some(StateMutability::Payable);
+        self
+    }
+
+    /// Declares the fallback function.
+    pub fn fallback(mut self, state_mutability: StateMutability) -> Self {
+        self.abi.fallback = Some(state_mutability);
+        self
+    }
+
+    /// Validates and returns the ABI.
+    ///
+    /// Fails on the first invalid name or type, and on the issues reported by
+    /// [`Abi::validate`], such as functions, events or errors defined twice
+    /// with the same signature, and events with more indexed inputs than
+    /// there are topics for.
+    pub fn build(self) -> Result<Abi> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        self.abi.validate()?;
+
+        Ok(self.abi)
+    }
+
+    fn add_event(mut self, name: &str, inputs: &[(&str, &str, bool)], anonymous: bool) -> Self {
+        self.check_name(name);
+
+        let params: Option<Vec<Param>> = inputs
+            .iter()
+            .map(|(name, ty, indexed)| {
+                self.param(name, ty).map(|mut param| {
+                    param.indexed = Some(*indexed);
+                    param
+                })
+            })
+            .collect();
+
+        if let Some(inputs) = params {
+            self.abi.events.push(Event::new(name, inputs, anonymous));
+        }
+
+        self
+    }
+
+    fn params(&mut self, params: &[(&str, &str)]) -> Option<Vec<Param>> {
+        params
+            .iter()
+            .map(|(name, ty)| self.param(name, ty))
+            .collect()
+    }
+
+    fn param(&mut self, name: &str, ty: &str) -> Option<Param> {
+        if !name.is_empty() {
+            self.check_name(name);
+        }
+
+        match parse_named_type(ty) {
+            Ok(type_) => Some(Param {
+                name: name.into(),
+                type_,
+                indexed: None,
+                internal_type: None,
+            }),
+            Err(err) => {
+                self.fail(err);
+                None
+            }
+        }
+    }
+
+    fn check_name(&mut self, name: &str) {
+        if !is_identifier(name) {
+            self.fail(anyhow!("invalid name: {:?}", name));
+        }
+    }
+
+    // Keeps the first error, which is the one reported by `build`.
+    fn fail(&mut self, err: anyhow::Error) {
+        self.error.get_or_insert(err);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::Type;
+
+    use super::*;
+
+    #[test]
+    fn build_abi() {
+        let abi = Abi::builder()
+            .constructor(&[("supply", "uint256")], StateMutability::NonPayable)
+            .function(
+                "swap",
+                &[("path", "(address token,uint24 fee)[]"), ("", "bytes")],
+                &[],
+                StateMutability::Payable,
+            )
+            .error("Unauthorized", &[("caller", "address")])
+            .anonymous_event("Log", &[("data", "bytes32", true)])
+            .receive()
+            .build()
+            .unwrap();
+
+        assert_eq!(abi.constructor.unwrap().inputs[0].type_, Type::Uint(256));
+        assert_eq!(
+            abi.functions[0].signature(),
+            "swap((address,uint24)[],bytes)"
+        );
+        assert_eq!(
+            abi.functions[0].inputs[0].type_,
+            Type::Array(Box::new(Type::Tuple(vec![
+                ("token".into(), Type::Address),
+                ("fee".into(), Type::Uint(24)),
+            ])))
+        );
+        assert_eq!(abi.errors[0].signature(), "Unauthorized(address)");
+        assert!(abi.events[0].anonymous);
+        assert_eq!(abi.events[0].inputs[0].indexed, Some(true));
+        assert_eq!(abi.receive, Some(StateMutability::Payable));
+        assert_eq!(abi.fallback, None);
+    }
+
+    #[test]
+    fn builder_validation() {
+        let res = Abi::builder()
+            .function("f", &[("x", "uint7")], &[], StateMutability::Pure)
+            .build();
+        assert!(res.is_err());
+
+        let res = Abi::builder()
+            .function("f", &[], &[], StateMutability::Pure)
+            .function("f", &[], &[("", "uint256")], StateMutability::View)
+            .build();
+        assert!(res.is_err());
+
+        let res = Abi::builder().error("1Bad", &[]).build();
+        assert!(res.is_err());
+
+        let res = Abi::builder().error("E", &[("", "uint256 x")]).build();
+        assert!(res.is_err());
+
+        let indexed = ("x", "uint256", true);
+        let res = Abi::builder()
+            .event("E", &[indexed, indexed, indexed, indexed])
+            .build();
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::Result;
+
+use crate::{revert::decode_revert, Abi, DecodedParams, Revert, Value};
+
+/// Data a call returned.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CallOutput {
+    /// The call succeeded with the given return data.
+    Return(Vec<u8>),
+    /// The call reverted with the given revert data.
+    Revert(Vec<u8>),
+}
+
+/// Executes raw calls against a contract, e.g. with `eth_call`.
+///
+/// Closures taking the calldata implement this trait, so an executor can
+/// wrap any JSON-RPC client.
+pub trait CallExecutor {
+    /// Executes a call with the given input, selector included.
+    ///
+    /// Errors are reserved for failures to execute the call; reverts are
+    /// reported as [`CallOutput::Revert`].
+    fn execute(&self, input: &[u8]) -> Result<CallOutput>;
+}
+
+impl<F> CallExecutor for F
+where
+    F: Fn(&[u8]) -> Result<CallOutput>,
+{
+    fn execute(&self, input: &[u8]) -> Result<CallOutput> {
+        self(input)
+    }
+}
+
+impl Abi {
+    /// Calls the named function: encodes the call, executes it and decodes
+    /// its outputs, or the reason it reverted.
+    ///
+    /// Overloads are resolved as in [`Abi::resolve_function`]. The outer
+    /// error covers encoding, execution and decoding failures, while a
+    /// revert is returned as the inner error.
+    ///
+    /// ```
+    /// use ethereum_abi::{Abi, CallOutput, Revert, StateMutability, Value};
+    /// use ethereum_types::{H160, U256};
+    ///
+    /// let abi = Abi::builder()
+    ///     .function("balanceOf", &[("owner", "address")], &[("", "uint256")], StateMutability::View)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let executor = |_input: &[u8]| {
+    ///     Ok(CallOutput::Return(Value::encode(&[Value::Uint(U256::from(5), 256)])))
+    /// };
+    ///
+    /// let balance = abi
+    ///     .call(&executor, "balanceOf", &[Value::Address(H160::zero())])
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(balance[0], Value::Uint(U256::from(5), 256));
+    ///
+    /// let executor = |_input: &[u8]| Ok(CallOutput::Revert(vec![]));
+    ///
+    /// let revert = abi
+    ///     .call(&executor, "balanceOf", &[Value::Address(H160::zero())])
+    ///     .unwrap()
+    ///     .unwrap_err();
+    /// assert_eq!(revert, Revert::Empty);
+    /// ```
+    pub fn call<E: CallExecutor + ?Sized>(
+        &self,
+        executor: &E,
+        name: &str,
+        args: &[Value],
+    ) -> Result<std::result::Result<DecodedParams, Revert<'_>>> {
+        let function = self.resolve_function(name, args)?;
+
+        match executor.execute(&function.encode_input(args)?)? {
+            CallOutput::Return(output) => Ok(Ok(function.decode_output_from_slice(&output)?)),
+            CallOutput::Revert(data) => Ok(Err(decode_revert(Some(self), &data)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::anyhow;
+    use ethereum_types::{H160, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::StateMutability;
+
+    use super::*;
+
+    #[test]
+    fn call_through_executor() {
+        let abi = Abi::builder()
+            .function(
+                "transfer",
+                &[("to", "address"), ("amount", "uint256")],
+                &[("", "bool")],
+                StateMutability::NonPayable,
+            )
+            .error("InsufficientBalance", &[("available", "uint256")])
+            .build()
+            .unwrap();
+
+        let transfer = abi.functions[0].clone();
+        let error = abi.errors[0].clone();
+
+        let executor = move |input: &[u8]| {
+            let args = transfer.decode_input_from_slice(&input[4..])?;
+
+            match &args[1] {
+                Value::Uint(amount, _) if *amount <= U256::from(10) => {
+                    Ok(CallOutput::Return(Value::encode(&[Value::Bool(true)])))
+                }
+                Value::Uint(amount, _) if *amount <= U256::from(20) => {
+                    let mut data = error.selector().to_vec();
+                    data.extend(Value::encode(&[Value::Uint(U256::from(10), 256)]));
+
+                    Ok(CallOutput::Revert(data))
+                }
+                _ => Err(anyhow!("connection refused")),
+            }
+        };
+
+        let args = |amount: u64| {
+            [
+                Value::Address(H160::repeat_byte(1)),
+                Value::Uint(U256::from(amount), 256),
+            ]
+        };
+
+        let output = abi.call(&executor, "transfer", &args(5)).unwrap().unwrap();
+        assert_eq!(output[0], Value::Bool(true));
+
+        match abi.call(&executor, "transfer", &args(15)).unwrap() {
+            Err(Revert::Custom(error, params)) => {
+                assert_eq!(error.name, "InsufficientBalance");
+                assert_eq!(params[0], Value::Uint(U256::from(10), 256));
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        assert!(abi.call(&executor, "transfer", &args(25)).is_err());
+        assert!(abi.call(&executor, "approve", &args(5)).is_err());
+
+        // Return data that does not match the outputs.
+        let executor = |_: &[u8]| Ok(CallOutput::Return(vec![]));
+        assert!(abi.call(&executor, "transfer", &args(5)).is_err());
+    }
+}
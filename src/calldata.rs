@@ -0,0 +1,439 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::U256;
+use std::ops::Range;
+
+use crate::{ArrayIter, Function, Param, Value};
+
+/// Encoded function call that can be edited in place.
+///
+/// ```
+/// use ethereum_abi::{Abi, Calldata, StateMutability, Value};
+///
+/// let abi = Abi::builder()
+///     .function("f", &[("data", "bytes"), ("n", "uint8")], &[], StateMutability::NonPayable)
+///     .build()
+///     .unwrap();
+/// let f = &abi.functions[0];
+///
+/// let input = f.encode_input(&[Value::Bytes(vec![1].into()), Value::Uint(7.into(), 8)]).unwrap();
+///
+/// let mut calldata = Calldata::new(input);
+/// calldata.patch(f, 0, &Value::Bytes(vec![2; 40].into())).unwrap();
+///
+/// assert_eq!(
+///     calldata.as_bytes(),
+///     &f.encode_input(&[Value::Bytes(vec![2; 40].into()), Value::Uint(7.into(), 8)]).unwrap()[..]
+/// );
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Calldata(Vec<u8>);
+
+impl Calldata {
+    /// Wraps encoded calldata, selector included.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the encoded calldata.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the encoded calldata.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Replaces the value of the function's `index`th argument, leaving the
+    /// other arguments' bytes untouched.
+    ///
+    /// Static values are overwritten in place. Dynamic values have their
+    /// data replaced, and the offsets of the values stored after them are
+    /// shifted accordingly.
+    pub fn patch(&mut self, function: &Function, index: usize, value: &Value) -> Result<()> {
+        let param = function
+            .inputs
+            .get(index)
+            .ok_or_else(|| anyhow!("{} has no argument {}", function.signature(), index))?;
+
+        if !value.matches(&param.type_) {
+            return Err(anyhow!(
+                "argument {:?} of {} expects {}, got {}",
+                param.name,
+                function.signature(),
+                param.type_,
+                value.type_of()
+            ));
+        }
+
+        let encoded = Value::encode(&[value.clone().normalize(&param.type_)]);
+
+        let args = args_of(function, &self.0)?;
+        let layout = layout(&function.inputs, args)?;
+
+        let old = match &layout[index].tail {
+            None => {
+                let head = layout[index].head.clone();
+                self.0[4 + head.start..4 + head.end].copy_from_slice(&encoded);
+
+                return Ok(());
+            }
+            Some(tail) => tail.clone(),
+        };
+
+        // Encoding a single dynamic value yields its offset then its data.
+        let new = &encoded[32..];
+
+        for (i, other) in layout.iter().enumerate() {
+            if let Some(tail) = &other.tail {
+                if i != index && tail.start >= old.end {
+                    let offset = tail.start - old.len() + new.len();
+                    let head = 4 + other.head.start..4 + other.head.end;
+
+                    U256::from(offset).to_big_endian(&mut self.0[head]);
+                }
+            }
+        }
+
+        self.0
+            .splice(4 + old.start..4 + old.end, new.iter().copied());
+
+        Ok(())
+    }
+}
+
+impl From<Vec<u8>> for Calldata {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for Calldata {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Read-only view of a function call that decodes arguments on demand.
+///
+/// Only the requested argument is decoded, by following its offset, which
+/// spares decoding large arrays or byte strings when another argument is
+/// needed.
+///
+/// ```
+/// use ethereum_abi::{Abi, CalldataView, StateMutability, Value};
+///
+/// let abi = Abi::builder()
+///     .function("f", &[("batch", "bytes"), ("n", "uint8")], &[], StateMutability::NonPayable)
+///     .build()
+///     .unwrap();
+/// let f = &abi.functions[0];
+///
+/// let input = f.encode_input(&[Value::Bytes(vec![1; 1000].into()), Value::Uint(7.into(), 8)]).unwrap();
+///
+/// let view = CalldataView::new(f, &input).unwrap();
+/// assert_eq!(view.param_by_name("n").unwrap(), Value::Uint(7.into(), 8));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CalldataView<'a> {
+    function: &'a Function,
+    args: &'a [u8],
+}
+
+impl<'a> CalldataView<'a> {
+    /// Creates a view of a call to the function.
+    ///
+    /// Fails if the input does not start with the function's selector.
+    pub fn new(function: &'a Function, input: &'a [u8]) -> Result<Self> {
+        Ok(Self {
+            function,
+            args: args_of(function, input)?,
+        })
+    }
+
+    /// Returns the called function.
+    pub fn function(&self) -> &'a Function {
+        self.function
+    }
+
+    /// Returns the number of arguments.
+    pub fn len(&self) -> usize {
+        self.function.inputs.len()
+    }
+
+    /// Returns whether the function takes no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.function.inputs.is_empty()
+    }
+
+    /// Decodes the `index`th argument.
+    pub fn param(&self, index: usize) -> Result<Value> {
+        let param =
+            self.function.inputs.get(index).ok_or_else(|| {
+                anyhow!("{} has no argument {}", self.function.signature(), index)
+            })?;
+
+        Value::decode(self.args, &param.type_, 0, self.head_of(index)?).map(|(value, _)| value)
+    }
+
+    /// Decodes the `index`th argument, an array, lazily one element at a
+    /// time.
+    ///
+    /// Suits calls such as airdrops, whose arrays can be too large to decode
+    /// at once.
+    pub fn param_iter(&self, index: usize) -> Result<ArrayIter<'a>> {
+        let param =
+            self.function.inputs.get(index).ok_or_else(|| {
+                anyhow!("{} has no argument {}", self.function.signature(), index)
+            })?;
+
+        ArrayIter::new(self.args, &param.type_, self.head_of(index)?)
+    }
+
+    /// Decodes the argument with the given name.
+    pub fn param_by_name(&self, name: &str) -> Result<Value> {
+        let index = self
+            .function
+            .inputs
+            .iter()
+            .position(|param| param.name == name)
+            .ok_or_else(|| anyhow!("{} has no argument {:?}", self.function.signature(), name))?;
+
+        self.param(index)
+    }
+
+    /// Returns where the head of the `index`th argument starts.
+    fn head_of(&self, index: usize) -> Result<usize> {
+        self.function.inputs[..index]
+            .iter()
+            .try_fold(0usize, |at, param| {
+                at.checked_add(param.type_.head_size()?)
+                    .ok_or_else(|| anyhow!("arguments are too large to encode"))
+            })
+    }
+}
+
+/// Where an argument is stored in encoded arguments.
+pub(crate) struct ParamLayout {
+    /// The argument's static encoding, or its offset if it is dynamic.
+    pub(crate) head: Range<usize>,
+    /// The data of a dynamic argument, up to the data of the next one.
+    pub(crate) tail: Option<Range<usize>>,
+}
+
+/// Returns the arguments of a call to the function, checking its selector.
+pub(crate) fn args_of<'a>(function: &Function, input: &'a [u8]) -> Result<&'a [u8]> {
+    if input.get(0..4) != Some(&function.method_id()[..]) {
+        return Err(anyhow!("input is not a call to {}", function.signature()));
+    }
+
+    Ok(&input[4..])
+}
+
+/// Locates each argument in encoded arguments, without decoding them.
+///
+/// Dynamic arguments are assumed to be laid out in order of their offsets,
+/// as encoders do.
+pub(crate) fn layout(inputs: &[Param], args: &[u8]) -> Result<Vec<ParamLayout>> {
+    let mut heads = vec![];
+    let mut at = 0usize;
+
+    for param in inputs {
+        let end = at
+            .checked_add(param.type_.head_size()?)
+            .filter(|end| *end <= args.len())
+            .ok_or_else(|| anyhow!("reached end of input while reading {}", param.name))?;
+
+        heads.push(at..end);
+        at = end;
+    }
+
+    let offsets = inputs
+        .iter()
+        .zip(&heads)
+        .map(|(param, head)| {
+            if !param.type_.is_dynamic() {
+                return Ok(None);
+            }
+
+            let offset = U256::from_big_endian(&args[head.clone()]);
+
+            if offset < U256::from(at) || offset > U256::from(args.len()) {
+                return Err(anyhow!("invalid offset {} for {}", offset, param.name));
+            }
+
+            Ok(Some(offset.as_usize()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(heads
+        .into_iter()
+        .zip(&offsets)
+        .map(|(head, offset)| ParamLayout {
+            head,
+            tail: offset.map(|start| {
+                let end = offsets
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .filter(|other| *other > start)
+                    .min()
+                    .unwrap_or(args.len());
+
+                start..end
+            }),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::H160;
+    use pretty_assertions::assert_eq;
+
+    use crate::{Abi, StateMutability, Type};
+
+    use super::*;
+
+    #[test]
+    fn patch_calldata() {
+        let abi = Abi::builder()
+            .function(
+                "f",
+                &[
+                    ("to", "address"),
+                    ("names", "string[]"),
+                    ("data", "bytes"),
+                    ("pair", "(uint8,uint8)"),
+                ],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .build()
+            .unwrap();
+        let f = &abi.functions[0];
+
+        let mut args = vec![
+            Value::Address(H160::repeat_byte(1)),
+            Value::Array(vec![Value::String("a".to_string())], Type::String.into()),
+            Value::Bytes(vec![1, 2, 3].into()),
+            Value::Tuple(vec![
+                ("".into(), Value::Uint(1.into(), 8)),
+                ("".into(), Value::Uint(2.into(), 8)),
+            ]),
+        ];
+
+        let mut calldata = Calldata::new(f.encode_input(&args).unwrap());
+
+        let patches = vec![
+            (
+                1,
+                Value::Array(
+                    vec![
+                        Value::String("long name spanning more than a word".to_string()),
+                        Value::String("b".to_string()),
+                    ],
+                    Type::String.into(),
+                ),
+            ),
+            (2, Value::Bytes(vec![].into())),
+            (0, Value::Address(H160::repeat_byte(2))),
+            (
+                3,
+                Value::Tuple(vec![
+                    ("".into(), Value::Uint(3.into(), 8)),
+                    ("".into(), Value::Uint(4.into(), 8)),
+                ]),
+            ),
+        ];
+
+        for (index, value) in patches {
+            calldata.patch(f, index, &value).unwrap();
+            args[index] = value;
+
+            assert_eq!(calldata.as_bytes(), &f.encode_input(&args).unwrap()[..]);
+        }
+
+        assert!(calldata.patch(f, 4, &Value::Bool(true)).is_err());
+        assert!(calldata.patch(f, 0, &Value::Bool(true)).is_err());
+        assert!(Calldata::new(vec![0; 4]).patch(f, 0, &args[0]).is_err());
+    }
+
+    #[test]
+    fn view_calldata() {
+        let abi = Abi::builder()
+            .function(
+                "f",
+                &[
+                    ("pair", "(uint8,uint8)"),
+                    ("names", "string[]"),
+                    ("to", "address"),
+                ],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .build()
+            .unwrap();
+        let f = &abi.functions[0];
+
+        let args = vec![
+            Value::Tuple(vec![
+                ("".into(), Value::Uint(1.into(), 8)),
+                ("".into(), Value::Uint(2.into(), 8)),
+            ]),
+            Value::Array(vec![Value::String("a".to_string())], Type::String.into()),
+            Value::Address(H160::repeat_byte(1)),
+        ];
+
+        let input = f.encode_input(&args).unwrap();
+        let view = CalldataView::new(f, &input).unwrap();
+
+        assert_eq!(view.len(), 3);
+        for (i, arg) in args.iter().enumerate() {
+            assert_eq!(&view.param(i).unwrap(), arg);
+        }
+        assert_eq!(view.param_by_name("to").unwrap(), args[2]);
+
+        assert!(view.param(3).is_err());
+        assert!(view.param_by_name("from").is_err());
+        assert!(CalldataView::new(f, &input[4..]).is_err());
+
+        // Arguments past the truncation point fail on their own.
+        let truncated = CalldataView::new(f, &input[..4 + 64]).unwrap();
+        assert_eq!(truncated.param(0).unwrap(), args[0]);
+        assert!(truncated.param(2).is_err());
+    }
+
+    #[test]
+    fn iterate_array_argument() {
+        let abi = Abi::builder()
+            .function(
+                "airdrop",
+                &[("token", "address"), ("recipients", "address[]")],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .build()
+            .unwrap();
+        let f = &abi.functions[0];
+
+        let recipients = (1..=100)
+            .map(|i| Value::Address(H160::repeat_byte(i)))
+            .collect::<Vec<_>>();
+        let input = f
+            .encode_input(&[
+                Value::Address(H160::zero()),
+                Value::Array(recipients.clone(), Type::Address.into()),
+            ])
+            .unwrap();
+        let view = CalldataView::new(f, &input).unwrap();
+
+        let mut iter = view.param_iter(1).unwrap();
+        assert_eq!(iter.len(), 100);
+        assert_eq!(iter.next().unwrap().unwrap(), recipients[0]);
+        assert_eq!(iter.last().unwrap().unwrap(), recipients[99]);
+
+        assert!(view.param_iter(0).is_err());
+        assert!(view.param_iter(2).is_err());
+    }
+}
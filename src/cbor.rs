@@ -0,0 +1,374 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    abi::Def,
+    layout::{AbiLayout, SourceEntry},
+    Abi, Constructor, Docs, Error, Event, Function, Param, StateMutability, Type,
+};
+
+/// Version of the binary format, bumped whenever it changes.
+const FORMAT_VERSION: u32 = 1;
+
+impl Abi {
+    /// Encodes the ABI in a compact binary format (CBOR), for caches that
+    /// load many ABIs at once.
+    ///
+    /// Besides the definitions, their docs and the layout they serialize back
+    /// to JSON in, the bytes hold the hash of every signature, so
+    /// [`Abi::from_bytes`] neither parses JSON nor hashes signatures.
+    ///
+    /// ```
+    /// use ethereum_abi::Abi;
+    ///
+    /// let abi: Abi = serde_json::from_str(r#"[
+    ///     {"type": "function", "name": "ping", "inputs": [], "outputs": [], "stateMutability": "nonpayable"}
+    /// ]"#).unwrap();
+    ///
+    /// let cached = Abi::from_bytes(&abi.to_bytes()).unwrap();
+    ///
+    /// assert_eq!(cached, abi);
+    /// assert_eq!(cached.functions[0].method_id(), [0x5c, 0x36, 0xb1, 0x86]);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let order = if self.layout.entries().is_empty() {
+            vec![]
+        } else {
+            self.entry_order()
+                .into_iter()
+                .map(|(def, json)| (def, json.map(str::to_string)))
+                .collect()
+        };
+
+        let cached = CachedAbi {
+            version: FORMAT_VERSION,
+            constructor: self
+                .constructor
+                .as_ref()
+                .map(|c| (cache_params(&c.inputs), c.state_mutability)),
+            functions: self
+                .functions
+                .iter()
+                .map(|f| CachedFunction {
+                    name: f.name.clone(),
+                    inputs: cache_params(&f.inputs),
+                    outputs: cache_params(&f.outputs),
+                    state_mutability: f.state_mutability,
+                    gas: f.gas,
+                    hash: *f.hash.get_or_init(|| f.signature()),
+                    docs: f.docs().cloned(),
+                })
+                .collect(),
+            events: self
+                .events
+                .iter()
+                .map(|e| CachedEvent {
+                    name: e.name.clone(),
+                    inputs: cache_params(&e.inputs),
+                    anonymous: e.anonymous,
+                    hash: *e.hash.get_or_init(|| e.signature()),
+                    docs: e.docs().cloned(),
+                })
+                .collect(),
+            errors: self
+                .errors
+                .iter()
+                .map(|e| CachedError {
+                    name: e.name.clone(),
+                    inputs: cache_params(&e.inputs),
+                    hash: *e.hash.get_or_init(|| e.signature()),
+                    docs: e.docs().cloned(),
+                })
+                .collect(),
+            receive: self.receive,
+            fallback: self.fallback,
+            order,
+        };
+
+        let mut bytes = vec![];
+        ciborium::into_writer(&cached, &mut bytes).expect("writing to a vector cannot fail");
+
+        bytes
+    }
+
+    /// Decodes an ABI encoded by [`Abi::to_bytes`].
+    ///
+    /// The signature hashes are taken from the bytes as they are, so the
+    /// bytes must come from [`Abi::to_bytes`] and not from an untrusted
+    /// source. Fails if they are malformed or were written by an
+    /// incompatible version of this crate.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Abi> {
+        let cached: CachedAbi =
+            ciborium::from_reader(bytes).map_err(|e| anyhow!("invalid ABI bytes: {}", e))?;
+
+        if cached.version != FORMAT_VERSION {
+            return Err(anyhow!(
+                "unsupported ABI bytes version {}, expected {}",
+                cached.version,
+                FORMAT_VERSION
+            ));
+        }
+
+        let mut abi = Abi {
+            constructor: cached
+                .constructor
+                .map(|(inputs, state_mutability)| Constructor {
+                    inputs: restore_params(inputs),
+                    state_mutability,
+                }),
+            functions: cached
+                .functions
+                .into_iter()
+                .map(|f| {
+                    let mut function = Function::new(
+                        f.name,
+                        restore_params(f.inputs),
+                        restore_params(f.outputs),
+                        f.state_mutability,
+                    );
+                    function.gas = f.gas;
+                    function.hash = f.hash.into();
+                    function.set_docs(f.docs);
+                    function
+                })
+                .collect(),
+            events: cached
+                .events
+                .into_iter()
+                .map(|e| {
+                    let mut event = Event::new(e.name, restore_params(e.inputs), e.anonymous);
+                    event.hash = e.hash.into();
+                    event.set_docs(e.docs);
+                    event
+                })
+                .collect(),
+            errors: cached
+                .errors
+                .into_iter()
+                .map(|e| {
+                    let mut error = Error::new(e.name, restore_params(e.inputs));
+                    error.hash = e.hash.into();
+                    error.set_docs(e.docs);
+                    error
+                })
+                .collect(),
+            receive: cached.receive,
+            fallback: cached.fallback,
+            ..Default::default()
+        };
+
+        let definitions: Vec<_> = abi.definitions().collect();
+        let mut layout = Vec::with_capacity(cached.order.len());
+
+        for (def, json) in cached.order {
+            if !definitions.contains(&def) {
+                return Err(anyhow!("invalid ABI bytes: unknown definition {:?}", def));
+            }
+
+            layout.push(SourceEntry {
+                key: abi.key_of(def),
+                json: json.map(String::into_boxed_str),
+            });
+        }
+
+        abi.layout = AbiLayout::from(layout);
+
+        Ok(abi)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedAbi {
+    version: u32,
+    constructor: Option<(Vec<CachedParam>, StateMutability)>,
+    functions: Vec<CachedFunction>,
+    events: Vec<CachedEvent>,
+    errors: Vec<CachedError>,
+    receive: Option<StateMutability>,
+    fallback: Option<StateMutability>,
+    // Empty for ABIs built in code.
+    order: Vec<(Def, Option<String>)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedFunction {
+    name: String,
+    inputs: Vec<CachedParam>,
+    outputs: Vec<CachedParam>,
+    state_mutability: StateMutability,
+    gas: Option<u64>,
+    hash: [u8; 32],
+    docs: Option<Docs>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedEvent {
+    name: String,
+    inputs: Vec<CachedParam>,
+    anonymous: bool,
+    hash: [u8; 32],
+    docs: Option<Docs>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedError {
+    name: String,
+    inputs: Vec<CachedParam>,
+    hash: [u8; 32],
+    docs: Option<Docs>,
+}
+
+// Params are stored with their parsed types, so loading them parses no type
+// strings either.
+#[derive(Serialize, Deserialize)]
+struct CachedParam {
+    name: String,
+    type_: CachedType,
+    indexed: Option<bool>,
+    internal_type: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum CachedType {
+    Uint(usize),
+    Int(usize),
+    Fixed(usize, usize),
+    Ufixed(usize, usize),
+    Address,
+    Bool,
+    Function,
+    FixedBytes(usize),
+    FixedArray(Box<CachedType>, usize),
+    String,
+    Bytes,
+    Array(Box<CachedType>),
+    Tuple(Vec<(String, CachedType)>),
+    Struct(String, Vec<(String, CachedType)>),
+}
+
+fn cache_params(params: &[Param]) -> Vec<CachedParam> {
+    params
+        .iter()
+        .map(|param| CachedParam {
+            name: param.name.clone(),
+            type_: cache_type(&param.type_),
+            indexed: param.indexed,
+            internal_type: param.internal_type.clone(),
+        })
+        .collect()
+}
+
+fn restore_params(params: Vec<CachedParam>) -> Vec<Param> {
+    params
+        .into_iter()
+        .map(|param| Param {
+            name: param.name,
+            type_: restore_type(param.type_),
+            indexed: param.indexed,
+            internal_type: param.internal_type,
+        })
+        .collect()
+}
+
+fn cache_type(ty: &Type) -> CachedType {
+    let fields = |fields: &[(String, Type)]| {
+        fields
+            .iter()
+            .map(|(name, ty)| (name.clone(), cache_type(ty)))
+            .collect()
+    };
+
+    match ty {
+        Type::Uint(size) => CachedType::Uint(*size),
+        Type::Int(size) => CachedType::Int(*size),
+        Type::Fixed(size, decimals) => CachedType::Fixed(*size, *decimals),
+        Type::Ufixed(size, decimals) => CachedType::Ufixed(*size, *decimals),
+        Type::Address => CachedType::Address,
+        Type::Bool => CachedType::Bool,
+        Type::Function => CachedType::Function,
+        Type::FixedBytes(size) => CachedType::FixedBytes(*size),
+        Type::FixedArray(ty, size) => CachedType::FixedArray(Box::new(cache_type(ty)), *size),
+        Type::String => CachedType::String,
+        Type::Bytes => CachedType::Bytes,
+        Type::Array(ty) => CachedType::Array(Box::new(cache_type(ty))),
+        Type::Tuple(tys) => CachedType::Tuple(fields(tys)),
+        Type::Struct(name, tys) => CachedType::Struct(name.clone(), fields(tys)),
+    }
+}
+
+fn restore_type(ty: CachedType) -> Type {
+    let fields = |fields: Vec<(String, CachedType)>| {
+        fields
+            .into_iter()
+            .map(|(name, ty)| (name, restore_type(ty)))
+            .collect()
+    };
+
+    match ty {
+        CachedType::Uint(size) => Type::Uint(size),
+        CachedType::Int(size) => Type::Int(size),
+        CachedType::Fixed(size, decimals) => Type::Fixed(size, decimals),
+        CachedType::Ufixed(size, decimals) => Type::Ufixed(size, decimals),
+        CachedType::Address => Type::Address,
+        CachedType::Bool => Type::Bool,
+        CachedType::Function => Type::Function,
+        CachedType::FixedBytes(size) => Type::FixedBytes(size),
+        CachedType::FixedArray(ty, size) => Type::FixedArray(Box::new(restore_type(*ty)), size),
+        CachedType::String => Type::String,
+        CachedType::Bytes => Type::Bytes,
+        CachedType::Array(ty) => Type::Array(Box::new(restore_type(*ty))),
+        CachedType::Tuple(tys) => Type::Tuple(fields(tys)),
+        CachedType::Struct(name, tys) => Type::Struct(name, fields(tys)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn bytes_round_trip() {
+        let json = r#"[{"inputs":[{"components":[{"internalType":"address","name":"to","type":"address"},{"internalType":"uint256[2]","name":"amounts","type":"uint256[2]"}],"internalType":"struct Order","name":"order","type":"tuple"}],"name":"fill","outputs":[],"stateMutability":"payable","type":"function"},{"anonymous":false,"inputs":[{"indexed":true,"internalType":"address","name":"to","type":"address"}],"name":"Filled","type":"event"},{"inputs":[],"name":"Expired","type":"error"},{"stateMutability":"payable","type":"receive"},{"gas":1234,"inputs":[],"name":"ping","outputs":[{"name":"","type":"bytes"}],"stateMutability":"view","type":"function","x-extra":true}]"#;
+
+        let mut abi: Abi = serde_json::from_str(json).unwrap();
+        abi.attach_natspec(
+            Some(&json!({"methods": {"ping()": {"notice": "Pings."}}})),
+            None,
+        );
+
+        let cached = Abi::from_bytes(&abi.to_bytes()).unwrap();
+
+        assert_eq!(cached, abi);
+        assert_eq!(serde_json::to_string(&cached).unwrap(), json);
+        assert_eq!(cached.functions[1].docs(), abi.functions[1].docs());
+        assert_eq!(cached.functions[1].gas, Some(1234));
+
+        // Hashes are read rather than computed.
+        for (cached, f) in cached.functions.iter().zip(&abi.functions) {
+            assert!(
+                cached.hash.get_or_init(|| unreachable!()) == f.hash.get_or_init(|| unreachable!())
+            );
+        }
+        assert_eq!(cached.events[0].topic(), abi.events[0].topic());
+        assert_eq!(cached.errors[0].selector(), abi.errors[0].selector());
+
+        let built = Abi::builder()
+            .function("f", &[("x", "uint8")], &[], StateMutability::Pure)
+            .build()
+            .unwrap();
+        assert_eq!(Abi::from_bytes(&built.to_bytes()).unwrap(), built);
+    }
+
+    #[test]
+    fn reject_invalid_bytes() {
+        assert!(Abi::from_bytes(&[]).is_err());
+        assert!(Abi::from_bytes(b"not cbor").is_err());
+
+        let mut bytes = vec![];
+        ciborium::into_writer(&json!({"version": 0}), &mut bytes).unwrap();
+        assert!(Abi::from_bytes(&bytes).is_err());
+    }
+}
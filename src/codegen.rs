@@ -0,0 +1,1294 @@
+//! Rust code generation from ABI files, for build scripts.
+//!
+//! The [`Builder`] turns every ABI or artifact file of a directory into a
+//! Rust module with a typed helper per function and a struct per event:
+//!
+//! ```no_run
+//! // build.rs
+//! ethereum_abi::codegen::Builder::new()
+//!     .compile_dir("abis")
+//!     .unwrap();
+//! ```
+//!
+//! The modules are then included from `OUT_DIR`:
+//!
+//! ```ignore
+//! pub mod erc20 {
+//!     include!(concat!(env!("OUT_DIR"), "/erc20.rs"));
+//! }
+//! ```
+//!
+//! A generated module holds:
+//!
+//! - `ABI`, the lazily parsed [`Abi`], and `ABI_JSON`, its JSON;
+//! - a `<NAME>_SELECTOR` constant, an `encode_<name>` function taking typed
+//!   arguments and, for functions with outputs, a `decode_<name>_output`
+//!   function per function;
+//! - a `<Name>Event` struct per event, with a `decode` function taking any
+//!   [`EventLog`](crate::EventLog) and, unless anonymous, a `TOPIC`
+//!   constant;
+//! - a `<NAME>_ERROR_SELECTOR` constant per custom error.
+//!
+//! Names are converted to the usual Rust case, and suffixed with the selector
+//! when overloaded. Types map to Rust types as follows, other types being
+//! passed and returned as [`Value`]s:
+//!
+//! | Solidity   | Argument  | Output or event field |
+//! |------------|-----------|-----------------------|
+//! | `address`  | `H160`    | `H160`                |
+//! | `bool`     | `bool`    | `bool`                |
+//! | `uint<M>`  | `U256`    | `U256`                |
+//! | `int<M>`   | `I256`    | `I256`                |
+//! | `string`   | `&str`    | `String`              |
+//! | `bytes`    | `&[u8]`   | `Vec<u8>`             |
+//! | `bytes<M>` | `[u8; M]` | `Vec<u8>`             |
+//! | `T[]`      | `Vec<T>`  | `Value`               |
+//!
+//! Indexed event fields of dynamic types are logged hashed, and generated as
+//! `H256`s.
+//!
+//! [`graphql_schema`] generates the GraphQL schema of a subgraph indexing an
+//! ABI's events instead, and [`proto_schema`] the protobuf messages of a
+//! substreams module.
+
+use anyhow::{anyhow, Result};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{Abi, Event, Param, Type};
+
+/// Generates Rust modules from a directory of ABI files.
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    out_dir: Option<PathBuf>,
+}
+
+impl Builder {
+    /// Creates a builder writing to `OUT_DIR`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the directory generated modules are written to, instead of
+    /// `OUT_DIR`.
+    pub fn out_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(dir.into());
+        self
+    }
+
+    /// Generates a module for every `.json` file of `dir`, holding either a
+    /// bare ABI or a build tool artifact.
+    ///
+    /// Each module is written to `<out_dir>/<name>.rs`, where the name is the
+    /// file stem in snake case. Returns the written paths. When run from a
+    /// build script, the directory and files are registered with
+    /// `cargo:rerun-if-changed`.
+    pub fn compile_dir(&self, dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => std::env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or_else(|| anyhow!("OUT_DIR is not set, use Builder::out_dir"))?,
+        };
+
+        let in_build_script = std::env::var_os("OUT_DIR").is_some();
+        if in_build_script {
+            println!("cargo:rerun-if-changed={}", dir.display());
+        }
+
+        let mut files = fs::read_dir(dir)?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?;
+        files.retain(|path| path.extension().is_some_and(|ext| ext == "json"));
+        files.sort();
+
+        fs::create_dir_all(&out_dir)?;
+
+        let mut modules = HashSet::new();
+        let mut written = vec![];
+
+        for file in files {
+            let stem = file
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .ok_or_else(|| anyhow!("invalid file name: {}", file.display()))?;
+
+            let module = snake_case(stem);
+            if !is_identifier(&module) || !modules.insert(module.clone()) {
+                return Err(anyhow!(
+                    "{} does not map to a unique module name",
+                    file.display()
+                ));
+            }
+
+            let abi = read_abi(&file)
+                .map_err(|err| anyhow!("failed to read {}: {:#}", file.display(), err))?;
+
+            let file_name = file.file_name().unwrap_or_default().to_string_lossy();
+            let path = out_dir.join(format!("{}.rs", module));
+
+            fs::write(&path, generate(&abi, &file_name)?)?;
+
+            if in_build_script {
+                println!("cargo:rerun-if-changed={}", file.display());
+            }
+
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+}
+
+fn read_abi(path: &Path) -> Result<Abi> {
+    let value: serde_json::Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+    if value.is_array() {
+        Ok(serde_json::from_value(value)?)
+    } else {
+        Ok(Abi::from_artifact_value(value)?.abi)
+    }
+}
+
+/// Generates the module for an ABI read from `source`, which is only
+/// mentioned in the module's header comment.
+pub fn generate(abi: &Abi, source: &str) -> Result<String> {
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "// Generated by `ethereum_abi::codegen` from `{}`. Do not edit.",
+        source
+    )?;
+    out.push_str(HEADER);
+    writeln!(
+        out,
+        "pub const ABI_JSON: &str = {:?};",
+        serde_json::to_string(abi)?
+    )?;
+
+    let function_names = overload_names(abi.functions.iter().map(|f| (&f.name, f.method_id())));
+    for (index, function) in abi.functions.iter().enumerate() {
+        let name = &function_names[index];
+        let signature = function.signature();
+
+        writeln!(out)?;
+        writeln!(out, "/// Selector of `{}`.", signature)?;
+        writeln!(
+            out,
+            "pub const {}_SELECTOR: [u8; 4] = {};",
+            name.to_uppercase(),
+            byte_array(&function.method_id())
+        )?;
+
+        write_encode(
+            &mut out,
+            index,
+            &name.to_lowercase(),
+            &signature,
+            &function.inputs,
+        )?;
+
+        if !function.outputs.is_empty() {
+            write_decode_output(
+                &mut out,
+                index,
+                &name.to_lowercase(),
+                &signature,
+                &function.outputs,
+            )?;
+        }
+    }
+
+    let event_names = overload_names(
+        abi.events
+            .iter()
+            .map(|e| (&e.name, e.topic().to_fixed_bytes())),
+    );
+    for (index, event) in abi.events.iter().enumerate() {
+        write_event(&mut out, index, &event_names[index], event)?;
+    }
+
+    let error_names = overload_names(abi.errors.iter().map(|e| (&e.name, e.selector())));
+    for (error, name) in abi.errors.iter().zip(error_names) {
+        writeln!(out)?;
+        writeln!(out, "/// Selector of `{}`.", error.signature())?;
+        writeln!(
+            out,
+            "pub const {}_ERROR_SELECTOR: [u8; 4] = {};",
+            name.to_uppercase(),
+            byte_array(&error.selector())
+        )?;
+    }
+
+    Ok(out)
+}
+
+const HEADER: &str = r#"
+#[allow(unused_imports)]
+use ::ethereum_abi::{
+    Abi, EventLog, Value, I256,
+    __private::{
+        anyhow::{anyhow, Result},
+        ethereum_types::{H160, H256, U256},
+    },
+};
+
+/// The contract ABI, parsed on first use.
+pub static ABI: ::std::sync::LazyLock<Abi> = ::std::sync::LazyLock::new(|| {
+    ::ethereum_abi::__private::serde_json::from_str(ABI_JSON).expect("generated ABI is valid")
+});
+
+/// The contract ABI as JSON.
+"#;
+
+fn write_encode(
+    out: &mut String,
+    index: usize,
+    name: &str,
+    signature: &str,
+    inputs: &[Param],
+) -> Result<()> {
+    let args = arg_names(inputs);
+
+    writeln!(out)?;
+    writeln!(out, "/// Encodes a call to `{}`.", signature)?;
+    write_user_types(out, &args, inputs)?;
+    writeln!(
+        out,
+        "pub fn encode_{}({}) -> Result<Vec<u8>> {{",
+        name,
+        args.iter()
+            .zip(inputs)
+            .map(|(arg, input)| format!("{}: {}", arg, arg_type(&input.type_)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )?;
+    let values = args
+        .iter()
+        .zip(inputs)
+        .map(|(arg, input)| to_value(&input.type_, arg))
+        .collect::<Vec<_>>();
+
+    if values.len() > 1 {
+        writeln!(out, "    ABI.functions[{}].encode_input(&[", index)?;
+        for value in values {
+            writeln!(out, "        {},", value)?;
+        }
+        writeln!(out, "    ])")?;
+    } else {
+        writeln!(
+            out,
+            "    ABI.functions[{}].encode_input(&[{}])",
+            index,
+            values.join("")
+        )?;
+    }
+    writeln!(out, "}}")?;
+
+    Ok(())
+}
+
+fn write_decode_output(
+    out: &mut String,
+    index: usize,
+    name: &str,
+    signature: &str,
+    outputs: &[Param],
+) -> Result<()> {
+    let (output_type, output_value) = match outputs {
+        [output] => (
+            output_type(&output.type_, false),
+            from_value(&output.type_, false, "params[0].clone()"),
+        ),
+        _ => (
+            format!(
+                "({})",
+                outputs
+                    .iter()
+                    .map(|output| output_type(&output.type_, false))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!(
+                "Ok((\n{}    ))",
+                outputs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, output)| {
+                        let value =
+                            field_value(&output.type_, false, &format!("params[{}].clone()", i));
+
+                        format!("        {},\n", value)
+                    })
+                    .collect::<String>()
+            ),
+        ),
+    };
+
+    writeln!(out)?;
+    writeln!(out, "/// Decodes the data returned by `{}`.", signature)?;
+    write_user_types(out, &arg_names(outputs), outputs)?;
+    writeln!(
+        out,
+        "pub fn decode_{}_output(output: &[u8]) -> Result<{}> {{",
+        name, output_type
+    )?;
+    writeln!(
+        out,
+        "    let params = ABI.functions[{}].decode_output_from_slice(output)?;",
+        index
+    )?;
+    writeln!(out)?;
+    writeln!(out, "    {}", output_value)?;
+    writeln!(out, "}}")?;
+
+    Ok(())
+}
+
+// Documents the user-defined value types params are declared as, which the
+// generated code leaves as their underlying types.
+fn write_user_types(out: &mut String, names: &[String], params: &[Param]) -> Result<()> {
+    let mut first = true;
+
+    for (name, param) in names.iter().zip(params) {
+        if let (Some(_), Some(internal_type)) = (param.user_type(), &param.internal_type) {
+            if first {
+                writeln!(out, "///")?;
+                first = false;
+            }
+            writeln!(out, "/// `{}` is declared as `{}`.", name, internal_type)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_event(out: &mut String, index: usize, name: &str, event: &Event) -> Result<()> {
+    let struct_name = format!("{}Event", pascal_case(name));
+    let signature = event.signature();
+    let fields = arg_names(&event.inputs);
+    let hashed =
+        |input: &Param| input.indexed.unwrap_or(false) && Event::is_encoded_to_keccak(&input.type_);
+
+    writeln!(out)?;
+    writeln!(out, "/// `{}` event.", signature)?;
+    writeln!(out, "#[derive(Debug, Clone, PartialEq, Eq)]")?;
+    writeln!(out, "pub struct {} {{", struct_name)?;
+    for (field, input) in fields.iter().zip(&event.inputs) {
+        if let (Some(_), Some(internal_type)) = (input.user_type(), &input.internal_type) {
+            writeln!(out, "    /// Declared as `{}`.", internal_type)?;
+        }
+        writeln!(
+            out,
+            "    pub {}: {},",
+            field,
+            output_type(&input.type_, hashed(input))
+        )?;
+    }
+    writeln!(out, "}}")?;
+
+    writeln!(out)?;
+    writeln!(out, "impl {} {{", struct_name)?;
+    if !event.anonymous {
+        writeln!(out, "    /// Topic of `{}`.", signature)?;
+        writeln!(
+            out,
+            "    pub const TOPIC: H256 = H256({});",
+            byte_array(event.topic().as_bytes())
+        )?;
+        writeln!(out)?;
+    }
+    writeln!(out, "    /// Decodes the event from a log.")?;
+    writeln!(
+        out,
+        "    pub fn decode<L: EventLog + ?Sized>(log: &L) -> Result<Self> {{"
+    )?;
+    if !event.anonymous {
+        writeln!(
+            out,
+            "        if log.topics().first() != Some(&Self::TOPIC) {{"
+        )?;
+        writeln!(
+            out,
+            "            return Err(anyhow!(\"not a {} event\"));",
+            event.name
+        )?;
+        writeln!(out, "        }}")?;
+        writeln!(out)?;
+    }
+    writeln!(
+        out,
+        "        let params = ABI.events[{}].decode_data_from_slice(log.topics(), log.data())?;",
+        index
+    )?;
+    writeln!(out)?;
+    writeln!(out, "        Ok(Self {{")?;
+    for (i, (field, input)) in fields.iter().zip(&event.inputs).enumerate() {
+        writeln!(
+            out,
+            "            {}: {},",
+            field,
+            field_value(
+                &input.type_,
+                hashed(input),
+                &format!("params[{}].clone()", i)
+            )
+        )?;
+    }
+    writeln!(out, "        }})")?;
+    writeln!(out, "    }}")?;
+    writeln!(out, "}}")?;
+
+    Ok(())
+}
+
+/// Generates a GraphQL schema for a subgraph indexing the ABI's events, as
+/// a starting point to edit.
+///
+/// Each event gets an immutable entity named after it, suffixed with the
+/// first 4 bytes of its topic when overloaded, with an `id`, a field per
+/// input and the `blockNumber`, `blockTimestamp` and `transactionHash` of
+/// the log. Types map to GraphQL scalars as `graph codegen` expects:
+///
+/// | Solidity                               | GraphQL      |
+/// |----------------------------------------|--------------|
+/// | `address`, `bytes`, `bytes<M>`         | `Bytes`      |
+/// | `bool`                                 | `Boolean`    |
+/// | `uint8` to `uint24`, `int8` to `int32` | `Int`        |
+/// | other `uint<M>` and `int<M>`           | `BigInt`     |
+/// | `fixed<M>x<N>`, `ufixed<M>x<N>`        | `BigDecimal` |
+/// | `string`                               | `String`     |
+/// | `T[]`, `T[k]`                          | `[T!]`       |
+///
+/// Tuple inputs are flattened into a field per component, named
+/// `<input>_<component>`. Tuples within arrays, having no GraphQL
+/// counterpart, and indexed inputs of dynamic types, which are logged
+/// hashed, are `Bytes`.
+///
+/// ```
+/// use ethereum_abi::{codegen::graphql_schema, Abi};
+///
+/// let abi: Abi = serde_json::from_str(r#"[
+///     {"type": "event", "name": "Approval", "anonymous": false, "inputs": [
+///         {"name": "owner", "type": "address", "indexed": true},
+///         {"name": "spender", "type": "address", "indexed": true},
+///         {"name": "value", "type": "uint256", "indexed": false}
+///     ]}
+/// ]"#).unwrap();
+///
+/// assert_eq!(graphql_schema(&abi), "\
+/// type Approval @entity(immutable: true) {
+///   id: Bytes!
+///   owner: Bytes! # address
+///   spender: Bytes! # address
+///   value: BigInt! # uint256
+///   blockNumber: BigInt!
+///   blockTimestamp: BigInt!
+///   transactionHash: Bytes!
+/// }
+/// ");
+/// ```
+pub fn graphql_schema(abi: &Abi) -> String {
+    let mut counts = HashMap::new();
+    for event in &abi.events {
+        *counts.entry(&event.name).or_insert(0) += 1;
+    }
+
+    let mut schema = String::new();
+
+    for event in &abi.events {
+        let entity = if counts[&event.name] > 1 {
+            format!(
+                "{}_{}",
+                event.name,
+                faster_hex::hex_string(&event.topic()[..4])
+            )
+        } else {
+            event.name.clone()
+        };
+
+        let mut fields = vec![];
+        for (i, input) in event.inputs.iter().enumerate() {
+            let name = if input.name.is_empty() {
+                format!("param{}", i)
+            } else {
+                input.name.to_string()
+            };
+            let hashed =
+                input.indexed.unwrap_or(false) && Event::is_encoded_to_keccak(&input.type_);
+
+            graphql_fields(&mut fields, name, &input.type_, hashed);
+        }
+
+        if !schema.is_empty() {
+            schema.push('\n');
+        }
+        schema.push_str(&format!("type {} @entity(immutable: true) {{\n", entity));
+        schema.push_str("  id: Bytes!\n");
+
+        let mut seen: HashSet<_> = GRAPHQL_LOG_FIELDS
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect();
+        for (name, type_, abi_type) in fields {
+            let mut name = name;
+            // Keeps clear of the log fields, and of earlier fields.
+            while !seen.insert(name.clone()) {
+                name = format!("internal_{}", name);
+            }
+
+            schema.push_str(&format!("  {}: {}! # {}\n", name, type_, abi_type));
+        }
+
+        for (name, type_) in GRAPHQL_LOG_FIELDS.iter().skip(1) {
+            schema.push_str(&format!("  {}: {}!\n", name, type_));
+        }
+        schema.push_str("}\n");
+    }
+
+    schema
+}
+
+const GRAPHQL_LOG_FIELDS: &[(&str, &str)] = &[
+    ("id", "Bytes"),
+    ("blockNumber", "BigInt"),
+    ("blockTimestamp", "BigInt"),
+    ("transactionHash", "Bytes"),
+];
+
+// Adds the fields of an event input: its name, GraphQL type and ABI type.
+fn graphql_fields(
+    fields: &mut Vec<(String, String, String)>,
+    name: String,
+    ty: &Type,
+    hashed: bool,
+) {
+    if hashed {
+        fields.push((name, String::from("Bytes"), format!("keccak256({})", ty)));
+        return;
+    }
+
+    match ty {
+        Type::Tuple(components) | Type::Struct(_, components) => {
+            for (i, (component, ty)) in components.iter().enumerate() {
+                let component = if component.is_empty() {
+                    format!("{}_{}", name, i)
+                } else {
+                    format!("{}_{}", name, component)
+                };
+
+                graphql_fields(fields, component, ty, false);
+            }
+        }
+        _ => fields.push((name, graphql_type(ty), ty.to_string())),
+    }
+}
+
+fn graphql_type(ty: &Type) -> String {
+    match ty {
+        Type::Uint(size) if *size <= 24 => String::from("Int"),
+        Type::Int(size) if *size <= 32 => String::from("Int"),
+        Type::Uint(_) | Type::Int(_) => String::from("BigInt"),
+        Type::Fixed(_, _) | Type::Ufixed(_, _) => String::from("BigDecimal"),
+        Type::Bool => String::from("Boolean"),
+        Type::String => String::from("String"),
+        Type::Address
+        | Type::Function
+        | Type::FixedBytes(_)
+        | Type::Bytes
+        | Type::Tuple(_)
+        | Type::Struct(_, _) => String::from("Bytes"),
+        Type::Array(ty) | Type::FixedArray(ty, _) => format!("[{}!]", graphql_type(ty)),
+    }
+}
+
+/// Generates protobuf (proto3) message definitions for the ABI's events,
+/// and for the inputs of its functions if `calls` is set, to scaffold
+/// substreams modules with.
+///
+/// Each event gets a `<Name>Event` message, and each function a
+/// `<Name>Call` one, named as in generated Rust modules, with a field per
+/// param. `Events` and `Calls` messages gather them in repeated fields.
+/// Every field is commented with its ABI type, and how its values are
+/// converted when the protobuf type differs:
+///
+/// | Solidity                        | Protobuf                     |
+/// |---------------------------------|------------------------------|
+/// | `address`                       | `bytes`, 20 bytes            |
+/// | `bool`                          | `bool`                       |
+/// | `uint8` to `uint64`             | `uint32` or `uint64`         |
+/// | `int8` to `int64`               | `int32` or `int64`           |
+/// | other `uint<M>` and `int<M>`    | `string`, in decimal         |
+/// | `fixed<M>x<N>`, `ufixed<M>x<N>` | `string`, in decimal         |
+/// | `bytes`, `bytes<M>`, `function` | `bytes`                      |
+/// | `string`                        | `string`                     |
+/// | tuples                          | a nested message             |
+/// | `T[]`, `T[k]`                   | `repeated T`                 |
+///
+/// Arrays of arrays, which protobuf can't repeat, are `bytes` holding their
+/// ABI encoding. Indexed event inputs of dynamic types are `bytes` holding
+/// the hash they are logged as.
+pub fn proto_schema(abi: &Abi, package: &str, calls: bool) -> String {
+    let mut proto = format!("syntax = \"proto3\";\n\npackage {};\n", package);
+
+    let event_names = overload_names(
+        abi.events
+            .iter()
+            .map(|e| (&e.name, e.topic().to_fixed_bytes())),
+    );
+    let mut events = vec![];
+    for (event, name) in abi.events.iter().zip(event_names) {
+        let message = format!("{}Event", pascal_case(&name));
+
+        proto.push_str(&format!("\n// {}\n", event.signature()));
+        proto_message(&mut proto, "", &message, &event.inputs, true);
+        events.push((format!("{}_events", name.to_lowercase()), message));
+    }
+    proto_list(&mut proto, "Events", &events);
+
+    if calls {
+        let function_names = overload_names(abi.functions.iter().map(|f| (&f.name, f.method_id())));
+        let mut calls = vec![];
+        for (function, name) in abi.functions.iter().zip(function_names) {
+            let message = format!("{}Call", pascal_case(&name));
+
+            proto.push_str(&format!("\n// {}\n", function.signature()));
+            proto_message(&mut proto, "", &message, &function.inputs, false);
+            calls.push((format!("{}_calls", name.to_lowercase()), message));
+        }
+        proto_list(&mut proto, "Calls", &calls);
+    }
+
+    proto
+}
+
+fn proto_message(out: &mut String, indent: &str, name: &str, params: &[Param], event: bool) {
+    let nested_indent = format!("{}  ", indent);
+    let fields = arg_names(params);
+
+    out.push_str(&format!("{}message {} {{\n", indent, name));
+
+    for (field, param) in fields.iter().zip(params) {
+        let mut ty = &param.type_;
+        if let Type::Array(inner) | Type::FixedArray(inner, _) = ty {
+            ty = inner;
+        }
+
+        if let Type::Tuple(components) | Type::Struct(_, components) = ty {
+            if !is_hashed(param, event) {
+                let components: Vec<_> = components
+                    .iter()
+                    .map(|(name, ty)| Param {
+                        name: name.clone(),
+                        type_: ty.clone(),
+                        indexed: None,
+                        internal_type: None,
+                    })
+                    .collect();
+
+                proto_message(out, &nested_indent, &pascal_case(field), &components, false);
+            }
+        }
+    }
+
+    for (i, (field, param)) in fields.iter().zip(params).enumerate() {
+        let (label, proto_type, conversion) = if is_hashed(param, event) {
+            (
+                "",
+                String::from("bytes"),
+                Some(String::from("keccak256 hash")),
+            )
+        } else {
+            proto_field(&param.type_, &pascal_case(field))
+        };
+
+        let mut note = param.type_.to_string();
+        if let Some(conversion) = conversion {
+            note.push_str(&format!(" -> {}", conversion));
+        }
+
+        out.push_str(&format!(
+            "{}{}{} {} = {}; // {}\n",
+            nested_indent,
+            label,
+            proto_type,
+            field,
+            i + 1,
+            note
+        ));
+    }
+
+    out.push_str(&format!("{}}}\n", indent));
+}
+
+fn is_hashed(param: &Param, event: bool) -> bool {
+    event && param.indexed.unwrap_or(false) && Event::is_encoded_to_keccak(&param.type_)
+}
+
+// The label, protobuf type and conversion of a field, whose tuples are
+// messages named `nested`.
+fn proto_field(ty: &Type, nested: &str) -> (&'static str, String, Option<String>) {
+    let decimal = || Some(String::from("decimal string"));
+
+    match ty {
+        Type::Uint(size) if *size <= 32 => ("", String::from("uint32"), None),
+        Type::Uint(size) if *size <= 64 => ("", String::from("uint64"), None),
+        Type::Int(size) if *size <= 32 => ("", String::from("int32"), None),
+        Type::Int(size) if *size <= 64 => ("", String::from("int64"), None),
+        Type::Uint(_) | Type::Int(_) | Type::Fixed(_, _) | Type::Ufixed(_, _) => {
+            ("", String::from("string"), decimal())
+        }
+        Type::Address => ("", String::from("bytes"), Some(String::from("20 bytes"))),
+        Type::Bool => ("", String::from("bool"), None),
+        Type::Function => (
+            "",
+            String::from("bytes"),
+            Some(String::from("24 bytes, address and selector")),
+        ),
+        Type::FixedBytes(size) => ("", String::from("bytes"), Some(format!("{} bytes", size))),
+        Type::String => ("", String::from("string"), None),
+        Type::Bytes => ("", String::from("bytes"), None),
+        Type::Tuple(_) | Type::Struct(_, _) => ("", nested.to_string(), None),
+        Type::Array(ty) | Type::FixedArray(ty, _) => match **ty {
+            Type::Array(_) | Type::FixedArray(_, _) => (
+                "",
+                String::from("bytes"),
+                Some(String::from("ABI encoding")),
+            ),
+            _ => {
+                let (_, proto_type, conversion) = proto_field(ty, nested);
+                ("repeated ", proto_type, conversion)
+            }
+        },
+    }
+}
+
+fn proto_list(out: &mut String, name: &str, messages: &[(String, String)]) {
+    out.push_str(&format!("\nmessage {} {{\n", name));
+    for (i, (field, message)) in messages.iter().enumerate() {
+        out.push_str(&format!("  repeated {} {} = {};\n", message, field, i + 1));
+    }
+    out.push_str("}\n");
+}
+
+// Names of functions, events or errors, suffixed with the first 4 bytes of the
+// given selector or topic when overloaded.
+fn overload_names<'a, S: AsRef<[u8]>>(
+    items: impl Iterator<Item = (&'a String, S)> + Clone,
+) -> Vec<String> {
+    let mut counts = HashMap::new();
+    for (name, _) in items.clone() {
+        *counts.entry(name).or_insert(0) += 1;
+    }
+
+    items
+        .map(|(name, selector)| {
+            if counts[name] > 1 {
+                format!(
+                    "{}_{}",
+                    snake_case(name),
+                    faster_hex::hex_string(&selector.as_ref()[..4])
+                )
+            } else {
+                snake_case(name)
+            }
+        })
+        .collect()
+}
+
+// Snake case argument or field names, falling back to `argN` for unnamed,
+// duplicate or reserved ones.
+fn arg_names(params: &[Param]) -> Vec<String> {
+    field_names(params.iter().map(|param| param.name.as_str()))
+}
+
+// The names of the fields params are generated as, see `arg_names`.
+pub(crate) fn field_names<'a>(names: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut seen = HashSet::new();
+
+    names
+        .enumerate()
+        .map(|(i, name)| {
+            Some(snake_case(name))
+                .filter(|name| is_identifier(name) && !KEYWORDS.contains(&name.as_str()))
+                .filter(|name| seen.insert(name.clone()))
+                .unwrap_or_else(|| format!("arg{}", i))
+        })
+        .collect()
+}
+
+fn arg_type(ty: &Type) -> String {
+    match ty {
+        Type::Address => "H160".to_string(),
+        Type::Bool => "bool".to_string(),
+        Type::String => "&str".to_string(),
+        Type::Bytes => "&[u8]".to_string(),
+        Type::FixedBytes(size) => format!("[u8; {}]", size),
+        Type::Uint(_) => "U256".to_string(),
+        Type::Int(_) => "I256".to_string(),
+        Type::Array(inner) if is_typed(inner) => format!("Vec<{}>", arg_type(inner)),
+        _ => "Value".to_string(),
+    }
+}
+
+fn to_value(ty: &Type, arg: &str) -> String {
+    match ty {
+        Type::Address => format!("Value::Address({})", arg),
+        Type::Bool => format!("Value::Bool({})", arg),
+        Type::String => format!("Value::String({}.to_string())", arg),
+        Type::Bytes => format!("Value::Bytes({}.to_vec().into())", arg),
+        Type::FixedBytes(_) => format!("Value::FixedBytes({}.to_vec())", arg),
+        Type::Uint(size) => format!("Value::Uint({}, {})", arg, size),
+        Type::Int(size) => format!("Value::Int({}.into_raw(), {})", arg, size),
+        // The array is retyped after the declared type when encoded.
+        Type::Array(inner) if is_typed(inner) => {
+            let element = match inner.as_ref() {
+                Type::Address => "Value::Address".to_string(),
+                Type::Bool => "Value::Bool".to_string(),
+                _ => format!("|element| {}", to_value(inner, "element")),
+            };
+
+            format!(
+                "Value::from({}.into_iter().map({}).collect::<Vec<Value>>())",
+                arg, element
+            )
+        }
+        _ => arg.to_string(),
+    }
+}
+
+fn output_type(ty: &Type, hashed: bool) -> String {
+    match ty {
+        _ if hashed => "H256",
+        Type::Address => "H160",
+        Type::Bool => "bool",
+        Type::String => "String",
+        Type::Bytes | Type::FixedBytes(_) => "Vec<u8>",
+        Type::Uint(_) => "U256",
+        Type::Int(_) => "I256",
+        _ => "Value",
+    }
+    .to_string()
+}
+
+// A `Result` converting a decoded value to its `output_type`.
+fn from_value(ty: &Type, hashed: bool, value: &str) -> String {
+    if hashed || is_typed(ty) {
+        format!(
+            "<{} as ::std::convert::TryFrom<Value>>::try_from({})",
+            output_type(ty, hashed),
+            value
+        )
+    } else {
+        format!("Ok({})", value)
+    }
+}
+
+// A decoded value converted to its `output_type`, errors being propagated.
+fn field_value(ty: &Type, hashed: bool, value: &str) -> String {
+    if hashed || is_typed(ty) {
+        format!("{}?", from_value(ty, hashed, value))
+    } else {
+        value.to_string()
+    }
+}
+
+fn is_typed(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Address
+            | Type::Bool
+            | Type::String
+            | Type::Bytes
+            | Type::FixedBytes(_)
+            | Type::Uint(_)
+            | Type::Int(_)
+    )
+}
+
+fn byte_array(bytes: &[u8]) -> String {
+    format!(
+        "[{}]",
+        bytes
+            .iter()
+            .map(|b| format!("0x{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Converts an identifier such as `balanceOf`, `ERC20Transfer` or
+/// `Uniswap-V2` to snake case.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|c| c.is_ascii_lowercase());
+
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                out.push('_');
+            }
+        }
+
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push('_');
+        }
+    }
+
+    out
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self",
+    "static", "struct", "super", "trait", "true", "try", "type", "typeof", "union", "unsafe",
+    "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const TOKEN_ABI: &str = include_str!("../testdata/codegen/token.json");
+
+    #[allow(dead_code)]
+    mod token {
+        include!("../testdata/codegen/token.rs");
+    }
+
+    #[test]
+    fn generate_module() {
+        let abi: Abi = serde_json::from_str(TOKEN_ABI).unwrap();
+
+        assert_eq!(
+            generate(&abi, "token.json").unwrap(),
+            include_str!("../testdata/codegen/token.rs")
+        );
+    }
+
+    #[test]
+    fn generated_module() {
+        use ethereum_types::{H160, H256, U256};
+
+        use crate::{EventLog, Value, I256};
+
+        struct RawLog(Vec<H256>, Vec<u8>);
+
+        impl EventLog for RawLog {
+            fn topics(&self) -> &[H256] {
+                &self.0
+            }
+
+            fn data(&self) -> &[u8] {
+                &self.1
+            }
+        }
+
+        assert_eq!(token::ABI.functions.len(), 5);
+        assert_eq!(token::TRANSFER_SELECTOR, [0xa9, 0x05, 0x9c, 0xbb]);
+
+        let to = H160::repeat_byte(1);
+        let calldata = token::encode_transfer(to, U256::from(5)).unwrap();
+        assert_eq!(
+            calldata,
+            token::ABI
+                .encode_call("transfer", &[to.into(), U256::from(5).into()])
+                .unwrap()
+        );
+
+        token::encode_mint_40c10f19(to, U256::one()).unwrap();
+        token::encode_mint_5487ebbf(vec![to, to], vec![U256::one(), U256::one()], *b"ab").unwrap();
+
+        let output = Value::encode(&[
+            Value::Uint(U256::from(18), 8),
+            Value::Int(I256::from(-1i64).into_raw(), 16),
+        ]);
+        assert_eq!(
+            token::decode_info_output(&output).unwrap(),
+            (U256::from(18), I256::from(-1i64))
+        );
+
+        let log = RawLog(
+            vec![
+                token::TransferEvent::TOPIC,
+                H256::from(H160::repeat_byte(2)),
+                H256::from(to),
+            ],
+            Value::encode(&[Value::Uint(U256::from(7), 256)]),
+        );
+        assert_eq!(
+            token::TransferEvent::decode(&log).unwrap(),
+            token::TransferEvent {
+                from: H160::repeat_byte(2),
+                to,
+                value: U256::from(7),
+            }
+        );
+
+        let memo = H256::repeat_byte(9);
+        let log = RawLog(
+            vec![token::NoteEvent::TOPIC, memo],
+            Value::encode(&[Value::Tuple(vec![
+                ("a".into(), Value::Bool(true)),
+                ("b".into(), Value::String("gm".to_string())),
+            ])]),
+        );
+        let note = token::NoteEvent::decode(&log).unwrap();
+        assert_eq!(note.memo, memo);
+        assert!(token::TransferEvent::decode(&log).is_err());
+
+        assert_eq!(
+            token::INSUFFICIENT_BALANCE_ERROR_SELECTOR,
+            token::ABI.errors[0].selector()
+        );
+    }
+
+    #[test]
+    fn generate_graphql_schema() {
+        let abi: Abi = serde_json::from_str(TOKEN_ABI).unwrap();
+
+        assert_eq!(
+            graphql_schema(&abi),
+            r#"type Transfer @entity(immutable: true) {
+  id: Bytes!
+  from: Bytes! # address
+  to: Bytes! # address
+  value: BigInt! # uint256
+  blockNumber: BigInt!
+  blockTimestamp: BigInt!
+  transactionHash: Bytes!
+}
+
+type Note @entity(immutable: true) {
+  id: Bytes!
+  memo: Bytes! # keccak256(string)
+  data_a: Boolean! # bool
+  data_b: String! # string
+  blockNumber: BigInt!
+  blockTimestamp: BigInt!
+  transactionHash: Bytes!
+}
+"#
+        );
+
+        let abi: Abi = serde_json::from_str(
+            r#"[
+                {"type": "event", "name": "Set", "anonymous": false, "inputs": [
+                    {"name": "id", "type": "uint24", "indexed": true},
+                    {"name": "", "type": "int40", "indexed": false},
+                    {"name": "ratio", "type": "ufixed128x18", "indexed": false},
+                    {"name": "keys", "type": "bytes32[2]", "indexed": false},
+                    {"name": "pairs", "type": "tuple[]", "indexed": false, "components": [{"name": "a", "type": "uint8"}]}
+                ]},
+                {"type": "event", "name": "Set", "anonymous": false, "inputs": []}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            graphql_schema(&abi),
+            r#"type Set_c0d447cc @entity(immutable: true) {
+  id: Bytes!
+  internal_id: Int! # uint24
+  param1: BigInt! # int40
+  ratio: BigDecimal! # ufixed128x18
+  keys: [Bytes!]! # bytes32[2]
+  pairs: [Bytes!]! # (uint8)[]
+  blockNumber: BigInt!
+  blockTimestamp: BigInt!
+  transactionHash: Bytes!
+}
+
+type Set_2081e911 @entity(immutable: true) {
+  id: Bytes!
+  blockNumber: BigInt!
+  blockTimestamp: BigInt!
+  transactionHash: Bytes!
+}
+"#
+        );
+    }
+
+    #[test]
+    fn generate_proto_schema() {
+        let abi: Abi = serde_json::from_str(TOKEN_ABI).unwrap();
+
+        let proto = proto_schema(&abi, "token.v1", true);
+
+        assert_eq!(
+            proto,
+            r#"syntax = "proto3";
+
+package token.v1;
+
+// Transfer(address,address,uint256)
+message TransferEvent {
+  bytes from = 1; // address -> 20 bytes
+  bytes to = 2; // address -> 20 bytes
+  string value = 3; // uint256 -> decimal string
+}
+
+// Note(string,(bool,string))
+message NoteEvent {
+  message Data {
+    bool a = 1; // bool
+    string b = 2; // string
+  }
+  bytes memo = 1; // string -> keccak256 hash
+  Data data = 2; // (bool,string)
+}
+
+message Events {
+  repeated TransferEvent transfer_events = 1;
+  repeated NoteEvent note_events = 2;
+}
+
+// transfer(address,uint256)
+message TransferCall {
+  bytes to = 1; // address -> 20 bytes
+  string amount = 2; // uint256 -> decimal string
+}
+
+// mint(address,uint256)
+message Mint40c10f19Call {
+  bytes to = 1; // address -> 20 bytes
+  string amount = 2; // uint256 -> decimal string
+}
+
+// mint(address[],uint256[],bytes2)
+message Mint5487ebbfCall {
+  repeated bytes recipients = 1; // address[] -> 20 bytes
+  repeated string amounts = 2; // uint256[] -> decimal string
+  bytes tag = 3; // bytes2 -> 2 bytes
+}
+
+// info()
+message InfoCall {
+}
+
+// configure((uint256,bool))
+message ConfigureCall {
+  message Config {
+    string cap = 1; // uint256 -> decimal string
+    bool paused = 2; // bool
+  }
+  Config config = 1; // (uint256,bool)
+}
+
+message Calls {
+  repeated TransferCall transfer_calls = 1;
+  repeated Mint40c10f19Call mint_40c10f19_calls = 2;
+  repeated Mint5487ebbfCall mint_5487ebbf_calls = 3;
+  repeated InfoCall info_calls = 4;
+  repeated ConfigureCall configure_calls = 5;
+}
+"#
+        );
+
+        let events_only = proto_schema(&abi, "token.v1", false);
+        assert!(proto.starts_with(&events_only));
+        assert!(!events_only.contains("Call"));
+    }
+
+    #[test]
+    fn compile_dir() {
+        let dir = std::env::temp_dir().join(format!("ethereum_abi_codegen_{}", std::process::id()));
+        let abis = dir.join("abis");
+        fs::create_dir_all(&abis).unwrap();
+
+        fs::write(abis.join("Token.json"), TOKEN_ABI).unwrap();
+        fs::write(
+            abis.join("UniswapV2Pair.json"),
+            r#"{"contractName":"UniswapV2Pair","abi":[{"type":"function","name":"sync","inputs":[],"outputs":[],"stateMutability":"nonpayable"}]}"#,
+        )
+        .unwrap();
+        fs::write(abis.join("README.md"), "not an ABI").unwrap();
+
+        let written = Builder::new()
+            .out_dir(dir.join("out"))
+            .compile_dir(&abis)
+            .unwrap();
+
+        assert_eq!(
+            written,
+            vec![
+                dir.join("out").join("token.rs"),
+                dir.join("out").join("uniswap_v2_pair.rs"),
+            ]
+        );
+        assert!(fs::read_to_string(&written[1])
+            .unwrap()
+            .contains("pub fn encode_sync() -> Result<Vec<u8>>"));
+
+        fs::write(abis.join("token.json"), TOKEN_ABI).unwrap();
+        assert!(Builder::new()
+            .out_dir(dir.join("out"))
+            .compile_dir(&abis)
+            .is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(snake_case("balanceOf"), "balance_of");
+        assert_eq!(snake_case("ERC20Transfer"), "erc20_transfer");
+        assert_eq!(snake_case("DOMAIN_SEPARATOR"), "domain_separator");
+        assert_eq!(pascal_case("approval_for_all"), "ApprovalForAll");
+
+        let params = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| Param {
+                    name: (*name).into(),
+                    type_: Type::Bool,
+                    indexed: None,
+                    internal_type: None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            arg_names(&params(&["_to", "", "type", "to", "amountIn"])),
+            vec!["_to", "arg1", "arg2", "to", "amount_in"]
+        );
+    }
+}
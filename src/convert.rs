@@ -0,0 +1,336 @@
+use anyhow::{anyhow, Result};
+use bytes::Bytes;
+use ethereum_types::{H160, H256, U256};
+use std::{convert::TryFrom, sync::Arc};
+
+use crate::{Type, Value};
+
+impl Value {
+    /// Returns the integer of a `uint` value.
+    pub fn as_uint(&self) -> Option<U256> {
+        match self {
+            Value::Uint(v, _) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the address of an `address` value.
+    pub fn as_address(&self) -> Option<H160> {
+        match self {
+            Value::Address(address) => Some(*address),
+            _ => None,
+        }
+    }
+
+    /// Returns the flag of a `bool` value.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes of a `bytes` or `bytes<M>` value.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            Value::FixedBytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the text of a `string` value.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the hash of an indexed event param that was logged hashed.
+    pub fn as_hash(&self) -> Option<H256> {
+        match self {
+            Value::Hash(hash) => Some(*hash),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of a `T[]` or `T[k]` value.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values, _) | Value::FixedArray(values, _) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the (name, value) fields of a tuple value.
+    pub fn as_tuple(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Tuple(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+fn mismatch(expected: &str, value: &Value) -> anyhow::Error {
+    anyhow!("expected {}, got {}", expected, value.type_of())
+}
+
+impl TryFrom<Value> for U256 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        value.as_uint().ok_or_else(|| mismatch("uint", &value))
+    }
+}
+
+impl TryFrom<Value> for u64 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        let v = U256::try_from(value)?;
+
+        if v.bits() > 64 {
+            return Err(anyhow!("{} does not fit in u64", v));
+        }
+
+        Ok(v.low_u64())
+    }
+}
+
+impl TryFrom<Value> for u128 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        let v = U256::try_from(value)?;
+
+        if v.bits() > 128 {
+            return Err(anyhow!("{} does not fit in u128", v));
+        }
+
+        Ok(v.low_u128())
+    }
+}
+
+impl TryFrom<Value> for H160 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        value
+            .as_address()
+            .ok_or_else(|| mismatch("address", &value))
+    }
+}
+
+impl TryFrom<Value> for H256 {
+    type Error = anyhow::Error;
+
+    /// Converts a hashed indexed param or a `bytes32` value.
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Hash(hash) => Ok(hash),
+            Value::FixedBytes(bytes) if bytes.len() == 32 => Ok(H256::from_slice(&bytes)),
+            _ => Err(mismatch("bytes32", &value)),
+        }
+    }
+}
+
+impl TryFrom<Value> for bool {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        value.as_bool().ok_or_else(|| mismatch("bool", &value))
+    }
+}
+
+impl TryFrom<Value> for Vec<u8> {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Bytes(bytes) => Ok(bytes.into()),
+            Value::FixedBytes(bytes) => Ok(bytes),
+            _ => Err(mismatch("bytes", &value)),
+        }
+    }
+}
+
+impl TryFrom<Value> for Bytes {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Bytes(bytes) => Ok(bytes),
+            Value::FixedBytes(bytes) => Ok(bytes.into()),
+            _ => Err(mismatch("bytes", &value)),
+        }
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::String(s) => Ok(s),
+            _ => Err(mismatch("string", &value)),
+        }
+    }
+}
+
+impl From<U256> for Value {
+    /// Builds a `uint256` value.
+    fn from(v: U256) -> Self {
+        Value::Uint(v, 256)
+    }
+}
+
+impl From<H160> for Value {
+    fn from(address: H160) -> Self {
+        Value::Address(address)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    /// Builds a dynamic `bytes` value.
+    fn from(bytes: Vec<u8>) -> Self {
+        Value::Bytes(bytes.into())
+    }
+}
+
+impl From<Bytes> for Value {
+    /// Builds a dynamic `bytes` value.
+    fn from(bytes: Bytes) -> Self {
+        Value::Bytes(bytes)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    /// Builds a dynamic array value typed after its first element.
+    ///
+    /// Empty vectors are typed as `uint256[]`; [`Abi::encode_call`] retypes
+    /// arrays after the declared parameter type anyway.
+    ///
+    /// [`Abi::encode_call`]: crate::Abi::encode_call
+    fn from(values: Vec<T>) -> Self {
+        let values: Vec<Value> = values.into_iter().map(Into::into).collect();
+        let ty = values
+            .first()
+            .map(Value::type_of)
+            .unwrap_or(Type::Uint(256));
+
+        Value::Array(values, Arc::new(ty))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::Abi;
+
+    use super::*;
+
+    #[test]
+    fn typed_getters() {
+        let value = Value::Tuple(vec![
+            ("to".into(), Value::Address(H160::repeat_byte(1))),
+            (
+                "ids".into(),
+                Value::Array(
+                    vec![Value::Uint(U256::from(7), 256)],
+                    Type::Uint(256).into(),
+                ),
+            ),
+        ]);
+
+        let fields = value.as_tuple().unwrap();
+
+        assert_eq!(fields[0].1.as_address(), Some(H160::repeat_byte(1)));
+        assert_eq!(
+            fields[1].1.as_array().unwrap()[0].as_uint(),
+            Some(U256::from(7))
+        );
+        assert_eq!(fields[0].1.as_uint(), None);
+        assert_eq!(value.as_str(), None);
+        assert_eq!(
+            Value::FixedBytes(vec![1, 2]).as_bytes(),
+            Some(&[1u8, 2][..])
+        );
+    }
+
+    #[test]
+    fn try_from_values() {
+        let big = Value::Uint(U256::MAX, 256);
+
+        assert_eq!(U256::try_from(big.clone()).unwrap(), U256::MAX);
+        assert!(u128::try_from(big).is_err());
+        assert_eq!(u64::try_from(Value::Uint(U256::from(42), 8)).unwrap(), 42);
+        assert!(bool::try_from(Value::Uint(U256::one(), 8)).is_err());
+        assert_eq!(
+            String::try_from(Value::String("gm".to_string())).unwrap(),
+            "gm"
+        );
+        assert_eq!(
+            Vec::<u8>::try_from(Value::Bytes(vec![9].into())).unwrap(),
+            vec![9]
+        );
+
+        let err = H160::try_from(Value::Bool(true)).unwrap_err();
+        assert_eq!(err.to_string(), "expected address, got bool");
+    }
+
+    #[test]
+    fn values_from_native_types() {
+        let abi =
+            Abi::from_signatures(&["send(address,uint96,address[],bytes,string,bool)"]).unwrap();
+
+        let to = H160::repeat_byte(1);
+        let input = abi
+            .encode_call(
+                "send",
+                &[
+                    to.into(),
+                    U256::from(5).into(),
+                    vec![to, to].into(),
+                    vec![0xde_u8, 0xad].into(),
+                    "memo".into(),
+                    true.into(),
+                ],
+            )
+            .unwrap();
+
+        let (_, params) = abi.decode_input_from_slice(&input).unwrap();
+
+        assert_eq!(params[1], Value::Uint(U256::from(5), 96));
+        assert_eq!(
+            params[2],
+            Value::Array(vec![to.into(), to.into()], Type::Address.into())
+        );
+        assert_eq!(params[3], Value::Bytes(vec![0xde, 0xad].into()));
+        assert_eq!(params[4], "memo".into());
+
+        assert_eq!(
+            Value::from(Vec::<bool>::new()),
+            Value::Array(vec![], Type::Uint(256).into())
+        );
+    }
+}
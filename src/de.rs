@@ -0,0 +1,360 @@
+use anyhow::{anyhow, Result};
+use serde::{
+    de::{
+        value::{Error, MapDeserializer, SeqDeserializer},
+        Deserializer, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Deserialize,
+};
+use std::convert::TryFrom;
+
+use crate::{encode_hex, values::field_key, DecodedParams, Value, I256};
+
+impl DecodedParams {
+    /// Deserializes the params into a user type, mapping param names to
+    /// struct fields and recursing into tuples.
+    ///
+    /// Structs can also be filled positionally from unnamed params by
+    /// deserializing into a tuple or tuple struct. Integers deserialize into
+    /// native integers that can hold them or into `U256`, addresses into
+    /// `H160`, and bytes into `Vec<u8>`, byte arrays or `H256`.
+    ///
+    /// ```
+    /// use ethereum_abi::{Abi, Value};
+    /// use ethereum_types::{H160, U256};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct TransferArgs {
+    ///     to: H160,
+    ///     amount: U256,
+    /// }
+    ///
+    /// let abi: Abi = serde_json::from_str(r#"[{
+    ///     "type": "function",
+    ///     "name": "transfer",
+    ///     "stateMutability": "nonpayable",
+    ///     "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+    ///     "outputs": []
+    /// }]"#).unwrap();
+    ///
+    /// let input = abi
+    ///     .encode_call(
+    ///         "transfer",
+    ///         &[Value::Address(H160::repeat_byte(1)), Value::Uint(U256::from(5), 256)],
+    ///     )
+    ///     .unwrap();
+    /// let (_, decoded) = abi.decode_input_from_slice(&input).unwrap();
+    ///
+    /// let args: TransferArgs = decoded.deserialize().unwrap();
+    /// assert_eq!(args.amount, U256::from(5));
+    /// ```
+    pub fn deserialize<'de, T: Deserialize<'de>>(&'de self) -> Result<T> {
+        T::deserialize(ParamsDeserializer(self)).map_err(|err| anyhow!("{}", err))
+    }
+}
+
+struct ParamsDeserializer<'de>(&'de DecodedParams);
+
+impl<'de> ParamsDeserializer<'de> {
+    fn values(&self) -> impl Iterator<Item = ValueDeserializer<'de>> {
+        self.0
+            .iter()
+            .map(|decoded| ValueDeserializer(&decoded.value))
+    }
+}
+
+impl<'de> Deserializer<'de> for ParamsDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(MapDeserializer::new(self.0.iter().enumerate().map(
+            |(i, decoded)| {
+                (
+                    field_key(&decoded.param.name, i),
+                    ValueDeserializer(&decoded.value),
+                )
+            },
+        )))
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(SeqDeserializer::new(self.values()))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+struct ValueDeserializer<'de>(&'de Value);
+
+impl<'de> IntoDeserializer<'de, Error> for ValueDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Uint(v, _) => {
+                if v.bits() <= 64 {
+                    visitor.visit_u64(v.low_u64())
+                } else if v.bits() <= 128 {
+                    visitor.visit_u128(v.low_u128())
+                } else {
+                    visitor.visit_string(v.to_string())
+                }
+            }
+            Value::Int(v, size) => {
+                let v = I256::sign_extend(*v, *size);
+
+                if let Ok(i) = i64::try_from(v) {
+                    visitor.visit_i64(i)
+                } else if let Ok(i) = i128::try_from(v) {
+                    visitor.visit_i128(i)
+                } else {
+                    visitor.visit_string(v.to_string())
+                }
+            }
+            Value::Fixed(_, _, _)
+            | Value::Ufixed(_, _, _)
+            | Value::Address(_)
+            | Value::Function(_, _) => visitor.visit_string(self.0.to_string()),
+            Value::Bool(b) => visitor.visit_bool(*b),
+            Value::String(s) => visitor.visit_borrowed_str(s),
+            Value::FixedBytes(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Value::Bytes(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Value::Hash(hash) => visitor.visit_borrowed_bytes(hash.as_bytes()),
+            Value::FixedArray(values, _) | Value::Array(values, _) => {
+                visitor.visit_seq(SeqDeserializer::new(values.iter().map(ValueDeserializer)))
+            }
+            Value::Tuple(values) => {
+                if !values.is_empty() && values.iter().all(|(name, _)| !name.is_empty()) {
+                    self.deserialize_map(visitor)
+                } else {
+                    self.deserialize_seq(visitor)
+                }
+            }
+        }
+    }
+
+    // Hash and big integer types read themselves from `0x` hex strings.
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Uint(v, _) | Value::Int(v, _) => visitor.visit_string(format!("{:#x}", v)),
+            Value::FixedBytes(bytes) => visitor.visit_string(encode_hex(bytes)),
+            Value::Bytes(bytes) => visitor.visit_string(encode_hex(bytes)),
+            Value::Hash(hash) => visitor.visit_string(format!("{:?}", hash)),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::FixedBytes(bytes) => {
+                visitor.visit_seq(SeqDeserializer::new(bytes.iter().copied()))
+            }
+            Value::Bytes(bytes) => visitor.visit_seq(SeqDeserializer::new(bytes.iter().copied())),
+            Value::Hash(hash) => {
+                visitor.visit_seq(SeqDeserializer::new(hash.as_bytes().iter().copied()))
+            }
+            Value::Tuple(values) => visitor.visit_seq(SeqDeserializer::new(
+                values.iter().map(|(_, v)| ValueDeserializer(v)),
+            )),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Tuple(values) => visitor.visit_map(MapDeserializer::new(
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (name, value))| (field_key(name, i), ValueDeserializer(value))),
+            )),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char
+        bytes byte_buf unit unit_struct enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::{H160, H256, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::{Param, Type};
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Order {
+        maker: H160,
+        amount: U256,
+        fee: u32,
+        delta: i64,
+        salt: H256,
+        path: Vec<H160>,
+        memo: String,
+        data: Vec<u8>,
+    }
+
+    fn param(name: &str, type_: Type) -> Param {
+        Param {
+            name: name.into(),
+            type_,
+            indexed: None,
+            internal_type: None,
+        }
+    }
+
+    #[test]
+    fn deserialize_named_params() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Args {
+            order: Order,
+            deadline: u64,
+            flags: (bool, bool),
+        }
+
+        let order = Value::Tuple(vec![
+            ("maker".into(), Value::Address(H160::repeat_byte(1))),
+            ("amount".into(), Value::Uint(U256::MAX, 256)),
+            ("fee".into(), Value::Uint(U256::from(3000), 24)),
+            ("delta".into(), Value::Int(U256::MAX - 9, 256)),
+            ("salt".into(), Value::FixedBytes(vec![7; 32])),
+            (
+                "path".into(),
+                Value::Array(
+                    vec![Value::Address(H160::repeat_byte(2))],
+                    Type::Address.into(),
+                ),
+            ),
+            ("memo".into(), Value::String("gm".to_string())),
+            ("data".into(), Value::Bytes(vec![1, 2, 3].into())),
+        ]);
+
+        let params = DecodedParams::from(vec![
+            (param("order", order.type_of()), order),
+            (
+                param("deadline", Type::Uint(64)),
+                Value::Uint(U256::from(1_700_000_000u64), 64),
+            ),
+            (
+                param("flags", Type::Tuple(vec![])),
+                Value::Tuple(vec![
+                    ("".into(), Value::Bool(true)),
+                    ("".into(), Value::Bool(false)),
+                ]),
+            ),
+        ]);
+
+        let args: Args = params.deserialize().unwrap();
+
+        assert_eq!(
+            args,
+            Args {
+                order: Order {
+                    maker: H160::repeat_byte(1),
+                    amount: U256::MAX,
+                    fee: 3000,
+                    delta: -10,
+                    salt: H256::repeat_byte(7),
+                    path: vec![H160::repeat_byte(2)],
+                    memo: "gm".to_string(),
+                    data: vec![1, 2, 3],
+                },
+                deadline: 1_700_000_000,
+                flags: (true, false),
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_positional_params() {
+        let params = DecodedParams::from(vec![
+            (
+                param("", Type::Address),
+                Value::Address(H160::repeat_byte(1)),
+            ),
+            (param("", Type::Uint(8)), Value::Uint(U256::from(300), 256)),
+        ]);
+
+        let (to, amount): (H160, u16) = params.deserialize().unwrap();
+
+        assert_eq!((to, amount), (H160::repeat_byte(1), 300));
+        assert!(params.deserialize::<(H160, u8)>().is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::{H160, H256};
+
+use crate::{keccak::keccak256, Abi, Artifact, Value};
+
+/// Builder for the creation calldata deploying a contract: its bytecode
+/// followed by its encoded constructor arguments.
+///
+/// ```
+/// use ethereum_abi::{Abi, DeploymentBuilder, StateMutability, Value};
+/// use ethereum_types::H160;
+///
+/// let abi = Abi::builder()
+///     .constructor(&[("owner", "address")], StateMutability::NonPayable)
+///     .build()
+///     .unwrap();
+///
+/// let calldata = DeploymentBuilder::new(&[0x60, 0x80])
+///     .abi(&abi)
+///     .constructor_args(&[Value::Address(H160::repeat_byte(1))])
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(calldata.len(), 2 + 32);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeploymentBuilder<'a> {
+    bytecode: Vec<u8>,
+    abi: Option<&'a Abi>,
+    args: Vec<Value>,
+}
+
+impl<'a> DeploymentBuilder<'a> {
+    /// Starts a deployment of the given creation bytecode.
+    pub fn new(bytecode: &[u8]) -> Self {
+        Self {
+            bytecode: bytecode.to_vec(),
+            abi: None,
+            args: vec![],
+        }
+    }
+
+    /// Sets the contract ABI, whose constructor the arguments are checked
+    /// against.
+    ///
+    /// Without an ABI the arguments are encoded as given.
+    pub fn abi(mut self, abi: &'a Abi) -> Self {
+        self.abi = Some(abi);
+        self
+    }
+
+    /// Sets the constructor arguments.
+    pub fn constructor_args(mut self, args: &[Value]) -> Self {
+        self.args = args.to_vec();
+        self
+    }
+
+    /// Builds the creation calldata.
+    ///
+    /// Fails if the arguments do not match the ABI's constructor, or if
+    /// arguments are given and the ABI has no constructor.
+    pub fn build(&self) -> Result<Vec<u8>> {
+        let args = match self.abi.map(|abi| abi.constructor.as_ref()) {
+            Some(Some(constructor)) => constructor.encode_input(&self.args)?,
+            Some(None) if !self.args.is_empty() => {
+                return Err(anyhow!(
+                    "ABI has no constructor, got {} arguments",
+                    self.args.len()
+                ))
+            }
+            _ => Value::encode(&self.args),
+        };
+
+        let mut calldata = self.bytecode.clone();
+        calldata.extend(args);
+
+        Ok(calldata)
+    }
+}
+
+impl Artifact {
+    /// Starts a deployment of the artifact's creation bytecode, checking
+    /// constructor arguments against its ABI.
+    ///
+    /// Fails if the artifact has no bytecode or it is not linked.
+    pub fn deployment(&self) -> Result<DeploymentBuilder<'_>> {
+        let bytecode = self
+            .decode_bytecode()?
+            .ok_or_else(|| anyhow!("artifact has no creation bytecode"))?;
+
+        Ok(DeploymentBuilder::new(&bytecode).abi(&self.abi))
+    }
+}
+
+/// Computes the address of a contract created with `CREATE` by the account
+/// at `deployer` when its nonce is `nonce`.
+pub fn compute_create_address(deployer: &H160, nonce: u64) -> H160 {
+    // RLP encoding of the list [deployer, nonce].
+    let nonce_bytes = nonce.to_be_bytes();
+    let nonce_bytes = &nonce_bytes[nonce.leading_zeros() as usize / 8..];
+
+    let mut encoded_nonce = vec![];
+    match nonce_bytes {
+        [byte] if *byte < 0x80 => encoded_nonce.push(*byte),
+        _ => {
+            encoded_nonce.push(0x80 + nonce_bytes.len() as u8);
+            encoded_nonce.extend_from_slice(nonce_bytes);
+        }
+    }
+
+    let mut rlp = vec![0xc0 + 21 + encoded_nonce.len() as u8, 0x80 + 20];
+    rlp.extend_from_slice(deployer.as_bytes());
+    rlp.extend(encoded_nonce);
+
+    address_of(&keccak256(&rlp))
+}
+
+/// Computes the address of a contract created with `CREATE2` by the contract
+/// at `deployer`, as defined by EIP-1014.
+pub fn compute_create2_address(deployer: &H160, salt: &H256, init_code: &[u8]) -> H160 {
+    let mut preimage = vec![0xff];
+    preimage.extend_from_slice(deployer.as_bytes());
+    preimage.extend_from_slice(salt.as_bytes());
+    preimage.extend_from_slice(&keccak256(init_code));
+
+    address_of(&keccak256(&preimage))
+}
+
+/// Assembles init code from a contract's creation bytecode and its ABI
+/// encoded constructor arguments.
+///
+/// Arguments are not checked against the constructor; see
+/// [`DeploymentBuilder`] for that.
+pub fn init_code(bytecode: &[u8], constructor_args: &[Value]) -> Vec<u8> {
+    let mut code = bytecode.to_vec();
+    code.extend(Value::encode(constructor_args));
+
+    code
+}
+
+fn address_of(hash: &[u8; 32]) -> H160 {
+    H160::from_slice(&hash[12..])
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::U256;
+    use pretty_assertions::assert_eq;
+
+    use crate::{address::to_checksum, StateMutability};
+
+    use super::*;
+
+    #[test]
+    fn create_addresses() {
+        let deployer: H160 = "6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0".parse().unwrap();
+
+        for (nonce, expected) in &[
+            (0, "cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+            (1, "343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+            (2, "f778b86fa74e846c4f0a1fbd1335fe81c00a0c91"),
+            (3, "fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"),
+        ] {
+            assert_eq!(
+                compute_create_address(&deployer, *nonce),
+                expected.parse().unwrap()
+            );
+        }
+
+        // Nonces past a single byte are length prefixed.
+        assert_ne!(
+            compute_create_address(&deployer, 0x80),
+            compute_create_address(&deployer, 0x8000)
+        );
+    }
+
+    #[test]
+    fn create2_addresses() {
+        // Test vectors from EIP-1014.
+        for (deployer, salt, init_code, expected) in &[
+            (
+                "0000000000000000000000000000000000000000",
+                0u64,
+                "00",
+                "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+            ),
+            (
+                "deadbeef00000000000000000000000000000000",
+                0,
+                "00",
+                "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3",
+            ),
+            (
+                "00000000000000000000000000000000deadbeef",
+                0xcafebabe,
+                "deadbeef",
+                "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7",
+            ),
+            (
+                "0000000000000000000000000000000000000000",
+                0,
+                "",
+                "0xE33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0",
+            ),
+        ] {
+            let salt = H256::from_low_u64_be(*salt);
+            let address = compute_create2_address(
+                &deployer.parse().unwrap(),
+                &salt,
+                &hex::decode(init_code).unwrap(),
+            );
+
+            assert_eq!(to_checksum(&address), *expected);
+        }
+    }
+
+    #[test]
+    fn assemble_init_code() {
+        assert_eq!(
+            init_code(&[0x60, 0x80], &[Value::Uint(U256::from(1), 256)]),
+            [&[0x60, 0x80][..], &[0; 31], &[1]].concat()
+        );
+    }
+
+    #[test]
+    fn build_deployment() {
+        let abi = Abi::builder()
+            .constructor(
+                &[("owner", "address"), ("supply", "uint256")],
+                StateMutability::NonPayable,
+            )
+            .build()
+            .unwrap();
+
+        let args = [
+            Value::Address(H160::repeat_byte(1)),
+            Value::Uint(U256::from(100), 256),
+        ];
+
+        let calldata = DeploymentBuilder::new(&[0x60, 0x80])
+            .abi(&abi)
+            .constructor_args(&args)
+            .build()
+            .unwrap();
+
+        assert_eq!(calldata, init_code(&[0x60, 0x80], &args));
+
+        let err = DeploymentBuilder::new(&[])
+            .abi(&abi)
+            .constructor_args(&args[..1])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "constructor(address,uint256) expects 2 arguments, got 1"
+        );
+
+        let no_constructor = Abi::builder().build().unwrap();
+        let deployment = DeploymentBuilder::new(&[0x60]).abi(&no_constructor);
+
+        assert_eq!(deployment.build().unwrap(), vec![0x60]);
+        assert!(deployment.constructor_args(&args).build().is_err());
+    }
+}
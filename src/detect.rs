@@ -0,0 +1,245 @@
+use crate::Abi;
+
+/// Well-known contract interface standards.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Standard {
+    /// ERC-20 fungible token.
+    Erc20,
+    /// ERC-165 interface detection.
+    Erc165,
+    /// ERC-173 contract ownership.
+    Erc173,
+    /// ERC-721 non-fungible token.
+    Erc721,
+    /// ERC-721 metadata extension.
+    Erc721Metadata,
+    /// ERC-1155 multi token.
+    Erc1155,
+    /// ERC-2612 permit extension for ERC-20 tokens.
+    Erc2612,
+    /// ERC-4626 tokenized vault.
+    Erc4626,
+}
+
+impl Standard {
+    /// All known standards.
+    pub const ALL: [Standard; 8] = [
+        Standard::Erc20,
+        Standard::Erc165,
+        Standard::Erc173,
+        Standard::Erc721,
+        Standard::Erc721Metadata,
+        Standard::Erc1155,
+        Standard::Erc2612,
+        Standard::Erc4626,
+    ];
+
+    /// Returns the standard's name, e.g. `ERC-20`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Standard::Erc20 => "ERC-20",
+            Standard::Erc165 => "ERC-165",
+            Standard::Erc173 => "ERC-173",
+            Standard::Erc721 => "ERC-721",
+            Standard::Erc721Metadata => "ERC-721 Metadata",
+            Standard::Erc1155 => "ERC-1155",
+            Standard::Erc2612 => "ERC-2612",
+            Standard::Erc4626 => "ERC-4626",
+        }
+    }
+
+    /// Returns the signatures of the functions the standard requires.
+    pub fn required_functions(&self) -> &'static [&'static str] {
+        match self {
+            Standard::Erc20 => &[
+                "totalSupply()",
+                "balanceOf(address)",
+                "transfer(address,uint256)",
+                "transferFrom(address,address,uint256)",
+                "approve(address,uint256)",
+                "allowance(address,address)",
+            ],
+            Standard::Erc165 => &["supportsInterface(bytes4)"],
+            Standard::Erc173 => &["owner()", "transferOwnership(address)"],
+            Standard::Erc721 => &[
+                "balanceOf(address)",
+                "ownerOf(uint256)",
+                "safeTransferFrom(address,address,uint256,bytes)",
+                "safeTransferFrom(address,address,uint256)",
+                "transferFrom(address,address,uint256)",
+                "approve(address,uint256)",
+                "setApprovalForAll(address,bool)",
+                "getApproved(uint256)",
+                "isApprovedForAll(address,address)",
+            ],
+            Standard::Erc721Metadata => &["name()", "symbol()", "tokenURI(uint256)"],
+            Standard::Erc1155 => &[
+                "safeTransferFrom(address,address,uint256,uint256,bytes)",
+                "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+                "balanceOf(address,uint256)",
+                "balanceOfBatch(address[],uint256[])",
+                "setApprovalForAll(address,bool)",
+                "isApprovedForAll(address,address)",
+            ],
+            Standard::Erc2612 => &[
+                "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+                "nonces(address)",
+                "DOMAIN_SEPARATOR()",
+            ],
+            Standard::Erc4626 => &[
+                "asset()",
+                "totalAssets()",
+                "convertToShares(uint256)",
+                "convertToAssets(uint256)",
+                "maxDeposit(address)",
+                "previewDeposit(uint256)",
+                "deposit(uint256,address)",
+                "maxMint(address)",
+                "previewMint(uint256)",
+                "mint(uint256,address)",
+                "maxWithdraw(address)",
+                "previewWithdraw(uint256)",
+                "withdraw(uint256,address,address)",
+                "maxRedeem(address)",
+                "previewRedeem(uint256)",
+                "redeem(uint256,address,address)",
+            ],
+        }
+    }
+
+    /// Returns the signatures of the events the standard requires.
+    pub fn required_events(&self) -> &'static [&'static str] {
+        match self {
+            Standard::Erc20 => &[
+                "Transfer(address,address,uint256)",
+                "Approval(address,address,uint256)",
+            ],
+            Standard::Erc165 | Standard::Erc721Metadata | Standard::Erc2612 => &[],
+            Standard::Erc173 => &["OwnershipTransferred(address,address)"],
+            Standard::Erc721 => &[
+                "Transfer(address,address,uint256)",
+                "Approval(address,address,uint256)",
+                "ApprovalForAll(address,address,bool)",
+            ],
+            Standard::Erc1155 => &[
+                "TransferSingle(address,address,address,uint256,uint256)",
+                "TransferBatch(address,address,address,uint256[],uint256[])",
+                "ApprovalForAll(address,address,bool)",
+                "URI(string,uint256)",
+            ],
+            Standard::Erc4626 => &[
+                "Deposit(address,address,uint256,uint256)",
+                "Withdraw(address,address,address,uint256,uint256)",
+            ],
+        }
+    }
+
+    // Standards this one extends.
+    fn base(&self) -> &'static [Standard] {
+        match self {
+            Standard::Erc2612 | Standard::Erc4626 => &[Standard::Erc20],
+            Standard::Erc721Metadata => &[Standard::Erc721],
+            _ => &[],
+        }
+    }
+}
+
+impl std::fmt::Display for Standard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Abi {
+    /// Returns whether the ABI declares every function and event the
+    /// standard (and the standards it extends) requires.
+    ///
+    /// Only signatures are compared: ERC-20 and ERC-721 `Transfer` events
+    /// share the same topic and are not told apart by their indexed inputs.
+    pub fn implements(&self, standard: Standard) -> bool {
+        let functions: Vec<_> = self.functions.iter().map(|f| f.signature()).collect();
+        let events: Vec<_> = self.events.iter().map(|e| e.signature()).collect();
+
+        let has_all = |required: &[&str], declared: &[String]| {
+            required.iter().all(|sig| declared.iter().any(|d| d == sig))
+        };
+
+        has_all(standard.required_functions(), &functions)
+            && has_all(standard.required_events(), &events)
+            && standard.base().iter().all(|base| self.implements(*base))
+    }
+
+    /// Returns the well-known standards the ABI satisfies.
+    pub fn detect_standards(&self) -> Vec<Standard> {
+        Standard::ALL
+            .iter()
+            .copied()
+            .filter(|standard| self.implements(*standard))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{Event, Function};
+
+    use super::*;
+
+    fn abi_from_signatures(functions: &[&str], events: &[&str]) -> Abi {
+        let mut abi = Abi::builder().build().unwrap();
+
+        abi.functions = functions
+            .iter()
+            .map(|sig| Function::parse(sig).unwrap())
+            .collect();
+        abi.events = events
+            .iter()
+            .map(|sig| Event::parse(sig).unwrap())
+            .collect();
+
+        abi
+    }
+
+    #[test]
+    fn detect_erc20_permit() {
+        let functions: Vec<_> = Standard::Erc20
+            .required_functions()
+            .iter()
+            .chain(Standard::Erc2612.required_functions())
+            .chain(&["name()", "symbol()", "decimals()"])
+            .copied()
+            .collect();
+
+        let abi = abi_from_signatures(&functions, Standard::Erc20.required_events());
+
+        assert_eq!(
+            abi.detect_standards(),
+            vec![Standard::Erc20, Standard::Erc2612]
+        );
+    }
+
+    #[test]
+    fn detect_erc721() {
+        let functions: Vec<_> = Standard::Erc721
+            .required_functions()
+            .iter()
+            .chain(Standard::Erc165.required_functions())
+            .chain(Standard::Erc721Metadata.required_functions())
+            .copied()
+            .collect();
+
+        let abi = abi_from_signatures(&functions, Standard::Erc721.required_events());
+
+        assert_eq!(
+            abi.detect_standards(),
+            vec![Standard::Erc165, Standard::Erc721, Standard::Erc721Metadata]
+        );
+
+        // A missing event disqualifies the ABI.
+        let abi = abi_from_signatures(&functions, &[]);
+
+        assert_eq!(abi.detect_standards(), vec![Standard::Erc165]);
+    }
+}
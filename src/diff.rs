@@ -0,0 +1,443 @@
+use crate::{Abi, Constructor, Error, Event, Function, StateMutability};
+
+/// Change to a single ABI entry.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Change<T> {
+    /// Entry only present in the new ABI.
+    Added(T),
+    /// Entry only present in the old ABI.
+    Removed(T),
+    /// Entry present in both ABIs with a different definition.
+    Changed { old: T, new: T },
+}
+
+/// Differences between two versions of an ABI, see [`Abi::diff`].
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct AbiDiff {
+    /// Constructor change.
+    pub constructor: Option<Change<Constructor>>,
+    /// Function changes.
+    pub functions: Vec<Change<Function>>,
+    /// Event changes.
+    pub events: Vec<Change<Event>>,
+    /// Error changes.
+    pub errors: Vec<Change<Error>>,
+    /// New value of `receive`, if it changed.
+    pub receive: Option<Option<StateMutability>>,
+    /// New value of `fallback`, if it changed.
+    pub fallback: Option<Option<StateMutability>>,
+}
+
+/// Change that breaks existing callers or log consumers, see
+/// [`AbiDiff::breaking_changes`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BreakingChange {
+    /// Function selector no longer exists (removed or inputs changed).
+    FunctionRemoved(String),
+    /// Function return types changed.
+    OutputsChanged(String),
+    /// Function no longer accepts Ether, or is no longer read-only.
+    MutabilityChanged {
+        signature: String,
+        old: StateMutability,
+        new: StateMutability,
+    },
+    /// Event topic no longer exists (removed or inputs changed).
+    EventRemoved(String),
+    /// Event inputs moved between topics and data, or anonymity changed.
+    EventLayoutChanged(String),
+    /// Custom error no longer exists.
+    ErrorRemoved(String),
+    /// Contract no longer has a receive function.
+    ReceiveRemoved,
+    /// Contract no longer has a fallback function.
+    FallbackRemoved,
+}
+
+impl std::fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BreakingChange::FunctionRemoved(sig) => write!(f, "function {} removed", sig),
+            BreakingChange::OutputsChanged(sig) => write!(f, "function {} outputs changed", sig),
+            BreakingChange::MutabilityChanged {
+                signature,
+                old,
+                new,
+            } => write!(
+                f,
+                "function {} state mutability changed from {:?} to {:?}",
+                signature, old, new
+            ),
+            BreakingChange::EventRemoved(sig) => write!(f, "event {} removed", sig),
+            BreakingChange::EventLayoutChanged(sig) => {
+                write!(f, "event {} indexed inputs changed", sig)
+            }
+            BreakingChange::ErrorRemoved(sig) => write!(f, "error {} removed", sig),
+            BreakingChange::ReceiveRemoved => write!(f, "receive function removed"),
+            BreakingChange::FallbackRemoved => write!(f, "fallback function removed"),
+        }
+    }
+}
+
+impl AbiDiff {
+    /// Returns whether both ABIs are identical.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Returns the changes that break existing integrations.
+    ///
+    /// Everything else (new entries, renamed parameters, relaxed state
+    /// mutability, constructor changes) is additive for callers of a deployed
+    /// contract.
+    pub fn breaking_changes(&self) -> Vec<BreakingChange> {
+        let mut breaking = vec![];
+
+        for change in &self.functions {
+            match change {
+                Change::Added(_) => {}
+                Change::Removed(f) => breaking.push(BreakingChange::FunctionRemoved(f.signature())),
+                Change::Changed { old, new } => {
+                    if old.signature() != new.signature() {
+                        breaking.push(BreakingChange::FunctionRemoved(old.signature()));
+                        continue;
+                    }
+
+                    // Struct and field names don't reach the wire, so
+                    // compare canonical types.
+                    let old_outputs = old.outputs.iter().map(|p| p.type_.to_string());
+                    if !old_outputs.eq(new.outputs.iter().map(|p| p.type_.to_string())) {
+                        breaking.push(BreakingChange::OutputsChanged(old.signature()));
+                    }
+
+                    if is_restricted(old.state_mutability, new.state_mutability) {
+                        breaking.push(BreakingChange::MutabilityChanged {
+                            signature: old.signature(),
+                            old: old.state_mutability,
+                            new: new.state_mutability,
+                        });
+                    }
+                }
+            }
+        }
+
+        for change in &self.events {
+            match change {
+                Change::Added(_) => {}
+                Change::Removed(e) => breaking.push(BreakingChange::EventRemoved(e.signature())),
+                Change::Changed { old, new } => {
+                    if old.signature() != new.signature() {
+                        breaking.push(BreakingChange::EventRemoved(old.signature()));
+                    } else if old.anonymous != new.anonymous
+                        || !old
+                            .inputs
+                            .iter()
+                            .map(|p| p.indexed.unwrap_or(false))
+                            .eq(new.inputs.iter().map(|p| p.indexed.unwrap_or(false)))
+                    {
+                        breaking.push(BreakingChange::EventLayoutChanged(old.signature()));
+                    }
+                }
+            }
+        }
+
+        for change in &self.errors {
+            match change {
+                Change::Removed(e) => breaking.push(BreakingChange::ErrorRemoved(e.signature())),
+                Change::Changed { old, new } if old.signature() != new.signature() => {
+                    breaking.push(BreakingChange::ErrorRemoved(old.signature()))
+                }
+                _ => {}
+            }
+        }
+
+        if self.receive == Some(None) {
+            breaking.push(BreakingChange::ReceiveRemoved);
+        }
+
+        if self.fallback == Some(None) {
+            breaking.push(BreakingChange::FallbackRemoved);
+        }
+
+        breaking
+    }
+
+    /// Returns whether any change breaks existing integrations.
+    pub fn is_breaking(&self) -> bool {
+        !self.breaking_changes().is_empty()
+    }
+}
+
+// Whether callers relying on the old state mutability may fail: payable
+// functions must keep accepting Ether and read-only functions must stay
+// callable through STATICCALL.
+fn is_restricted(old: StateMutability, new: StateMutability) -> bool {
+    use StateMutability::*;
+
+    let read_only = |m| matches!(m, Pure | View);
+
+    (old == Payable && new != Payable) || (read_only(old) && !read_only(new))
+}
+
+impl Abi {
+    /// Compares two versions of an ABI.
+    ///
+    /// Entries are matched by signature first. Remaining entries sharing a
+    /// name that is not overloaded on either side are matched by name, so a
+    /// function whose parameters changed is reported as changed rather than
+    /// removed and added. Parameter names and `internalType`s are compared
+    /// too.
+    pub fn diff(old: &Abi, new: &Abi) -> AbiDiff {
+        let constructor = match (&old.constructor, &new.constructor) {
+            (None, None) => None,
+            (None, Some(c)) => Some(Change::Added(c.clone())),
+            (Some(c), None) => Some(Change::Removed(c.clone())),
+            (Some(o), Some(n)) if o != n => Some(Change::Changed {
+                old: o.clone(),
+                new: n.clone(),
+            }),
+            _ => None,
+        };
+
+        AbiDiff {
+            constructor,
+            functions: diff_entries(
+                &old.functions,
+                &new.functions,
+                |f| &f.name,
+                |f| f.signature(),
+            ),
+            events: diff_entries(&old.events, &new.events, |e| &e.name, |e| e.signature()),
+            errors: diff_entries(&old.errors, &new.errors, |e| &e.name, |e| e.signature()),
+            receive: Some(new.receive).filter(|r| *r != old.receive),
+            fallback: Some(new.fallback).filter(|f| *f != old.fallback),
+        }
+    }
+}
+
+fn diff_entries<T, N, S>(old: &[T], new: &[T], name: N, signature: S) -> Vec<Change<T>>
+where
+    T: Clone + PartialEq,
+    N: Fn(&T) -> &String,
+    S: Fn(&T) -> String,
+{
+    let mut changes = vec![];
+
+    let mut old_left: Vec<&T> = vec![];
+    let mut new_left: Vec<&T> = new
+        .iter()
+        .filter(|n| !old.iter().any(|o| signature(o) == signature(n)))
+        .collect();
+
+    for o in old {
+        match new.iter().find(|n| signature(n) == signature(o)) {
+            Some(n) if n != o => changes.push(Change::Changed {
+                old: o.clone(),
+                new: n.clone(),
+            }),
+            Some(_) => {}
+            None => old_left.push(o),
+        }
+    }
+
+    let overloaded =
+        |entries: &[T], n: &String| entries.iter().filter(|e| name(e) == n).count() > 1;
+
+    for o in &old_left {
+        let candidate = new_left.iter().position(|n| {
+            name(n) == name(o) && !overloaded(old, name(o)) && !overloaded(new, name(n))
+        });
+
+        match candidate {
+            Some(i) => {
+                let n = new_left.remove(i);
+
+                changes.push(Change::Changed {
+                    old: (*o).clone(),
+                    new: n.clone(),
+                });
+            }
+            None => changes.push(Change::Removed((*o).clone())),
+        }
+    }
+
+    changes.extend(new_left.into_iter().cloned().map(Change::Added));
+
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::StateMutability;
+
+    use super::*;
+
+    fn v1() -> Abi {
+        Abi::builder()
+            .function("name", &[], &[("", "string")], StateMutability::View)
+            .function(
+                "mint",
+                &[("to", "address")],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .function(
+                "burn",
+                &[("id", "uint256")],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .function("f", &[("", "uint8")], &[], StateMutability::Pure)
+            .function("f", &[("", "uint16")], &[], StateMutability::Pure)
+            .event("Minted", &[("to", "address", true)])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn identical_abis() {
+        assert!(Abi::diff(&v1(), &v1()).is_empty());
+    }
+
+    #[test]
+    fn diff_abis() {
+        let v2 = Abi::builder()
+            .function("name", &[], &[("", "string")], StateMutability::Pure)
+            .function(
+                "mint",
+                &[("to", "address"), ("amount", "uint256")],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .function("f", &[("", "uint8")], &[], StateMutability::Pure)
+            .function("f", &[("", "uint32")], &[], StateMutability::Pure)
+            .event("Minted", &[("to", "address", false)])
+            .error("Paused", &[])
+            .receive()
+            .build()
+            .unwrap();
+
+        let old = v1();
+        let diff = Abi::diff(&old, &v2);
+
+        let signatures = |changes: &[Change<Function>]| {
+            changes
+                .iter()
+                .map(|c| match c {
+                    Change::Added(f) => format!("+{}", f.signature()),
+                    Change::Removed(f) => format!("-{}", f.signature()),
+                    Change::Changed { old, new } => {
+                        format!("~{}->{}", old.signature(), new.signature())
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            signatures(&diff.functions),
+            vec![
+                "~name()->name()",
+                "~mint(address)->mint(address,uint256)",
+                "-burn(uint256)",
+                "-f(uint16)",
+                "+f(uint32)",
+            ]
+        );
+        assert_eq!(
+            diff.events,
+            vec![Change::Changed {
+                old: old.events[0].clone(),
+                new: v2.events[0].clone()
+            }]
+        );
+        assert_eq!(diff.errors, vec![Change::Added(v2.errors[0].clone())]);
+        assert_eq!(diff.receive, Some(Some(StateMutability::Payable)));
+        assert_eq!(diff.fallback, None);
+        assert_eq!(diff.constructor, None);
+    }
+
+    #[test]
+    fn breaking_changes() {
+        let v2 = Abi::builder()
+            .function("name", &[], &[("", "bytes32")], StateMutability::NonPayable)
+            .function(
+                "mint",
+                &[("recipient", "address")],
+                &[],
+                StateMutability::Payable,
+            )
+            .function("f", &[("", "uint8")], &[], StateMutability::Pure)
+            .function("f", &[("", "uint16")], &[], StateMutability::Pure)
+            .function(
+                "burn",
+                &[("id", "uint256")],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .event("Minted", &[("to", "address", false)])
+            .build()
+            .unwrap();
+
+        let diff = Abi::diff(&v1(), &v2);
+
+        assert_eq!(
+            diff.breaking_changes(),
+            vec![
+                BreakingChange::OutputsChanged("name()".to_string()),
+                BreakingChange::MutabilityChanged {
+                    signature: "name()".to_string(),
+                    old: StateMutability::View,
+                    new: StateMutability::NonPayable,
+                },
+                BreakingChange::EventLayoutChanged("Minted(address)".to_string()),
+            ]
+        );
+
+        // New entries, renamed parameters and accepting Ether are additive.
+        let mut v3 = v1();
+        v3.functions[1].inputs_mut()[0].name = "recipient".into();
+        v3.functions[1].state_mutability = StateMutability::Payable;
+        v3.functions.push(v2.functions[4].clone());
+        v3.functions[5].set_name("burnFrom");
+        v3.fallback = Some(StateMutability::NonPayable);
+
+        let diff = Abi::diff(&v1(), &v3);
+
+        assert!(!diff.is_empty());
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn renamed_output_structs() {
+        let abi = |output: &str| -> Abi {
+            serde_json::from_str(&format!(
+                r#"[{{"type": "function", "name": "order", "inputs": [], "stateMutability": "view", "outputs": [{}]}}]"#,
+                output
+            ))
+            .unwrap()
+        };
+
+        let old = abi(
+            r#"{"name": "", "type": "tuple", "internalType": "struct Order", "components": [{"name": "maker", "type": "address"}, {"name": "amount", "type": "uint256"}]}"#,
+        );
+        let renamed = abi(
+            r#"{"name": "", "type": "tuple", "internalType": "struct Exchange.Trade", "components": [{"name": "owner", "type": "address"}, {"name": "size", "type": "uint256"}]}"#,
+        );
+        let unnamed = abi(
+            r#"{"name": "", "type": "tuple", "components": [{"name": "", "type": "address"}, {"name": "", "type": "uint256"}]}"#,
+        );
+        let changed = abi(
+            r#"{"name": "", "type": "tuple", "internalType": "struct Order", "components": [{"name": "maker", "type": "address"}, {"name": "amount", "type": "uint128"}]}"#,
+        );
+
+        assert!(!Abi::diff(&old, &renamed).is_empty());
+        assert!(!Abi::diff(&old, &renamed).is_breaking());
+        assert!(!Abi::diff(&old, &unnamed).is_breaking());
+        assert_eq!(
+            Abi::diff(&old, &changed).breaking_changes(),
+            vec![BreakingChange::OutputsChanged("order()".to_string())]
+        );
+    }
+}
@@ -0,0 +1,524 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::{
+    params::{fmt_params, UserTyped},
+    values::{field_key, write_list, WriteNested},
+    DecodedParam, DecodedParams, Param, Type, Value,
+};
+
+/// Variant names of Solidity enums, used to label decoded `uint8` values.
+///
+/// ABIs only record enums through the `internalType` of their parameters
+/// (`enum Order.Status`), so variant names have to be registered by hand or
+/// read from the compiler's AST.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct EnumRegistry {
+    enums: HashMap<String, Vec<String>>,
+    // Enum names of struct fields, by struct then field name.
+    fields: HashMap<String, HashMap<String, String>>,
+}
+
+impl EnumRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the enums defined in the sources of `solc --standard-json`
+    /// output. Requires the `ast` output to be selected.
+    pub fn from_solc_output(json: &str) -> Result<Self> {
+        let output: serde_json::Value = serde_json::from_str(json)?;
+
+        let mut registry = Self::new();
+
+        if let Some(sources) = output.get("sources").and_then(|s| s.as_object()) {
+            for source in sources.values() {
+                if let Some(ast) = source.get("ast") {
+                    registry.collect_ast(ast);
+                }
+            }
+        }
+
+        Ok(registry)
+    }
+
+    /// Registers the variants of an enum by its canonical name, e.g.
+    /// `Order.Status` for an enum declared in contract `Order`, or `Status`
+    /// for a file level enum.
+    pub fn insert<I, S>(&mut self, name: &str, variants: I) -> Option<Vec<String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.enums.insert(
+            name.to_string(),
+            variants.into_iter().map(Into::into).collect(),
+        )
+    }
+
+    /// Registers the enum a struct field is declared as, since the ABI types
+    /// of decoded values don't record the `internalType` of struct fields.
+    ///
+    /// Struct and enum names are canonical, e.g. `Order.Leg` and
+    /// `Order.Status`.
+    pub fn insert_field(&mut self, struct_name: &str, field: &str, enum_name: &str) {
+        self.fields
+            .entry(struct_name.to_string())
+            .or_default()
+            .insert(field.to_string(), enum_name.to_string());
+    }
+
+    /// Returns the variants of an enum.
+    ///
+    /// Falls back to matching the unqualified name if the canonical name is
+    /// unknown, unless several registered enums share that name.
+    pub fn variants(&self, name: &str) -> Option<&[String]> {
+        lookup(&self.enums, name).map(Vec::as_slice)
+    }
+
+    /// Returns the canonical name of the enum a struct field is declared as.
+    ///
+    /// The struct name falls back to matching unqualified names like
+    /// [`EnumRegistry::variants`] does.
+    pub fn field_enum(&self, struct_name: &str, field: &str) -> Option<&str> {
+        lookup(&self.fields, struct_name)?
+            .get(field)
+            .map(String::as_str)
+    }
+
+    /// Returns the variant name of a decoded enum parameter.
+    pub fn variant<'a>(&'a self, param: &Param, value: &Value) -> Option<&'a str> {
+        let index = match value {
+            Value::Uint(index, _) if *index <= usize::MAX.into() => index.as_usize(),
+            _ => return None,
+        };
+
+        self.variants(param.enum_name()?)?
+            .get(index)
+            .map(String::as_str)
+    }
+
+    /// Renders a decoded enum parameter as `Status::Active`.
+    ///
+    /// Returns `None` for parameters that are not of a registered enum type
+    /// or whose value is out of range.
+    pub fn format(&self, decoded: &DecodedParam) -> Option<String> {
+        let variant = self.variant(&decoded.param, &decoded.value)?;
+        let name = decoded.param.enum_name()?;
+
+        Some(format!("{}::{}", short_name(name), variant))
+    }
+
+    fn collect_ast(&mut self, node: &serde_json::Value) {
+        match node {
+            serde_json::Value::Object(fields) => {
+                if fields.get("nodeType").and_then(|t| t.as_str()) == Some("EnumDefinition") {
+                    let name = fields
+                        .get("canonicalName")
+                        .or_else(|| fields.get("name"))
+                        .and_then(|n| n.as_str());
+
+                    let members = fields.get("members").and_then(|m| m.as_array());
+
+                    if let (Some(name), Some(members)) = (name, members) {
+                        self.insert(
+                            name,
+                            members
+                                .iter()
+                                .filter_map(|m| m.get("name").and_then(|n| n.as_str())),
+                        );
+                    }
+                }
+
+                if fields.get("nodeType").and_then(|t| t.as_str()) == Some("StructDefinition") {
+                    let name = fields
+                        .get("canonicalName")
+                        .or_else(|| fields.get("name"))
+                        .and_then(|n| n.as_str());
+
+                    let members = fields.get("members").and_then(|m| m.as_array());
+
+                    if let (Some(name), Some(members)) = (name, members) {
+                        for member in members {
+                            let field = member.get("name").and_then(|n| n.as_str());
+                            let enum_name = member
+                                .pointer("/typeDescriptions/typeString")
+                                .and_then(|t| t.as_str())
+                                .and_then(|t| t.strip_prefix("enum "));
+
+                            if let (Some(field), Some(enum_name)) = (field, enum_name) {
+                                let enum_name = enum_name.split('[').next().unwrap_or(enum_name);
+                                self.insert_field(name, field, enum_name);
+                            }
+                        }
+                    }
+                }
+
+                fields.values().for_each(|v| self.collect_ast(v));
+            }
+
+            serde_json::Value::Array(nodes) => nodes.iter().for_each(|n| self.collect_ast(n)),
+
+            _ => {}
+        }
+    }
+}
+
+impl DecodedParams {
+    /// Returns a view of the params that displays and serializes enum values
+    /// by variant name, e.g. `Status::Active` rather than `1`, including
+    /// within arrays and struct fields.
+    ///
+    /// Values of enums missing from the registry, or out of their range, are
+    /// left as numbers.
+    pub fn with_enums<'a>(&'a self, enums: &'a EnumRegistry) -> WithEnums<'a> {
+        WithEnums {
+            params: self,
+            enums,
+        }
+    }
+}
+
+/// Decoded params labelled with enum variant names, see
+/// [`DecodedParams::with_enums`].
+#[derive(Debug, Clone, Copy)]
+pub struct WithEnums<'a> {
+    params: &'a DecodedParams,
+    enums: &'a EnumRegistry,
+}
+
+impl<'a> WithEnums<'a> {
+    fn entries(&self) -> impl Iterator<Item = (&'a str, Labeled<'a>)> {
+        let enums = self.enums;
+
+        self.params.iter().map(move |decoded| {
+            (
+                decoded.param.name.as_str(),
+                Labeled {
+                    enums,
+                    ty: &decoded.param.type_,
+                    enum_name: decoded.param.enum_name(),
+                    user_type: decoded.param.user_type(),
+                    value: &decoded.value,
+                },
+            )
+        })
+    }
+}
+
+impl std::fmt::Display for WithEnums<'_> {
+    /// Formats the params like [`DecodedParams`] does.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_params(f, self.entries())
+    }
+}
+
+impl Serialize for WithEnums<'_> {
+    /// Serializes the params like [`DecodedParams`] does, with enum values as
+    /// strings.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(
+            self.entries()
+                .enumerate()
+                .map(|(i, (name, value))| (field_key(name, i), value)),
+        )
+    }
+}
+
+// A value along with its type, and the enum or user-defined value type it is
+// declared as if known.
+#[derive(Clone, Copy)]
+struct Labeled<'a> {
+    enums: &'a EnumRegistry,
+    ty: &'a Type,
+    enum_name: Option<&'a str>,
+    user_type: Option<&'a str>,
+    value: &'a Value,
+}
+
+impl<'a> Labeled<'a> {
+    fn variant(&self) -> Option<String> {
+        let name = self.enum_name?;
+        let index = match self.value {
+            Value::Uint(index, _) if *index <= usize::MAX.into() => index.as_usize(),
+            _ => return None,
+        };
+
+        let variant = self.enums.variants(name)?.get(index)?;
+
+        Some(format!("{}::{}", short_name(name), variant))
+    }
+
+    fn elements(&self) -> Option<impl ExactSizeIterator<Item = Labeled<'a>>> {
+        let (values, ty) = match (self.value, self.ty) {
+            (Value::Array(values, _), Type::Array(ty))
+            | (Value::FixedArray(values, _), Type::FixedArray(ty, _)) => (values, ty),
+            _ => return None,
+        };
+        let this = *self;
+
+        Some(
+            values
+                .iter()
+                .map(move |value| Labeled { ty, value, ..this }),
+        )
+    }
+
+    fn fields(&self) -> Option<impl ExactSizeIterator<Item = (&'a str, Labeled<'a>)>> {
+        let (values, struct_name, tys) = match (self.value, self.ty) {
+            (Value::Tuple(values), Type::Tuple(tys)) => (values, None, tys),
+            (Value::Tuple(values), Type::Struct(name, tys)) => (values, Some(name), tys),
+            _ => return None,
+        };
+        let enums = self.enums;
+
+        Some(values.iter().zip(tys).map(move |((name, value), (_, ty))| {
+            (
+                name.as_str(),
+                Labeled {
+                    enums,
+                    ty,
+                    enum_name: struct_name
+                        .and_then(|struct_name| enums.field_enum(struct_name, name)),
+                    user_type: None,
+                    value,
+                },
+            )
+        }))
+    }
+}
+
+impl WriteNested for Labeled<'_> {
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        if let Some(variant) = self.variant() {
+            return write!(f, "{}", variant);
+        }
+
+        if let Some(elements) = self.elements() {
+            return write_list(f, indent, ('[', ']'), elements.map(|v| ("", v)));
+        }
+
+        match self.fields() {
+            Some(fields) => write_list(f, indent, ('(', ')'), fields),
+            None => UserTyped {
+                user_type: self.user_type,
+                value: self.value,
+            }
+            .write(f, indent),
+        }
+    }
+}
+
+impl Serialize for Labeled<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if let Some(variant) = self.variant() {
+            return serializer.serialize_str(&variant);
+        }
+
+        if let Some(elements) = self.elements() {
+            return serializer.collect_seq(elements);
+        }
+
+        match self.fields() {
+            Some(fields) => serializer.collect_map(
+                fields
+                    .enumerate()
+                    .map(|(i, (name, value))| (field_key(name, i), value)),
+            ),
+            None => self.value.serialize(serializer),
+        }
+    }
+}
+
+fn short_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+// Looks up a canonical name, or an unqualified one if it is unambiguous.
+fn lookup<'a, T>(map: &'a HashMap<String, T>, name: &str) -> Option<&'a T> {
+    if let Some(found) = map.get(name) {
+        return Some(found);
+    }
+
+    let short = short_name(name);
+    let mut matches = map
+        .iter()
+        .filter(|(known, _)| short_name(known) == short)
+        .map(|(_, found)| found);
+
+    match (matches.next(), matches.next()) {
+        (Some(found), None) => Some(found),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::U256;
+    use pretty_assertions::assert_eq;
+
+    use crate::Type;
+
+    use super::*;
+
+    fn status_param() -> Param {
+        Param {
+            name: "status".into(),
+            type_: Type::Uint(8),
+            indexed: None,
+            internal_type: Some("enum Order.Status".into()),
+        }
+    }
+
+    #[test]
+    fn format_registered_enum() {
+        let mut registry = EnumRegistry::new();
+        registry.insert("Order.Status", ["Pending", "Active", "Closed"]);
+
+        let decoded = DecodedParam::from((status_param(), Value::Uint(U256::from(1), 8)));
+
+        assert_eq!(
+            registry.format(&decoded),
+            Some("Status::Active".to_string())
+        );
+
+        let decoded = DecodedParam::from((status_param(), Value::Uint(U256::from(7), 8)));
+
+        assert_eq!(registry.format(&decoded), None);
+    }
+
+    #[test]
+    fn unqualified_fallback() {
+        let mut registry = EnumRegistry::new();
+        registry.insert("Status", ["Pending", "Active"]);
+
+        assert_eq!(
+            registry.variant(&status_param(), &Value::Uint(U256::zero(), 8)),
+            Some("Pending")
+        );
+
+        registry.insert("Market.Status", ["Open", "Halted"]);
+
+        assert_eq!(registry.variants("Order.Status"), None);
+        assert_eq!(
+            registry.variants("Market.Status"),
+            Some(&["Open".to_string(), "Halted".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn display_and_serialize_with_enums() {
+        let mut registry = EnumRegistry::new();
+        registry.insert("Order.Status", ["Pending", "Active", "Closed"]);
+        registry.insert_field("Order.Leg", "status", "Order.Status");
+
+        let leg = Type::Struct(
+            "Leg".into(),
+            vec![
+                ("size".into(), Type::Uint(8)),
+                ("status".into(), Type::Uint(8)),
+            ],
+        );
+        let params = DecodedParams::from(vec![
+            (
+                Param {
+                    internal_type: Some("enum Order.Status[]".into()),
+                    type_: Type::Array(Box::new(Type::Uint(8))),
+                    ..status_param()
+                },
+                Value::Array(
+                    vec![Value::Uint(U256::from(2), 8), Value::Uint(U256::from(9), 8)],
+                    Type::Uint(8).into(),
+                ),
+            ),
+            (
+                Param {
+                    name: "leg".into(),
+                    type_: leg,
+                    indexed: None,
+                    internal_type: Some("struct Order.Leg".into()),
+                },
+                Value::Tuple(vec![
+                    ("size".into(), Value::Uint(U256::from(1), 8)),
+                    ("status".into(), Value::Uint(U256::from(1), 8)),
+                ]),
+            ),
+        ]);
+
+        assert_eq!(
+            params.with_enums(&registry).to_string(),
+            "status: [Status::Closed, 9], leg: (1, Status::Active)"
+        );
+        assert_eq!(
+            serde_json::to_value(params.with_enums(&registry)).unwrap(),
+            serde_json::json!({
+                "status": ["Status::Closed", "9"],
+                "leg": {"size": "1", "status": "Status::Active"},
+            })
+        );
+
+        assert_eq!(
+            params.with_enums(&EnumRegistry::new()).to_string(),
+            params.to_string()
+        );
+    }
+
+    #[test]
+    fn enums_from_solc_ast() {
+        let output = serde_json::json!({
+            "sources": {
+                "contracts/Order.sol": {
+                    "id": 0,
+                    "ast": {
+                        "nodeType": "SourceUnit",
+                        "nodes": [{
+                            "nodeType": "ContractDefinition",
+                            "name": "Order",
+                            "nodes": [{
+                                "nodeType": "EnumDefinition",
+                                "name": "Status",
+                                "canonicalName": "Order.Status",
+                                "members": [
+                                    {"nodeType": "EnumValue", "name": "Pending"},
+                                    {"nodeType": "EnumValue", "name": "Active"}
+                                ]
+                            }, {
+                                "nodeType": "StructDefinition",
+                                "name": "Leg",
+                                "canonicalName": "Order.Leg",
+                                "members": [{
+                                    "nodeType": "VariableDeclaration",
+                                    "name": "statuses",
+                                    "typeDescriptions": {
+                                        "typeString": "enum Order.Status[]"
+                                    }
+                                }, {
+                                    "nodeType": "VariableDeclaration",
+                                    "name": "size",
+                                    "typeDescriptions": {"typeString": "uint256"}
+                                }]
+                            }]
+                        }]
+                    }
+                }
+            }
+        });
+
+        let registry = EnumRegistry::from_solc_output(&output.to_string()).unwrap();
+
+        assert_eq!(
+            registry.variants("Order.Status"),
+            Some(&["Pending".to_string(), "Active".to_string()][..])
+        );
+        assert_eq!(registry.field_enum("Leg", "statuses"), Some("Order.Status"));
+        assert_eq!(registry.field_enum("Leg", "size"), None);
+    }
+}
@@ -0,0 +1,438 @@
+//! Conversions from and to the types of the `ethabi` crate.
+//!
+//! `ethabi` has no fixed point or external function types: they convert to
+//! the types they are encoded as, `int<M>`, `uint<M>` and `bytes24`. Tuple
+//! component names are lost the same way.
+
+use anyhow::{anyhow, Result};
+use ethabi::{ParamType, Token};
+use ethereum_types::H160;
+use std::{collections::BTreeMap, convert::TryInto, sync::Arc};
+
+use crate::{
+    params::name_struct, Abi, Constructor, Error, Event, Function, Param, StateMutability, Type,
+    Value,
+};
+
+impl From<&Type> for ParamType {
+    fn from(ty: &Type) -> Self {
+        match ty {
+            Type::Uint(size) => ParamType::Uint(*size),
+            Type::Int(size) => ParamType::Int(*size),
+            Type::Fixed(size, _) => ParamType::Int(*size),
+            Type::Ufixed(size, _) => ParamType::Uint(*size),
+            Type::Address => ParamType::Address,
+            Type::Bool => ParamType::Bool,
+            Type::Function => ParamType::FixedBytes(24),
+            Type::FixedBytes(size) => ParamType::FixedBytes(*size),
+            Type::FixedArray(ty, size) => {
+                ParamType::FixedArray(Box::new(ty.as_ref().into()), *size)
+            }
+            Type::String => ParamType::String,
+            Type::Bytes => ParamType::Bytes,
+            Type::Array(ty) => ParamType::Array(Box::new(ty.as_ref().into())),
+            Type::Tuple(tys) | Type::Struct(_, tys) => {
+                ParamType::Tuple(tys.iter().map(|(_, ty)| ty.into()).collect())
+            }
+        }
+    }
+}
+
+impl From<ParamType> for Type {
+    fn from(ty: ParamType) -> Self {
+        match ty {
+            ParamType::Uint(size) => Type::Uint(size),
+            ParamType::Int(size) => Type::Int(size),
+            ParamType::Address => Type::Address,
+            ParamType::Bool => Type::Bool,
+            ParamType::FixedBytes(size) => Type::FixedBytes(size),
+            ParamType::FixedArray(ty, size) => Type::FixedArray(Box::new((*ty).into()), size),
+            ParamType::String => Type::String,
+            ParamType::Bytes => Type::Bytes,
+            ParamType::Array(ty) => Type::Array(Box::new((*ty).into())),
+            ParamType::Tuple(tys) => Type::Tuple(
+                tys.into_iter()
+                    .map(|ty| (String::new(), ty.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<&Value> for Token {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Uint(v, _) | Value::Ufixed(v, _, _) => Token::Uint(*v),
+            Value::Int(v, _) | Value::Fixed(v, _, _) => Token::Int(*v),
+            Value::Address(address) => Token::Address(*address),
+            Value::Bool(b) => Token::Bool(*b),
+            Value::Function(address, selector) => {
+                Token::FixedBytes([address.as_bytes(), &selector[..]].concat())
+            }
+            Value::FixedBytes(bytes) => Token::FixedBytes(bytes.clone()),
+            Value::FixedArray(values, _) => {
+                Token::FixedArray(values.iter().map(Into::into).collect())
+            }
+            Value::String(s) => Token::String(s.clone()),
+            Value::Bytes(bytes) => Token::Bytes(bytes.to_vec()),
+            Value::Array(values, _) => Token::Array(values.iter().map(Into::into).collect()),
+            Value::Tuple(values) => {
+                Token::Tuple(values.iter().map(|(_, value)| value.into()).collect())
+            }
+            Value::Hash(hash) => Token::FixedBytes(hash.as_bytes().to_vec()),
+        }
+    }
+}
+
+impl Value {
+    /// Converts an `ethabi` token to a value of the given type.
+    ///
+    /// Tokens do not carry integer sizes, array element types or tuple
+    /// component names, so these are taken from the type. Fails if the
+    /// token does not match the type.
+    pub fn from_token(token: Token, ty: &Type) -> Result<Value> {
+        let values = |tokens: Vec<Token>, ty: &Type| -> Result<Vec<Value>> {
+            tokens
+                .into_iter()
+                .map(|token| Value::from_token(token, ty))
+                .collect()
+        };
+
+        Ok(match (token, ty) {
+            (Token::Uint(v), Type::Uint(size)) => Value::Uint(v, *size),
+            (Token::Uint(v), Type::Ufixed(size, decimals)) => Value::Ufixed(v, *size, *decimals),
+            (Token::Int(v), Type::Int(size)) => Value::Int(v, *size),
+            (Token::Int(v), Type::Fixed(size, decimals)) => Value::Fixed(v, *size, *decimals),
+            (Token::Address(address), Type::Address) => Value::Address(address),
+            (Token::Bool(b), Type::Bool) => Value::Bool(b),
+            (Token::FixedBytes(bytes), Type::Function) if bytes.len() == 24 => {
+                Value::Function(H160::from_slice(&bytes[..20]), bytes[20..].try_into()?)
+            }
+            (Token::FixedBytes(bytes), Type::FixedBytes(size)) if bytes.len() == *size => {
+                Value::FixedBytes(bytes)
+            }
+            (Token::FixedArray(tokens), Type::FixedArray(ty, size)) if tokens.len() == *size => {
+                Value::FixedArray(values(tokens, ty)?, Arc::new((**ty).clone()))
+            }
+            (Token::String(s), Type::String) => Value::String(s),
+            (Token::Bytes(bytes), Type::Bytes) => Value::Bytes(bytes.into()),
+            (Token::Array(tokens), Type::Array(ty)) => {
+                Value::Array(values(tokens, ty)?, Arc::new((**ty).clone()))
+            }
+            (Token::Tuple(tokens), Type::Tuple(tys))
+            | (Token::Tuple(tokens), Type::Struct(_, tys))
+                if tokens.len() == tys.len() =>
+            {
+                Value::Tuple(
+                    tokens
+                        .into_iter()
+                        .zip(tys)
+                        .map(|(token, (name, ty))| {
+                            Ok((name.clone(), Value::from_token(token, ty)?))
+                        })
+                        .collect::<Result<_>>()?,
+                )
+            }
+            (token, ty) => return Err(anyhow!("ethabi token {:?} is not a {}", token, ty)),
+        })
+    }
+}
+
+impl From<StateMutability> for ethabi::StateMutability {
+    fn from(state_mutability: StateMutability) -> Self {
+        match state_mutability {
+            StateMutability::Pure => ethabi::StateMutability::Pure,
+            StateMutability::View => ethabi::StateMutability::View,
+            StateMutability::NonPayable => ethabi::StateMutability::NonPayable,
+            StateMutability::Payable => ethabi::StateMutability::Payable,
+        }
+    }
+}
+
+impl From<ethabi::StateMutability> for StateMutability {
+    fn from(state_mutability: ethabi::StateMutability) -> Self {
+        match state_mutability {
+            ethabi::StateMutability::Pure => StateMutability::Pure,
+            ethabi::StateMutability::View => StateMutability::View,
+            ethabi::StateMutability::NonPayable => StateMutability::NonPayable,
+            ethabi::StateMutability::Payable => StateMutability::Payable,
+        }
+    }
+}
+
+impl From<&Param> for ethabi::Param {
+    fn from(param: &Param) -> Self {
+        Self {
+            name: param.name.clone(),
+            kind: (&param.type_).into(),
+            internal_type: param.internal_type.clone(),
+        }
+    }
+}
+
+impl From<ethabi::Param> for Param {
+    fn from(param: ethabi::Param) -> Self {
+        Self {
+            name: param.name,
+            type_: name_struct(param.kind.into(), param.internal_type.as_deref()),
+            indexed: None,
+            internal_type: param.internal_type,
+        }
+    }
+}
+
+impl From<&Param> for ethabi::EventParam {
+    fn from(param: &Param) -> Self {
+        Self {
+            name: param.name.clone(),
+            kind: (&param.type_).into(),
+            indexed: param.indexed.unwrap_or(false),
+        }
+    }
+}
+
+impl From<ethabi::EventParam> for Param {
+    fn from(param: ethabi::EventParam) -> Self {
+        Self {
+            name: param.name,
+            type_: param.kind.into(),
+            indexed: Some(param.indexed),
+            internal_type: None,
+        }
+    }
+}
+
+impl From<&Function> for ethabi::Function {
+    #[allow(deprecated)]
+    fn from(function: &Function) -> Self {
+        Self {
+            name: function.name.clone(),
+            inputs: function.inputs.iter().map(Into::into).collect(),
+            outputs: function.outputs.iter().map(Into::into).collect(),
+            constant: None,
+            state_mutability: function.state_mutability.into(),
+        }
+    }
+}
+
+impl From<ethabi::Function> for Function {
+    fn from(function: ethabi::Function) -> Self {
+        Self::new(
+            function.name,
+            function.inputs.into_iter().map(Into::into).collect(),
+            function.outputs.into_iter().map(Into::into).collect(),
+            function.state_mutability.into(),
+        )
+    }
+}
+
+impl From<&Event> for ethabi::Event {
+    fn from(event: &Event) -> Self {
+        Self {
+            name: event.name.clone(),
+            inputs: event.inputs.iter().map(Into::into).collect(),
+            anonymous: event.anonymous,
+        }
+    }
+}
+
+impl From<ethabi::Event> for Event {
+    fn from(event: ethabi::Event) -> Self {
+        Self::new(
+            event.name,
+            event.inputs.into_iter().map(Into::into).collect(),
+            event.anonymous,
+        )
+    }
+}
+
+impl From<&Error> for ethabi::AbiError {
+    fn from(error: &Error) -> Self {
+        Self {
+            name: error.name.clone(),
+            inputs: error.inputs.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ethabi::AbiError> for Error {
+    fn from(error: ethabi::AbiError) -> Self {
+        Self::new(
+            error.name,
+            error.inputs.into_iter().map(Into::into).collect(),
+        )
+    }
+}
+
+impl From<&Abi> for ethabi::Contract {
+    fn from(abi: &Abi) -> Self {
+        fn by_name<'a, T: 'a, U: From<&'a T>>(
+            entries: &'a [T],
+            name: impl Fn(&T) -> &str,
+        ) -> BTreeMap<String, Vec<U>> {
+            let mut map: BTreeMap<String, Vec<U>> = BTreeMap::new();
+
+            for entry in entries {
+                map.entry(name(entry).to_string())
+                    .or_default()
+                    .push(entry.into());
+            }
+
+            map
+        }
+
+        Self {
+            constructor: abi
+                .constructor
+                .as_ref()
+                .map(|constructor| ethabi::Constructor {
+                    inputs: constructor.inputs.iter().map(Into::into).collect(),
+                }),
+            functions: by_name(&abi.functions, |f| &f.name),
+            events: by_name(&abi.events, |e| &e.name),
+            errors: by_name(&abi.errors, |e| &e.name),
+            receive: abi.receive.is_some(),
+            fallback: abi.fallback.is_some(),
+        }
+    }
+}
+
+impl From<ethabi::Contract> for Abi {
+    /// Converts an `ethabi` contract, whose constructor and fallback are
+    /// assumed to be non-payable as `ethabi` does not record it.
+    fn from(contract: ethabi::Contract) -> Self {
+        Self {
+            constructor: contract.constructor.map(|constructor| Constructor {
+                inputs: constructor.inputs.into_iter().map(Into::into).collect(),
+                state_mutability: StateMutability::NonPayable,
+            }),
+            functions: contract
+                .functions
+                .into_values()
+                .flatten()
+                .map(Into::into)
+                .collect(),
+            events: contract
+                .events
+                .into_values()
+                .flatten()
+                .map(Into::into)
+                .collect(),
+            errors: contract
+                .errors
+                .into_values()
+                .flatten()
+                .map(Into::into)
+                .collect(),
+            receive: contract.receive.then_some(StateMutability::Payable),
+            fallback: contract.fallback.then_some(StateMutability::NonPayable),
+            layout: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::U256;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn ethabi_values() {
+        let ty = Type::Tuple(vec![
+            ("amount".into(), Type::Int(64)),
+            ("to".into(), Type::Array(Box::new(Type::Address))),
+            ("callback".into(), Type::Function),
+            ("data".into(), Type::FixedArray(Box::new(Type::Bytes), 2)),
+        ]);
+        let value = Value::Tuple(vec![
+            ("amount".into(), Value::Int(U256::MAX, 64)),
+            (
+                "to".into(),
+                Value::Array(
+                    vec![Value::Address(H160::repeat_byte(1))],
+                    Type::Address.into(),
+                ),
+            ),
+            (
+                "callback".into(),
+                Value::Function(H160::repeat_byte(2), [1, 2, 3, 4]),
+            ),
+            (
+                "data".into(),
+                Value::FixedArray(
+                    vec![Value::Bytes(vec![1].into()), Value::Bytes(vec![].into())],
+                    Type::Bytes.into(),
+                ),
+            ),
+        ]);
+
+        let encoded = Value::encode(std::slice::from_ref(&value));
+
+        // Both encoders agree, and values survive the round trip.
+        assert_eq!(ethabi::encode(&[Token::from(&value)]), encoded);
+        let decoded = ethabi::decode(&[ParamType::from(&ty)], &encoded)
+            .unwrap()
+            .remove(0);
+        assert_eq!(Value::from_token(decoded, &ty).unwrap(), value);
+
+        assert!(Value::from_token(Token::Bool(true), &Type::Uint(8)).is_err());
+        assert!(Value::from_token(Token::FixedBytes(vec![0; 4]), &Type::FixedBytes(8)).is_err());
+    }
+
+    #[test]
+    fn ethabi_contract() {
+        let abi: Abi = serde_json::from_str(
+            r#"[
+                {"type": "constructor", "inputs": [{"name": "owner", "type": "address"}], "stateMutability": "nonpayable"},
+                {"type": "function", "name": "f", "inputs": [{"name": "x", "type": "uint256"}], "outputs": [], "stateMutability": "view"},
+                {"type": "function", "name": "f", "inputs": [{"name": "x", "type": "bytes"}], "outputs": [{"name": "", "type": "bool"}], "stateMutability": "payable"},
+                {
+                    "type": "function",
+                    "name": "fill",
+                    "inputs": [{
+                        "name": "order",
+                        "type": "tuple",
+                        "internalType": "struct Exchange.Order",
+                        "components": [{"name": "maker", "type": "address"}]
+                    }],
+                    "outputs": [],
+                    "stateMutability": "nonpayable"
+                },
+                {"type": "event", "name": "E", "inputs": [{"name": "from", "type": "address", "indexed": true}, {"name": "v", "type": "uint256", "indexed": false}], "anonymous": false},
+                {"type": "error", "name": "Unauthorized", "inputs": [{"name": "caller", "type": "address"}]},
+                {"type": "receive", "stateMutability": "payable"}
+            ]"#,
+        )
+        .unwrap();
+
+        let contract = ethabi::Contract::from(&abi);
+
+        assert_eq!(contract.functions_by_name("f").unwrap().len(), 2);
+        assert!(contract.receive);
+        for function in &abi.functions {
+            assert!(contract
+                .functions()
+                .any(|f| f.short_signature() == function.method_id()));
+        }
+        assert_eq!(
+            contract.event("E").unwrap().signature(),
+            abi.events[0].topic()
+        );
+
+        let back = Abi::from(contract);
+
+        assert_eq!(back.events, abi.events);
+        assert_eq!(back.errors, abi.errors);
+        assert_eq!(back.constructor, abi.constructor);
+        assert_eq!(back.receive, abi.receive);
+        assert_eq!(
+            back.function("fill").unwrap().inputs[0].type_,
+            Type::Struct(
+                "Exchange.Order".into(),
+                vec![(String::new(), Type::Address)]
+            )
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Conversions from and to the types of the `ethers-core` crate.
+//!
+//! `ethers-core` tokens are `ethabi` tokens, converted as described in the
+//! `ethabi` conversions.
+
+use anyhow::Result;
+use ethereum_types::{H160, U256};
+use ethers_core::{
+    abi::{self, LogParam},
+    types::{TransactionRequest, U64},
+};
+
+use crate::{Abi, DecodedParams, Log, Transaction, Value};
+
+impl From<&ethers_core::types::Log> for Log {
+    fn from(log: &ethers_core::types::Log) -> Self {
+        Self {
+            address: log.address,
+            topics: log.topics.clone(),
+            data: log.data.to_vec(),
+            block_number: log.block_number.map(|n| n.as_u64()),
+            block_hash: log.block_hash,
+            transaction_hash: log.transaction_hash,
+            transaction_index: log.transaction_index.map(|i| i.as_u64()),
+            log_index: log.log_index.map(|i| i.low_u64()),
+            removed: log.removed.unwrap_or(false),
+        }
+    }
+}
+
+impl From<&Log> for ethers_core::types::Log {
+    fn from(log: &Log) -> Self {
+        Self {
+            address: log.address,
+            topics: log.topics.clone(),
+            data: log.data.clone().into(),
+            block_hash: log.block_hash,
+            block_number: log.block_number.map(U64::from),
+            transaction_hash: log.transaction_hash,
+            transaction_index: log.transaction_index.map(U64::from),
+            log_index: log.log_index.map(U256::from),
+            transaction_log_index: None,
+            log_type: None,
+            removed: Some(log.removed),
+        }
+    }
+}
+
+impl From<&DecodedParams> for abi::Log {
+    fn from(params: &DecodedParams) -> Self {
+        Self {
+            params: params
+                .entries()
+                .map(|(name, value)| LogParam {
+                    name: name.to_string(),
+                    value: value.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&Transaction> for TransactionRequest {
+    fn from(tx: &Transaction) -> Self {
+        let request = TransactionRequest::new()
+            .from(tx.from)
+            .value(tx.value)
+            .data(tx.input.clone())
+            .nonce(tx.nonce);
+
+        match tx.to {
+            Some(to) => request.to(to),
+            None => request,
+        }
+    }
+}
+
+impl Abi {
+    /// Builds an ethers transaction request calling the named function of
+    /// the contract at `to`.
+    ///
+    /// Overloads are resolved as in [`Abi::resolve_function`].
+    pub fn transaction_request(
+        &self,
+        to: H160,
+        name: &str,
+        args: &[Value],
+    ) -> Result<TransactionRequest> {
+        Ok(TransactionRequest::new()
+            .to(to)
+            .data(self.encode_call(name, args)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethers_core::types::NameOrAddress;
+    use pretty_assertions::assert_eq;
+
+    use crate::StateMutability;
+
+    use super::*;
+
+    #[test]
+    fn ethers_logs() {
+        let abi = Abi::builder()
+            .event(
+                "Transfer",
+                &[("from", "address", true), ("value", "uint256", false)],
+            )
+            .build()
+            .unwrap();
+        let event = &abi.events[0];
+
+        let log = ethers_core::types::Log {
+            address: H160::repeat_byte(1),
+            topics: vec![event.topic(), H160::repeat_byte(2).into()],
+            data: Value::encode(&[Value::Uint(U256::from(7), 256)]).into(),
+            block_number: Some(U64::from(10)),
+            log_index: Some(U256::from(3)),
+            ..Default::default()
+        };
+
+        let ours = Log::from(&log);
+        assert_eq!(ours.block_number, Some(10));
+        assert_eq!(ours.log_index, Some(3));
+        assert_eq!(ethers_core::types::Log::from(&ours).data, log.data);
+
+        let (_, params) = abi.decode_log(&ours).unwrap();
+        let decoded = abi::Log::from(&params);
+
+        assert_eq!(
+            decoded.params,
+            vec![
+                LogParam {
+                    name: "from".to_string(),
+                    value: abi::Token::Address(H160::repeat_byte(2)),
+                },
+                LogParam {
+                    name: "value".to_string(),
+                    value: abi::Token::Uint(U256::from(7)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ethers_transaction_request() {
+        let abi = Abi::builder()
+            .function(
+                "transfer",
+                &[("to", "address"), ("amount", "uint256")],
+                &[("", "bool")],
+                StateMutability::NonPayable,
+            )
+            .build()
+            .unwrap();
+        let args = [
+            Value::Address(H160::repeat_byte(2)),
+            Value::Uint(U256::from(5), 256),
+        ];
+
+        let request = abi
+            .transaction_request(H160::repeat_byte(1), "transfer", &args)
+            .unwrap();
+
+        assert_eq!(
+            request.to,
+            Some(NameOrAddress::Address(H160::repeat_byte(1)))
+        );
+        assert_eq!(
+            request.data.unwrap().to_vec(),
+            abi.encode_call("transfer", &args).unwrap()
+        );
+        assert!(abi
+            .transaction_request(H160::zero(), "approve", &args)
+            .is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::H160;
+use serde::Deserialize;
+
+use crate::{unblock::unblock, Abi, AbiProvider};
+
+#[cfg(feature = "async")]
+use crate::{AbiFuture, AsyncAbiProvider};
+
+const ETHERSCAN_URL: &str = "https://api.etherscan.io/v2/api";
+
+/// Client fetching the ABIs of verified contracts from Etherscan.
+///
+/// Uses the multichain V2 API, so one API key covers every chain Etherscan
+/// supports.
+///
+/// ```no_run
+/// use ethereum_abi::Etherscan;
+/// use ethereum_types::H160;
+///
+/// let etherscan = Etherscan::new("API_KEY");
+/// let abi = etherscan.fetch_abi_blocking(1, &H160::zero()).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Etherscan {
+    agent: ureq::Agent,
+    api_key: String,
+    url: String,
+}
+
+impl Etherscan {
+    /// Creates a client using the given API key.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            agent: ureq::Agent::new_with_defaults(),
+            api_key: api_key.into(),
+            url: ETHERSCAN_URL.to_string(),
+        }
+    }
+
+    /// Overrides the API endpoint, e.g. for Etherscan compatible explorers.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Fetches the ABI of the verified contract at the given address.
+    ///
+    /// Fails if the contract is not verified on that chain.
+    pub fn fetch_abi_blocking(&self, chain_id: u64, address: &H160) -> Result<Abi> {
+        parse_etherscan_response(self.request(chain_id, address)?)
+    }
+
+    /// Fetches the ABI of the verified contract at the given address,
+    /// without blocking the calling task.
+    ///
+    /// The request runs on a thread spawned for it.
+    pub async fn fetch_abi(&self, chain_id: u64, address: &H160) -> Result<Abi> {
+        let client = self.clone();
+        let address = *address;
+
+        unblock(move || client.fetch_abi_blocking(chain_id, &address)).await
+    }
+
+    fn request(&self, chain_id: u64, address: &H160) -> Result<EtherscanResponse> {
+        Ok(self
+            .agent
+            .get(&self.url)
+            .query("chainid", chain_id.to_string())
+            .query("module", "contract")
+            .query("action", "getabi")
+            .query("address", format!("{:?}", address))
+            .query("apikey", &self.api_key)
+            .call()?
+            .body_mut()
+            .read_json()?)
+    }
+
+    fn get_abi_blocking(&self, chain_id: u64, address: &H160) -> Result<Option<Abi>> {
+        let response = self.request(chain_id, address)?;
+
+        if response.status != "1" && response.result.contains("not verified") {
+            return Ok(None);
+        }
+
+        parse_etherscan_response(response).map(Some)
+    }
+}
+
+impl AbiProvider for Etherscan {
+    fn get_abi(&self, chain_id: u64, address: &H160) -> Result<Option<Abi>> {
+        self.get_abi_blocking(chain_id, address)
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncAbiProvider for Etherscan {
+    fn get_abi_async<'a>(&'a self, chain_id: u64, address: &'a H160) -> AbiFuture<'a> {
+        let client = self.clone();
+        let address = *address;
+
+        Box::pin(unblock(move || client.get_abi_blocking(chain_id, &address)))
+    }
+}
+
+impl Abi {
+    /// Fetches the ABI of a verified contract from Etherscan.
+    pub fn fetch_from_etherscan_blocking(
+        chain_id: u64,
+        address: &H160,
+        api_key: &str,
+    ) -> Result<Abi> {
+        Etherscan::new(api_key).fetch_abi_blocking(chain_id, address)
+    }
+
+    /// Fetches the ABI of a verified contract from Etherscan, without
+    /// blocking the calling task.
+    pub async fn fetch_from_etherscan(chain_id: u64, address: &H160, api_key: &str) -> Result<Abi> {
+        Etherscan::new(api_key).fetch_abi(chain_id, address).await
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EtherscanResponse {
+    status: String,
+    message: String,
+    result: String,
+}
+
+fn parse_etherscan_response(response: EtherscanResponse) -> Result<Abi> {
+    if response.status != "1" {
+        return Err(anyhow!(
+            "etherscan request failed: {}: {}",
+            response.message,
+            response.result
+        ));
+    }
+
+    Ok(serde_json::from_str(&response.result)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::unblock::block_on;
+
+    #[test]
+    fn etherscan_response() {
+        let body = serde_json::json!({
+            "status": "1",
+            "message": "OK",
+            "result": r#"[{"type":"function","name":"totalSupply","inputs":[],"outputs":[{"name":"","type":"uint256"}],"stateMutability":"view"}]"#
+        });
+
+        let abi = parse_etherscan_response(serde_json::from_value(body).unwrap()).unwrap();
+
+        assert_eq!(abi.functions[0].signature(), "totalSupply()");
+    }
+
+    #[test]
+    fn etherscan_error() {
+        let body = serde_json::json!({
+            "status": "0",
+            "message": "NOTOK",
+            "result": "Contract source code not verified"
+        });
+
+        let err = parse_etherscan_response(serde_json::from_value(body).unwrap()).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "etherscan request failed: NOTOK: Contract source code not verified"
+        );
+    }
+
+    #[test]
+    fn unreachable_etherscan() {
+        let etherscan = Etherscan::new("key").with_url("http://127.0.0.1:0");
+
+        assert!(block_on(etherscan.fetch_abi(1, &H160::zero())).is_err());
+    }
+}
@@ -1,30 +1,198 @@
 use anyhow::{anyhow, Result};
 use ethereum_types::H256;
-use std::collections::VecDeque;
+use serde::Serialize;
+use std::fmt;
 
-use crate::{DecodedParams, Param, Type, Value};
+use crate::{
+    memo::SignatureHash, natspec::DocsSlot, params::declarations, DecodedParams, DecodedParamsRef,
+    Docs, Param, Type, Value,
+};
 
 /// Contract Error Definition
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// The selector is computed on first use and then cached. The name and inputs
+/// are only reachable through methods so that changing them clears it.
+#[derive(Clone, Eq, PartialEq)]
 pub struct Error {
-    /// Error name.
-    pub name: String,
-    /// Error inputs.
-    pub inputs: Vec<Param>,
+    pub(crate) name: String,
+    pub(crate) inputs: Vec<Param>,
+    pub(crate) hash: SignatureHash,
+    docs: DocsSlot,
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Error")
+            .field("name", &self.name)
+            .field("inputs", &self.inputs)
+            .finish()
+    }
+}
+
+impl Error {
+    /// Creates an error definition.
+    pub fn new(name: impl Into<String>, inputs: Vec<Param>) -> Self {
+        Self {
+            name: name.into(),
+            inputs,
+            hash: SignatureHash::default(),
+            docs: DocsSlot::default(),
+        }
+    }
+
+    /// Returns the error's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Renames the error, clearing its cached selector.
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+        self.hash = SignatureHash::default();
+    }
+
+    /// Returns the error's inputs.
+    pub fn inputs(&self) -> &[Param] {
+        &self.inputs
+    }
+
+    /// Returns the error's inputs for editing, clearing its cached selector.
+    pub fn inputs_mut(&mut self) -> &mut Vec<Param> {
+        self.hash = SignatureHash::default();
+        &mut self.inputs
+    }
+
+    /// Returns the NatSpec documentation attached to the error, see
+    /// [`Abi::attach_natspec`](crate::Abi::attach_natspec).
+    pub fn docs(&self) -> Option<&Docs> {
+        self.docs.get()
+    }
+
+    /// Attaches NatSpec documentation to the error, or removes it.
+    pub fn set_docs(&mut self, docs: Option<Docs>) {
+        self.docs.set(docs);
+    }
+
+    /// Computes the error's selector, the first four bytes of the signature
+    /// hash, which prefixes its revert data.
+    pub fn selector(&self) -> [u8; 4] {
+        let mut selector = [0u8; 4];
+        selector.copy_from_slice(&self.hash.get_or_init(|| self.signature())[0..4]);
+
+        selector
+    }
+
+    /// Returns the error's signature.
+    pub fn signature(&self) -> String {
+        format!(
+            "{}({})",
+            self.name,
+            self.inputs
+                .iter()
+                .map(|param| param.type_.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+
+    /// Decode the error's params from revert data, without the selector.
+    pub fn decode_data_from_slice(&self, data: &[u8]) -> Result<DecodedParams> {
+        self.decode_data_ref(data).map(DecodedParamsRef::into_owned)
+    }
+
+    /// Decodes the error's params from revert data, without the selector,
+    /// borrowing the params from the error rather than cloning them.
+    pub fn decode_data_ref(&self, data: &[u8]) -> Result<DecodedParamsRef<'_>> {
+        let values = Value::decode_all(data, self.inputs.iter().map(|param| &param.type_))?;
+
+        Ok(DecodedParamsRef::new(&self.inputs, values))
+    }
+
+    /// Returns the error's declaration with parameter names, e.g.
+    /// `InsufficientBalance(uint256 available, uint256 required)`.
+    pub fn full_signature(&self) -> String {
+        format!("{}({})", self.name, declarations(&self.inputs))
+    }
+}
+
+/// Event params decoded from a log, split by where they were read from.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct DecodedEvent {
+    /// Indexed params, read from the log's topics.
+    pub indexed: DecodedParams,
+    /// Non-indexed params, read from the log's data.
+    pub body: DecodedParams,
 }
 
 /// Contract event definition.
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// The topic is computed on first use and then cached. The name and inputs
+/// are only reachable through methods so that changing them clears it.
+#[derive(Clone, Eq, PartialEq)]
 pub struct Event {
-    /// Event name.
-    pub name: String,
-    /// Event inputs.
-    pub inputs: Vec<Param>,
+    pub(crate) name: String,
+    pub(crate) inputs: Vec<Param>,
     /// Whether the event is anonymous or not.
     pub anonymous: bool,
+    pub(crate) hash: SignatureHash,
+    docs: DocsSlot,
+}
+
+impl fmt::Debug for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Event")
+            .field("name", &self.name)
+            .field("inputs", &self.inputs)
+            .field("anonymous", &self.anonymous)
+            .finish()
+    }
 }
 
 impl Event {
+    /// Creates an event definition.
+    pub fn new(name: impl Into<String>, inputs: Vec<Param>, anonymous: bool) -> Self {
+        Self {
+            name: name.into(),
+            inputs,
+            anonymous,
+            hash: SignatureHash::default(),
+            docs: DocsSlot::default(),
+        }
+    }
+
+    /// Returns the event's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Renames the event, clearing its cached topic.
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+        self.hash = SignatureHash::default();
+    }
+
+    /// Returns the event's inputs.
+    pub fn inputs(&self) -> &[Param] {
+        &self.inputs
+    }
+
+    /// Returns the event's inputs for editing, clearing its cached topic.
+    pub fn inputs_mut(&mut self) -> &mut Vec<Param> {
+        self.hash = SignatureHash::default();
+        &mut self.inputs
+    }
+
+    /// Returns the NatSpec documentation attached to the event, see
+    /// [`Abi::attach_natspec`](crate::Abi::attach_natspec).
+    pub fn docs(&self) -> Option<&Docs> {
+        self.docs.get()
+    }
+
+    /// Attaches NatSpec documentation to the event, or removes it.
+    pub fn set_docs(&mut self, docs: Option<Docs>) {
+        self.docs.set(docs);
+    }
+
     /// Returns the event's signature.
     pub fn signature(&self) -> String {
         format!(
@@ -38,24 +206,37 @@ impl Event {
         )
     }
 
-    /// Compute the event's topic hash
-    pub fn topic(&self) -> H256 {
-        use tiny_keccak::{Hasher, Keccak};
+    /// Returns the event's declaration with parameter names and `indexed`
+    /// markers, e.g. `Transfer(address indexed from, address indexed to,
+    /// uint256 value)`.
+    pub fn full_signature(&self) -> String {
+        let mut sig = format!("{}({})", self.name, declarations(&self.inputs));
+
+        if self.anonymous {
+            sig.push_str(" anonymous");
+        }
 
-        let mut keccak_out = [0u8; 32];
-        let mut hasher = Keccak::v256();
-        hasher.update(self.signature().as_bytes());
-        hasher.finalize(&mut keccak_out);
+        sig
+    }
 
-        H256::from_slice(&keccak_out)
+    /// Compute the event's topic hash
+    pub fn topic(&self) -> H256 {
+        H256(*self.hash.get_or_init(|| self.signature()))
     }
 
     /// Decode event params from a log's topics and data.
-    pub fn decode_data_from_slice(
+    pub fn decode_data_from_slice(&self, topics: &[H256], data: &[u8]) -> Result<DecodedParams> {
+        self.decode_data_ref(topics, data)
+            .map(DecodedParamsRef::into_owned)
+    }
+
+    /// Decodes event params from a log's topics and data, borrowing the
+    /// params from the event rather than cloning them.
+    pub fn decode_data_ref(
         &self,
         mut topics: &[H256],
         data: &[u8],
-    ) -> Result<DecodedParams> {
+    ) -> Result<DecodedParamsRef<'_>> {
         // strip event topic from the topics array
         // so that we end up with only the values we
         // need to decode
@@ -65,51 +246,64 @@ impl Event {
                 .ok_or_else(|| anyhow!("missing event topic"))?;
         }
 
-        let mut topics_values = VecDeque::from(topics.to_vec());
+        let mut topics_values = topics.iter();
 
-        let mut data_values = VecDeque::from(Value::decode_from_slice(
+        let mut data_values = Value::decode_all(
             data,
-            &self
-                .inputs
+            self.inputs
                 .iter()
                 .filter(|input| !input.indexed.unwrap_or(false))
-                .map(|input| input.type_.clone())
-                .collect::<Vec<_>>(),
-        )?);
+                .map(|input| &input.type_),
+        )?
+        .into_iter();
 
-        let mut decoded = vec![];
-        for input in self.inputs.iter().cloned() {
+        let mut decoded = Vec::with_capacity(self.inputs.len());
+        for input in &self.inputs {
             let decoded_value = if input.indexed.unwrap_or(false) {
                 let val = topics_values
-                    .pop_front()
+                    .next()
                     .ok_or_else(|| anyhow!("insufficient topics entries"))?;
 
-                let bytes = val.to_fixed_bytes().to_vec();
-
                 if Self::is_encoded_to_keccak(&input.type_) {
-                    Ok(Value::FixedBytes(bytes))
+                    Value::Hash(*val)
                 } else {
-                    Value::decode_from_slice(&bytes, &[input.type_.clone()])?
-                        .first()
-                        .ok_or_else(|| anyhow!("no value decoded from topics entry"))
-                        .map(Clone::clone)
+                    Value::decode(val.as_bytes(), &input.type_, 0, 0)?.0
                 }
             } else {
                 data_values
-                    .pop_front()
-                    .ok_or_else(|| anyhow!("insufficient data values"))
+                    .next()
+                    .ok_or_else(|| anyhow!("insufficient data values"))?
             };
 
-            decoded.push((input, decoded_value?));
+            decoded.push(decoded_value);
         }
 
-        Ok(DecodedParams::from(decoded))
+        Ok(DecodedParamsRef::new(&self.inputs, decoded))
+    }
+
+    /// Decode event params from a log's topics and data, keeping indexed and
+    /// non-indexed params apart.
+    pub fn decode_event_from_slice(&self, topics: &[H256], data: &[u8]) -> Result<DecodedEvent> {
+        let (indexed, body): (Vec<_>, Vec<_>) = self
+            .decode_data_from_slice(topics, data)?
+            .into_iter()
+            .partition(|decoded| decoded.param.indexed.unwrap_or(false));
+
+        Ok(DecodedEvent {
+            indexed: indexed.into(),
+            body: body.into(),
+        })
     }
 
-    fn is_encoded_to_keccak(ty: &Type) -> bool {
+    pub(crate) fn is_encoded_to_keccak(ty: &Type) -> bool {
         matches!(
             ty,
-            Type::FixedArray(_, _) | Type::Array(_) | Type::Bytes | Type::String | Type::Tuple(_)
+            Type::FixedArray(_, _)
+                | Type::Array(_)
+                | Type::Bytes
+                | Type::String
+                | Type::Tuple(_)
+                | Type::Struct(_, _)
         )
     }
 }
@@ -126,22 +320,24 @@ mod test {
     use pretty_assertions::assert_eq;
 
     fn test_event() -> Event {
-        Event {
-            name: "Approve".to_string(),
-            inputs: vec![
+        Event::new(
+            "Approve".to_string(),
+            vec![
                 Param {
-                    name: "x".to_string(),
+                    name: "x".into(),
                     type_: Type::Uint(56),
                     indexed: Some(true),
+                    internal_type: None,
                 },
                 Param {
-                    name: "y".to_string(),
+                    name: "y".into(),
                     type_: Type::String,
                     indexed: Some(true),
+                    internal_type: None,
                 },
             ],
-            anonymous: false,
-        }
+            false,
+        )
     }
 
     #[test]
@@ -151,6 +347,23 @@ mod test {
         assert_eq!(evt.signature(), "Approve(uint56,string)");
     }
 
+    #[test]
+    fn test_full_signature() {
+        let mut evt = test_event();
+        evt.inputs[1].indexed = Some(false);
+
+        assert_eq!(evt.full_signature(), "Approve(uint56 indexed x, string y)");
+
+        evt.anonymous = true;
+        assert_eq!(
+            evt.full_signature(),
+            "Approve(uint56 indexed x, string y) anonymous"
+        );
+
+        let err = Error::parse("Unauthorized(address,bytes32)").unwrap();
+        assert_eq!(err.full_signature(), "Unauthorized(address, bytes32)");
+    }
+
     #[test]
     fn test_topic() {
         let evt = test_event();
@@ -176,44 +389,50 @@ mod test {
         let data = hex::decode("00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000036162630000000000000000000000000000000000000000000000000000000000").unwrap();
 
         let x = Param {
-            name: "x".to_string(),
+            name: "x".into(),
             type_: Type::Uint(256),
             indexed: None,
+            internal_type: None,
         };
         let y = Param {
-            name: "y".to_string(),
+            name: "y".into(),
             type_: Type::Uint(256),
             indexed: Some(true),
+            internal_type: None,
         };
         let x1 = Param {
-            name: "x1".to_string(),
+            name: "x1".into(),
             type_: Type::Uint(256),
             indexed: None,
+            internal_type: None,
         };
         let y1 = Param {
-            name: "y1".to_string(),
+            name: "y1".into(),
             type_: Type::Uint(256),
             indexed: Some(true),
+            internal_type: None,
         };
         let s = Param {
-            name: "s".to_string(),
+            name: "s".into(),
             type_: Type::String,
             indexed: None,
+            internal_type: None,
         };
 
-        let evt = Event {
-            name: "Test".to_string(),
-            inputs: vec![x.clone(), y.clone(), x1.clone(), y1.clone(), s.clone()],
-            anonymous: false,
-        };
+        let evt = Event::new(
+            "Test".to_string(),
+            vec![x.clone(), y.clone(), x1.clone(), y1.clone(), s.clone()],
+            false,
+        );
 
         let abi = Abi {
             constructor: None,
             functions: vec![],
             events: vec![evt],
             errors: vec![],
-            has_receive: false,
-            has_fallback: false,
+            receive: None,
+            fallback: None,
+            ..Default::default()
         };
 
         assert_eq!(
@@ -231,4 +450,55 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn test_decode_hashed_indexed_params() {
+        let evt = test_event();
+        let hash = H256::repeat_byte(0xab);
+
+        let decoded = evt
+            .decode_data_from_slice(&[evt.topic(), H256::from_low_u64_be(7), hash], &[])
+            .unwrap();
+
+        assert_eq!(decoded[0], Value::Uint(U256::from(7), 56));
+        assert_eq!(decoded[1], Value::Hash(hash));
+        assert_eq!(decoded[1].as_str(), None);
+        assert_eq!(decoded[1].to_string(), format!("{:?}", hash));
+    }
+
+    #[test]
+    fn test_decode_event_split() {
+        let abi = Abi::builder()
+            .event(
+                "Swap",
+                &[
+                    ("sender", "address", true),
+                    ("amount", "uint256", false),
+                    ("to", "address", true),
+                ],
+            )
+            .build()
+            .unwrap();
+
+        let sender = H256::from_low_u64_be(1);
+        let to = H256::from_low_u64_be(2);
+        let data = Value::encode(&[Value::Uint(U256::from(5), 256)]);
+
+        let (evt, decoded) = abi
+            .decode_event_from_slice(&[abi.events[0].topic(), sender, to], &data)
+            .unwrap();
+
+        assert_eq!(evt.name, "Swap");
+        assert_eq!(
+            decoded
+                .indexed
+                .entries()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            vec!["sender", "to"]
+        );
+        assert_eq!(decoded.indexed["to"], Value::Address(to.into()));
+        assert_eq!(decoded.body.len(), 1);
+        assert_eq!(decoded.body["amount"], Value::Uint(U256::from(5), 256));
+    }
 }
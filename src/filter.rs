@@ -0,0 +1,321 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::H256;
+use serde::{Serialize, Serializer};
+
+use crate::{keccak::keccak256, Event, Value};
+
+/// Constraint on one entry of a log's topics, as taken by `eth_getLogs` and
+/// `eth_subscribe`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Topic {
+    /// Matches any topic, serialized as `null`.
+    Any,
+    /// Matches this topic only.
+    One(H256),
+    /// Matches any of these topics.
+    OneOf(Vec<H256>),
+}
+
+impl Serialize for Topic {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Topic::Any => serializer.serialize_none(),
+            Topic::One(topic) => topic.serialize(serializer),
+            Topic::OneOf(topics) => topics.serialize(serializer),
+        }
+    }
+}
+
+/// Builder for the `topics` of a log filter matching an event.
+///
+/// Indexed inputs are unconstrained unless given values by name; dynamic
+/// types are hashed the way they are in logs.
+///
+/// ```
+/// use ethereum_abi::{Abi, Topic, Value};
+/// use ethereum_types::H160;
+///
+/// let abi = Abi::builder()
+///     .event(
+///         "Transfer",
+///         &[("from", "address", true), ("to", "address", true), ("value", "uint256", false)],
+///     )
+///     .build()
+///     .unwrap();
+///
+/// let to = H160::repeat_byte(1);
+/// let topics = abi.events[0]
+///     .filter()
+///     .topic("to", Value::Address(to))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(topics[0], Topic::One(abi.events[0].topic()));
+/// assert_eq!(topics[1], Topic::Any);
+/// assert_eq!(topics[2], Topic::One(to.into()));
+/// ```
+#[derive(Debug)]
+pub struct EventFilter<'a> {
+    event: &'a Event,
+    topics: Vec<Topic>,
+    error: Option<anyhow::Error>,
+}
+
+impl Event {
+    /// Returns a builder for log filter topics matching this event.
+    pub fn filter(&self) -> EventFilter<'_> {
+        let mut topics = vec![];
+
+        if !self.anonymous {
+            topics.push(Topic::One(self.topic()));
+        }
+
+        topics.extend(
+            self.inputs
+                .iter()
+                .filter(|input| input.indexed == Some(true))
+                .map(|_| Topic::Any),
+        );
+
+        EventFilter {
+            event: self,
+            topics,
+            error: None,
+        }
+    }
+}
+
+impl EventFilter<'_> {
+    /// Matches logs whose indexed input `name` equals `value`.
+    pub fn topic(self, name: &str, value: Value) -> Self {
+        self.topic_any_of(name, vec![value])
+    }
+
+    /// Matches logs whose indexed input `name` equals any of `values`.
+    ///
+    /// Inputs of dynamic types also take [`Value::Hash`] values, which are
+    /// used as is. An empty list leaves the input unconstrained.
+    pub fn topic_any_of(mut self, name: &str, values: Vec<Value>) -> Self {
+        match self.encode_topic(name, values) {
+            Ok((index, topic)) => self.topics[index] = topic,
+            Err(err) => {
+                self.error.get_or_insert(err);
+            }
+        }
+
+        self
+    }
+
+    /// Returns the topics, without the unconstrained trailing entries.
+    ///
+    /// Fails on the first unknown or non-indexed input name, or value not
+    /// matching its input type.
+    pub fn build(self) -> Result<Vec<Topic>> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        let mut topics = self.topics;
+
+        while topics.last() == Some(&Topic::Any) {
+            topics.pop();
+        }
+
+        Ok(topics)
+    }
+
+    fn encode_topic(&self, name: &str, values: Vec<Value>) -> Result<(usize, Topic)> {
+        let (position, input) = self
+            .event
+            .inputs
+            .iter()
+            .filter(|input| input.indexed == Some(true))
+            .enumerate()
+            .find(|(_, input)| input.name == name)
+            .ok_or_else(|| anyhow!("event {} has no indexed input {:?}", self.event.name, name))?;
+
+        let index = if self.event.anonymous {
+            position
+        } else {
+            position + 1
+        };
+
+        let mut topics = values
+            .into_iter()
+            .map(|value| {
+                if let (Value::Hash(hash), true) =
+                    (&value, Event::is_encoded_to_keccak(&input.type_))
+                {
+                    return Ok(*hash);
+                }
+
+                if !value.matches(&input.type_) {
+                    return Err(anyhow!(
+                        "input {} is {}, got {}",
+                        name,
+                        input.type_,
+                        value.type_of()
+                    ));
+                }
+
+                Ok(topic_of(&value.normalize(&input.type_)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let topic = match topics.len() {
+            0 => Topic::Any,
+            1 => Topic::One(topics.remove(0)),
+            _ => Topic::OneOf(topics),
+        };
+
+        Ok((index, topic))
+    }
+}
+
+/// Returns the topic a value is logged as when indexed: the value itself for
+/// static types, the hash of its in-place encoding for dynamic ones and for
+/// arrays and tuples.
+pub(crate) fn topic_of(value: &Value) -> H256 {
+    match value {
+        Value::String(s) => keccak(s.as_bytes()),
+        Value::Bytes(bytes) => keccak(bytes),
+        Value::FixedArray(_, _) | Value::Array(_, _) | Value::Tuple(_) => {
+            let mut buf = vec![];
+            encode_in_place(value, &mut buf);
+            keccak(&buf)
+        }
+        _ => H256::from_slice(&Value::encode(std::slice::from_ref(value))),
+    }
+}
+
+// Encodes nested values without offsets or lengths, each element padded to
+// a multiple of 32 bytes, as Solidity does when hashing indexed params.
+fn encode_in_place(value: &Value, buf: &mut Vec<u8>) {
+    match value {
+        Value::String(s) => pad_into(s.as_bytes(), buf),
+        Value::Bytes(bytes) => pad_into(bytes, buf),
+        Value::FixedArray(values, _) | Value::Array(values, _) => {
+            for value in values {
+                encode_in_place(value, buf);
+            }
+        }
+        Value::Tuple(fields) => {
+            for (_, value) in fields {
+                encode_in_place(value, buf);
+            }
+        }
+        _ => buf.extend(Value::encode(std::slice::from_ref(value))),
+    }
+}
+
+fn pad_into(bytes: &[u8], buf: &mut Vec<u8>) {
+    buf.extend_from_slice(bytes);
+    buf.resize(buf.len() + (32 - bytes.len() % 32) % 32, 0);
+}
+
+fn keccak(bytes: &[u8]) -> H256 {
+    H256(keccak256(bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::{H160, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::{Abi, Type};
+
+    use super::*;
+
+    fn test_abi() -> Abi {
+        Abi::builder()
+            .event(
+                "Swap",
+                &[
+                    ("sender", "address", true),
+                    ("tick", "int24", true),
+                    ("memo", "string", true),
+                    ("amount", "uint256", false),
+                ],
+            )
+            .anonymous_event("Ping", &[("path", "(uint8,bytes)[]", true)])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn event_filter_topics() {
+        let abi = test_abi();
+        let swap = &abi.events[0];
+
+        let a = H160::repeat_byte(1);
+        let b = H160::repeat_byte(2);
+
+        let topics = swap
+            .filter()
+            .topic_any_of("sender", vec![Value::Address(a), Value::Address(b)])
+            .topic("tick", Value::Int(U256::MAX, 256))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            topics,
+            vec![
+                Topic::One(swap.topic()),
+                Topic::OneOf(vec![a.into(), b.into()]),
+                Topic::One(H256::repeat_byte(0xff)),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&topics).unwrap()[1][0],
+            serde_json::json!(format!("{:?}", H256::from(a)))
+        );
+
+        let topics = swap
+            .filter()
+            .topic("memo", Value::String("gm".to_string()))
+            .build()
+            .unwrap();
+
+        assert_eq!(topics[1], Topic::Any);
+        assert_eq!(topics[3], Topic::One(keccak(b"gm")));
+        assert_eq!(
+            serde_json::to_value(&topics).unwrap()[1],
+            serde_json::Value::Null
+        );
+
+        assert!(swap
+            .filter()
+            .topic("amount", Value::Uint(U256::one(), 256))
+            .build()
+            .is_err());
+        assert!(swap
+            .filter()
+            .topic("sender", Value::Bool(true))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn hash_nested_topics() {
+        let abi = test_abi();
+        let ping = &abi.events[1];
+
+        let path = Value::Array(
+            vec![Value::Tuple(vec![
+                ("".into(), Value::Uint(U256::from(7), 8)),
+                ("".into(), Value::Bytes(vec![0xab; 33].into())),
+            ])],
+            Type::Tuple(vec![]).into(),
+        );
+
+        let mut expected = vec![0u8; 32];
+        expected[31] = 7;
+        expected.extend(vec![0xab; 33]);
+        expected.extend(vec![0; 31]);
+
+        let topics = ping.filter().topic("path", path).build().unwrap();
+
+        assert_eq!(topics, vec![Topic::One(keccak(&expected))]);
+        assert_eq!(ping.filter().build().unwrap(), vec![]);
+    }
+}
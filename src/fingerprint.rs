@@ -0,0 +1,153 @@
+use ethereum_types::H256;
+use serde_json::Value;
+
+use crate::{keccak::keccak256, Abi};
+
+impl Abi {
+    /// Returns the canonical JSON of the ABI: compact, with entries sorted by
+    /// type, name and content, keys sorted, and nothing but what defines the
+    /// interface.
+    ///
+    /// How the ABI was laid out when parsed, unknown fields and gas
+    /// estimates are left out. Parameter names and `internalType`s are kept.
+    ///
+    /// ```
+    /// use ethereum_abi::Abi;
+    ///
+    /// let abi: Abi = serde_json::from_str(r#"[
+    ///     {"type": "event", "name": "Ping", "inputs": [], "anonymous": false},
+    ///     {"type": "function", "name": "ping", "inputs": [], "outputs": [], "stateMutability": "nonpayable", "gas": 21000}
+    /// ]"#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     abi.canonical_json(),
+    ///     r#"[{"anonymous":false,"inputs":[],"name":"Ping","type":"event"},{"inputs":[],"name":"ping","outputs":[],"stateMutability":"nonpayable","type":"function"}]"#
+    /// );
+    /// ```
+    pub fn canonical_json(&self) -> String {
+        let mut entries: Vec<_> = self
+            .definitions()
+            .map(|def| {
+                let mut entry = self.entry_json(def);
+
+                if let Some(entry) = entry.as_object_mut() {
+                    entry.remove("gas");
+                }
+
+                let mut json = String::new();
+                write_sorted(&entry, &mut json);
+                let ty = entry["type"].as_str().unwrap_or_default().to_string();
+                let name = entry["name"].as_str().unwrap_or_default().to_string();
+
+                (ty, name, json)
+            })
+            .collect();
+
+        entries.sort();
+
+        let entries: Vec<_> = entries.into_iter().map(|(_, _, json)| json).collect();
+
+        format!("[{}]", entries.join(","))
+    }
+
+    /// Returns the Keccak-256 hash of the ABI's [canonical
+    /// JSON](Abi::canonical_json).
+    ///
+    /// ABIs defining the same interface share a fingerprint however their
+    /// JSON was formatted or ordered, so it can key caches or tell whether
+    /// two deployments expose the same interface.
+    pub fn fingerprint(&self) -> H256 {
+        H256(keccak256(self.canonical_json().as_bytes()))
+    }
+}
+
+// Writes compact JSON with object keys sorted. `serde_json::Map` only keeps
+// them sorted while its `preserve_order` feature is off, and any crate in the
+// build can turn it on.
+fn write_sorted(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut fields: Vec<_> = map.iter().collect();
+            fields.sort_by_key(|(key, _)| *key);
+
+            out.push('{');
+            for (i, (key, value)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::from(key.as_str()).to_string());
+                out.push(':');
+                write_sorted(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_sorted(value, out);
+            }
+            out.push(']');
+        }
+        value => out.push_str(&value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::StateMutability;
+
+    #[test]
+    fn keys_sorted_at_every_level() {
+        let value = serde_json::json!({
+            "b": {"d": 1, "c": [{"f": "x\"y", "e": 2.5}]},
+            "a": null,
+        });
+
+        let mut json = String::new();
+        write_sorted(&value, &mut json);
+
+        assert_eq!(json, r#"{"a":null,"b":{"c":[{"e":2.5,"f":"x\"y"}],"d":1}}"#);
+    }
+
+    #[test]
+    fn same_interface_same_fingerprint() {
+        let a: Abi = serde_json::from_str(
+            r#"[
+                {"type": "function", "name": "f", "inputs": [{"name": "x", "type": "uint256"}], "outputs": [], "stateMutability": "view"},
+                {"type": "function", "name": "f", "inputs": [], "outputs": [], "stateMutability": "view"},
+                {"type": "error", "name": "E", "inputs": []},
+                {"type": "receive", "stateMutability": "payable"}
+            ]"#,
+        )
+        .unwrap();
+        let b: Abi = serde_json::from_str(
+            r#"[
+                {"stateMutability": "payable", "type": "receive"},
+                {"inputs": [], "name": "E", "type": "error", "signature": "0x92bbf6e8"},
+                {"inputs": [], "name": "f", "outputs": [], "stateMutability": "view", "type": "function", "gas": 300},
+                {"inputs": [{"name": "x", "type": "uint256"}], "name": "f", "outputs": [], "stateMutability": "view", "type": "function"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(a.canonical_json(), b.canonical_json());
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_eq!(
+            a.fingerprint(),
+            H256(keccak256(a.canonical_json().as_bytes()))
+        );
+
+        let mut c = a.clone();
+        c.functions[0].state_mutability = StateMutability::Pure;
+        assert_ne!(a.fingerprint(), c.fingerprint());
+
+        assert_ne!(a.fingerprint(), Abi::default().fingerprint());
+        assert_eq!(Abi::default().canonical_json(), "[]");
+    }
+}
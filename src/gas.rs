@@ -0,0 +1,201 @@
+use anyhow::Result;
+
+use crate::{
+    calldata::{args_of, layout},
+    values::field_key,
+    Function,
+};
+
+/// Hard fork whose calldata pricing applies.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Fork {
+    /// Before Istanbul: 4 gas per zero byte, 68 per non-zero byte.
+    Frontier,
+    /// Istanbul (EIP-2028): 4 gas per zero byte, 16 per non-zero byte.
+    Istanbul,
+    /// Prague (EIP-7623): Istanbul pricing, plus a floor of 10 gas per zero
+    /// byte and 40 per non-zero byte for transactions doing little
+    /// execution.
+    Prague,
+}
+
+/// Gas charged for a piece of calldata.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct CalldataGas {
+    /// Number of zero bytes.
+    pub zero_bytes: usize,
+    /// Number of non-zero bytes.
+    pub non_zero_bytes: usize,
+    /// Intrinsic calldata gas.
+    pub gas: u64,
+    /// EIP-7623 floor on calldata and execution gas together, zero before
+    /// Prague.
+    pub floor_gas: u64,
+}
+
+impl CalldataGas {
+    /// Returns the gas charged for the calldata along with the given
+    /// execution gas, taking the EIP-7623 floor into account.
+    pub fn with_execution(&self, execution_gas: u64) -> u64 {
+        (self.gas + execution_gas).max(self.floor_gas)
+    }
+}
+
+impl std::ops::Add for CalldataGas {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            zero_bytes: self.zero_bytes + other.zero_bytes,
+            non_zero_bytes: self.non_zero_bytes + other.non_zero_bytes,
+            gas: self.gas + other.gas,
+            floor_gas: self.floor_gas + other.floor_gas,
+        }
+    }
+}
+
+/// Computes the gas charged for calldata under the given fork's pricing.
+///
+/// Only the calldata part of the intrinsic gas is included, not the 21000
+/// base cost of a transaction.
+///
+/// ```
+/// use ethereum_abi::{calldata_gas, Fork};
+///
+/// let gas = calldata_gas(&[0xa9, 0x05, 0x9c, 0xbb, 0, 0], Fork::Prague);
+///
+/// assert_eq!(gas.gas, 4 * 16 + 2 * 4);
+/// assert_eq!(gas.with_execution(0), 4 * 40 + 2 * 10);
+/// ```
+pub fn calldata_gas(bytes: &[u8], fork: Fork) -> CalldataGas {
+    let zero_bytes = bytes.iter().filter(|b| **b == 0).count();
+    let non_zero_bytes = bytes.len() - zero_bytes;
+
+    let non_zero_cost = match fork {
+        Fork::Frontier => 68,
+        Fork::Istanbul | Fork::Prague => 16,
+    };
+
+    // EIP-7623 counts 4 tokens per non-zero byte, 10 gas per token.
+    let floor_gas = match fork {
+        Fork::Prague => 10 * (zero_bytes as u64 + 4 * non_zero_bytes as u64),
+        Fork::Frontier | Fork::Istanbul => 0,
+    };
+
+    CalldataGas {
+        zero_bytes,
+        non_zero_bytes,
+        gas: 4 * zero_bytes as u64 + non_zero_cost * non_zero_bytes as u64,
+        floor_gas,
+    }
+}
+
+impl Function {
+    /// Breaks down the calldata gas of a call to the function by parameter.
+    ///
+    /// The first entry is the selector, followed by each parameter keyed by
+    /// its name (or position if unnamed). A dynamic parameter is charged for
+    /// its offset in the head along with its data in the tail.
+    pub fn calldata_gas(&self, input: &[u8], fork: Fork) -> Result<Vec<(String, CalldataGas)>> {
+        let args = args_of(self, input)?;
+
+        let mut breakdown = vec![("selector".to_string(), calldata_gas(&input[..4], fork))];
+
+        for (i, (param, layout)) in self
+            .inputs
+            .iter()
+            .zip(layout(&self.inputs, args)?)
+            .enumerate()
+        {
+            let mut gas = calldata_gas(&args[layout.head], fork);
+
+            if let Some(tail) = layout.tail {
+                gas = gas + calldata_gas(&args[tail], fork);
+            }
+
+            breakdown.push((field_key(&param.name, i), gas));
+        }
+
+        Ok(breakdown)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::{H160, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::{Abi, StateMutability, Value};
+
+    use super::*;
+
+    #[test]
+    fn calldata_gas_by_fork() {
+        let data = [0, 0, 1, 2, 0];
+
+        assert_eq!(calldata_gas(&data, Fork::Frontier).gas, 3 * 4 + 2 * 68);
+        assert_eq!(calldata_gas(&data, Fork::Istanbul).gas, 3 * 4 + 2 * 16);
+
+        let prague = calldata_gas(&data, Fork::Prague);
+        assert_eq!(prague.floor_gas, 3 * 10 + 2 * 40);
+        assert_eq!(prague.with_execution(0), 110);
+        assert_eq!(prague.with_execution(1000), 1044);
+    }
+
+    #[test]
+    fn calldata_gas_breakdown() {
+        let abi = Abi::builder()
+            .function(
+                "f",
+                &[("to", "address"), ("data", "bytes"), ("", "uint256")],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .build()
+            .unwrap();
+        let function = &abi.functions[0];
+
+        let input = function
+            .encode_input(&[
+                Value::Address(H160::repeat_byte(0xff)),
+                Value::Bytes(vec![1; 33].into()),
+                Value::Uint(U256::from(1), 256),
+            ])
+            .unwrap();
+
+        let breakdown = function.calldata_gas(&input, Fork::Istanbul).unwrap();
+
+        let keys: Vec<_> = breakdown.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, vec!["selector", "to", "data", "2"]);
+
+        // Offset word, length word, then 33 bytes padded to 64.
+        assert_eq!(breakdown[2].1.non_zero_bytes, 1 + 1 + 33);
+        assert_eq!(breakdown[2].1.zero_bytes, 31 + 31 + 31);
+
+        let total = breakdown
+            .iter()
+            .fold(CalldataGas::default(), |total, (_, gas)| total + *gas);
+        assert_eq!(total, calldata_gas(&input, Fork::Istanbul));
+
+        assert!(function.calldata_gas(&input[4..], Fork::Istanbul).is_err());
+    }
+
+    #[test]
+    fn calldata_gas_of_oversized_params() {
+        let abi = Abi::builder()
+            .function(
+                "f",
+                &[("", "uint256[576460752303423488]")],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .build()
+            .unwrap();
+        let function = &abi.functions[0];
+
+        let mut input = function.method_id().to_vec();
+        input.extend_from_slice(&[0; 64]);
+
+        assert!(function.calldata_gas(&input, Fork::Istanbul).is_err());
+    }
+}
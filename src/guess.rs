@@ -0,0 +1,354 @@
+use anyhow::{anyhow, Result};
+use bytes::Bytes;
+use ethereum_types::{H160, U256};
+use std::sync::Arc;
+
+use crate::{Type, Value};
+
+/// A parameter value inferred from calldata without knowing its ABI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuessedParam {
+    /// Best-guess decoded value.
+    pub value: Value,
+    /// How confident the guess is, from 0 (wild guess) to 1 (certain).
+    pub confidence: f64,
+}
+
+// Nested dynamic values are only followed this deep.
+const MAX_DEPTH: usize = 4;
+
+/// Guesses the parameters of calldata whose function selector is unknown.
+///
+/// Returns the selector along with the guessed parameters. See
+/// [`guess_params`] for the heuristics used.
+pub fn guess_calldata(calldata: &[u8]) -> Result<([u8; 4], Vec<GuessedParam>)> {
+    if calldata.len() < 4 {
+        return Err(anyhow!("calldata is shorter than a function selector"));
+    }
+
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&calldata[0..4]);
+
+    Ok((selector, guess_params(&calldata[4..])?))
+}
+
+/// Guesses the types and values of ABI encoded parameters.
+///
+/// Head words pointing inside the input to a plausible length are followed
+/// and decoded as `bytes`, `string` or arrays, words holding a left-padded
+/// 20-byte value become addresses, right-padded words become `bytes32`,
+/// sign-extended words become `int256` and everything else is decoded as
+/// `uint256`. This is the same trick block explorers use, so expect it to be
+/// wrong every now and then.
+pub fn guess_params(data: &[u8]) -> Result<Vec<GuessedParam>> {
+    if !data.len().is_multiple_of(32) {
+        return Err(anyhow!(
+            "encoded parameters length {} is not a multiple of 32",
+            data.len()
+        ));
+    }
+
+    Ok(guess_sequence(data, 0, data.len(), 0))
+}
+
+// Guesses a head/tail encoded sequence located at data[base..end].
+fn guess_sequence(data: &[u8], base: usize, end: usize, depth: usize) -> Vec<GuessedParam> {
+    let mut params = vec![];
+    let mut head_end = end;
+    let mut at = base;
+
+    while at + 32 <= head_end {
+        let word = &data[at..(at + 32)];
+
+        match guess_dynamic(data, base, end, at, word, depth) {
+            Some((param, offset)) => {
+                head_end = head_end.min(base + offset);
+                params.push(param);
+            }
+            None => params.push(guess_word(word)),
+        }
+
+        at += 32;
+    }
+
+    params
+}
+
+// Tries to interpret a head word as an offset to dynamic data.
+fn guess_dynamic(
+    data: &[u8],
+    base: usize,
+    end: usize,
+    at: usize,
+    word: &[u8],
+    depth: usize,
+) -> Option<(GuessedParam, usize)> {
+    if depth >= MAX_DEPTH {
+        return None;
+    }
+
+    let offset = small_uint(word)?;
+    let start = base.checked_add(offset)?;
+
+    // Dynamic data lives after the head word pointing to it.
+    if offset == 0 || !offset.is_multiple_of(32) || start <= at || start + 32 > end {
+        return None;
+    }
+
+    let len = small_uint(&data[start..(start + 32)])?;
+    let content = start + 32;
+
+    if let Some(value) = guess_bytes(data, content, end, len) {
+        let confidence = if matches!(value, Value::String(_)) {
+            0.7
+        } else {
+            0.8
+        };
+
+        return Some((GuessedParam { value, confidence }, offset));
+    }
+
+    let array_end = content.checked_add(len.checked_mul(32)?)?;
+    if array_end > end {
+        return None;
+    }
+
+    let elements = guess_array_elements(data, content, end, len, depth);
+    let (values, confidence) = unify(elements);
+    let elem_ty = values
+        .first()
+        .map(Value::type_of)
+        .unwrap_or(Type::Uint(256));
+
+    Some((
+        GuessedParam {
+            value: Value::Array(values, Arc::new(elem_ty)),
+            confidence: confidence * 0.9,
+        },
+        offset,
+    ))
+}
+
+// Interprets `len` bytes at data[content..] as bytes or string, requiring
+// zeroed padding up to the next word boundary.
+fn guess_bytes(data: &[u8], content: usize, end: usize, len: usize) -> Option<Value> {
+    let padded_len = len.checked_add(31)? / 32 * 32;
+    if content + padded_len > end {
+        return None;
+    }
+
+    let (bytes, padding) = data[content..(content + padded_len)].split_at(len);
+    if padding.iter().any(|b| *b != 0) {
+        return None;
+    }
+
+    // A word-aligned length with zero padding is just as likely to be an
+    // array, leave it to the caller unless the content looks like text.
+    let text = std::str::from_utf8(bytes)
+        .ok()
+        .filter(|s| !s.is_empty() && s.chars().all(|c| !c.is_control() || c.is_whitespace()));
+
+    match text {
+        Some(s) => Some(Value::String(s.to_string())),
+        None if !len.is_multiple_of(32) || len == 0 => {
+            Some(Value::Bytes(Bytes::copy_from_slice(bytes)))
+        }
+        None => None,
+    }
+}
+
+fn guess_array_elements(
+    data: &[u8],
+    content: usize,
+    end: usize,
+    len: usize,
+    depth: usize,
+) -> Vec<GuessedParam> {
+    let first = data
+        .get(content..(content + 32))
+        .and_then(small_uint)
+        .unwrap_or(0);
+
+    // Arrays of dynamic elements start with an offset table whose first entry
+    // points right past the table itself.
+    if len > 0 && first == len * 32 {
+        let elements = guess_sequence(data, content, end, depth + 1);
+        if elements.len() == len {
+            return elements;
+        }
+    }
+
+    (0..len)
+        .map(|i| guess_word(&data[(content + i * 32)..(content + i * 32 + 32)]))
+        .collect()
+}
+
+// Makes all elements share the same type, falling back to uint256 words
+// when static guesses disagree.
+fn unify(elements: Vec<GuessedParam>) -> (Vec<Value>, f64) {
+    let confidence = elements.iter().map(|e| e.confidence).fold(1.0, f64::min);
+
+    let same_type = elements
+        .windows(2)
+        .all(|w| w[0].value.type_of() == w[1].value.type_of());
+
+    if same_type {
+        return (elements.into_iter().map(|e| e.value).collect(), confidence);
+    }
+
+    let values = elements
+        .into_iter()
+        .map(|e| {
+            let encoded = Value::encode(std::slice::from_ref(&e.value));
+            match e.value {
+                Value::Uint(..) | Value::Int(..) | Value::Address(_) | Value::FixedBytes(_) => {
+                    Value::Uint(U256::from_big_endian(&encoded[0..32]), 256)
+                }
+                value => value,
+            }
+        })
+        .collect();
+
+    (values, confidence * 0.5)
+}
+
+fn guess_word(word: &[u8]) -> GuessedParam {
+    let uint = U256::from_big_endian(word);
+
+    let leading_zeros = word.iter().take_while(|b| **b == 0).count();
+    let trailing_zeros = word.iter().rev().take_while(|b| **b == 0).count();
+
+    let (value, confidence) = if word[0] == 0xff {
+        (Value::Int(uint, 256), 0.6)
+    } else if (12..=14).contains(&leading_zeros) {
+        (Value::Address(H160::from_slice(&word[12..32])), 0.8)
+    } else if leading_zeros == 0 && trailing_zeros >= 4 {
+        (Value::FixedBytes(word.to_vec()), 0.6)
+    } else if uint <= U256::one() {
+        (Value::Uint(uint, 256), 0.5)
+    } else {
+        (Value::Uint(uint, 256), 0.7)
+    };
+
+    GuessedParam { value, confidence }
+}
+
+fn small_uint(word: &[u8]) -> Option<usize> {
+    // Anything above 2^32 can't be an offset or length into real calldata.
+    if word[0..28].iter().any(|b| *b != 0) {
+        return None;
+    }
+
+    Some(U256::from_big_endian(word).as_usize())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn values(params: Vec<GuessedParam>) -> Vec<Value> {
+        params.into_iter().map(|p| p.value).collect()
+    }
+
+    #[test]
+    fn guess_transfer() {
+        let to =
+            H160::from_slice(&hex::decode("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap());
+        let amount = U256::exp10(18);
+
+        let mut calldata = vec![0xa9, 0x05, 0x9c, 0xbb];
+        calldata.extend(Value::encode(&[
+            Value::Address(to),
+            Value::Uint(amount, 256),
+        ]));
+
+        let (selector, params) = guess_calldata(&calldata).unwrap();
+
+        assert_eq!(selector, [0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(
+            values(params),
+            vec![Value::Address(to), Value::Uint(amount, 256)]
+        );
+    }
+
+    #[test]
+    fn guess_dynamic_params() {
+        let input = Value::encode(&[
+            Value::Uint(U256::from(5), 256),
+            Value::String("hello".to_string()),
+            Value::Bytes(vec![1, 2, 3].into()),
+            Value::Array(
+                vec![
+                    Value::Uint(U256::from(1000), 256),
+                    Value::Uint(U256::from(2000), 256),
+                ],
+                Type::Uint(256).into(),
+            ),
+        ]);
+
+        assert_eq!(
+            values(guess_params(&input).unwrap()),
+            vec![
+                Value::Uint(U256::from(5), 256),
+                Value::String("hello".to_string()),
+                Value::Bytes(vec![1, 2, 3].into()),
+                Value::Array(
+                    vec![
+                        Value::Uint(U256::from(1000), 256),
+                        Value::Uint(U256::from(2000), 256),
+                    ],
+                    Type::Uint(256).into(),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn guess_nested_dynamic_array() {
+        let input = Value::encode(&[Value::Array(
+            vec![
+                Value::Bytes(vec![0xaa; 40].into()),
+                Value::Bytes(vec![0xbb; 3].into()),
+            ],
+            Type::Bytes.into(),
+        )]);
+
+        assert_eq!(
+            values(guess_params(&input).unwrap()),
+            vec![Value::Array(
+                vec![
+                    Value::Bytes(vec![0xaa; 40].into()),
+                    Value::Bytes(vec![0xbb; 3].into())
+                ],
+                Type::Bytes.into(),
+            )]
+        );
+    }
+
+    #[test]
+    fn guess_static_words() {
+        let mut bytes32 = [0u8; 32];
+        bytes32[0..4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+        let input = Value::encode(&[
+            Value::FixedBytes(bytes32.to_vec()),
+            Value::Int(U256::MAX, 256),
+        ]);
+
+        assert_eq!(
+            values(guess_params(&input).unwrap()),
+            vec![
+                Value::FixedBytes(bytes32.to_vec()),
+                Value::Int(U256::MAX, 256)
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_length() {
+        assert!(guess_params(&[0u8; 33]).is_err());
+        assert!(guess_calldata(&[0u8; 3]).is_err());
+    }
+}
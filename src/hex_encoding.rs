@@ -0,0 +1,93 @@
+use anyhow::{anyhow, Result};
+
+/// Decodes a hex string, with or without `0x` prefix.
+///
+/// Uses SIMD instructions when the CPU supports them, which makes a
+/// difference on the long calldata and log data indexers go through.
+pub fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    let digits = strip_prefix(s).as_bytes();
+
+    if !digits.len().is_multiple_of(2) {
+        return Err(anyhow!("invalid hex: odd number of digits"));
+    }
+
+    let mut bytes = vec![0u8; digits.len() / 2];
+    faster_hex::hex_decode(digits, &mut bytes).map_err(|err| anyhow!("invalid hex: {}", err))?;
+
+    Ok(bytes)
+}
+
+/// Decodes a hex string, with or without `0x` prefix, into a slice of the
+/// exact decoded length.
+pub(crate) fn decode_hex_to_slice(s: &str, bytes: &mut [u8]) -> Result<()> {
+    let digits = strip_prefix(s).as_bytes();
+
+    if digits.len() != bytes.len() * 2 {
+        return Err(anyhow!(
+            "invalid hex: expected {} bytes, got {} digits",
+            bytes.len(),
+            digits.len()
+        ));
+    }
+
+    faster_hex::hex_decode(digits, bytes).map_err(|err| anyhow!("invalid hex: {}", err))
+}
+
+/// Encodes bytes as a `0x` prefixed lowercase hex string.
+pub fn encode_hex(bytes: impl AsRef<[u8]>) -> String {
+    let bytes = bytes.as_ref();
+    let mut out = vec![0u8; 2 + bytes.len() * 2];
+
+    out[..2].copy_from_slice(b"0x");
+    faster_hex::hex_encode(bytes, &mut out[2..]).expect("output has room for every digit");
+
+    String::from_utf8(out).expect("hex digits are ASCII")
+}
+
+fn strip_prefix(s: &str) -> &str {
+    s.strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            decode_hex("0xa9059cbb").unwrap(),
+            vec![0xa9, 0x05, 0x9c, 0xbb]
+        );
+        assert_eq!(
+            decode_hex("A9059CBB").unwrap(),
+            vec![0xa9, 0x05, 0x9c, 0xbb]
+        );
+        assert_eq!(decode_hex("0X").unwrap(), Vec::<u8>::new());
+
+        // Long enough to go through the vectorized path.
+        let long = "00112233445566778899aabbccddeeff".repeat(5);
+        assert_eq!(decode_hex(&long).unwrap().len(), 80);
+
+        assert!(decode_hex("0xa9059cb").is_err());
+        assert!(decode_hex("0xzz059cbb").is_err());
+        assert!(decode_hex(&format!("{}zz", long)).is_err());
+
+        let mut selector = [0u8; 4];
+        decode_hex_to_slice("0xa9059cbb", &mut selector).unwrap();
+        assert_eq!(selector, [0xa9, 0x05, 0x9c, 0xbb]);
+        assert!(decode_hex_to_slice("0xa9059cbb00", &mut selector).is_err());
+    }
+
+    #[test]
+    fn encode() {
+        assert_eq!(encode_hex([0xa9, 0x05, 0x9c, 0xbb]), "0xa9059cbb");
+        assert_eq!(encode_hex([]), "0x");
+
+        let long = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(decode_hex(&encode_hex(&long)).unwrap(), long);
+    }
+}
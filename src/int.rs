@@ -0,0 +1,274 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::U256;
+use std::{cmp::Ordering, convert::TryFrom, str::FromStr};
+
+use crate::Value;
+
+/// Signed 256 bit integer, stored in two's complement.
+///
+/// This is how `int<M>` values are laid out in ABI encoded data once sign
+/// extended to 256 bits, so decoded [`Value::Int`] words convert to and from
+/// it without loss.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct I256(U256);
+
+impl I256 {
+    /// Smallest value, `-2^255`.
+    pub const MIN: I256 = I256(U256([0, 0, 0, 1 << 63]));
+    /// Largest value, `2^255 - 1`.
+    pub const MAX: I256 = I256(U256([u64::MAX, u64::MAX, u64::MAX, u64::MAX >> 1]));
+
+    /// Zero.
+    pub fn zero() -> Self {
+        I256(U256::zero())
+    }
+
+    /// Reads a 256 bit two's complement word.
+    pub fn from_raw(raw: U256) -> Self {
+        I256(raw)
+    }
+
+    /// Reads the low `bits` bits of `raw` as a two's complement integer of
+    /// that width, sign extending it to 256 bits.
+    pub fn sign_extend(raw: U256, bits: usize) -> Self {
+        if bits == 0 || bits >= 256 {
+            return I256(raw);
+        }
+
+        let mask = (U256::one() << bits) - 1;
+        let low = raw & mask;
+
+        if low.bit(bits - 1) {
+            I256(low | !mask)
+        } else {
+            I256(low)
+        }
+    }
+
+    /// Builds a value from its sign and magnitude, failing if it is out of
+    /// range.
+    pub fn from_sign_and_abs(negative: bool, abs: U256) -> Result<Self> {
+        let min_abs = U256::one() << 255;
+
+        if negative && abs > min_abs || !negative && abs >= min_abs {
+            return Err(anyhow!("integer out of range"));
+        }
+
+        Ok(if negative { -I256(abs) } else { I256(abs) })
+    }
+
+    /// Returns the 256 bit two's complement word.
+    pub fn into_raw(self) -> U256 {
+        self.0
+    }
+
+    /// Returns whether the value is below zero.
+    pub fn is_negative(&self) -> bool {
+        self.0.bit(255)
+    }
+
+    /// Returns whether the value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Returns the magnitude of the value, which for [`I256::MIN`] does not
+    /// fit in an `I256` itself.
+    pub fn unsigned_abs(&self) -> U256 {
+        if self.is_negative() {
+            (-*self).0
+        } else {
+            self.0
+        }
+    }
+
+    /// Returns whether the value fits in a signed integer of `bits` bits.
+    pub fn fits(&self, bits: usize) -> bool {
+        bits >= 256 || *self == I256::sign_extend(self.0, bits)
+    }
+}
+
+impl std::ops::Neg for I256 {
+    type Output = I256;
+
+    /// Negates the value, wrapping around for [`I256::MIN`].
+    fn neg(self) -> I256 {
+        I256((!self.0).overflowing_add(U256::one()).0)
+    }
+}
+
+impl Ord for I256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => self.0.cmp(&other.0),
+        }
+    }
+}
+
+impl PartialOrd for I256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for I256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_negative() {
+            write!(f, "-")?;
+        }
+
+        write!(f, "{}", self.unsigned_abs())
+    }
+}
+
+impl FromStr for I256 {
+    type Err = anyhow::Error;
+
+    /// Parses an optionally signed decimal integer.
+    fn from_str(s: &str) -> Result<Self> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+
+        let abs = U256::from_dec_str(digits).map_err(|_| anyhow!("invalid integer {:?}", s))?;
+
+        I256::from_sign_and_abs(negative, abs)
+    }
+}
+
+impl From<i64> for I256 {
+    fn from(v: i64) -> Self {
+        I256::from(v as i128)
+    }
+}
+
+impl From<i128> for I256 {
+    fn from(v: i128) -> Self {
+        let abs = I256(U256::from(v.unsigned_abs()));
+
+        if v < 0 {
+            -abs
+        } else {
+            abs
+        }
+    }
+}
+
+impl TryFrom<I256> for i128 {
+    type Error = anyhow::Error;
+
+    fn try_from(v: I256) -> Result<Self> {
+        if !v.fits(128) {
+            return Err(anyhow!("{} does not fit in i128", v));
+        }
+
+        Ok(v.0.low_u128() as i128)
+    }
+}
+
+impl TryFrom<I256> for i64 {
+    type Error = anyhow::Error;
+
+    fn try_from(v: I256) -> Result<Self> {
+        if !v.fits(64) {
+            return Err(anyhow!("{} does not fit in i64", v));
+        }
+
+        Ok(v.0.low_u64() as i64)
+    }
+}
+
+impl Value {
+    /// Returns the integer of an `int<M>` value, sign extended from `M` bits.
+    pub fn as_int(&self) -> Option<I256> {
+        match self {
+            Value::Int(v, size) => Some(I256::sign_extend(*v, *size)),
+            _ => None,
+        }
+    }
+}
+
+impl From<I256> for Value {
+    /// Builds an `int256` value.
+    fn from(v: I256) -> Self {
+        Value::Int(v.into_raw(), 256)
+    }
+}
+
+impl TryFrom<Value> for I256 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        value
+            .as_int()
+            .ok_or_else(|| anyhow!("expected int, got {}", value.type_of()))
+    }
+}
+
+impl TryFrom<Value> for i64 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        i64::try_from(I256::try_from(value)?)
+    }
+}
+
+impl TryFrom<Value> for i128 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        i128::try_from(I256::try_from(value)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn signed_integers() {
+        let minus_one = I256::from(-1i64);
+
+        assert_eq!(minus_one.into_raw(), U256::MAX);
+        assert_eq!(minus_one.to_string(), "-1");
+        assert!(minus_one < I256::zero());
+        assert!(I256::MIN < minus_one);
+        assert!(I256::from(2i64) > I256::from(1i64));
+        assert_eq!(I256::MIN.unsigned_abs(), U256::one() << 255);
+        assert_eq!(
+            I256::MIN.to_string(),
+            "-57896044618658097711785492504343953926634992332820282019728792003956564819968"
+        );
+        assert_eq!(
+            "-57896044618658097711785492504343953926634992332820282019728792003956564819968"
+                .parse::<I256>()
+                .unwrap(),
+            I256::MIN
+        );
+        assert!(
+            "57896044618658097711785492504343953926634992332820282019728792003956564819968"
+                .parse::<I256>()
+                .is_err()
+        );
+        assert_eq!(i64::try_from(I256::from(i64::MIN)).unwrap(), i64::MIN);
+        assert!(i64::try_from(I256::from(i64::MAX as i128 + 1)).is_err());
+    }
+
+    #[test]
+    fn sign_extension() {
+        // int8 -1 stored without sign extension.
+        let value = Value::Int(U256::from(0xff), 8);
+
+        assert_eq!(value.as_int(), Some(I256::from(-1i64)));
+        assert_eq!(i64::try_from(value).unwrap(), -1);
+
+        assert_eq!(I256::sign_extend(U256::from(0x7f), 8), I256::from(127i64));
+        assert!(I256::from(-128i64).fits(8));
+        assert!(!I256::from(128i64).fits(8));
+    }
+}
@@ -0,0 +1,499 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::{H160, U256};
+use std::sync::Arc;
+
+use crate::{
+    address::parse_address, decode_hex, units::parse_number, Abi, Function, Type, Value, I256,
+};
+
+impl Abi {
+    /// Encodes a call to the function named `name` from JSON arguments.
+    ///
+    /// Arguments are given either as an array in declaration order or as an
+    /// object keyed by input name, and coerced to the declared input types.
+    /// Overloads are tried in order; the call fails if no overload or more
+    /// than one accepts the arguments.
+    pub fn encode_call_from_json(&self, name: &str, args: &serde_json::Value) -> Result<Vec<u8>> {
+        let overloads = self.functions_by_name(name);
+
+        if overloads.is_empty() {
+            return Err(anyhow!("ABI function not found: {}", name));
+        }
+
+        let mut encoded = vec![];
+        let mut errors = vec![];
+
+        for f in &overloads {
+            match f.encode_input_from_json(args) {
+                Ok(input) => encoded.push((f.signature(), input)),
+                Err(err) => errors.push(format!("{}: {}", f.signature(), err)),
+            }
+        }
+
+        match encoded.len() {
+            1 => Ok(encoded.remove(0).1),
+            0 if overloads.len() == 1 => Err(anyhow!("{}", errors.remove(0))),
+            0 => Err(anyhow!(
+                "no overload of {} accepts the arguments: {}",
+                name,
+                errors.join("; ")
+            )),
+            _ => Err(anyhow!(
+                "ambiguous call to {}, candidates: {}",
+                name,
+                encoded
+                    .into_iter()
+                    .map(|(signature, _)| signature)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+}
+
+impl Function {
+    /// Encodes the function's selector followed by arguments coerced from
+    /// JSON, given as an array or as an object keyed by input name.
+    pub fn encode_input_from_json(&self, args: &serde_json::Value) -> Result<Vec<u8>> {
+        let args = match args {
+            serde_json::Value::Array(args) => {
+                if args.len() != self.inputs.len() {
+                    return Err(anyhow!(
+                        "expected {} arguments, got {}",
+                        self.inputs.len(),
+                        args.len()
+                    ));
+                }
+                args.iter().collect::<Vec<_>>()
+            }
+
+            serde_json::Value::Object(args) => self
+                .inputs
+                .iter()
+                .map(|param| {
+                    args.get(&param.name)
+                        .ok_or_else(|| anyhow!("missing argument {:?}", param.name))
+                })
+                .collect::<Result<_>>()?,
+
+            _ => return Err(anyhow!("arguments must be a JSON array or object")),
+        };
+
+        let values = self
+            .inputs
+            .iter()
+            .zip(args)
+            .enumerate()
+            .map(|(i, (param, arg))| {
+                let path = if param.name.is_empty() {
+                    format!("[{}]", i)
+                } else {
+                    param.name.clone()
+                };
+
+                coerce(&param.type_, arg, &path)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.encode_input(&values)
+    }
+}
+
+impl Value {
+    /// Coerces a JSON value into a value of the given type.
+    ///
+    /// Integers are read from JSON numbers or from decimal or `0x` hex
+    /// strings, fixed point numbers from decimal strings, addresses and bytes
+    /// from hex strings, arrays from JSON arrays and tuples from arrays or from
+    /// objects keyed by field name. Errors name the path of the offending
+    /// value, e.g. `orders[1].maker: address must be 20 bytes, got 2`.
+    ///
+    /// ```
+    /// use ethereum_abi::{Type, Value};
+    /// use ethereum_types::U256;
+    ///
+    /// let ty = Type::Array(Box::new(Type::Uint(256)));
+    /// let value = Value::from_json(&ty, &serde_json::json!([1, "0x10"])).unwrap();
+    ///
+    /// assert_eq!(
+    ///     value,
+    ///     Value::Array(
+    ///         vec![Value::Uint(U256::from(1), 256), Value::Uint(U256::from(16), 256)],
+    ///         Type::Uint(256).into(),
+    ///     )
+    /// );
+    /// ```
+    pub fn from_json(ty: &Type, json: &serde_json::Value) -> Result<Value> {
+        coerce(ty, json, "")
+    }
+}
+
+// Coercion error already prefixed with the path of the offending value.
+#[derive(Debug)]
+struct PathError(String);
+
+impl std::fmt::Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for PathError {}
+
+fn coerce(ty: &Type, json: &serde_json::Value, path: &str) -> Result<Value> {
+    coerce_value(ty, json, path).map_err(|err| {
+        if path.is_empty() || err.is::<PathError>() {
+            err
+        } else {
+            PathError(format!("{}: {}", path, err)).into()
+        }
+    })
+}
+
+fn coerce_value(ty: &Type, json: &serde_json::Value, path: &str) -> Result<Value> {
+    use serde_json::Value as Json;
+
+    let mismatch = || anyhow!("expected {}, got {}", ty, json);
+
+    match ty {
+        Type::Uint(size) => {
+            let (negative, v) = parse_number(&number_text(json).ok_or_else(mismatch)?, 0)?;
+
+            if negative && !v.is_zero() {
+                return Err(anyhow!("{} cannot be negative", ty));
+            }
+            if v.bits() > *size {
+                return Err(anyhow!("{} does not fit in {}", v, ty));
+            }
+
+            Ok(Value::Uint(v, *size))
+        }
+
+        Type::Int(size) => {
+            let (negative, v) = parse_number(&number_text(json).ok_or_else(mismatch)?, 0)?;
+
+            Ok(Value::Int(to_signed(negative, v, *size, ty)?, *size))
+        }
+
+        Type::Fixed(size, decimals) => {
+            let (negative, v) = parse_number(&number_text(json).ok_or_else(mismatch)?, *decimals)?;
+
+            Ok(Value::Fixed(
+                to_signed(negative, v, *size, ty)?,
+                *size,
+                *decimals,
+            ))
+        }
+
+        Type::Ufixed(size, decimals) => {
+            let (negative, v) = parse_number(&number_text(json).ok_or_else(mismatch)?, *decimals)?;
+
+            if negative && !v.is_zero() {
+                return Err(anyhow!("{} cannot be negative", ty));
+            }
+            if v.bits() > *size {
+                return Err(anyhow!("value does not fit in {}", ty));
+            }
+
+            Ok(Value::Ufixed(v, *size, *decimals))
+        }
+
+        Type::Address => Ok(Value::Address(parse_address(
+            json.as_str().ok_or_else(mismatch)?,
+        )?)),
+
+        Type::Bool => match json {
+            Json::Bool(b) => Ok(Value::Bool(*b)),
+            Json::String(s) if s == "true" => Ok(Value::Bool(true)),
+            Json::String(s) if s == "false" => Ok(Value::Bool(false)),
+            _ => Err(mismatch()),
+        },
+
+        Type::Function => {
+            let bytes = hex_bytes(json).ok_or_else(mismatch)??;
+
+            if bytes.len() != 24 {
+                return Err(anyhow!("function must be 24 bytes, got {}", bytes.len()));
+            }
+
+            let mut selector = [0u8; 4];
+            selector.copy_from_slice(&bytes[20..]);
+
+            Ok(Value::Function(H160::from_slice(&bytes[..20]), selector))
+        }
+
+        Type::FixedBytes(size) => {
+            let bytes = hex_bytes(json).ok_or_else(mismatch)??;
+
+            if bytes.len() != *size {
+                return Err(anyhow!(
+                    "{} must be {} bytes, got {}",
+                    ty,
+                    size,
+                    bytes.len()
+                ));
+            }
+
+            Ok(Value::FixedBytes(bytes))
+        }
+
+        Type::String => match json {
+            Json::String(s) => Ok(Value::String(s.clone())),
+            _ => Err(mismatch()),
+        },
+
+        Type::Bytes => Ok(Value::Bytes(hex_bytes(json).ok_or_else(mismatch)??.into())),
+
+        Type::FixedArray(item_ty, size) => {
+            let items = json.as_array().ok_or_else(mismatch)?;
+
+            if items.len() != *size {
+                return Err(anyhow!(
+                    "{} must have {} elements, got {}",
+                    ty,
+                    size,
+                    items.len()
+                ));
+            }
+
+            Ok(Value::FixedArray(
+                coerce_items(item_ty, items, path)?,
+                Arc::new((**item_ty).clone()),
+            ))
+        }
+
+        Type::Array(item_ty) => {
+            let items = json.as_array().ok_or_else(mismatch)?;
+
+            Ok(Value::Array(
+                coerce_items(item_ty, items, path)?,
+                Arc::new((**item_ty).clone()),
+            ))
+        }
+
+        Type::Tuple(fields) | Type::Struct(_, fields) => {
+            let items: Vec<&Json> = match json {
+                Json::Array(items) => {
+                    if items.len() != fields.len() {
+                        return Err(anyhow!(
+                            "{} must have {} fields, got {}",
+                            ty,
+                            fields.len(),
+                            items.len()
+                        ));
+                    }
+                    items.iter().collect()
+                }
+
+                Json::Object(items) => fields
+                    .iter()
+                    .map(|(name, _)| {
+                        items
+                            .get(name)
+                            .ok_or_else(|| anyhow!("missing field {:?}", name))
+                    })
+                    .collect::<Result<_>>()?,
+
+                _ => return Err(mismatch()),
+            };
+
+            let values = fields
+                .iter()
+                .zip(items)
+                .enumerate()
+                .map(|(i, ((name, field_ty), item))| {
+                    let field_path = if name.is_empty() {
+                        format!("{}[{}]", path, i)
+                    } else if path.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{}.{}", path, name)
+                    };
+
+                    Ok((name.clone(), coerce(field_ty, item, &field_path)?))
+                })
+                .collect::<Result<_>>()?;
+
+            Ok(Value::Tuple(values))
+        }
+    }
+}
+
+fn coerce_items(ty: &Type, items: &[serde_json::Value], path: &str) -> Result<Vec<Value>> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| coerce(ty, item, &format!("{}[{}]", path, i)))
+        .collect()
+}
+
+fn number_text(json: &serde_json::Value) -> Option<String> {
+    match json {
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::String(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn hex_bytes(json: &serde_json::Value) -> Option<Result<Vec<u8>>> {
+    let s = json.as_str()?;
+
+    Some(decode_hex(s).map_err(|err| anyhow!("invalid hex {:?}: {}", s, err)))
+}
+
+/// Converts a sign and magnitude into a two's complement value, failing if it
+/// does not fit in a signed integer of `size` bits.
+pub(crate) fn to_signed(negative: bool, v: U256, size: usize, ty: &Type) -> Result<U256> {
+    I256::from_sign_and_abs(negative, v)
+        .ok()
+        .filter(|v| v.fits(size))
+        .map(I256::into_raw)
+        .ok_or_else(|| anyhow!("value does not fit in {}", ty))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::params::parse_type_str;
+
+    use super::*;
+
+    #[test]
+    fn coerce_json_values() {
+        let ty = parse_type_str("(address,uint256,int8,bytes4,bool[2],ufixed128x18)").unwrap();
+
+        let value = Value::from_json(
+            &ty,
+            &json!([
+                "0x1111111111111111111111111111111111111111",
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                -128,
+                "0xa9059cbb",
+                [true, "false"],
+                "1.5"
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            value,
+            Value::Tuple(vec![
+                ("".into(), Value::Address(H160::repeat_byte(0x11))),
+                ("".into(), Value::Uint(U256::MAX, 256)),
+                ("".into(), Value::Int(U256::MAX - U256::from(127), 8)),
+                ("".into(), Value::FixedBytes(vec![0xa9, 0x05, 0x9c, 0xbb])),
+                (
+                    "".into(),
+                    Value::FixedArray(
+                        vec![Value::Bool(true), Value::Bool(false)],
+                        Type::Bool.into()
+                    )
+                ),
+                ("".into(), Value::Ufixed(U256::exp10(17) * 15, 128, 18)),
+            ])
+        );
+
+        assert!(Value::from_json(&Type::Int(8), &json!(128)).is_err());
+        assert!(Value::from_json(&Type::Uint(8), &json!("0x100")).is_err());
+        assert!(Value::from_json(&Type::Uint(256), &json!(-1)).is_err());
+        assert!(Value::from_json(&Type::Uint(256), &json!(1.5)).is_err());
+        assert!(Value::from_json(&Type::Ufixed(256, 80), &json!("0x1")).is_err());
+        assert_eq!(
+            Value::from_json(&Type::Ufixed(256, 80), &json!("0x0")).unwrap(),
+            Value::Ufixed(U256::zero(), 256, 80)
+        );
+        assert!(Value::from_json(&Type::Address, &json!("0x1234")).is_err());
+    }
+
+    #[test]
+    fn coercion_error_paths() {
+        let ty = parse_type_str("(address,uint8)[]").unwrap();
+        let err = |json| Value::from_json(&ty, &json).unwrap_err().to_string();
+
+        assert_eq!(
+            err(json!([
+                ["0x1111111111111111111111111111111111111111", 1],
+                ["0x11", 1]
+            ])),
+            "[1][0]: address must be 20 bytes, got 1"
+        );
+        assert_eq!(
+            err(json!([["0x1111111111111111111111111111111111111111", 256]])),
+            "[0][1]: 256 does not fit in uint8"
+        );
+        assert_eq!(
+            err(json!([[true, 1]])),
+            "[0][0]: expected address, got true"
+        );
+        assert_eq!(err(json!({})), "expected (address,uint8)[], got {}");
+
+        let ty = Type::Tuple(vec![
+            ("maker".into(), Type::Address),
+            ("fee".into(), Type::Uint(8)),
+        ]);
+        let err = Value::from_json(&ty, &json!({"maker": "0x"})).unwrap_err();
+
+        assert_eq!(err.to_string(), "missing field \"fee\"");
+    }
+
+    #[test]
+    fn encode_call_from_json_args() {
+        let abi: Abi = serde_json::from_value(json!([{
+            "type": "function",
+            "name": "swap",
+            "stateMutability": "nonpayable",
+            "inputs": [
+                {
+                    "name": "order",
+                    "type": "tuple",
+                    "components": [
+                        {"name": "maker", "type": "address"},
+                        {"name": "amount", "type": "uint256"}
+                    ]
+                },
+                {"name": "data", "type": "bytes"}
+            ],
+            "outputs": []
+        }]))
+        .unwrap();
+
+        let by_position = abi
+            .encode_call_from_json(
+                "swap",
+                &json!([["0x1111111111111111111111111111111111111111", 5], "0xdead"]),
+            )
+            .unwrap();
+
+        let by_name = abi
+            .encode_call_from_json(
+                "swap",
+                &json!({
+                    "order": {
+                        "maker": "0x1111111111111111111111111111111111111111",
+                        "amount": "5"
+                    },
+                    "data": "0xdead"
+                }),
+            )
+            .unwrap();
+
+        assert_eq!(by_position, by_name);
+
+        let (_, params) = abi.decode_input_from_slice(&by_name).unwrap();
+        assert_eq!(params[1], Value::Bytes(vec![0xde, 0xad].into()));
+
+        let err = abi
+            .encode_call_from_json(
+                "swap",
+                &json!({"order": {"maker": "0x11", "amount": 5}, "data": "0x"}),
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "swap((address,uint256),bytes): order.maker: address must be 20 bytes, got 1"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+/// Keccak-256 hash of the given bytes.
+///
+/// Uses `tiny-keccak`, or the assembly implementations of `keccak-asm` with
+/// the `asm-keccak` feature.
+pub(crate) fn keccak256(data: &[u8]) -> [u8; 32] {
+    #[cfg(feature = "asm-keccak")]
+    {
+        use keccak_asm::Keccak256;
+
+        Keccak256::digest(data).into()
+    }
+
+    #[cfg(not(feature = "asm-keccak"))]
+    {
+        use tiny_keccak::{Hasher, Keccak};
+
+        let mut hash = [0u8; 32];
+        let mut hasher = Keccak::v256();
+        hasher.update(data);
+        hasher.finalize(&mut hash);
+
+        hash
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn hashes() {
+        assert_eq!(
+            hex::encode(keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hex::encode(keccak256(b"Transfer(address,address,uint256)")),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+
+        // Longer than the 136 bytes rate, so that several blocks are absorbed.
+        assert_eq!(
+            hex::encode(keccak256(&[0xab; 300])),
+            hex::encode({
+                use tiny_keccak::{Hasher, Keccak};
+
+                let mut hash = [0u8; 32];
+                let mut hasher = Keccak::v256();
+                hasher.update(&[0xab; 300]);
+                hasher.finalize(&mut hash);
+
+                hash
+            })
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use ethereum_types::H256;
+use std::sync::Arc;
+
+/// Identifies an ABI entry independently of its position in the definition
+/// lists, so edits to those lists don't mismatch entries.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) enum EntryKey {
+    Constructor,
+    Function([u8; 4]),
+    Event(H256),
+    Error([u8; 4]),
+    Receive,
+    Fallback,
+}
+
+/// An entry of a parsed ABI, as it was read.
+#[derive(Debug)]
+pub(crate) struct SourceEntry {
+    pub(crate) key: EntryKey,
+    /// The entry's JSON, kept only when serializing the parsed definition
+    /// would not reproduce it, e.g. because of unknown fields or component
+    /// `internalType`s.
+    pub(crate) json: Option<Box<str>>,
+}
+
+/// The order and original JSON of the entries of a parsed ABI, used to
+/// serialize it back the way it was read.
+///
+/// ABIs built in code have an empty layout. The layout is not part of an
+/// ABI's identity, so it is ignored by `PartialEq`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AbiLayout(Arc<[SourceEntry]>);
+
+impl AbiLayout {
+    pub(crate) fn entries(&self) -> &[SourceEntry] {
+        &self.0
+    }
+}
+
+impl From<Vec<SourceEntry>> for AbiLayout {
+    fn from(entries: Vec<SourceEntry>) -> Self {
+        Self(entries.into())
+    }
+}
+
+impl PartialEq for AbiLayout {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for AbiLayout {}
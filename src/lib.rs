@@ -1,13 +1,133 @@
 //! Ethereum Smart Contracts ABI (abstract binary interface) utility library.
 
+// Lets macro expansions and generated code refer to `::ethereum_abi` from
+// within this crate.
+extern crate self as ethereum_abi;
+
 mod abi;
+mod address;
+#[cfg(feature = "alloy")]
+mod alloy_compat;
+mod array_iter;
+mod artifact;
+mod bloom;
+mod builder;
+mod call;
+mod calldata;
+#[cfg(feature = "cbor")]
+mod cbor;
+pub mod codegen;
+mod convert;
+mod de;
+mod deploy;
+mod detect;
+mod diff;
+mod enums;
+#[cfg(feature = "ethabi")]
+mod ethabi_compat;
+#[cfg(feature = "ethers")]
+mod ethers_compat;
+#[cfg(feature = "etherscan")]
+mod etherscan;
 mod event;
+mod filter;
+mod fingerprint;
+mod gas;
+mod guess;
+mod hex_encoding;
+mod int;
+mod json;
+mod keccak;
+mod layout;
+#[cfg(feature = "online-lookup")]
+mod lookup;
+#[cfg(feature = "macros")]
+mod macros;
+mod memo;
+mod merge;
+pub mod metadata;
+mod multicall;
+mod natspec;
+mod nested;
+#[cfg(feature = "parallel")]
+mod parallel;
 mod params;
+mod project;
+#[cfg(feature = "prost")]
+mod prost_compat;
+mod provider;
+mod proxy;
+mod receipt;
+mod registry;
+mod revert;
+mod safe;
+mod signature;
+mod signature_db;
+#[cfg(feature = "sourcify")]
+mod sourcify;
+pub mod standards;
+mod text;
+mod trace;
 mod types;
+#[cfg(any(feature = "etherscan", feature = "sourcify"))]
+mod unblock;
+mod units;
+mod validate;
 mod values;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "web3")]
+mod web3_compat;
 
 pub use abi::*;
+pub use address::*;
+pub use array_iter::*;
+pub use artifact::*;
+pub use builder::*;
+pub use call::*;
+pub use calldata::*;
+pub use deploy::*;
+pub use detect::*;
+pub use diff::*;
+pub use enums::*;
+#[cfg(feature = "etherscan")]
+pub use etherscan::*;
 pub use event::*;
+pub use filter::*;
+pub use gas::*;
+pub use guess::*;
+pub use hex_encoding::*;
+pub use int::*;
+#[cfg(feature = "online-lookup")]
+pub use lookup::*;
+#[cfg(feature = "macros")]
+pub use macros::*;
+pub use merge::*;
+pub use multicall::*;
+pub use natspec::*;
+pub use nested::*;
 pub use params::*;
+pub use project::*;
+pub use provider::*;
+pub use proxy::*;
+pub use receipt::*;
+pub use registry::*;
+pub use revert::*;
+pub use safe::*;
+pub use signature_db::*;
+#[cfg(feature = "sourcify")]
+pub use sourcify::*;
+pub use trace::*;
 pub use types::*;
+pub use units::*;
+pub use validate::*;
 pub use values::*;
+
+/// Items the macros' expansions and generated code refer to, so that callers
+/// do not need to depend on them.
+#[doc(hidden)]
+pub mod __private {
+    pub use anyhow;
+    pub use ethereum_types;
+    pub use serde_json;
+}
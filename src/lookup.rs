@@ -0,0 +1,377 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::H256;
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use crate::{encode_hex, Event, Function};
+
+const OPENCHAIN_URL: &str = "https://api.openchain.xyz/signature-database/v1/lookup";
+const FOURBYTE_URL: &str = "https://www.4byte.directory/api/v1";
+
+/// Client resolving unknown function selectors and event topics against the
+/// public signature directories (openchain.xyz, falling back to 4byte.directory).
+///
+/// Only signatures which hash back to the requested selector or topic are
+/// returned. Results can optionally be cached on disk, except for hashes
+/// with no known signature, which may be registered later.
+///
+/// ```no_run
+/// use ethereum_abi::SignatureLookup;
+///
+/// let lookup = SignatureLookup::new().with_cache_dir("/tmp/signatures");
+/// let functions = lookup.lookup_function([0xa9, 0x05, 0x9c, 0xbb]).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SignatureLookup {
+    agent: ureq::Agent,
+    openchain_url: String,
+    fourbyte_url: String,
+    cache_dir: Option<PathBuf>,
+}
+
+impl Default for SignatureLookup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignatureLookup {
+    /// Creates a client using the public signature directories.
+    pub fn new() -> Self {
+        Self {
+            agent: ureq::Agent::new_with_defaults(),
+            openchain_url: OPENCHAIN_URL.to_string(),
+            fourbyte_url: FOURBYTE_URL.to_string(),
+            cache_dir: None,
+        }
+    }
+
+    /// Caches lookup results as JSON files in the given directory.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Overrides the openchain.xyz lookup endpoint.
+    pub fn with_openchain_url(mut self, url: impl Into<String>) -> Self {
+        self.openchain_url = url.into();
+        self
+    }
+
+    /// Overrides the 4byte.directory API base URL.
+    pub fn with_fourbyte_url(mut self, url: impl Into<String>) -> Self {
+        self.fourbyte_url = url.into();
+        self
+    }
+
+    /// Resolves a function selector into candidate function definitions.
+    pub fn lookup_function(&self, selector: [u8; 4]) -> Result<Vec<Function>> {
+        Ok(self
+            .function_signatures(selector)?
+            .iter()
+            .filter_map(|sig| Function::parse(sig).ok())
+            .filter(|f| f.method_id() == selector)
+            .collect())
+    }
+
+    /// Resolves an event topic into candidate event definitions.
+    ///
+    /// Text signatures do not carry `indexed` markers, so every input of the
+    /// returned events is non-indexed.
+    pub fn lookup_event(&self, topic: H256) -> Result<Vec<Event>> {
+        Ok(self
+            .event_signatures(topic)?
+            .iter()
+            .filter_map(|sig| Event::parse(sig).ok())
+            .filter(|e| e.topic() == topic)
+            .collect())
+    }
+
+    /// Returns the text signatures known for a function selector.
+    pub fn function_signatures(&self, selector: [u8; 4]) -> Result<Vec<String>> {
+        self.signatures(Kind::Function, &encode_hex(selector))
+    }
+
+    /// Returns the text signatures known for an event topic.
+    pub fn event_signatures(&self, topic: H256) -> Result<Vec<String>> {
+        self.signatures(Kind::Event, &encode_hex(topic))
+    }
+
+    fn signatures(&self, kind: Kind, hash: &str) -> Result<Vec<String>> {
+        let cache_path = self
+            .cache_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}-{}.json", kind.as_str(), hash)));
+
+        if let Some(path) = &cache_path {
+            if let Ok(cached) = fs::read(path) {
+                return Ok(serde_json::from_slice(&cached)?);
+            }
+        }
+
+        let signatures = match self.fetch_openchain(kind, hash) {
+            Ok(sigs) if !sigs.is_empty() => sigs,
+            openchain => match self.fetch_fourbyte(kind, hash) {
+                Ok(sigs) => sigs,
+                // Report the first failure when both directories failed.
+                Err(err) => return openchain.and(Err(err)),
+            },
+        };
+
+        // Unknown hashes may be registered later, so only hits are cached.
+        if let (Some(path), false) = (&cache_path, signatures.is_empty()) {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, serde_json::to_vec(&signatures)?)?;
+        }
+
+        Ok(signatures)
+    }
+
+    fn fetch_openchain(&self, kind: Kind, hash: &str) -> Result<Vec<String>> {
+        let body: OpenchainResponse = self
+            .agent
+            .get(&self.openchain_url)
+            .query(kind.as_str(), hash)
+            .query("filter", "true")
+            .call()?
+            .body_mut()
+            .read_json()?;
+
+        parse_openchain_response(body, kind, hash)
+    }
+
+    fn fetch_fourbyte(&self, kind: Kind, hash: &str) -> Result<Vec<String>> {
+        let path = match kind {
+            Kind::Function => "signatures",
+            Kind::Event => "event-signatures",
+        };
+
+        let body: FourbyteResponse = self
+            .agent
+            .get(&format!("{}/{}/", self.fourbyte_url, path))
+            .query("hex_signature", hash)
+            .call()?
+            .body_mut()
+            .read_json()?;
+
+        Ok(parse_fourbyte_response(body))
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Kind {
+    Function,
+    Event,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Kind::Function => "function",
+            Kind::Event => "event",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenchainResponse {
+    ok: bool,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    result: OpenchainResult,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenchainResult {
+    #[serde(default)]
+    function: HashMap<String, Option<Vec<OpenchainSignature>>>,
+    #[serde(default)]
+    event: HashMap<String, Option<Vec<OpenchainSignature>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenchainSignature {
+    name: String,
+    #[serde(default)]
+    filtered: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct FourbyteResponse {
+    results: Vec<FourbyteSignature>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FourbyteSignature {
+    id: u64,
+    text_signature: String,
+}
+
+fn parse_openchain_response(
+    response: OpenchainResponse,
+    kind: Kind,
+    hash: &str,
+) -> Result<Vec<String>> {
+    if !response.ok {
+        return Err(anyhow!(
+            "openchain lookup failed: {}",
+            response.error.unwrap_or_default()
+        ));
+    }
+
+    let mut entries = match kind {
+        Kind::Function => response.result.function,
+        Kind::Event => response.result.event,
+    };
+
+    Ok(entries
+        .remove(hash)
+        .flatten()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|sig| !sig.filtered)
+        .map(|sig| sig.name)
+        .collect())
+}
+
+fn parse_fourbyte_response(response: FourbyteResponse) -> Vec<String> {
+    let mut results = response.results;
+
+    // Older submissions are more likely to be the canonical signature, the
+    // newer ones being collisions crafted on purpose.
+    results.sort_by_key(|sig| sig.id);
+
+    results.into_iter().map(|sig| sig.text_signature).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn openchain_response() {
+        let body = serde_json::json!({
+            "ok": true,
+            "result": {
+                "event": {},
+                "function": {
+                    "0xa9059cbb": [
+                        {"name": "transfer(address,uint256)", "filtered": false},
+                        {"name": "many_msg_babbage(bytes1)", "filtered": true}
+                    ]
+                }
+            }
+        });
+
+        let response = serde_json::from_value(body).unwrap();
+
+        assert_eq!(
+            parse_openchain_response(response, Kind::Function, "0xa9059cbb").unwrap(),
+            vec!["transfer(address,uint256)".to_string()]
+        );
+    }
+
+    #[test]
+    fn openchain_error() {
+        let body = serde_json::json!({"ok": false, "error": "invalid hash"});
+
+        let response = serde_json::from_value(body).unwrap();
+
+        assert!(parse_openchain_response(response, Kind::Event, "0x00").is_err());
+    }
+
+    #[test]
+    fn fourbyte_response() {
+        let body = serde_json::json!({
+            "count": 2,
+            "results": [
+                {"id": 313067, "text_signature": "join_tg_invmru_haha_fd06787(address,bool)"},
+                {"id": 31780, "text_signature": "transfer(address,uint256)"}
+            ]
+        });
+
+        let response = serde_json::from_value(body).unwrap();
+
+        assert_eq!(
+            parse_fourbyte_response(response),
+            vec![
+                "transfer(address,uint256)".to_string(),
+                "join_tg_invmru_haha_fd06787(address,bool)".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn cached_lookup() {
+        let dir = std::env::temp_dir().join(format!("ethereum_abi_lookup_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("function-0xa9059cbb.json"),
+            r#"["transfer(address,uint256)","transfer(bytes4[9],bytes5[6],int48[11])"]"#,
+        )
+        .unwrap();
+
+        // Unreachable endpoints make sure the cache is hit.
+        let lookup = SignatureLookup::new()
+            .with_cache_dir(&dir)
+            .with_openchain_url("http://127.0.0.1:0")
+            .with_fourbyte_url("http://127.0.0.1:0");
+
+        let functions = lookup.lookup_function([0xa9, 0x05, 0x9c, 0xbb]).unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].signature(), "transfer(address,uint256)");
+    }
+
+    #[test]
+    fn misses_are_not_cached() {
+        use std::io::{Read, Write};
+
+        // Answers both directories with no signatures.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let body = r#"{"ok": true, "result": {}, "results": []}"#;
+
+            for mut stream in listener.incoming().flatten() {
+                let mut request = vec![];
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    match stream.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        let dir =
+            std::env::temp_dir().join(format!("ethereum_abi_lookup_miss_{}", std::process::id()));
+        let lookup = SignatureLookup::new()
+            .with_cache_dir(&dir)
+            .with_openchain_url(&url)
+            .with_fourbyte_url(&url);
+
+        let functions = lookup.lookup_function([0xde, 0xad, 0xbe, 0xef]).unwrap();
+        let cached = dir.join("function-0xdeadbeef.json").exists();
+
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(functions.is_empty());
+        assert!(!cached);
+    }
+}
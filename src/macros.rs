@@ -0,0 +1,133 @@
+pub use ethereum_abi_macros::{abi, selector, sol_abi, topic};
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::{H160, H256, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::{StateMutability, Value, I256};
+
+    mod erc20 {
+        crate::abi!("testdata/legacy_erc20.json");
+    }
+
+    mod token {
+        crate::sol_abi! {
+            constructor(uint256 supply) payable;
+            function balanceOf(address owner) external view returns (uint256);
+            function transfer(address to, uint256 amount) external returns (bool);
+            function batch(address[] calldata recipients, int64 delta, bytes4 tag, string memo);
+            function info() pure returns (string name, uint8 decimals, int16 offset);
+            function safeTransferFrom(address from, address to, uint256 id);
+            function safeTransferFrom(address from, address to, uint256 id, bytes data);
+            event Transfer(address indexed from, address indexed to, uint256 value);
+            error Unauthorized(address who);
+            receive() external payable;
+            fallback() external;
+        }
+    }
+
+    #[test]
+    fn embedded_abi() {
+        assert_eq!(erc20::ABI.functions.len(), 3);
+
+        assert_eq!(erc20::TRANSFER_SELECTOR, [0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(
+            erc20::TOTAL_SUPPLY_SELECTOR,
+            erc20::ABI.functions[0].method_id()
+        );
+        assert_eq!(erc20::TRANSFER_TOPIC, erc20::ABI.events[0].topic());
+
+        let topic: H256 = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            .parse()
+            .unwrap();
+        assert_eq!(erc20::TRANSFER_TOPIC, topic);
+    }
+
+    #[test]
+    fn inline_abi() {
+        let abi = &*token::ABI;
+
+        assert_eq!(abi.functions.len(), 6);
+        assert_eq!(
+            abi.constructor.as_ref().unwrap().state_mutability,
+            StateMutability::Payable
+        );
+        assert_eq!(abi.receive, Some(StateMutability::Payable));
+        assert_eq!(abi.fallback, Some(StateMutability::NonPayable));
+        assert_eq!(abi.functions[0].state_mutability, StateMutability::View);
+        assert_eq!(abi.functions[0].inputs[0].name, "owner");
+
+        assert_eq!(token::TRANSFER_SELECTOR, [0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(token::TRANSFER_TOPIC, erc20::TRANSFER_TOPIC);
+        assert_eq!(token::UNAUTHORIZED_ERROR_SELECTOR, abi.errors[0].selector());
+        assert_eq!(
+            token::SAFE_TRANSFER_FROM_B88D4FDE_SELECTOR,
+            abi.functions[5].method_id()
+        );
+
+        let to = H160::repeat_byte(1);
+        let calldata = token::encode_transfer(to, U256::from(5)).unwrap();
+        assert_eq!(
+            calldata,
+            abi.encode_call("transfer", &[to.into(), U256::from(5).into()])
+                .unwrap()
+        );
+
+        let calldata =
+            token::encode_batch(vec![to, to], I256::from(-1i64), *b"gm!!", "hi").unwrap();
+        let params = abi.functions[2]
+            .decode_input_from_slice(&calldata[4..])
+            .unwrap();
+        assert_eq!(params[0].as_array().unwrap().len(), 2);
+        assert_eq!(params[1], Value::Int(I256::from(-1i64).into_raw(), 64));
+        assert_eq!(params[2], Value::FixedBytes(b"gm!!".to_vec()));
+
+        let output = Value::encode(&[Value::Uint(U256::from(7), 256)]);
+        assert_eq!(
+            token::decode_balance_of_output(&output).unwrap(),
+            U256::from(7)
+        );
+
+        let output = Value::encode(&[
+            Value::String("Token".to_string()),
+            Value::Uint(U256::from(18), 8),
+            Value::Int(I256::from(-2i64).into_raw(), 16),
+        ]);
+        assert_eq!(
+            token::decode_info_output(&output).unwrap(),
+            ("Token".to_string(), U256::from(18), I256::from(-2i64))
+        );
+        assert!(token::decode_transfer_output(&[]).is_err());
+
+        token::encode_safe_transfer_from_b88d4fde(to, to, U256::one(), &[1, 2]).unwrap();
+    }
+
+    #[test]
+    fn compile_time_hashes() {
+        const TRANSFER: [u8; 4] = crate::selector!("transfer(address,uint256)");
+        const TRANSFER_TOPIC: H256 = crate::topic!("Transfer(address, address, uint256)");
+
+        assert_eq!(TRANSFER, erc20::TRANSFER_SELECTOR);
+        assert_eq!(TRANSFER_TOPIC, erc20::TRANSFER_TOPIC);
+        assert_eq!(
+            crate::selector!("safeTransferFrom(address,address,uint256,bytes)"),
+            token::SAFE_TRANSFER_FROM_B88D4FDE_SELECTOR
+        );
+
+        let dispatch = |selector: [u8; 4]| match selector {
+            crate::selector!("transfer(address,uint256)") => "transfer",
+            crate::selector!("balanceOf(address)") => "balanceOf",
+            _ => "unknown",
+        };
+        assert_eq!(dispatch(token::TRANSFER_SELECTOR), "transfer");
+        assert_eq!(dispatch(token::BALANCE_OF_SELECTOR), "balanceOf");
+        assert_eq!(dispatch([0; 4]), "unknown");
+
+        let topic = match erc20::TRANSFER_TOPIC {
+            crate::topic!("Transfer(address,address,uint256)") => "Transfer",
+            _ => "unknown",
+        };
+        assert_eq!(topic, "Transfer");
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::OnceLock;
+
+use crate::keccak::keccak256;
+
+/// Keccak-256 hash of a definition's signature, computed on first use.
+///
+/// Definitions are compared by their fields only, so the hash is ignored by
+/// `PartialEq`. It is not carried over by `Clone` either, letting a cloned
+/// definition be edited before its hash is first needed.
+#[derive(Default)]
+pub(crate) struct SignatureHash(OnceLock<[u8; 32]>);
+
+impl SignatureHash {
+    /// Returns the hash of the signature, computing it on the first call.
+    pub(crate) fn get_or_init(&self, signature: impl FnOnce() -> String) -> &[u8; 32] {
+        self.0.get_or_init(|| keccak256(signature().as_bytes()))
+    }
+}
+
+impl From<[u8; 32]> for SignatureHash {
+    /// Seeds the hash with one computed before, e.g. read from a cache.
+    fn from(hash: [u8; 32]) -> Self {
+        Self(OnceLock::from(hash))
+    }
+}
+
+impl Clone for SignatureHash {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl PartialEq for SignatureHash {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for SignatureHash {}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn computed_once() {
+        let hash = SignatureHash::default();
+        let mut calls = 0;
+
+        let first = *hash.get_or_init(|| {
+            calls += 1;
+            "transfer(address,uint256)".to_string()
+        });
+        let second = *hash.get_or_init(|| unreachable!());
+
+        assert_eq!(calls, 1);
+        assert_eq!(first, second);
+        assert_eq!(&first[..4], &[0xa9, 0x05, 0x9c, 0xbb]);
+
+        assert!(hash.clone().0.get().is_none());
+    }
+}
@@ -0,0 +1,343 @@
+use anyhow::Result;
+use ethereum_types::H256;
+
+use crate::{encode_hex, Abi, Error, Event, Function, Param};
+
+/// Conflict found while merging two ABIs.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MergeConflict {
+    /// Two functions with different signatures share a selector.
+    SelectorCollision {
+        selector: [u8; 4],
+        existing: String,
+        incoming: String,
+    },
+    /// Two functions share a signature but differ in outputs or state
+    /// mutability.
+    FunctionMismatch { signature: String },
+    /// Two events share a topic but differ in indexed inputs or anonymity.
+    EventMismatch { topic: H256, signature: String },
+    /// Two errors with different signatures share a selector.
+    ErrorSelectorCollision {
+        selector: [u8; 4],
+        existing: String,
+        incoming: String,
+    },
+}
+
+impl std::fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeConflict::SelectorCollision {
+                selector,
+                existing,
+                incoming,
+            } => write!(
+                f,
+                "functions {} and {} share selector {}",
+                existing,
+                incoming,
+                encode_hex(selector)
+            ),
+            MergeConflict::FunctionMismatch { signature } => {
+                write!(f, "function {} is defined differently", signature)
+            }
+            MergeConflict::EventMismatch { signature, .. } => {
+                write!(f, "event {} is defined differently", signature)
+            }
+            MergeConflict::ErrorSelectorCollision {
+                selector,
+                existing,
+                incoming,
+            } => write!(
+                f,
+                "errors {} and {} share selector {}",
+                existing,
+                incoming,
+                encode_hex(selector)
+            ),
+        }
+    }
+}
+
+/// Error returned by [`Abi::merge`], listing every conflict found.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MergeError {
+    /// Conflicts found, in the order of the incoming ABI's entries.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl std::fmt::Display for MergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "conflicting ABI definitions: ")?;
+
+        for (i, conflict) in self.conflicts.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", conflict)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+impl Abi {
+    /// Combines the functions, events and errors of two ABIs, e.g. a proxy
+    /// and its implementation or the facets of a diamond.
+    ///
+    /// Entries defined identically in both ABIs are kept once. Parameter
+    /// names are not compared. The constructor of `self` is kept.
+    ///
+    /// Fails with a [`MergeError`] if entries collide on their selector or
+    /// topic without being the same definition.
+    pub fn merge(&self, other: &Abi) -> Result<Abi> {
+        let conflicts = self.merge_conflicts(other);
+
+        if !conflicts.is_empty() {
+            return Err(MergeError { conflicts }.into());
+        }
+
+        let mut merged = self.clone();
+
+        for f in &other.functions {
+            if !merged.functions.iter().any(|g| same_function(f, g)) {
+                merged.functions.push(f.clone());
+            }
+        }
+
+        for e in &other.events {
+            if !merged.events.iter().any(|g| same_event(e, g)) {
+                merged.events.push(e.clone());
+            }
+        }
+
+        for e in &other.errors {
+            if !merged.errors.iter().any(|g| same_error(e, g)) {
+                merged.errors.push(e.clone());
+            }
+        }
+
+        if merged.constructor.is_none() {
+            merged.constructor = other.constructor.clone();
+        }
+
+        if merged.receive.is_none() {
+            merged.receive = other.receive;
+        }
+
+        if merged.fallback.is_none() {
+            merged.fallback = other.fallback;
+        }
+
+        Ok(merged)
+    }
+
+    /// Returns the conflicts [`Abi::merge`] would report.
+    pub fn merge_conflicts(&self, other: &Abi) -> Vec<MergeConflict> {
+        let mut conflicts = vec![];
+
+        for f in &other.functions {
+            for g in &self.functions {
+                if f.method_id() != g.method_id() {
+                    continue;
+                }
+
+                if f.signature() != g.signature() {
+                    conflicts.push(MergeConflict::SelectorCollision {
+                        selector: f.method_id(),
+                        existing: g.signature(),
+                        incoming: f.signature(),
+                    });
+                } else if !same_function(f, g) {
+                    conflicts.push(MergeConflict::FunctionMismatch {
+                        signature: f.signature(),
+                    });
+                }
+            }
+        }
+
+        for e in &other.events {
+            for g in &self.events {
+                if e.topic() == g.topic() && !same_event(e, g) {
+                    conflicts.push(MergeConflict::EventMismatch {
+                        topic: e.topic(),
+                        signature: e.signature(),
+                    });
+                }
+            }
+        }
+
+        for e in &other.errors {
+            for g in &self.errors {
+                let selector = e.selector();
+
+                if selector == g.selector() && e.signature() != g.signature() {
+                    conflicts.push(MergeConflict::ErrorSelectorCollision {
+                        selector,
+                        existing: g.signature(),
+                        incoming: e.signature(),
+                    });
+                }
+            }
+        }
+
+        conflicts
+    }
+}
+
+/// Compares canonical types, ignoring struct and field names.
+fn same_types(a: &[Param], b: &[Param]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| a.type_.to_string() == b.type_.to_string())
+}
+
+fn same_function(a: &Function, b: &Function) -> bool {
+    a.signature() == b.signature()
+        && same_types(&a.outputs, &b.outputs)
+        && a.state_mutability == b.state_mutability
+}
+
+fn same_event(a: &Event, b: &Event) -> bool {
+    a.signature() == b.signature()
+        && a.anonymous == b.anonymous
+        && a.inputs
+            .iter()
+            .zip(&b.inputs)
+            .all(|(a, b)| a.indexed.unwrap_or(false) == b.indexed.unwrap_or(false))
+}
+
+fn same_error(a: &Error, b: &Error) -> bool {
+    a.signature() == b.signature()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::StateMutability;
+
+    use super::*;
+
+    #[test]
+    fn merge_proxy_and_implementation() {
+        let proxy = Abi::builder()
+            .constructor(&[("logic", "address")], StateMutability::NonPayable)
+            .function(
+                "upgradeTo",
+                &[("impl", "address")],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .event("Upgraded", &[("implementation", "address", true)])
+            .fallback(StateMutability::Payable)
+            .build()
+            .unwrap();
+
+        let implementation = Abi::builder()
+            .function(
+                "balanceOf",
+                &[("owner", "address")],
+                &[("", "uint256")],
+                StateMutability::View,
+            )
+            .event("Upgraded", &[("newImplementation", "address", true)])
+            .build()
+            .unwrap();
+
+        let merged = proxy.merge(&implementation).unwrap();
+
+        assert_eq!(merged.functions.len(), 2);
+        assert_eq!(merged.events.len(), 1);
+        assert_eq!(merged.constructor, proxy.constructor);
+        assert_eq!(merged.fallback, Some(StateMutability::Payable));
+    }
+
+    #[test]
+    fn merge_conflicts() {
+        let a = Abi::builder()
+            .function(
+                "transfer",
+                &[("", "address"), ("", "uint256")],
+                &[("", "bool")],
+                StateMutability::NonPayable,
+            )
+            .event(
+                "Transfer",
+                &[("from", "address", true), ("value", "uint256", false)],
+            )
+            .build()
+            .unwrap();
+
+        let b = Abi::builder()
+            // Same selector as transfer(address,uint256).
+            .function(
+                "transfer",
+                &[("", "bytes4[9]"), ("", "bytes5[6]"), ("", "int48[11]")],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .event(
+                "Transfer",
+                &[("from", "address", false), ("value", "uint256", false)],
+            )
+            .build()
+            .unwrap();
+
+        let c = Abi::builder()
+            .function(
+                "transfer",
+                &[("", "address"), ("", "uint256")],
+                &[],
+                StateMutability::NonPayable,
+            )
+            .build()
+            .unwrap();
+
+        let err = a.merge(&b).unwrap_err();
+        let conflicts = &err.downcast_ref::<MergeError>().unwrap().conflicts;
+
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(
+            conflicts[0],
+            MergeConflict::SelectorCollision {
+                selector: [0xa9, 0x05, 0x9c, 0xbb],
+                existing: "transfer(address,uint256)".to_string(),
+                incoming: "transfer(bytes4[9],bytes5[6],int48[11])".to_string(),
+            }
+        );
+        assert!(matches!(conflicts[1], MergeConflict::EventMismatch { .. }));
+
+        assert_eq!(
+            a.merge_conflicts(&c),
+            vec![MergeConflict::FunctionMismatch {
+                signature: "transfer(address,uint256)".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn merge_renamed_output_structs() {
+        let abi = |internal_type: &str, field: &str| -> Abi {
+            serde_json::from_str(&format!(
+                r#"[{{"type": "function", "name": "order", "inputs": [], "stateMutability": "view",
+                    "outputs": [{{"name": "", "type": "tuple", "internalType": "{}",
+                        "components": [{{"name": "{}", "type": "address"}}]}}]}}]"#,
+                internal_type, field
+            ))
+            .unwrap()
+        };
+
+        let a = abi("struct Order", "maker");
+        let b = abi("struct Exchange.Trade", "owner");
+        let c = abi("tuple", "");
+
+        assert_eq!(a.merge_conflicts(&b), vec![]);
+        assert_eq!(a.merge_conflicts(&c), vec![]);
+        assert_eq!(a.merge(&b).unwrap().functions.len(), 1);
+    }
+}
@@ -0,0 +1,346 @@
+//! Solidity bytecode metadata trailer decoding.
+//!
+//! The Solidity compiler appends a CBOR encoded map to the runtime bytecode of
+//! every contract, followed by its length as a big-endian `u16`. It holds the
+//! hash of the contract's metadata file (IPFS or Swarm) and the compiler version.
+
+use anyhow::{anyhow, Result};
+use ethereum_types::H256;
+
+use crate::encode_hex;
+
+/// Decoded bytecode metadata trailer.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Metadata {
+    /// IPFS multihash of the metadata file (`ipfs` key).
+    pub ipfs: Option<Vec<u8>>,
+    /// Swarm hash of the metadata file, legacy version (`bzzr0` key).
+    pub bzzr0: Option<H256>,
+    /// Swarm hash of the metadata file (`bzzr1` key).
+    pub bzzr1: Option<H256>,
+    /// Compiler version, e.g. `0.8.19` for releases or the full version string
+    /// for pre-release builds (`solc` key).
+    pub solc: Option<String>,
+    /// Whether experimental compiler features were enabled (`experimental` key).
+    pub experimental: bool,
+    /// Length in bytes of the trailer, including the 2 length bytes.
+    pub trailer_len: usize,
+}
+
+impl Metadata {
+    /// Returns the base58 encoded IPFS CIDv0 of the metadata file, as used by
+    /// gateways (`Qm...`).
+    pub fn ipfs_cid(&self) -> Option<String> {
+        self.ipfs.as_deref().map(base58_encode)
+    }
+}
+
+/// Decodes the metadata trailer at the end of the given (runtime) bytecode.
+pub fn decode(bytecode: &[u8]) -> Result<Metadata> {
+    let (_, cbor) = split(bytecode).ok_or_else(|| anyhow!("missing metadata trailer"))?;
+
+    let mut reader = CborReader { data: cbor, at: 0 };
+
+    let entries = match reader.read_head()? {
+        (MAJOR_MAP, len) => len,
+        _ => return Err(anyhow!("metadata trailer is not a CBOR map")),
+    };
+
+    let mut metadata = Metadata {
+        trailer_len: cbor.len() + 2,
+        ..Default::default()
+    };
+
+    for _ in 0..entries {
+        let key = reader.read_text()?;
+
+        match key.as_str() {
+            "ipfs" => metadata.ipfs = Some(reader.read_bytes()?.to_vec()),
+            "bzzr0" => metadata.bzzr0 = Some(read_hash(&mut reader)?),
+            "bzzr1" => metadata.bzzr1 = Some(read_hash(&mut reader)?),
+            "solc" => {
+                metadata.solc = Some(match reader.peek_major()? {
+                    MAJOR_TEXT => reader.read_text()?,
+                    _ => match reader.read_bytes()? {
+                        [major, minor, patch] => format!("{}.{}.{}", major, minor, patch),
+                        v => return Err(anyhow!("invalid solc version: {}", encode_hex(v))),
+                    },
+                })
+            }
+            "experimental" => metadata.experimental = reader.read_bool()?,
+            _ => reader.skip()?,
+        }
+    }
+
+    if reader.at != cbor.len() {
+        return Err(anyhow!("trailing bytes in metadata trailer"));
+    }
+
+    Ok(metadata)
+}
+
+/// Splits bytecode into the code itself and its CBOR metadata trailer
+/// (excluding the 2 length bytes).
+///
+/// Returns `None` if the declared trailer length does not fit the bytecode.
+pub fn split(bytecode: &[u8]) -> Option<(&[u8], &[u8])> {
+    let len_at = bytecode.len().checked_sub(2)?;
+    let len = u16::from_be_bytes([bytecode[len_at], bytecode[len_at + 1]]) as usize;
+
+    if len == 0 {
+        return None;
+    }
+
+    let start = len_at.checked_sub(len)?;
+
+    Some((&bytecode[..start], &bytecode[start..len_at]))
+}
+
+fn read_hash(reader: &mut CborReader) -> Result<H256> {
+    let bytes = reader.read_bytes()?;
+
+    if bytes.len() != 32 {
+        return Err(anyhow!("invalid swarm hash length: {}", bytes.len()));
+    }
+
+    Ok(H256::from_slice(bytes))
+}
+
+const MAJOR_UINT: u8 = 0;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_SIMPLE: u8 = 7;
+
+// Just enough of CBOR (RFC 8949) to read the metadata map.
+struct CborReader<'a> {
+    data: &'a [u8],
+    at: usize,
+}
+
+impl<'a> CborReader<'a> {
+    fn peek_major(&self) -> Result<u8> {
+        self.data
+            .get(self.at)
+            .map(|b| b >> 5)
+            .ok_or_else(|| anyhow!("unexpected end of metadata trailer"))
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let slice = self
+            .at
+            .checked_add(n)
+            .and_then(|end| self.data.get(self.at..end))
+            .ok_or_else(|| anyhow!("unexpected end of metadata trailer"))?;
+
+        self.at += n;
+
+        Ok(slice)
+    }
+
+    // Reads an item head, returning its major type and argument.
+    fn read_head(&mut self) -> Result<(u8, u64)> {
+        let initial = self.take(1)?[0];
+        let major = initial >> 5;
+
+        let arg = match initial & 0x1f {
+            info @ 0..=23 => info as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => u16::from_be_bytes([self.take(1)?[0], self.take(1)?[0]]) as u64,
+            26 => {
+                let mut buf = [0u8; 4];
+                buf.copy_from_slice(self.take(4)?);
+                u32::from_be_bytes(buf) as u64
+            }
+            27 => {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(self.take(8)?);
+                u64::from_be_bytes(buf)
+            }
+            info => return Err(anyhow!("unsupported CBOR additional info: {}", info)),
+        };
+
+        Ok((major, arg))
+    }
+
+    fn read_bytes(&mut self) -> Result<&'a [u8]> {
+        match self.read_head()? {
+            (MAJOR_BYTES, len) => self.take(len as usize),
+            (major, _) => Err(anyhow!(
+                "expected CBOR byte string, got major type {}",
+                major
+            )),
+        }
+    }
+
+    fn read_text(&mut self) -> Result<String> {
+        match self.read_head()? {
+            (MAJOR_TEXT, len) => Ok(std::str::from_utf8(self.take(len as usize)?)?.to_string()),
+            (major, _) => Err(anyhow!(
+                "expected CBOR text string, got major type {}",
+                major
+            )),
+        }
+    }
+
+    fn read_bool(&mut self) -> Result<bool> {
+        match self.read_head()? {
+            (MAJOR_SIMPLE, 20) => Ok(false),
+            (MAJOR_SIMPLE, 21) => Ok(true),
+            (major, _) => Err(anyhow!("expected CBOR boolean, got major type {}", major)),
+        }
+    }
+
+    fn skip(&mut self) -> Result<()> {
+        self.skip_nested(0)
+    }
+
+    // Skips an item nested `depth` arrays or maps deep, failing past
+    // `MAX_DEPTH` rather than overflowing the stack.
+    fn skip_nested(&mut self, depth: usize) -> Result<()> {
+        const MAX_DEPTH: usize = 16;
+
+        if depth > MAX_DEPTH {
+            return Err(anyhow!("metadata trailer nested too deep"));
+        }
+
+        match self.read_head()? {
+            (MAJOR_BYTES, len) | (MAJOR_TEXT, len) => self.take(len as usize).map(|_| ()),
+            (MAJOR_ARRAY, len) => (0..len).try_for_each(|_| self.skip_nested(depth + 1)),
+            (MAJOR_MAP, len) => (0..len).try_for_each(|_| {
+                self.skip_nested(depth + 1)?;
+                self.skip_nested(depth + 1)
+            }),
+            (MAJOR_UINT, _) | (1, _) | (MAJOR_SIMPLE, _) => Ok(()),
+            (major, _) => Err(anyhow!("unsupported CBOR major type: {}", major)),
+        }
+    }
+}
+
+fn base58_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    // Base 58 digits, least significant first.
+    let mut digits: Vec<u8> = vec![];
+
+    for byte in bytes {
+        let mut carry = *byte as u32;
+
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let leading_zeros = bytes.iter().take_while(|b| **b == 0).count();
+
+    std::iter::repeat_n(b'1', leading_zeros)
+        .chain(digits.iter().rev().map(|d| ALPHABET[*d as usize]))
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    // Trailer in the layout emitted by solc 0.8.19: {"ipfs": <34 bytes>, "solc": 0x000813}
+    const IPFS_TRAILER: &str = "a2646970667358221220a0b91f60d1bdc67c9e6bc2e43c1e7d9d8a25ca2b1dbb0e2a0b50c88c5e5e3c1f64736f6c63430008130033";
+
+    #[test]
+    fn decode_ipfs_trailer() {
+        let mut bytecode = hex::decode("6080604052348015600f57600080fd5b5000fe").unwrap();
+        let code_len = bytecode.len();
+        bytecode.extend(hex::decode(IPFS_TRAILER).unwrap());
+
+        let metadata = decode(&bytecode).unwrap();
+
+        assert_eq!(metadata.solc, Some("0.8.19".to_string()));
+        assert_eq!(metadata.ipfs.as_ref().map(Vec::len), Some(34));
+        assert_eq!(metadata.trailer_len, bytecode.len() - code_len);
+        assert!(!metadata.experimental);
+        assert_eq!(
+            metadata.ipfs_cid(),
+            Some("QmZA3fVhU17orjWqpLhDjvqGPHMCPhvoLLJoYZVBwNpXcJ".to_string())
+        );
+
+        let (code, _) = split(&bytecode).unwrap();
+        assert_eq!(code.len(), code_len);
+    }
+
+    #[test]
+    fn decode_swarm_trailer() {
+        // solc 0.5.x style trailer: {"bzzr1": <32 bytes>, "solc": <3 bytes>}
+        let mut trailer = vec![0xa2, 0x65];
+        trailer.extend(b"bzzr1");
+        trailer.extend([0x58, 0x20]);
+        trailer.extend([0x11; 32]);
+        trailer.push(0x64);
+        trailer.extend(b"solc");
+        trailer.extend([0x43, 0x00, 0x05, 0x10]);
+
+        let mut bytecode = vec![0x60, 0x80];
+        bytecode.extend(&trailer);
+        bytecode.extend((trailer.len() as u16).to_be_bytes());
+
+        let metadata = decode(&bytecode).unwrap();
+
+        assert_eq!(
+            metadata,
+            Metadata {
+                bzzr1: Some(H256::repeat_byte(0x11)),
+                solc: Some("0.5.16".to_string()),
+                trailer_len: trailer.len() + 2,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn missing_trailer() {
+        assert!(decode(&[]).is_err());
+        assert!(decode(&[0x60, 0x80, 0x00, 0x00]).is_err());
+        assert!(decode(&[0x60, 0x80, 0x00, 0x10]).is_err());
+    }
+
+    #[test]
+    fn malformed_trailer() {
+        // {"x": <value>}
+        let decode_value = |value: &[u8]| {
+            let mut trailer = vec![0xa1, 0x61, b'x'];
+            trailer.extend(value);
+
+            let mut bytecode = trailer.clone();
+            bytecode.extend((trailer.len() as u16).to_be_bytes());
+
+            decode(&bytecode)
+        };
+
+        // Byte string and map lengths of 2^64 - 1.
+        assert!(decode_value(&[0x5b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]).is_err());
+        assert!(decode_value(&[0xbb, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]).is_err());
+
+        // Arrays nested 60000 deep.
+        assert!(decode_value(&[0x81; 60000]).is_err());
+
+        // Nested up to the limit.
+        let mut nested = vec![0x81; 16];
+        nested.push(0x00);
+        assert!(decode_value(&nested).is_ok());
+    }
+
+    #[test]
+    fn base58() {
+        assert_eq!(base58_encode(b"hello world"), "StV1DL6CwTryKyV");
+        assert_eq!(base58_encode(&[0, 0, 1]), "112");
+    }
+}
@@ -0,0 +1,253 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::H160;
+use std::sync::Arc;
+
+use crate::{revert::decode_revert, standards::MULTICALL3, DecodedParams, Function, Type, Value};
+
+/// Address Multicall3 is deployed at on most chains.
+pub const MULTICALL3_ADDRESS: H160 = H160([
+    0xca, 0x11, 0xbd, 0xe0, 0x59, 0x77, 0xb3, 0x63, 0x11, 0x67, 0x02, 0x88, 0x62, 0xbe, 0x2a, 0x17,
+    0x39, 0x76, 0xca, 0x11,
+]);
+
+/// Batch of calls bundled into a single Multicall3 `aggregate3` call.
+///
+/// ```
+/// use ethereum_abi::{standards::ERC20, Multicall, Value};
+/// use ethereum_types::H160;
+///
+/// let token = H160::repeat_byte(1);
+/// let balance_of = ERC20.function("balanceOf").unwrap();
+///
+/// let batch = Multicall::new()
+///     .call(token, balance_of, &[Value::Address(H160::repeat_byte(2))])
+///     .try_call(token, ERC20.function("symbol").unwrap(), &[]);
+///
+/// let calldata = batch.encode().unwrap();
+/// // Send `calldata` to MULTICALL3_ADDRESS with eth_call, then:
+/// // let results = batch.decode(&output)?;
+/// ```
+#[derive(Debug, Default)]
+pub struct Multicall<'a> {
+    calls: Vec<Call<'a>>,
+    error: Option<anyhow::Error>,
+}
+
+#[derive(Debug)]
+struct Call<'a> {
+    target: H160,
+    function: &'a Function,
+    input: Vec<u8>,
+    allow_failure: bool,
+}
+
+impl<'a> Multicall<'a> {
+    /// Creates an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a call that makes the whole batch revert if it fails.
+    pub fn call(self, target: H160, function: &'a Function, args: &[Value]) -> Self {
+        self.add(target, function, args, false)
+    }
+
+    /// Adds a call that is allowed to fail without failing the batch.
+    pub fn try_call(self, target: H160, function: &'a Function, args: &[Value]) -> Self {
+        self.add(target, function, args, true)
+    }
+
+    /// Returns the number of calls in the batch.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns whether the batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Encodes the `aggregate3` calldata for the batch.
+    ///
+    /// Fails if any call was given arguments its function cannot be called
+    /// with.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        if let Some(err) = &self.error {
+            return Err(anyhow!("{}", err));
+        }
+
+        let calls = self
+            .calls
+            .iter()
+            .map(|call| {
+                Value::Tuple(vec![
+                    ("target".into(), Value::Address(call.target)),
+                    ("allowFailure".into(), Value::Bool(call.allow_failure)),
+                    ("callData".into(), Value::Bytes(call.input.clone().into())),
+                ])
+            })
+            .collect();
+
+        aggregate3().encode_input(&[Value::Array(calls, Arc::new(call3().clone()))])
+    }
+
+    /// Decodes the data returned by `aggregate3` into each call's outputs, in
+    /// the order the calls were added.
+    ///
+    /// Calls that failed yield an error carrying their decoded revert reason.
+    pub fn decode(&self, output: &[u8]) -> Result<Vec<Result<DecodedParams>>> {
+        let decoded = aggregate3().decode_output_from_slice(output)?;
+
+        let results = match &decoded[0] {
+            Value::Array(results, _) => results,
+            _ => return Err(anyhow!("invalid aggregate3 return data")),
+        };
+
+        if results.len() != self.calls.len() {
+            return Err(anyhow!(
+                "expected {} results, got {}",
+                self.calls.len(),
+                results.len()
+            ));
+        }
+
+        Ok(self
+            .calls
+            .iter()
+            .zip(results)
+            .map(|(call, result)| match result.as_tuple() {
+                Some([(_, Value::Bool(true)), (_, Value::Bytes(data))]) => {
+                    call.function.decode_output_from_slice(data)
+                }
+                Some([(_, Value::Bool(false)), (_, Value::Bytes(data))]) => {
+                    let reason = decode_revert(None, data)?;
+
+                    Err(anyhow!("{} {}", call.function.signature(), reason))
+                }
+                _ => Err(anyhow!("invalid aggregate3 result")),
+            })
+            .collect())
+    }
+
+    fn add(
+        mut self,
+        target: H160,
+        function: &'a Function,
+        args: &[Value],
+        allow_failure: bool,
+    ) -> Self {
+        match function.encode_input(args) {
+            Ok(input) => self.calls.push(Call {
+                target,
+                function,
+                input,
+                allow_failure,
+            }),
+            Err(err) => {
+                self.error.get_or_insert(err);
+            }
+        }
+
+        self
+    }
+}
+
+fn aggregate3() -> &'static Function {
+    MULTICALL3
+        .function("aggregate3")
+        .expect("Multicall3 ABI has aggregate3")
+}
+
+// The `(address target, bool allowFailure, bytes callData)` element of
+// `aggregate3`'s input.
+fn call3() -> &'static Type {
+    match &aggregate3().inputs[0].type_ {
+        Type::Array(ty) => ty,
+        ty => panic!("aggregate3 takes {}", ty),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::U256;
+    use pretty_assertions::assert_eq;
+
+    use crate::{address::to_checksum, standards::ERC20};
+
+    use super::*;
+
+    #[test]
+    fn multicall3_batch() {
+        let token = H160::repeat_byte(1);
+        let owner = H160::repeat_byte(2);
+
+        let batch = Multicall::new()
+            .call(
+                token,
+                ERC20.function("balanceOf").unwrap(),
+                &[Value::Address(owner)],
+            )
+            .try_call(token, ERC20.function("decimals").unwrap(), &[]);
+
+        let calldata = batch.encode().unwrap();
+        let (function, params) = MULTICALL3.decode_input_from_slice(&calldata).unwrap();
+
+        assert_eq!(function.name(), "aggregate3");
+        assert_eq!(params.get_path("calls[1][1]"), Some(&Value::Bool(true)));
+        assert_eq!(call3().to_string(), "(address,bool,bytes)");
+        assert_eq!(
+            call3()
+                .tuple_fields()
+                .unwrap()
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            ["target", "allowFailure", "callData"]
+        );
+        assert_eq!(
+            hex::encode(&calldata[..4]),
+            "82ad56cb",
+            "aggregate3 selector"
+        );
+
+        let mut revert = vec![0x08, 0xc3, 0x79, 0xa0];
+        revert.extend(Value::encode(&[Value::String("nope".to_string())]));
+
+        let output = Value::encode(&[Value::Array(
+            vec![
+                Value::Tuple(vec![
+                    ("".into(), Value::Bool(true)),
+                    (
+                        "".into(),
+                        Value::Bytes(Value::encode(&[Value::Uint(U256::from(42), 256)]).into()),
+                    ),
+                ]),
+                Value::Tuple(vec![
+                    ("".into(), Value::Bool(false)),
+                    ("".into(), Value::Bytes(revert.into())),
+                ]),
+            ],
+            Type::Tuple(vec![]).into(),
+        )]);
+
+        let results = batch.decode(&output).unwrap();
+
+        assert_eq!(
+            results[0].as_ref().unwrap()[0],
+            Value::Uint(U256::from(42), 256)
+        );
+        assert_eq!(
+            results[1].as_ref().unwrap_err().to_string(),
+            "decimals() reverted: nope"
+        );
+
+        assert!(Multicall::new()
+            .call(token, ERC20.function("transfer").unwrap(), &[])
+            .encode()
+            .is_err());
+        assert_eq!(
+            to_checksum(&MULTICALL3_ADDRESS),
+            "0xcA11bde05977b3631167028862bE2a173976CA11"
+        );
+    }
+}
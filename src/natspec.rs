@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::Abi;
+
+/// NatSpec documentation of a function, event or error, as found in the
+/// `userdoc` and `devdoc` compiler outputs.
+///
+/// Attached by [`Abi::attach_natspec`] and read with e.g.
+/// [`Function::docs`](crate::Function::docs).
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Docs {
+    /// `@notice`, the description meant for end users.
+    pub notice: Option<String>,
+    /// `@dev`, the description meant for developers.
+    pub details: Option<String>,
+    /// `@param` descriptions by param name.
+    pub params: BTreeMap<String, String>,
+    /// `@return` descriptions by output name, or `_0`, `_1`, ... for unnamed
+    /// outputs.
+    pub returns: BTreeMap<String, String>,
+    /// `@custom:<tag>` descriptions by tag.
+    pub custom: BTreeMap<String, String>,
+}
+
+impl Docs {
+    /// Returns the description of the param named `name`.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+
+    /// Returns whether there is no documentation at all.
+    pub fn is_empty(&self) -> bool {
+        self == &Docs::default()
+    }
+
+    fn add(&mut self, doc: &Value) {
+        match doc {
+            // Older compilers give the notice of the constructor as a string.
+            Value::String(notice) => self.notice = Some(notice.clone()),
+
+            // Errors may be declared, and documented, more than once.
+            Value::Array(docs) => docs.iter().for_each(|doc| self.add(doc)),
+
+            Value::Object(doc) => {
+                for (key, value) in doc {
+                    match (key.as_str(), value) {
+                        ("notice", Value::String(text)) => self.notice = Some(text.clone()),
+                        ("details", Value::String(text)) => self.details = Some(text.clone()),
+                        ("params", Value::Object(params)) => add_strings(&mut self.params, params),
+                        ("returns", Value::Object(returns)) => {
+                            add_strings(&mut self.returns, returns)
+                        }
+                        (key, Value::String(text)) => {
+                            if let Some(tag) = key.strip_prefix("custom:") {
+                                self.custom.insert(tag.to_string(), text.clone());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+fn add_strings(map: &mut BTreeMap<String, String>, values: &serde_json::Map<String, Value>) {
+    for (key, value) in values {
+        if let Value::String(text) = value {
+            map.insert(key.clone(), text.clone());
+        }
+    }
+}
+
+/// Docs attached to a definition. Docs describe a definition rather than
+/// define it, so they are ignored by `PartialEq`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DocsSlot(Option<Arc<Docs>>);
+
+impl DocsSlot {
+    pub(crate) fn get(&self) -> Option<&Docs> {
+        self.0.as_deref()
+    }
+
+    pub(crate) fn set(&mut self, docs: Option<Docs>) {
+        self.0 = docs.filter(|docs| !docs.is_empty()).map(Arc::new);
+    }
+}
+
+impl PartialEq for DocsSlot {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for DocsSlot {}
+
+impl Abi {
+    /// Attaches the NatSpec documentation found in the `userdoc` and
+    /// `devdoc` compiler outputs to the functions, events and errors they
+    /// document, replacing the docs attached before.
+    ///
+    /// ```
+    /// use ethereum_abi::Abi;
+    /// use serde_json::json;
+    ///
+    /// let mut abi: Abi = serde_json::from_str(r#"[
+    ///     {"type": "function", "name": "transfer", "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}], "outputs": [], "stateMutability": "nonpayable"}
+    /// ]"#).unwrap();
+    ///
+    /// abi.attach_natspec(
+    ///     Some(&json!({"methods": {"transfer(address,uint256)": {"notice": "Sends `amount` tokens to `to`."}}})),
+    ///     Some(&json!({"methods": {"transfer(address,uint256)": {"params": {"to": "The recipient."}}}})),
+    /// );
+    ///
+    /// let docs = abi.functions[0].docs().unwrap();
+    /// assert_eq!(docs.notice.as_deref(), Some("Sends `amount` tokens to `to`."));
+    /// assert_eq!(docs.param("to"), Some("The recipient."));
+    /// ```
+    pub fn attach_natspec(&mut self, userdoc: Option<&Value>, devdoc: Option<&Value>) {
+        let docs = |section: &str, signature: String| {
+            let mut docs = Docs::default();
+
+            for doc in userdoc.into_iter().chain(devdoc) {
+                if let Some(doc) = doc.get(section).and_then(|docs| docs.get(&signature)) {
+                    docs.add(doc);
+                }
+            }
+
+            docs
+        };
+
+        for f in &mut self.functions {
+            f.set_docs(Some(docs("methods", f.signature())));
+        }
+
+        for e in &mut self.events {
+            e.set_docs(Some(docs("events", e.signature())));
+        }
+
+        for e in &mut self.errors {
+            e.set_docs(Some(docs("errors", e.signature())));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn attach_solc_natspec() {
+        let mut abi = Abi::builder()
+            .function(
+                "withdraw",
+                &[("amount", "uint256")],
+                &[("", "bool")],
+                crate::StateMutability::NonPayable,
+            )
+            .event("Withdrawn", &[("amount", "uint256", false)])
+            .error("Insufficient", &[("available", "uint256")])
+            .build()
+            .unwrap();
+
+        let userdoc = json!({
+            "kind": "user",
+            "methods": {"withdraw(uint256)": {"notice": "Withdraws your funds."}},
+            "events": {"Withdrawn(uint256)": {"notice": "Funds were withdrawn."}},
+            "errors": {"Insufficient(uint256)": [{"notice": "Not enough funds."}]},
+            "version": 1
+        });
+        let devdoc = json!({
+            "kind": "dev",
+            "methods": {
+                "withdraw(uint256)": {
+                    "details": "Reverts when paused.",
+                    "params": {"amount": "Amount in wei."},
+                    "returns": {"_0": "Whether funds were sent."},
+                    "custom:security": "Reentrancy guarded."
+                }
+            },
+            "errors": {"Insufficient(uint256)": [{"params": {"available": "Balance left."}}]},
+            "version": 1
+        });
+
+        let plain = abi.clone();
+        abi.attach_natspec(Some(&userdoc), Some(&devdoc));
+
+        assert_eq!(
+            abi.functions[0].docs(),
+            Some(&Docs {
+                notice: Some("Withdraws your funds.".to_string()),
+                details: Some("Reverts when paused.".to_string()),
+                params: [("amount".to_string(), "Amount in wei.".to_string())].into(),
+                returns: [("_0".to_string(), "Whether funds were sent.".to_string())].into(),
+                custom: [("security".to_string(), "Reentrancy guarded.".to_string())].into(),
+            })
+        );
+        assert_eq!(
+            abi.events[0].docs().unwrap().notice.as_deref(),
+            Some("Funds were withdrawn.")
+        );
+
+        let error_docs = abi.errors[0].docs().unwrap();
+        assert_eq!(error_docs.notice.as_deref(), Some("Not enough funds."));
+        assert_eq!(error_docs.param("available"), Some("Balance left."));
+
+        // Docs don't change what the ABI defines.
+        assert_eq!(abi, plain);
+
+        abi.attach_natspec(None, None);
+        assert_eq!(abi.functions[0].docs(), None);
+    }
+}
@@ -0,0 +1,260 @@
+use anyhow::Result;
+use ethereum_types::H160;
+
+use crate::{Abi, AbiRegistry, DecodedParams, Function, Value};
+
+/// Call decoded from calldata, along with the calls embedded in its `bytes`
+/// params, as taken by multicalls, Safe transactions and timelocks.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NestedCall<'a> {
+    /// Called function.
+    pub function: &'a Function,
+    /// Decoded call arguments.
+    pub params: DecodedParams,
+    /// Calls decoded from `bytes` values, along with their path in `params`
+    /// (see [`DecodedParams::get_path`]).
+    pub calls: Vec<(String, NestedCall<'a>)>,
+}
+
+impl Abi {
+    /// Decodes calldata along with the calldata embedded in its params, down
+    /// to `depth` levels of nesting.
+    ///
+    /// Each `bytes` value is decoded as a call to the contract at the address
+    /// found next to it, e.g. the `target` of a multicall `(address,bytes)`
+    /// tuple or the element of a `targets` array at the same position, using
+    /// the ABI registered for that address. Values with no such address are
+    /// decoded against this ABI, as for self multicalls. Values that do not
+    /// decode as calldata are skipped.
+    pub fn decode_nested<'a>(
+        &'a self,
+        input: &[u8],
+        registry: &'a AbiRegistry,
+        depth: usize,
+    ) -> Result<NestedCall<'a>> {
+        let (function, params) = self.decode_input_from_slice(input)?;
+
+        let mut walker = Walker {
+            abi: self,
+            registry,
+            depth,
+            calls: vec![],
+        };
+
+        if depth > 0 {
+            let siblings: Vec<_> = params
+                .iter()
+                .map(|decoded| (decoded.param.name.as_str(), &decoded.value))
+                .collect();
+
+            for (i, (name, value)) in siblings.iter().enumerate() {
+                let path = if name.is_empty() {
+                    format!("[{}]", i)
+                } else {
+                    name.to_string()
+                };
+
+                walker.visit(value, path, &siblings, None);
+            }
+        }
+
+        Ok(NestedCall {
+            function,
+            params,
+            calls: walker.calls,
+        })
+    }
+}
+
+struct Walker<'a> {
+    abi: &'a Abi,
+    registry: &'a AbiRegistry,
+    depth: usize,
+    calls: Vec<(String, NestedCall<'a>)>,
+}
+
+impl Walker<'_> {
+    fn visit(
+        &mut self,
+        value: &Value,
+        path: String,
+        siblings: &[(&str, &Value)],
+        index: Option<usize>,
+    ) {
+        match value {
+            Value::Bytes(bytes) => {
+                let abi = target(siblings, index)
+                    .and_then(|address| self.registry.get(&address))
+                    .unwrap_or(self.abi);
+
+                if let Ok(call) = abi.decode_nested(bytes, self.registry, self.depth - 1) {
+                    self.calls.push((path, call));
+                }
+            }
+
+            Value::FixedArray(values, _) | Value::Array(values, _) => {
+                for (i, value) in values.iter().enumerate() {
+                    self.visit(value, format!("{}[{}]", path, i), siblings, Some(i));
+                }
+            }
+
+            Value::Tuple(fields) => {
+                let siblings: Vec<_> = fields
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value))
+                    .collect();
+
+                for (i, (name, value)) in siblings.iter().enumerate() {
+                    let path = if name.is_empty() {
+                        format!("{}[{}]", path, i)
+                    } else {
+                        format!("{}.{}", path, name)
+                    };
+
+                    self.visit(value, path, &siblings, None);
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+// Finds the address a `bytes` value is sent to: an address next to it, or
+// the element at the same position of an address array next to the array
+// holding it.
+fn target(siblings: &[(&str, &Value)], index: Option<usize>) -> Option<H160> {
+    siblings.iter().find_map(|(_, value)| match (value, index) {
+        (Value::Address(address), _) => Some(*address),
+        (Value::Array(values, _), Some(i)) | (Value::FixedArray(values, _), Some(i)) => {
+            values.get(i)?.as_address()
+        }
+        _ => None,
+    })
+}
+
+impl std::fmt::Display for NestedCall<'_> {
+    /// Formats the call and, indented below it, the calls nested in it
+    /// prefixed with their path.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+impl NestedCall<'_> {
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{}({})", self.function.name, self.params)?;
+
+        for (path, call) in &self.calls {
+            write!(f, "{}{}: ", "  ".repeat(indent + 1), path)?;
+            call.write(f, indent + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::U256;
+    use pretty_assertions::assert_eq;
+
+    use crate::{StateMutability, Type};
+
+    use super::*;
+
+    #[test]
+    fn decode_nested_calls() {
+        let token = H160::repeat_byte(0xaa);
+        let to = H160::repeat_byte(0x22);
+
+        let multicall = Abi::builder()
+            .function(
+                "aggregate",
+                &[("calls", "(address,bytes)[]")],
+                &[],
+                StateMutability::Payable,
+            )
+            .function(
+                "multicall",
+                &[("data", "bytes[]")],
+                &[],
+                StateMutability::Payable,
+            )
+            .build()
+            .unwrap();
+
+        let erc20 = Abi::builder()
+            .function(
+                "transfer",
+                &[("to", "address"), ("amount", "uint256")],
+                &[("", "bool")],
+                StateMutability::NonPayable,
+            )
+            .build()
+            .unwrap();
+
+        let mut registry = AbiRegistry::new();
+        registry.register(token, erc20.clone());
+
+        let transfer = erc20
+            .encode_call(
+                "transfer",
+                &[Value::Address(to), Value::Uint(U256::from(5), 256)],
+            )
+            .unwrap();
+
+        let inner = multicall
+            .encode_call(
+                "aggregate",
+                &[Value::Array(
+                    vec![Value::Tuple(vec![
+                        ("".into(), Value::Address(token)),
+                        ("".into(), Value::Bytes(transfer.into())),
+                    ])],
+                    Type::Tuple(vec![]).into(),
+                )],
+            )
+            .unwrap();
+
+        let input = multicall
+            .encode_call(
+                "multicall",
+                &[Value::Array(
+                    vec![
+                        Value::Bytes(inner.into()),
+                        Value::Bytes(vec![1, 2, 3].into()),
+                    ],
+                    Type::Bytes.into(),
+                )],
+            )
+            .unwrap();
+
+        let call = multicall.decode_nested(&input, &registry, 2).unwrap();
+
+        assert_eq!(call.function.name, "multicall");
+        assert_eq!(call.calls.len(), 1);
+
+        let (path, aggregate) = &call.calls[0];
+        assert_eq!(path, "data[0]");
+        assert_eq!(aggregate.function.name, "aggregate");
+
+        let (path, transfer) = &aggregate.calls[0];
+        assert_eq!(path, "calls[0][1]");
+        assert_eq!(transfer.function.name, "transfer");
+        assert_eq!(transfer.params["to"], Value::Address(to));
+        assert_eq!(
+            aggregate.params.get_path(path),
+            Some(&Value::Bytes(input_of(transfer).into()))
+        );
+
+        let shallow = multicall.decode_nested(&input, &registry, 1).unwrap();
+        assert!(shallow.calls[0].1.calls.is_empty());
+    }
+
+    fn input_of(call: &NestedCall<'_>) -> Vec<u8> {
+        let values: Vec<_> = call.params.iter().map(|p| p.value.clone()).collect();
+
+        call.function.encode_input(&values).unwrap()
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use ethereum_types::H160;
+use rayon::prelude::*;
+
+use crate::{Abi, AbiRegistry, DecodedParams, Event, EventLog, Function};
+
+impl Abi {
+    /// Decodes a batch of logs across rayon's thread pool.
+    ///
+    /// Results are returned in the order of the logs, each one being what
+    /// [`Abi::decode_log`] returns for that log.
+    pub fn decode_logs_par<L: EventLog + Sync>(
+        &self,
+        logs: &[L],
+    ) -> Vec<Result<(&Event, DecodedParams)>> {
+        logs.par_iter().map(|log| self.decode_log(log)).collect()
+    }
+}
+
+impl AbiRegistry {
+    /// Decodes a batch of (callee, input) calls across rayon's thread pool.
+    ///
+    /// Results are returned in the order of the calls, each one being what
+    /// [`AbiRegistry::decode_call`] returns for that call.
+    pub fn decode_calls_par<I: AsRef<[u8]> + Sync>(
+        &self,
+        calls: &[(H160, I)],
+    ) -> Vec<Result<(&Function, DecodedParams)>> {
+        calls
+            .par_iter()
+            .map(|(to, input)| self.decode_call(to, input.as_ref()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::{H256, U256};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{standards::ERC20, Log, Value};
+
+    fn transfer_log(amount: u64) -> Log {
+        let transfer = ERC20.events.iter().find(|e| e.name == "Transfer").unwrap();
+
+        Log {
+            address: H160::zero(),
+            topics: vec![
+                transfer.topic(),
+                H256::from(H160::repeat_byte(1)),
+                H256::from(H160::repeat_byte(2)),
+            ],
+            data: Value::encode(&[Value::Uint(U256::from(amount), 256)]),
+            block_number: None,
+            block_hash: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            removed: false,
+        }
+    }
+
+    #[test]
+    fn decode_logs_in_order() {
+        let mut logs = (0..100).map(transfer_log).collect::<Vec<_>>();
+        logs[42].topics[0] = H256::zero();
+
+        let decoded = ERC20.decode_logs_par(&logs);
+
+        assert_eq!(decoded.len(), 100);
+        assert!(decoded[42].is_err());
+
+        for (i, result) in decoded.iter().enumerate().filter(|(i, _)| *i != 42) {
+            let (event, params) = result.as_ref().unwrap();
+
+            assert_eq!(event.name, "Transfer");
+            assert_eq!(params[2], Value::Uint(U256::from(i), 256));
+        }
+    }
+
+    #[test]
+    fn decode_calls_in_order() {
+        let token = H160::repeat_byte(9);
+        let mut registry = AbiRegistry::new();
+        registry.register(token, ERC20.clone());
+
+        let calls = (0..100u64)
+            .map(|i| {
+                let to = if i == 7 { H160::zero() } else { token };
+                let input = ERC20
+                    .encode_call(
+                        "transfer",
+                        &[
+                            Value::Address(H160::repeat_byte(1)),
+                            Value::Uint(U256::from(i), 256),
+                        ],
+                    )
+                    .unwrap();
+
+                (to, input)
+            })
+            .collect::<Vec<_>>();
+
+        let decoded = registry.decode_calls_par(&calls);
+
+        assert_eq!(decoded.len(), 100);
+        assert!(decoded[7].is_err());
+        assert_eq!(
+            decoded[8].as_ref().unwrap().1[1],
+            Value::Uint(U256::from(8), 256)
+        );
+    }
+}
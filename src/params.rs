@@ -1,7 +1,12 @@
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, rc::Rc};
 
-use crate::{types::Type, Value};
+use crate::{
+    types::Type,
+    values::{field_key, write_list, WriteNested},
+    Value,
+};
 
 /// ABI decoded param value.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -12,6 +17,56 @@ pub struct DecodedParam {
     pub value: Value,
 }
 
+impl DecodedParam {
+    /// Returns the user-defined value type the value is declared as, see
+    /// [`Param::user_type`].
+    pub fn user_type(&self) -> Option<&str> {
+        self.param.user_type()
+    }
+}
+
+impl std::fmt::Display for DecodedParam {
+    /// Formats the param as `name: value`, or just the value if unnamed.
+    ///
+    /// Values of user-defined value types are wrapped in the type name, as
+    /// in `price: Price(100)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_param(
+            f,
+            &self.param.name,
+            &UserTyped::of(&self.param, &self.value),
+        )
+    }
+}
+
+impl Serialize for DecodedParam {
+    /// Serializes the param as `{"name": .., "type": .., "value": ..}`, with
+    /// a `userType` entry for values of user-defined value types.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Entry<'a> {
+            name: &'a str,
+            #[serde(rename = "type")]
+            type_: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            user_type: Option<&'a str>,
+            value: &'a Value,
+        }
+
+        Entry {
+            name: &self.param.name,
+            type_: self.param.type_.to_string(),
+            user_type: self.user_type(),
+            value: &self.value,
+        }
+        .serialize(serializer)
+    }
+}
+
 impl From<(Param, Value)> for DecodedParam {
     fn from((param, value): (Param, Value)) -> Self {
         Self { param, value }
@@ -28,9 +83,54 @@ impl DecodedParams {
     /// Creates a reader.
     ///
     /// Parameters are indexed by name at reader creation.
-    pub fn reader(&self) -> DecodedParamsReader {
+    pub fn reader(&self) -> DecodedParamsReader<'_> {
         DecodedParamsReader::new(self)
     }
+
+    /// Returns the value of the first param named `name`.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.iter()
+            .find(|decoded| decoded.param.name == name)
+            .map(|decoded| &decoded.value)
+    }
+
+    /// Returns the value nested at `path`, e.g. `orders[2].maker`: a param name,
+    /// or `[index]` for a param position, followed by `.field` and `[index]`
+    /// accesses into tuples and arrays. See [`Value::get_path`].
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        get_path(
+            path,
+            |index| self.0.get(index).map(|decoded| &decoded.value),
+            |name| self.get(name),
+        )
+    }
+
+    /// Iterates over (name, value) pairs in declaration order. Unnamed params
+    /// have an empty name.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.iter()
+            .map(|decoded| (decoded.param.name.as_str(), &decoded.value))
+    }
+}
+
+impl std::ops::Index<usize> for DecodedParams {
+    type Output = Value;
+
+    /// Returns the value of the param at `index`, panicking if out of bounds.
+    fn index(&self, index: usize) -> &Value {
+        &self.0[index].value
+    }
+}
+
+impl std::ops::Index<&str> for DecodedParams {
+    type Output = Value;
+
+    /// Returns the value of the param named `name`, panicking if there is
+    /// none.
+    fn index(&self, name: &str) -> &Value {
+        self.get(name)
+            .unwrap_or_else(|| panic!("no param named {:?}", name))
+    }
 }
 
 impl std::ops::Deref for DecodedParams {
@@ -41,12 +141,302 @@ impl std::ops::Deref for DecodedParams {
     }
 }
 
+impl std::fmt::Display for DecodedParams {
+    /// Formats the params as `to: 0x.., value: 1`, or one per line in the
+    /// alternate form (`{:#}`), wrapping values of user-defined value types
+    /// like [`DecodedParam`] does.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_params(
+            f,
+            self.iter().map(|decoded| {
+                (
+                    decoded.param.name.as_str(),
+                    UserTyped::of(&decoded.param, &decoded.value),
+                )
+            }),
+        )
+    }
+}
+
+impl Serialize for DecodedParams {
+    /// Serializes the params as a map keyed by param name, or by position for
+    /// unnamed params, with values serialized as described on [`Value`].
+    ///
+    /// To keep param types, including user-defined value types, serialize
+    /// the list of [`DecodedParam`]s instead (`&*params`).
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(
+            self.entries()
+                .enumerate()
+                .map(|(i, (name, value))| (field_key(name, i), value)),
+        )
+    }
+}
+
+impl IntoIterator for DecodedParams {
+    type Item = DecodedParam;
+    type IntoIter = std::vec::IntoIter<DecodedParam>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl From<Vec<DecodedParam>> for DecodedParams {
+    fn from(params: Vec<DecodedParam>) -> Self {
+        Self(params)
+    }
+}
+
 impl From<Vec<(Param, Value)>> for DecodedParams {
     fn from(values: Vec<(Param, Value)>) -> Self {
         Self(values.into_iter().map(From::from).collect())
     }
 }
 
+/// ABI decoded values along with the params they were decoded for, borrowed
+/// from the function, event or error definition.
+///
+/// Decoding into this type, e.g. with [`Function::decode_input_ref`], spares
+/// cloning every param (and its type tree) as [`DecodedParams`] does, which
+/// adds up on hot decode paths.
+///
+/// [`Function::decode_input_ref`]: crate::Function::decode_input_ref
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DecodedParamsRef<'a> {
+    params: &'a [Param],
+    values: Vec<Value>,
+}
+
+impl<'a> DecodedParamsRef<'a> {
+    /// Pairs decoded values with the params they were decoded for.
+    pub(crate) fn new(params: &'a [Param], values: Vec<Value>) -> Self {
+        debug_assert_eq!(params.len(), values.len());
+
+        Self { params, values }
+    }
+
+    /// Returns the number of params.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns whether there are no params.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the param definitions.
+    pub fn params(&self) -> &'a [Param] {
+        self.params
+    }
+
+    /// Returns the decoded values, in declaration order.
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    /// Returns the decoded values, in declaration order.
+    pub fn into_values(self) -> Vec<Value> {
+        self.values
+    }
+
+    /// Iterates over (param, value) pairs in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a Param, &Value)> {
+        self.params.iter().zip(&self.values)
+    }
+
+    /// Returns the value of the first param named `name`.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.iter()
+            .find(|(param, _)| param.name == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the value nested at `path`, see [`DecodedParams::get_path`].
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        get_path(path, |index| self.values.get(index), |name| self.get(name))
+    }
+
+    /// Iterates over (name, value) pairs in declaration order. Unnamed params
+    /// have an empty name.
+    pub fn entries(&self) -> impl Iterator<Item = (&'a str, &Value)> {
+        self.iter()
+            .map(|(param, value)| (param.name.as_str(), value))
+    }
+
+    /// Clones the params into owned [`DecodedParams`].
+    pub fn into_owned(self) -> DecodedParams {
+        DecodedParams(
+            self.params
+                .iter()
+                .cloned()
+                .zip(self.values)
+                .map(From::from)
+                .collect(),
+        )
+    }
+}
+
+impl std::ops::Index<usize> for DecodedParamsRef<'_> {
+    type Output = Value;
+
+    /// Returns the value of the param at `index`, panicking if out of bounds.
+    fn index(&self, index: usize) -> &Value {
+        &self.values[index]
+    }
+}
+
+impl std::ops::Index<&str> for DecodedParamsRef<'_> {
+    type Output = Value;
+
+    /// Returns the value of the param named `name`, panicking if there is
+    /// none.
+    fn index(&self, name: &str) -> &Value {
+        self.get(name)
+            .unwrap_or_else(|| panic!("no param named {:?}", name))
+    }
+}
+
+impl std::fmt::Display for DecodedParamsRef<'_> {
+    /// Formats the params like [`DecodedParams`] does.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_params(
+            f,
+            self.iter()
+                .map(|(param, value)| (param.name.as_str(), UserTyped::of(param, value))),
+        )
+    }
+}
+
+impl Serialize for DecodedParamsRef<'_> {
+    /// Serializes the params like [`DecodedParams`] does.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(
+            self.entries()
+                .enumerate()
+                .map(|(i, (name, value))| (field_key(name, i), value)),
+        )
+    }
+}
+
+impl From<DecodedParamsRef<'_>> for DecodedParams {
+    fn from(params: DecodedParamsRef<'_>) -> Self {
+        params.into_owned()
+    }
+}
+
+// Looks up a param by name or `[index]`, then the rest of the path in its
+// value.
+fn get_path<'v>(
+    path: &str,
+    by_index: impl FnOnce(usize) -> Option<&'v Value>,
+    by_name: impl FnOnce(&str) -> Option<&'v Value>,
+) -> Option<&'v Value> {
+    let end = path.find(['.', '[']).unwrap_or(path.len());
+
+    let (value, rest) = if end == 0 {
+        let close = path.find(']')?;
+        let index: usize = path.strip_prefix('[')?[..close - 1].trim().parse().ok()?;
+
+        (by_index(index)?, &path[close + 1..])
+    } else {
+        (by_name(&path[..end])?, &path[end..])
+    };
+
+    if rest.starts_with(['.', '[']) || rest.is_empty() {
+        value.get_path(rest)
+    } else {
+        None
+    }
+}
+
+// A param value, wrapped in the user-defined value type it is declared as
+// when displayed.
+#[derive(Clone, Copy)]
+pub(crate) struct UserTyped<'a> {
+    pub(crate) user_type: Option<&'a str>,
+    pub(crate) value: &'a Value,
+}
+
+impl<'a> UserTyped<'a> {
+    fn of(param: &'a Param, value: &'a Value) -> Self {
+        Self {
+            user_type: param.user_type(),
+            value,
+        }
+    }
+}
+
+impl WriteNested for UserTyped<'_> {
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        let user_type = match self.user_type {
+            Some(user_type) => user_type,
+            None => return self.value.write(f, indent),
+        };
+
+        match self.value {
+            // Arrays of user-defined value types wrap each element.
+            Value::Array(values, _) | Value::FixedArray(values, _) => write_list(
+                f,
+                indent,
+                ('[', ']'),
+                values.iter().map(|value| {
+                    (
+                        "",
+                        UserTyped {
+                            user_type: Some(user_type),
+                            value,
+                        },
+                    )
+                }),
+            ),
+            value => {
+                write!(f, "{}(", user_type)?;
+                value.write(f, indent)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+fn fmt_param(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &str,
+    value: &impl WriteNested,
+) -> std::fmt::Result {
+    if !name.is_empty() {
+        write!(f, "{}: ", name)?;
+    }
+
+    value.write(f, 0)
+}
+
+pub(crate) fn fmt_params<'v, T: WriteNested>(
+    f: &mut std::fmt::Formatter<'_>,
+    params: impl Iterator<Item = (&'v str, T)>,
+) -> std::fmt::Result {
+    for (i, (name, value)) in params.enumerate() {
+        if i > 0 {
+            if f.alternate() {
+                writeln!(f)?;
+            } else {
+                write!(f, ", ")?;
+            }
+        }
+
+        fmt_param(f, name, &value)?;
+    }
+
+    Ok(())
+}
+
 /// Provides fast read access to decoded params by parameter index and name.
 pub struct DecodedParamsReader<'a> {
     /// Decoded params by parameter index.
@@ -78,23 +468,73 @@ pub struct Param {
     pub type_: Type,
     /// Whether it is an indexed parameter (events only).
     pub indexed: Option<bool>,
+    /// Solidity level type as reported by the compiler, e.g. `struct Order`,
+    /// `enum Side` or `contract IERC20`.
+    pub internal_type: Option<String>,
 }
 
 impl Param {
-    fn build_param_entry(&self) -> ParamEntry {
-        let tuple_params = match &self.type_ {
-            Type::Tuple(params) => Some(params.clone()),
-            Type::Array(ty) | Type::FixedArray(ty, _) => {
-                if let Type::Tuple(params) = ty.as_ref() {
-                    Some(params.clone())
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        };
+    /// Returns the Solidity level type name (`internalType`) if known, the
+    /// canonical ABI type otherwise.
+    pub fn type_name(&self) -> String {
+        self.internal_type
+            .clone()
+            .unwrap_or_else(|| self.type_.to_string())
+    }
+
+    /// Returns the canonical name of the enum this parameter is declared as,
+    /// e.g. `Order.Status` for `enum Order.Status`.
+    pub fn enum_name(&self) -> Option<&str> {
+        let name = self.internal_type.as_deref()?.strip_prefix("enum ")?;
+
+        Some(name.split('[').next().unwrap_or(name))
+    }
+
+    /// Returns the name of the user-defined value type (`type Price is
+    /// uint128;`) this parameter is declared as, if any.
+    ///
+    /// For arrays of such types this is the element type name.
+    pub fn user_type(&self) -> Option<&str> {
+        let internal_type = self.internal_type.as_deref()?;
+
+        let name = internal_type.split('[').next().unwrap_or(internal_type);
+
+        let is_builtin = ["struct ", "enum ", "contract ", "function"]
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+            || name == "address payable";
+
+        let mut ty = &self.type_;
+        while let Type::Array(inner) | Type::FixedArray(inner, _) = ty {
+            ty = inner;
+        }
+
+        if is_builtin || name == ty.to_string() {
+            return None;
+        }
+
+        name.rsplit('.').next()
+    }
+
+    /// Returns the parameter as declared in Solidity, e.g. `address to` or
+    /// `address indexed from`, leaving out the name when it is empty.
+    pub fn declaration(&self) -> String {
+        let mut decl = self.type_.to_string();
+
+        if self.indexed == Some(true) {
+            decl.push_str(" indexed");
+        }
+
+        if !self.name.is_empty() {
+            decl.push(' ');
+            decl.push_str(&self.name);
+        }
 
-        let components = tuple_params.map(|params| {
+        decl
+    }
+
+    pub(crate) fn build_param_entry(&self) -> ParamEntry {
+        let components = tuple_fields(&self.type_).map(|params| {
             params
                 .iter()
                 .map(|(name, ty)| {
@@ -102,6 +542,7 @@ impl Param {
                         name: name.clone(),
                         type_: ty.clone(),
                         indexed: None,
+                        internal_type: None,
                     }
                     .build_param_entry()
                 })
@@ -112,6 +553,11 @@ impl Param {
             name: self.name.clone(),
             type_: param_type_string(&self.type_),
             indexed: self.indexed,
+            // Params built in code may only name their struct in the type.
+            internal_type: self
+                .internal_type
+                .clone()
+                .or_else(|| struct_internal_type(&self.type_)),
             components,
         }
     }
@@ -133,35 +579,99 @@ impl<'a> Deserialize<'a> for Param {
     {
         let entry: ParamEntry = Deserialize::deserialize(deserializer)?;
 
-        let (_, ty) = parse_exact_type(Rc::new(entry.components), &entry.type_)
-            .map_err(|e| serde::de::Error::custom(e.to_string()))?;
+        Param::from_param_entry(entry).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Param {
+    pub(crate) fn from_param_entry(entry: ParamEntry) -> Result<Self> {
+        let (_, ty) = parse_exact_type(Components::Json(Rc::new(entry.components)), &entry.type_)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let ty = name_struct(ty, entry.internal_type.as_deref());
 
         Ok(Param {
-            name: entry.name.to_string(),
+            name: entry.name,
             type_: ty,
             indexed: entry.indexed,
+            internal_type: entry.internal_type,
         })
     }
 }
 
+// Returns the tuple fields of a tuple type or of an array of tuples.
+/// Joins the Solidity declarations of a parameter list with `", "`.
+pub(crate) fn declarations(params: &[Param]) -> String {
+    params
+        .iter()
+        .map(Param::declaration)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn tuple_fields(ty: &Type) -> Option<&[(String, Type)]> {
+    match ty {
+        Type::Array(ty) | Type::FixedArray(ty, _) => tuple_fields(ty),
+        ty => ty.tuple_fields(),
+    }
+}
+
+// Rebuilds the `internalType` of struct (array) types, e.g. `struct Order[]`.
+fn struct_internal_type(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Struct(name, _) => Some(format!("struct {}", name)),
+        Type::Array(ty) => struct_internal_type(ty).map(|s| format!("{}[]", s)),
+        Type::FixedArray(ty, size) => struct_internal_type(ty).map(|s| format!("{}[{}]", s, size)),
+        _ => None,
+    }
+}
+
+// Names the tuple within a parameter type after the struct its
+// `internalType` refers to, e.g. `struct Exchange.Order[]` names
+// `Exchange.Order`.
+pub(crate) fn name_struct(ty: Type, internal_type: Option<&str>) -> Type {
+    let name = internal_type
+        .and_then(|it| it.strip_prefix("struct "))
+        .map(|it| it.split('[').next().unwrap_or(it));
+
+    fn rename(ty: Type, name: &str) -> Type {
+        match ty {
+            Type::Tuple(tys) => Type::Struct(name.to_string(), tys),
+            Type::Array(ty) => Type::Array(Box::new(rename(*ty, name))),
+            Type::FixedArray(ty, size) => Type::FixedArray(Box::new(rename(*ty, name)), size),
+            ty => ty,
+        }
+    }
+
+    match name {
+        Some(name) => rename(ty, name),
+        None => ty,
+    }
+}
+
 fn param_type_string(ty: &Type) -> String {
     match ty {
-        Type::Tuple(_) => String::from("tuple"),
+        Type::Tuple(_) | Type::Struct(_, _) => String::from("tuple"),
         Type::Array(ty) => format!("{}[]", param_type_string(ty)),
         Type::FixedArray(ty, size) => format!("{}[{}]", param_type_string(ty), size),
         _ => format!("{}", ty),
     }
 }
 
+// Fields are declared in alphabetical order, the order solc writes them in.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct ParamEntry {
+pub(crate) struct ParamEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<ParamEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexed: Option<bool>,
+    #[serde(rename = "internalType", skip_serializing_if = "Option::is_none")]
+    pub internal_type: Option<String>,
+    // Vyper omits the name of unnamed outputs.
+    #[serde(default)]
     pub name: String,
     #[serde(rename = "type")]
     pub type_: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub indexed: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub components: Option<Vec<ParamEntry>>,
 }
 
 use nom::{
@@ -169,7 +679,7 @@ use nom::{
     bytes::complete::tag,
     character::complete::{char, digit1},
     combinator::{all_consuming, map_res, opt, recognize, verify},
-    multi::many1,
+    multi::{many1, separated_list0},
     sequence::delimited,
     IResult,
 };
@@ -202,16 +712,30 @@ fn map_error<I, O>(res: IResult<I, O>) -> TypeParseResult<I, O> {
     res.map_err(|err| err.map(From::from))
 }
 
-fn parse_exact_type(
-    components: Rc<Option<Vec<ParamEntry>>>,
-    input: &str,
-) -> TypeParseResult<&str, Type> {
+// Where the fields of tuple types are read from.
+#[derive(Clone)]
+enum Components {
+    // The `components` of a JSON ABI param, for its `tuple` types.
+    Json(Rc<Option<Vec<ParamEntry>>>),
+    // The type itself, as in `(address,bytes)` in signatures.
+    Inline,
+}
+
+/// Parses a canonical type string, e.g. `uint256[]` or `(address,bytes)[2]`.
+///
+/// Tuples are written in their component-free form since there is no
+/// `components` list to read them from.
+pub(crate) fn parse_type_str(input: &str) -> Result<Type> {
+    parse_exact_type(Components::Inline, input)
+        .map(|(_, ty)| ty)
+        .map_err(|_| anyhow!("invalid type: {}", input))
+}
+
+fn parse_exact_type(components: Components, input: &str) -> TypeParseResult<&str, Type> {
     all_consuming(parse_type(components))(input)
 }
 
-fn parse_type(
-    components: Rc<Option<Vec<ParamEntry>>>,
-) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
+fn parse_type(components: Components) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
     move |input: &str| {
         alt((
             parse_array(components.clone()),
@@ -220,16 +744,16 @@ fn parse_type(
     }
 }
 
-fn parse_simple_type(
-    components: Rc<Option<Vec<ParamEntry>>>,
-) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
+fn parse_simple_type(components: Components) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
     move |input: &str| {
         alt((
             parse_tuple(components.clone()),
             parse_uint,
             parse_int,
+            parse_fixed,
             parse_address,
             parse_bool,
+            parse_function,
             parse_string,
             parse_bytes,
         ))(input)
@@ -248,6 +772,32 @@ fn parse_int(input: &str) -> TypeParseResult<&str, Type> {
     )
 }
 
+// Parses fixed<M>x<N> and ufixed<M>x<N>, where the bare forms are aliases
+// for fixed128x18 and ufixed128x18.
+fn parse_fixed(input: &str) -> TypeParseResult<&str, Type> {
+    let (i, unsigned) = map_error(alt((tag("ufixed"), tag("fixed")))(input))?;
+    let (i, size) = map_error(opt(verify(
+        |i| {
+            let (i, size) = parse_integer(i)?;
+            let (i, _) = char('x')(i)?;
+            let (i, decimals) = parse_integer(i)?;
+
+            Ok((i, (size, decimals)))
+        },
+        |(size, decimals)| check_int_size(size) && *decimals <= 80,
+    ))(i))?;
+
+    let (size, decimals) = size.unwrap_or((128, 18));
+
+    let ty = if unsigned == "ufixed" {
+        Type::Ufixed(size, decimals)
+    } else {
+        Type::Fixed(size, decimals)
+    };
+
+    Ok((i, ty))
+}
+
 fn parse_address(input: &str) -> TypeParseResult<&str, Type> {
     map_error(tag("address")(input).map(|(i, _)| (i, Type::Address)))
 }
@@ -256,6 +806,10 @@ fn parse_bool(input: &str) -> TypeParseResult<&str, Type> {
     map_error(tag("bool")(input).map(|(i, _)| (i, Type::Bool)))
 }
 
+fn parse_function(input: &str) -> TypeParseResult<&str, Type> {
+    map_error(tag("function")(input).map(|(i, _)| (i, Type::Function)))
+}
+
 fn parse_string(input: &str) -> TypeParseResult<&str, Type> {
     map_error(tag("string")(input).map(|(i, _)| (i, Type::String)))
 }
@@ -269,9 +823,7 @@ fn parse_bytes(input: &str) -> TypeParseResult<&str, Type> {
     Ok((i, ty))
 }
 
-fn parse_array(
-    components: Rc<Option<Vec<ParamEntry>>>,
-) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
+fn parse_array(components: Components) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
     move |input: &str| {
         let (i, ty) = parse_simple_type(components.clone())(input)?;
 
@@ -291,36 +843,56 @@ fn parse_array(
     }
 }
 
-fn parse_tuple(
-    components: Rc<Option<Vec<ParamEntry>>>,
-) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
+fn parse_tuple(components: Components) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
     move |input: &str| {
+        let components = match &components {
+            Components::Json(components) => components,
+            Components::Inline => return parse_tuple_literal(input),
+        };
+
         let (i, _) = map_error(tag("tuple")(input))?;
 
-        let tys = match components.clone().as_ref() {
+        let tys = match components.as_ref() {
             Some(cs) => cs
                 .clone()
                 .into_iter()
                 .try_fold(vec![], |mut param_tys, param| {
                     let comps = param.components.as_ref().cloned();
 
-                    let ty = match parse_exact_type(Rc::new(comps), &param.type_) {
+                    let ty = match parse_exact_type(Components::Json(Rc::new(comps)), &param.type_)
+                    {
                         Ok((_, ty)) => ty,
                         Err(_) => return Err(nom::Err::Failure(TypeParseError::Error)),
                     };
 
+                    let ty = name_struct(ty, param.internal_type.as_deref());
+
                     param_tys.push((param.name, ty));
 
                     Ok(param_tys)
                 }),
 
-            None => Err(nom::Err::Failure(TypeParseError::Error)),
+            None => Err(nom::Err::Error(TypeParseError::Error)),
         }?;
 
         Ok((i, Type::Tuple(tys)))
     }
 }
 
+// Parses `(T1,T2,...)`, optionally spelled `tuple(T1,T2,...)`.
+fn parse_tuple_literal(input: &str) -> TypeParseResult<&str, Type> {
+    let (i, _) = map_error(opt(tag("tuple"))(input))?;
+    let (i, tys) = delimited(
+        char('('),
+        separated_list0(char(','), parse_type(Components::Inline)),
+        char(')'),
+    )(i)?;
+
+    let tys = tys.into_iter().map(|ty| (String::new(), ty)).collect();
+
+    Ok((i, Type::Tuple(tys)))
+}
+
 fn parse_sized(t: &str) -> impl Fn(&str) -> IResult<&str, usize> + '_ {
     move |input: &str| {
         let (i, _) = tag(t)(input)?;
@@ -336,7 +908,7 @@ fn parse_integer(input: &str) -> IResult<&str, usize> {
 fn check_int_size(i: &usize) -> bool {
     let i = *i;
 
-    i > 0 && i <= 256 && i % 8 == 0
+    i > 0 && i <= 256 && i.is_multiple_of(8)
 }
 
 fn check_fixed_bytes_size(i: &usize) -> bool {
@@ -352,6 +924,101 @@ mod test {
     use pretty_assertions::assert_eq;
     use serde_json::json;
 
+    #[test]
+    fn parse_fixed_types() {
+        assert_eq!(parse_type_str("fixed128x18").unwrap(), Type::Fixed(128, 18));
+        assert_eq!(parse_type_str("ufixed8x0").unwrap(), Type::Ufixed(8, 0));
+        assert_eq!(parse_type_str("fixed").unwrap(), Type::Fixed(128, 18));
+        assert_eq!(
+            parse_type_str("ufixed[2]").unwrap(),
+            Type::FixedArray(Box::new(Type::Ufixed(128, 18)), 2)
+        );
+
+        // Aliases render in their canonical form, as required for selectors.
+        assert_eq!(
+            parse_type_str("ufixed").unwrap().to_string(),
+            "ufixed128x18"
+        );
+
+        assert!(parse_type_str("fixed7x2").is_err());
+        assert!(parse_type_str("fixed128x81").is_err());
+        assert!(parse_type_str("fixed128").is_err());
+    }
+
+    #[test]
+    fn serde_function() {
+        let v = json!({
+            "name": "callback",
+            "type": "function",
+            "internalType": "function (uint256) external returns (bool)",
+        });
+
+        let param: Param = serde_json::from_value(v.clone()).expect("param deserialized");
+
+        assert_eq!(param.type_, Type::Function);
+        assert_eq!(serde_json::to_value(&param).expect("param serialized"), v);
+        assert_eq!(
+            parse_type_str("function[]").unwrap(),
+            Type::Array(Box::new(Type::Function))
+        );
+    }
+
+    #[test]
+    fn user_defined_value_types() {
+        let param = |ty: &str, internal_type: &str| Param {
+            name: "x".into(),
+            type_: parse_type_str(ty).unwrap(),
+            indexed: None,
+            internal_type: Some(internal_type.into()),
+        };
+
+        assert_eq!(
+            param("uint256", "CurrencyId").user_type(),
+            Some("CurrencyId")
+        );
+        assert_eq!(
+            param("uint128[]", "Pricing.Price[]").user_type(),
+            Some("Price")
+        );
+        assert_eq!(param("uint256", "uint256").user_type(), None);
+        assert_eq!(param("address", "address payable").user_type(), None);
+        assert_eq!(param("address", "contract IERC20").user_type(), None);
+        assert_eq!(param("uint8", "enum Side").user_type(), None);
+        assert_eq!(param("(uint8)[2]", "struct S[2]").user_type(), None);
+
+        let decoded =
+            DecodedParam::from((param("uint256", "CurrencyId"), Value::Uint(1.into(), 256)));
+
+        assert_eq!(decoded.user_type(), Some("CurrencyId"));
+        assert_eq!(decoded.to_string(), "x: CurrencyId(1)");
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            json!({"name": "x", "type": "uint256", "userType": "CurrencyId", "value": "1"})
+        );
+
+        let params = DecodedParams::from(vec![
+            decoded,
+            (
+                param("uint128[]", "Pricing.Price[]"),
+                Value::Array(
+                    vec![Value::Uint(2.into(), 128), Value::Uint(3.into(), 128)],
+                    Type::Uint(128).into(),
+                ),
+            )
+                .into(),
+            (param("uint8", "uint8"), Value::Uint(4.into(), 8)).into(),
+        ]);
+
+        assert_eq!(
+            params.to_string(),
+            "x: CurrencyId(1), x: [Price(2), Price(3)], x: 4"
+        );
+        assert_eq!(
+            serde_json::to_value(&*params).unwrap()[2],
+            json!({"name": "x", "type": "uint8", "value": "4"})
+        );
+    }
+
     #[test]
     fn serde_uint() {
         for i in (8..=256).step_by(8) {
@@ -365,9 +1032,10 @@ mod test {
             assert_eq!(
                 param,
                 Param {
-                    name: "a".to_string(),
+                    name: "a".into(),
                     type_: Type::Uint(i),
-                    indexed: None
+                    indexed: None,
+                    internal_type: None
                 }
             );
 
@@ -390,9 +1058,10 @@ mod test {
             assert_eq!(
                 param,
                 Param {
-                    name: "a".to_string(),
+                    name: "a".into(),
                     type_: Type::Int(i),
-                    indexed: None
+                    indexed: None,
+                    internal_type: None
                 }
             );
 
@@ -414,9 +1083,10 @@ mod test {
         assert_eq!(
             param,
             Param {
-                name: "a".to_string(),
+                name: "a".into(),
                 type_: Type::Address,
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -437,9 +1107,10 @@ mod test {
         assert_eq!(
             param,
             Param {
-                name: "a".to_string(),
+                name: "a".into(),
                 type_: Type::Bool,
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -460,9 +1131,10 @@ mod test {
         assert_eq!(
             param,
             Param {
-                name: "a".to_string(),
+                name: "a".into(),
                 type_: Type::String,
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -484,9 +1156,10 @@ mod test {
             assert_eq!(
                 param,
                 Param {
-                    name: "a".to_string(),
+                    name: "a".into(),
                     type_: Type::FixedBytes(i),
-                    indexed: None
+                    indexed: None,
+                    internal_type: None
                 }
             );
 
@@ -505,9 +1178,10 @@ mod test {
         assert_eq!(
             param,
             Param {
-                name: "a".to_string(),
+                name: "a".into(),
                 type_: Type::Bytes,
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -527,9 +1201,10 @@ mod test {
         assert_eq!(
             param,
             Param {
-                name: "a".to_string(),
+                name: "a".into(),
                 type_: Type::Array(Box::new(Type::Uint(256))),
                 indexed: None,
+                internal_type: None,
             }
         );
 
@@ -549,9 +1224,10 @@ mod test {
         assert_eq!(
             param,
             Param {
-                name: "a".to_string(),
+                name: "a".into(),
                 type_: Type::Array(Box::new(Type::Array(Box::new(Type::Address)))),
                 indexed: None,
+                internal_type: None,
             }
         );
 
@@ -571,9 +1247,10 @@ mod test {
         assert_eq!(
             param,
             Param {
-                name: "a".to_string(),
+                name: "a".into(),
                 type_: Type::Array(Box::new(Type::FixedArray(Box::new(Type::String), 2))),
                 indexed: None,
+                internal_type: None,
             }
         );
 
@@ -591,9 +1268,10 @@ mod test {
         assert_eq!(
             param,
             Param {
-                name: "a".to_string(),
+                name: "a".into(),
                 type_: Type::FixedArray(Box::new(Type::Array(Box::new(Type::String))), 3),
                 indexed: None,
+                internal_type: None,
             }
         );
 
@@ -638,24 +1316,243 @@ mod test {
         assert_eq!(
             param,
             Param {
-                name: "s".to_string(),
+                name: "s".into(),
                 type_: Type::Tuple(vec![
-                    ("a".to_string(), Type::Uint(256)),
-                    ("b".to_string(), Type::Array(Box::new(Type::Uint(256)))),
+                    ("a".into(), Type::Uint(256)),
+                    ("b".into(), Type::Array(Box::new(Type::Uint(256)))),
                     (
-                        "c".to_string(),
+                        "c".into(),
                         Type::Array(Box::new(Type::Tuple(vec![
-                            ("x".to_string(), Type::Uint(256)),
-                            ("y".to_string(), Type::Uint(256))
+                            ("x".into(), Type::Uint(256)),
+                            ("y".into(), Type::Uint(256))
                         ])))
                     )
                 ]),
                 indexed: None,
+                internal_type: None,
             }
         );
 
         let param_json = serde_json::to_value(param).expect("param serialized");
 
         assert_eq!(v, param_json);
+
+        // Tuple literals are only for signatures, JSON ABIs use components.
+        for ty in [
+            "(uint256,bool)",
+            "tuple(uint256,bool)",
+            "(uint256)[]",
+            "tuple",
+        ] {
+            assert!(serde_json::from_value::<Param>(json!({"name": "s", "type": ty})).is_err());
+        }
+    }
+
+    #[test]
+    fn serde_internal_type() {
+        let v = serde_json::json!({
+            "name": "order",
+            "type": "tuple",
+            "internalType": "struct Exchange.Order",
+            "components": [
+                {
+                    "name": "maker",
+                    "type": "address",
+                }
+            ]
+        });
+
+        let param: Param = serde_json::from_value(v.clone()).expect("param deserialized");
+
+        assert_eq!(
+            param.internal_type.as_deref(),
+            Some("struct Exchange.Order")
+        );
+        assert_eq!(param.type_name(), "struct Exchange.Order");
+
+        let param_json = serde_json::to_value(&param).expect("param serialized");
+
+        assert_eq!(v, param_json);
+
+        let param: Param = serde_json::from_value(serde_json::json!({"name": "", "type": "uint8"}))
+            .expect("param deserialized");
+
+        assert_eq!(param.type_name(), "uint8");
+    }
+
+    #[test]
+    fn serde_named_structs() {
+        let v = serde_json::json!({
+            "name": "orders",
+            "type": "tuple[]",
+            "internalType": "struct Exchange.Order[]",
+            "components": [
+                {
+                    "name": "maker",
+                    "type": "address",
+                    "internalType": "address",
+                },
+                {
+                    "name": "fee",
+                    "type": "tuple",
+                    "internalType": "struct Fee",
+                    "components": [
+                        {
+                            "name": "bps",
+                            "type": "uint16",
+                            "internalType": "uint16",
+                        }
+                    ]
+                }
+            ]
+        });
+
+        let param: Param = serde_json::from_value(v).expect("param deserialized");
+
+        let fee = Type::Struct("Fee".into(), vec![("bps".into(), Type::Uint(16))]);
+
+        assert_eq!(
+            param.type_,
+            Type::Array(Box::new(Type::Struct(
+                "Exchange.Order".into(),
+                vec![("maker".into(), Type::Address), ("fee".into(), fee.clone()),]
+            )))
+        );
+        assert_eq!(param.type_.to_string(), "(address,(uint16))[]");
+        assert_eq!(fee.struct_name(), Some("Fee"));
+
+        // Only names differ from the plain tuple.
+        let tuple: Type = "(address,(uint16))[]".parse().unwrap();
+        assert_ne!(param.type_, tuple);
+        assert!(param.type_.abi_eq(&tuple));
+        assert!(!param.type_.abi_eq(&"(address,(uint8))[]".parse().unwrap()));
+
+        // Struct names survive a serialization round trip.
+        let param_json = serde_json::to_value(&param).expect("param serialized");
+        assert_eq!(param_json["internalType"], "struct Exchange.Order[]");
+        assert_eq!(param_json["components"][1]["internalType"], "struct Fee");
+
+        let de_param: Param = serde_json::from_value(param_json).expect("param deserialized");
+        assert_eq!(de_param, param);
+    }
+
+    #[test]
+    fn display_decoded_params() {
+        use ethereum_types::{H160, U256};
+
+        let param = |name: &str, type_| Param {
+            name: name.into(),
+            type_,
+            indexed: None,
+            internal_type: None,
+        };
+
+        let params = DecodedParams::from(vec![
+            (
+                param("to", Type::Address),
+                Value::Address(H160::from_low_u64_be(0xdead)),
+            ),
+            (
+                param("", Type::Tuple(vec![("fee".into(), Type::Uint(24))])),
+                Value::Tuple(vec![("fee".into(), Value::Uint(U256::from(3000), 24))]),
+            ),
+        ]);
+
+        assert_eq!(
+            params.to_string(),
+            "to: 0x000000000000000000000000000000000000dEaD, (3000)"
+        );
+        assert_eq!(
+            serde_json::to_value(&params).unwrap(),
+            json!({
+                "to": "0x000000000000000000000000000000000000dEaD",
+                "1": {"fee": "3000"}
+            })
+        );
+        assert_eq!(
+            format!("{:#}", params),
+            "to: 0x000000000000000000000000000000000000dEaD\n(\n    fee: 3000,\n)"
+        );
+    }
+
+    #[test]
+    fn access_decoded_params() {
+        use ethereum_types::U256;
+
+        let param = |name: &str| Param {
+            name: name.into(),
+            type_: Type::Uint(256),
+            indexed: None,
+            internal_type: None,
+        };
+        let uint = |v: u64| Value::Uint(U256::from(v), 256);
+
+        let params = DecodedParams::from(vec![
+            (param("amount"), uint(1)),
+            (param(""), uint(2)),
+            (param("fee"), uint(3)),
+        ]);
+
+        assert_eq!(params.get("fee"), Some(&uint(3)));
+        assert_eq!(params.get("to"), None);
+        assert_eq!(params["amount"], uint(1));
+        assert_eq!(params[1], uint(2));
+        assert_eq!(params.get_path("fee"), Some(&uint(3)));
+        assert_eq!(params.get_path("[1]"), Some(&uint(2)));
+        assert_eq!(params.get_path("fee.x"), None);
+        assert_eq!(params.get_path("[3]"), None);
+        assert_eq!(
+            params.entries().collect::<Vec<_>>(),
+            vec![("amount", &uint(1)), ("", &uint(2)), ("fee", &uint(3))]
+        );
+    }
+
+    #[test]
+    fn borrowed_decoded_params() {
+        use ethereum_types::{H160, U256};
+
+        let f = crate::Function::new(
+            "swap",
+            vec![
+                Param {
+                    name: "to".into(),
+                    type_: Type::Address,
+                    indexed: None,
+                    internal_type: None,
+                },
+                Param {
+                    name: "".into(),
+                    type_: Type::Tuple(vec![("fee".into(), Type::Uint(24))]),
+                    indexed: None,
+                    internal_type: None,
+                },
+            ],
+            vec![],
+            crate::StateMutability::NonPayable,
+        );
+        let values = vec![
+            Value::Address(H160::repeat_byte(1)),
+            Value::Tuple(vec![("fee".into(), Value::Uint(U256::from(3000), 24))]),
+        ];
+        let input = Value::encode(&values);
+
+        let borrowed = f.decode_input_ref(&input).unwrap();
+        let owned = f.decode_input_from_slice(&input).unwrap();
+
+        assert!(std::ptr::eq(borrowed.params(), &f.inputs[..]));
+        assert_eq!(borrowed.len(), 2);
+        assert_eq!(borrowed["to"], values[0]);
+        assert_eq!(borrowed.get_path("[1].fee"), owned.get_path("[1].fee"));
+        assert_eq!(
+            borrowed.entries().collect::<Vec<_>>(),
+            owned.entries().collect::<Vec<_>>()
+        );
+        assert_eq!(borrowed.to_string(), owned.to_string());
+        assert_eq!(
+            serde_json::to_value(&borrowed).unwrap(),
+            serde_json::to_value(&owned).unwrap()
+        );
+        assert_eq!(borrowed.clone().into_values(), values);
+        assert_eq!(borrowed.into_owned(), owned);
     }
 }
@@ -0,0 +1,375 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    artifact::{normalize_bytecode, parse_method_identifiers},
+    Abi, Artifact,
+};
+
+/// Set of contracts produced by a single compiler run.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Project {
+    /// Contracts keyed by contract name.
+    ///
+    /// Contracts whose name is defined in more than one source file are keyed
+    /// by their fully qualified `<source>:<name>` instead.
+    pub contracts: BTreeMap<String, Artifact>,
+}
+
+impl Project {
+    /// Parses the output of `solc --standard-json`.
+    ///
+    /// Storage layouts and NatSpec documentation are picked up when they were
+    /// requested through `outputSelection`. Fails if the compiler reported any
+    /// error.
+    pub fn from_solc_output(json: &str) -> Result<Self> {
+        let output: SolcOutput = serde_json::from_str(json)?;
+
+        let errors: Vec<_> = output
+            .errors
+            .iter()
+            .filter(|e| e.severity == "error")
+            .map(|e| e.formatted_message.as_deref().unwrap_or(&e.message))
+            .collect();
+
+        if !errors.is_empty() {
+            return Err(anyhow!("compilation failed:\n{}", errors.join("\n")));
+        }
+
+        let mut artifacts = vec![];
+
+        for (source_name, contracts) in output.contracts {
+            for (contract_name, contract) in contracts {
+                let evm = contract.evm.unwrap_or_default();
+
+                let artifact = Artifact {
+                    contract_name: Some(contract_name),
+                    source_name: Some(source_name.clone()),
+                    abi: contract.abi.unwrap_or_default(),
+                    bytecode: normalize_bytecode(evm.bytecode.map(|code| code.object)),
+                    deployed_bytecode: normalize_bytecode(
+                        evm.deployed_bytecode.map(|code| code.object),
+                    ),
+                    method_identifiers: parse_method_identifiers(evm.method_identifiers)?,
+                    networks: BTreeMap::new(),
+                    storage_layout: contract.storage_layout,
+                    userdoc: contract.userdoc,
+                    devdoc: contract.devdoc,
+                    compiler_version: None,
+                    compiler_settings: None,
+                }
+                .with_natspec();
+
+                artifact.check_method_identifiers()?;
+
+                artifacts.push(artifact);
+            }
+        }
+
+        Ok(Self::from_artifacts(artifacts))
+    }
+
+    /// Parses the output of `solc --combined-json`.
+    ///
+    /// Needs at least the `abi` output; `bin`, `bin-runtime`, `hashes`,
+    /// `storage-layout`, `userdoc` and `devdoc` are picked up when present.
+    /// Older compilers emit these as JSON encoded strings, which is handled
+    /// transparently.
+    pub fn from_combined_json(json: &str) -> Result<Self> {
+        let output: CombinedJson = serde_json::from_str(json)?;
+
+        let mut artifacts = vec![];
+
+        for (qualified_name, contract) in output.contracts {
+            let (source_name, contract_name) = match qualified_name.rsplit_once(':') {
+                Some((source, name)) => (Some(source.to_string()), name.to_string()),
+                None => (None, qualified_name),
+            };
+
+            let abi = match contract.abi {
+                Some(abi) => serde_json::from_value(unstringify(abi)?)?,
+                None => return Err(anyhow!("missing abi output for {}", contract_name)),
+            };
+
+            let artifact = Artifact {
+                contract_name: Some(contract_name),
+                source_name,
+                abi,
+                bytecode: normalize_bytecode(contract.bin),
+                deployed_bytecode: normalize_bytecode(contract.bin_runtime),
+                method_identifiers: parse_method_identifiers(contract.hashes)?,
+                networks: BTreeMap::new(),
+                storage_layout: contract.storage_layout.map(unstringify).transpose()?,
+                userdoc: contract.userdoc.map(unstringify).transpose()?,
+                devdoc: contract.devdoc.map(unstringify).transpose()?,
+                compiler_version: None,
+                compiler_settings: None,
+            }
+            .with_natspec();
+
+            artifact.check_method_identifiers()?;
+
+            artifacts.push(artifact);
+        }
+
+        Ok(Self::from_artifacts(artifacts))
+    }
+
+    /// Returns a contract by name or by fully qualified `<source>:<name>`.
+    pub fn get(&self, name: &str) -> Option<&Artifact> {
+        if let Some(artifact) = self.contracts.get(name) {
+            return Some(artifact);
+        }
+
+        let (source_name, contract_name) = name.rsplit_once(':')?;
+
+        self.contracts.values().find(|artifact| {
+            artifact.source_name.as_deref() == Some(source_name)
+                && artifact.contract_name.as_deref() == Some(contract_name)
+        })
+    }
+
+    /// Returns the ABI of a contract, see [`Project::get`].
+    pub fn abi(&self, name: &str) -> Option<&Abi> {
+        self.get(name).map(|artifact| &artifact.abi)
+    }
+
+    /// Iterates over all (name, contract) pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Artifact)> {
+        self.contracts.iter()
+    }
+
+    /// Returns the number of contracts.
+    pub fn len(&self) -> usize {
+        self.contracts.len()
+    }
+
+    /// Returns whether the project has no contracts.
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+    }
+
+    pub(crate) fn from_artifacts(artifacts: Vec<Artifact>) -> Self {
+        let mut name_count: HashMap<String, usize> = HashMap::new();
+        for artifact in &artifacts {
+            *name_count
+                .entry(artifact.contract_name.clone().unwrap_or_default())
+                .or_default() += 1;
+        }
+
+        let contracts = artifacts
+            .into_iter()
+            .map(|artifact| {
+                let name = artifact.contract_name.clone().unwrap_or_default();
+
+                let key = if name_count[&name] > 1 {
+                    format!(
+                        "{}:{}",
+                        artifact.source_name.as_deref().unwrap_or_default(),
+                        name
+                    )
+                } else {
+                    name
+                };
+
+                (key, artifact)
+            })
+            .collect();
+
+        Self { contracts }
+    }
+}
+
+// Parses values that older compilers emit as JSON encoded strings.
+fn unstringify(value: serde_json::Value) -> Result<serde_json::Value> {
+    match value {
+        serde_json::Value::String(s) => Ok(serde_json::from_str(&s)?),
+        value => Ok(value),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CombinedJson {
+    contracts: BTreeMap<String, CombinedContract>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CombinedContract {
+    #[serde(default)]
+    abi: Option<serde_json::Value>,
+    #[serde(default)]
+    bin: Option<String>,
+    #[serde(default)]
+    bin_runtime: Option<String>,
+    #[serde(default)]
+    hashes: BTreeMap<String, String>,
+    #[serde(default)]
+    storage_layout: Option<serde_json::Value>,
+    #[serde(default)]
+    userdoc: Option<serde_json::Value>,
+    #[serde(default)]
+    devdoc: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SolcOutput {
+    #[serde(default)]
+    errors: Vec<SolcError>,
+    #[serde(default)]
+    contracts: BTreeMap<String, BTreeMap<String, SolcContract>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SolcError {
+    severity: String,
+    message: String,
+    #[serde(default)]
+    formatted_message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SolcContract {
+    #[serde(default)]
+    abi: Option<Abi>,
+    #[serde(default)]
+    evm: Option<SolcEvm>,
+    #[serde(default)]
+    storage_layout: Option<serde_json::Value>,
+    #[serde(default)]
+    userdoc: Option<serde_json::Value>,
+    #[serde(default)]
+    devdoc: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SolcEvm {
+    #[serde(default)]
+    bytecode: Option<SolcBytecode>,
+    #[serde(default)]
+    deployed_bytecode: Option<SolcBytecode>,
+    #[serde(default)]
+    method_identifiers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SolcBytecode {
+    object: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    const SOLC_OUTPUT: &str = r#"{
+        "errors": [
+            {"severity": "warning", "message": "Unused local variable.", "type": "Warning"}
+        ],
+        "sources": {"contracts/Token.sol": {"id": 0}, "contracts/Vault.sol": {"id": 1}},
+        "contracts": {
+            "contracts/Token.sol": {
+                "Token": {
+                    "abi": [
+                        {"inputs": [{"name": "account", "type": "address"}], "name": "balanceOf", "outputs": [{"name": "", "type": "uint256"}], "stateMutability": "view", "type": "function"}
+                    ],
+                    "evm": {
+                        "bytecode": {"object": "6080604052", "linkReferences": {}},
+                        "deployedBytecode": {"object": "60806040"},
+                        "methodIdentifiers": {"balanceOf(address)": "70a08231"}
+                    },
+                    "storageLayout": {"storage": [{"label": "balances", "slot": "0"}], "types": {}},
+                    "userdoc": {"kind": "user", "methods": {"balanceOf(address)": {"notice": "Returns the balance of `account`."}}, "version": 1}
+                },
+                "IERC20": {"abi": []}
+            },
+            "contracts/Vault.sol": {
+                "Vault": {"abi": []},
+                "IERC20": {"abi": []}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn solc_standard_json() {
+        let project = Project::from_solc_output(SOLC_OUTPUT).unwrap();
+
+        assert_eq!(
+            project.contracts.keys().collect::<Vec<_>>(),
+            vec![
+                "Token",
+                "Vault",
+                "contracts/Token.sol:IERC20",
+                "contracts/Vault.sol:IERC20"
+            ]
+        );
+
+        let token = project.get("Token").unwrap();
+        assert_eq!(token.abi.functions[0].name, "balanceOf");
+        assert_eq!(token.bytecode.as_deref(), Some("6080604052"));
+        assert_eq!(
+            token.storage_layout.as_ref().unwrap()["storage"][0]["label"],
+            "balances"
+        );
+        assert!(token.userdoc.is_some());
+        assert_eq!(token.devdoc, None);
+        assert_eq!(
+            token.abi.functions[0].docs().unwrap().notice.as_deref(),
+            Some("Returns the balance of `account`.")
+        );
+
+        assert_eq!(project.get("contracts/Token.sol:Token"), Some(token));
+        assert!(project.abi("Vault").is_some());
+        assert!(project.get("IERC20").is_none());
+    }
+
+    #[test]
+    fn solc_compilation_error() {
+        let res = Project::from_solc_output(
+            r#"{"errors": [{"severity": "error", "message": "Expected ';'", "formattedMessage": "ParserError: Expected ';'"}]}"#,
+        );
+
+        assert!(res.unwrap_err().to_string().contains("ParserError"));
+    }
+
+    #[test]
+    fn solc_combined_json() {
+        let json = serde_json::json!({
+            "contracts": {
+                "contracts/Token.sol:Token": {
+                    "abi": [
+                        {"inputs": [{"name": "account", "type": "address"}], "name": "balanceOf", "outputs": [{"name": "", "type": "uint256"}], "stateMutability": "view", "type": "function"}
+                    ],
+                    "bin": "6080604052",
+                    "bin-runtime": "60806040",
+                    "hashes": {"balanceOf(address)": "70a08231"}
+                },
+                // solc < 0.8 encodes outputs as strings.
+                "contracts/Old.sol:Old": {
+                    "abi": "[{\"constant\":true,\"inputs\":[],\"name\":\"x\",\"outputs\":[{\"name\":\"\",\"type\":\"uint256\"}],\"payable\":false,\"stateMutability\":\"view\",\"type\":\"function\"}]",
+                    "bin": "",
+                    "devdoc": "{\"methods\":{}}"
+                }
+            },
+            "version": "0.8.19+commit.7dd6d404.Linux.g++"
+        })
+        .to_string();
+
+        let project = Project::from_combined_json(&json).unwrap();
+
+        let token = project.get("Token").unwrap();
+        assert_eq!(token.source_name.as_deref(), Some("contracts/Token.sol"));
+        assert_eq!(token.deployed_bytecode.as_deref(), Some("60806040"));
+        assert_eq!(token.method_identifiers.len(), 1);
+
+        let old = project.get("Old").unwrap();
+        assert_eq!(old.abi.functions[0].name, "x");
+        assert_eq!(old.bytecode, None);
+        assert_eq!(old.devdoc, Some(serde_json::json!({"methods": {}})));
+    }
+}
@@ -0,0 +1,339 @@
+//! Bridge writing decoded params into protobuf messages.
+
+use anyhow::{anyhow, Result};
+use prost_reflect::{
+    prost::bytes::Bytes, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor, ReflectMessage,
+};
+
+use crate::{codegen::field_names, DecodedParams, Value, I256};
+
+impl DecodedParams {
+    /// Writes the params into a new message of the given type, without a
+    /// JSON hop.
+    ///
+    /// Params go to the fields named as in
+    /// [`proto_schema`](crate::codegen::proto_schema), and params with no
+    /// such field are left out. Values are converted to the field types
+    /// `proto_schema` generates: integers may also go to narrower or wider
+    /// integer fields they fit in, or to `string` fields in decimal, and
+    /// addresses to `bytes` or `string` fields.
+    ///
+    /// To fill a prost struct without a descriptor of its own, transcode the
+    /// message with [`DynamicMessage::transcode_to`].
+    pub fn to_dynamic_message(&self, descriptor: &MessageDescriptor) -> Result<DynamicMessage> {
+        message(
+            descriptor,
+            self.iter()
+                .map(|decoded| (decoded.param.name.as_str(), &decoded.value)),
+        )
+    }
+
+    /// Writes the params into a prost message, see
+    /// [`DecodedParams::to_dynamic_message`].
+    pub fn to_message<T: ReflectMessage + Default>(&self) -> Result<T> {
+        self.to_dynamic_message(&T::default().descriptor())?
+            .transcode_to()
+            .map_err(|e| anyhow!("{}", e))
+    }
+}
+
+fn message<'a>(
+    descriptor: &MessageDescriptor,
+    values: impl Iterator<Item = (&'a str, &'a Value)> + Clone,
+) -> Result<DynamicMessage> {
+    let mut message = DynamicMessage::new(descriptor.clone());
+    let names = field_names(values.clone().map(|(name, _)| name));
+
+    for (name, (_, value)) in names.iter().zip(values) {
+        let field = match descriptor.get_field_by_name(name) {
+            Some(field) => field,
+            None => continue,
+        };
+
+        let value = if field.is_list() {
+            match value {
+                Value::Array(values, _) | Value::FixedArray(values, _) => {
+                    prost_reflect::Value::List(
+                        values
+                            .iter()
+                            .map(|value| field_value(&field, value))
+                            .collect::<Result<_>>()?,
+                    )
+                }
+                _ => return Err(mismatch(&field, value)),
+            }
+        } else {
+            field_value(&field, value)?
+        };
+
+        message
+            .try_set_field(&field, value)
+            .map_err(|e| anyhow!("{}", e))?;
+    }
+
+    Ok(message)
+}
+
+fn field_value(field: &FieldDescriptor, value: &Value) -> Result<prost_reflect::Value> {
+    use prost_reflect::Value as Proto;
+
+    let integer = match value {
+        Value::Uint(v, _) => Some(I256::from_raw(*v)).filter(|_| v.bits() < 256),
+        Value::Int(v, size) => Some(I256::sign_extend(*v, *size)),
+        _ => None,
+    };
+    let int = |bits: usize| {
+        integer
+            .filter(|i| i.fits(bits))
+            .map(|i| i.into_raw().low_u64() as i64)
+    };
+    let uint = |bits: usize| {
+        integer
+            .filter(|i| !i.is_negative() && i.into_raw().bits() <= bits)
+            .map(|i| i.into_raw().low_u64())
+    };
+
+    let converted = match (field.kind(), value) {
+        (Kind::Bool, Value::Bool(b)) => Some(Proto::Bool(*b)),
+
+        (Kind::Uint32 | Kind::Fixed32, _) => uint(32).map(|v| Proto::U32(v as u32)),
+        (Kind::Uint64 | Kind::Fixed64, _) => uint(64).map(Proto::U64),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, _) => int(32).map(|v| Proto::I32(v as i32)),
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, _) => int(64).map(Proto::I64),
+        (Kind::Enum(_), _) => int(32).map(|v| Proto::EnumNumber(v as i32)),
+
+        (Kind::String, Value::String(s)) => Some(Proto::String(s.clone())),
+        (
+            Kind::String,
+            Value::Uint(..)
+            | Value::Int(..)
+            | Value::Fixed(..)
+            | Value::Ufixed(..)
+            | Value::Address(_),
+        ) => Some(Proto::String(value.to_string())),
+
+        (Kind::Bytes, Value::Address(address)) => {
+            Some(Proto::Bytes(address.as_bytes().to_vec().into()))
+        }
+        (Kind::Bytes, Value::Function(address, selector)) => {
+            let mut bytes = address.as_bytes().to_vec();
+            bytes.extend_from_slice(selector);
+            Some(Proto::Bytes(bytes.into()))
+        }
+        (Kind::Bytes, Value::FixedBytes(bytes)) => Some(Proto::Bytes(bytes.clone().into())),
+        (Kind::Bytes, Value::Bytes(bytes)) => Some(Proto::Bytes(Bytes::copy_from_slice(bytes))),
+        (Kind::Bytes, Value::Hash(hash)) => Some(Proto::Bytes(hash.as_bytes().to_vec().into())),
+        (Kind::Bytes, Value::Uint(v, _)) => {
+            let mut bytes = [0u8; 32];
+            v.to_big_endian(&mut bytes);
+            Some(Proto::Bytes(bytes.to_vec().into()))
+        }
+        // Arrays of arrays.
+        (Kind::Bytes, Value::Array(..) | Value::FixedArray(..)) => Some(Proto::Bytes(
+            Value::encode(std::slice::from_ref(value)).into(),
+        )),
+
+        (Kind::Message(descriptor), Value::Tuple(fields)) => Some(Proto::Message(message(
+            &descriptor,
+            fields.iter().map(|(name, value)| (name.as_str(), value)),
+        )?)),
+
+        _ => None,
+    };
+
+    converted.ok_or_else(|| mismatch(field, value))
+}
+
+fn mismatch(field: &FieldDescriptor, value: &Value) -> anyhow::Error {
+    anyhow!(
+        "cannot write {} into field {} of type {:?}",
+        value,
+        field.full_name(),
+        field.kind()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::{H160, U256};
+    use pretty_assertions::assert_eq;
+    use prost_reflect::{
+        prost_types::{
+            field_descriptor_proto::{Label, Type as ProtoType},
+            DescriptorProto, FieldDescriptorProto, FileDescriptorProto,
+        },
+        DescriptorPool,
+    };
+    use std::sync::LazyLock;
+
+    use super::*;
+    use crate::Abi;
+
+    fn field(name: &str, number: i32, ty: ProtoType, label: Label) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            r#type: Some(ty as i32),
+            label: Some(label as i32),
+            ..Default::default()
+        }
+    }
+
+    // A message laid out like `proto_schema` generates it, but with `to` as
+    // a string and without the `skipped` param.
+    static POOL: LazyLock<DescriptorPool> = LazyLock::new(|| {
+        let mut memo = field("memo", 4, ProtoType::Message, Label::Optional);
+        memo.type_name = Some(".token.v1.TransferEvent.Memo".to_string());
+
+        let file = FileDescriptorProto {
+            name: Some("token.proto".to_string()),
+            package: Some("token.v1".to_string()),
+            syntax: Some("proto3".to_string()),
+            message_type: vec![DescriptorProto {
+                name: Some("TransferEvent".to_string()),
+                field: vec![
+                    field("from", 1, ProtoType::Bytes, Label::Optional),
+                    field("to", 2, ProtoType::String, Label::Optional),
+                    field("value", 3, ProtoType::String, Label::Optional),
+                    memo,
+                    field("ids", 5, ProtoType::Uint64, Label::Repeated),
+                ],
+                nested_type: vec![DescriptorProto {
+                    name: Some("Memo".to_string()),
+                    field: vec![
+                        field("arg0", 1, ProtoType::Int32, Label::Optional),
+                        field("note", 2, ProtoType::String, Label::Optional),
+                    ],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        DescriptorPool::from_file_descriptor_set(prost_reflect::prost_types::FileDescriptorSet {
+            file: vec![file],
+        })
+        .unwrap()
+    });
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct TransferEvent {
+        #[prost(bytes = "vec", tag = "1")]
+        from: Vec<u8>,
+        #[prost(string, tag = "2")]
+        to: String,
+        #[prost(string, tag = "3")]
+        value: String,
+        #[prost(uint64, repeated, tag = "5")]
+        ids: Vec<u64>,
+    }
+
+    impl ReflectMessage for TransferEvent {
+        fn descriptor(&self) -> MessageDescriptor {
+            POOL.get_message_by_name("token.v1.TransferEvent").unwrap()
+        }
+    }
+
+    fn transfer() -> DecodedParams {
+        let abi: Abi = serde_json::from_str(
+            r#"[{"type": "function", "name": "transfer", "inputs": [
+                {"name": "from", "type": "address"},
+                {"name": "to", "type": "address"},
+                {"name": "value", "type": "uint256"},
+                {"name": "memo", "type": "tuple", "components": [{"name": "", "type": "int8"}, {"name": "note", "type": "string"}]},
+                {"name": "ids", "type": "uint64[]"},
+                {"name": "skipped", "type": "bool"}
+            ], "outputs": [], "stateMutability": "nonpayable"}]"#,
+        )
+        .unwrap();
+
+        let input = abi.functions[0]
+            .encode_input(&[
+                Value::Address(H160::repeat_byte(1)),
+                Value::Address(H160::repeat_byte(0xab)),
+                Value::Uint(U256::exp10(20), 256),
+                Value::Tuple(vec![
+                    ("".into(), Value::Int(I256::from(-3i64).into_raw(), 8)),
+                    ("note".into(), Value::String("gm".to_string())),
+                ]),
+                Value::Array(
+                    vec![Value::Uint(7.into(), 64), Value::Uint(8.into(), 64)],
+                    crate::Type::Uint(64).into(),
+                ),
+                Value::Bool(true),
+            ])
+            .unwrap();
+
+        abi.functions[0]
+            .decode_input_from_slice(&input[4..])
+            .unwrap()
+    }
+
+    #[test]
+    fn params_to_messages() {
+        let descriptor = POOL.get_message_by_name("token.v1.TransferEvent").unwrap();
+        let message = transfer().to_dynamic_message(&descriptor).unwrap();
+
+        let get = |name: &str| message.get_field_by_name(name).unwrap().into_owned();
+
+        assert_eq!(
+            get("from").as_bytes().unwrap().as_ref(),
+            H160::repeat_byte(1).as_bytes()
+        );
+        assert_eq!(
+            get("to").as_str(),
+            Some("0xABaBaBaBABabABabAbAbABAbABabababaBaBABaB")
+        );
+        assert_eq!(get("value").as_str(), Some("100000000000000000000"));
+        assert_eq!(
+            get("ids").as_list().unwrap(),
+            &[prost_reflect::Value::U64(7), prost_reflect::Value::U64(8)]
+        );
+
+        let memo = get("memo");
+        let memo = memo.as_message().unwrap();
+        assert_eq!(memo.get_field_by_name("arg0").unwrap().as_i32(), Some(-3));
+        assert_eq!(memo.get_field_by_name("note").unwrap().as_str(), Some("gm"));
+
+        let event: TransferEvent = transfer().to_message().unwrap();
+        assert_eq!(
+            event,
+            TransferEvent {
+                from: H160::repeat_byte(1).as_bytes().to_vec(),
+                to: "0xABaBaBaBABabABabAbAbABAbABabababaBaBABaB".to_string(),
+                value: "100000000000000000000".to_string(),
+                ids: vec![7, 8],
+            }
+        );
+    }
+
+    #[test]
+    fn reject_mismatched_values() {
+        let descriptor = POOL
+            .get_message_by_name("token.v1.TransferEvent.Memo")
+            .unwrap();
+
+        let params = |value: Value| {
+            DecodedParams::from(vec![(
+                crate::Param {
+                    name: "".into(),
+                    type_: crate::Type::Bool,
+                    indexed: None,
+                    internal_type: None,
+                },
+                value,
+            )])
+        };
+
+        assert!(params(Value::Bool(true))
+            .to_dynamic_message(&descriptor)
+            .is_err());
+        assert!(params(Value::Uint(U256::from(1u64 << 40), 64))
+            .to_dynamic_message(&descriptor)
+            .is_err());
+        assert!(params(Value::Uint(U256::from(5), 8))
+            .to_dynamic_message(&descriptor)
+            .is_ok());
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::Result;
+use ethereum_types::H160;
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{address::to_checksum, Abi};
+
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin};
+
+/// Source of contract ABIs keyed by chain and address, such as a block
+/// explorer or a local cache.
+///
+/// Providers return `Ok(None)` for contracts they do not know, and reserve
+/// errors for failed lookups.
+pub trait AbiProvider {
+    /// Returns the ABI of the contract at the given address.
+    fn get_abi(&self, chain_id: u64, address: &H160) -> Result<Option<Abi>>;
+}
+
+/// Future returned by [`AsyncAbiProvider::get_abi_async`].
+#[cfg(feature = "async")]
+pub type AbiFuture<'a> = Pin<Box<dyn Future<Output = Result<Option<Abi>>> + Send + 'a>>;
+
+/// [`AbiProvider`] whose lookups do not block the calling task.
+#[cfg(feature = "async")]
+pub trait AsyncAbiProvider {
+    /// Returns the ABI of the contract at the given address.
+    fn get_abi_async<'a>(&'a self, chain_id: u64, address: &'a H160) -> AbiFuture<'a>;
+}
+
+/// Provider reading ABIs from a directory, one file per contract.
+///
+/// Files are looked up at `<dir>/<chain id>/<address>.json`, with the
+/// address either in lowercase or checksummed, and hold either a bare ABI or
+/// a build artifact.
+///
+/// ```no_run
+/// use ethereum_abi::{AbiProvider, DirectoryProvider};
+/// use ethereum_types::H160;
+///
+/// let provider = DirectoryProvider::new("abis");
+/// let abi = provider.get_abi(1, &H160::zero()).unwrap();
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DirectoryProvider {
+    dir: PathBuf,
+}
+
+impl DirectoryProvider {
+    /// Creates a provider reading from the given directory.
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns the directory ABIs are read from.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl AbiProvider for DirectoryProvider {
+    fn get_abi(&self, chain_id: u64, address: &H160) -> Result<Option<Abi>> {
+        let chain_dir = self.dir.join(chain_id.to_string());
+
+        for name in &[format!("{:?}", address), to_checksum(address)] {
+            let json = match fs::read_to_string(chain_dir.join(format!("{}.json", name))) {
+                Ok(json) => json,
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+
+            let value: serde_json::Value = serde_json::from_str(&json)?;
+
+            return if value.is_array() {
+                Ok(Some(serde_json::from_value(value)?))
+            } else {
+                Ok(Some(Abi::from_artifact_value(value)?.abi))
+            };
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncAbiProvider for DirectoryProvider {
+    fn get_abi_async<'a>(&'a self, chain_id: u64, address: &'a H160) -> AbiFuture<'a> {
+        Box::pin(std::future::ready(self.get_abi(chain_id, address)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const ABI: &str = r#"[{"type":"function","name":"totalSupply","inputs":[],"outputs":[{"name":"","type":"uint256"}],"stateMutability":"view"}]"#;
+
+    #[test]
+    fn directory_provider() {
+        let dir = std::env::temp_dir().join(format!("ethereum_abi_{}", std::process::id()));
+        fs::create_dir_all(dir.join("1")).unwrap();
+
+        let bare = H160::repeat_byte(0xab);
+        let artifact = H160::repeat_byte(0xcd);
+
+        fs::write(dir.join("1").join(format!("{:?}.json", bare)), ABI).unwrap();
+        fs::write(
+            dir.join("1")
+                .join(format!("{}.json", to_checksum(&artifact))),
+            format!(r#"{{"contractName":"Token","abi":{}}}"#, ABI),
+        )
+        .unwrap();
+
+        let provider = DirectoryProvider::new(&dir);
+
+        for address in &[bare, artifact] {
+            let abi = provider.get_abi(1, address).unwrap().unwrap();
+            assert_eq!(abi.functions[0].signature(), "totalSupply()");
+        }
+
+        assert_eq!(provider.get_abi(5, &bare).unwrap(), None);
+        assert_eq!(provider.get_abi(1, &H160::zero()).unwrap(), None);
+
+        fs::write(dir.join("1").join(format!("{:?}.json", bare)), "{").unwrap();
+        assert!(provider.get_abi(1, &bare).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_directory_provider() {
+        use std::task::{Context, Poll, Waker};
+
+        let provider = DirectoryProvider::new(std::env::temp_dir().join("ethereum_abi_missing"));
+        let address = H160::zero();
+
+        let mut future = provider.get_abi_async(1, &address);
+        let poll = future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()));
+
+        assert!(matches!(poll, Poll::Ready(Ok(None))));
+    }
+}
@@ -0,0 +1,205 @@
+use ethereum_types::{H160, H256};
+
+/// EIP-1967 storage slot holding a proxy's implementation address:
+/// `keccak256("eip1967.proxy.implementation") - 1`.
+pub const EIP1967_IMPLEMENTATION_SLOT: H256 = H256([
+    0x36, 0x08, 0x94, 0xa1, 0x3b, 0xa1, 0xa3, 0x21, 0x06, 0x67, 0xc8, 0x28, 0x49, 0x2d, 0xb9, 0x8d,
+    0xca, 0x3e, 0x20, 0x76, 0xcc, 0x37, 0x35, 0xa9, 0x20, 0xa3, 0xca, 0x50, 0x5d, 0x38, 0x2b, 0xbc,
+]);
+
+/// EIP-1967 storage slot holding a beacon proxy's beacon address:
+/// `keccak256("eip1967.proxy.beacon") - 1`.
+pub const EIP1967_BEACON_SLOT: H256 = H256([
+    0xa3, 0xf0, 0xad, 0x74, 0xe5, 0x42, 0x3a, 0xeb, 0xfd, 0x80, 0xd3, 0xef, 0x43, 0x46, 0x57, 0x83,
+    0x35, 0xa9, 0xa7, 0x2a, 0xea, 0xee, 0x59, 0xff, 0x6c, 0xb3, 0x58, 0x2b, 0x35, 0x13, 0x3d, 0x50,
+]);
+
+/// EIP-1967 storage slot holding a proxy's admin address:
+/// `keccak256("eip1967.proxy.admin") - 1`.
+pub const EIP1967_ADMIN_SLOT: H256 = H256([
+    0xb5, 0x31, 0x27, 0x68, 0x4a, 0x56, 0x8b, 0x31, 0x73, 0xae, 0x13, 0xb9, 0xf8, 0xa6, 0x01, 0x6e,
+    0x24, 0x3e, 0x63, 0xb6, 0xe8, 0xee, 0x11, 0x78, 0xd6, 0xa7, 0x17, 0x85, 0x0b, 0x5d, 0x61, 0x03,
+]);
+
+/// EIP-1822 (UUPS) storage slot holding a proxy's implementation address:
+/// `keccak256("PROXIABLE")`.
+pub const EIP1822_PROXIABLE_SLOT: H256 = H256([
+    0xc5, 0xf1, 0x6f, 0x0f, 0xcc, 0x63, 0x9f, 0xa4, 0x8a, 0x69, 0x47, 0x83, 0x6d, 0x98, 0x50, 0xf5,
+    0x04, 0x79, 0x85, 0x23, 0xbf, 0x8c, 0x9a, 0x3a, 0x87, 0xd5, 0x87, 0x6c, 0xf6, 0x22, 0xbc, 0xf7,
+]);
+
+/// Storage slot of the implementation address of proxies predating EIP-1967,
+/// such as USDC's: `keccak256("org.zeppelinos.proxy.implementation")`.
+pub const ZEPPELINOS_IMPLEMENTATION_SLOT: H256 = H256([
+    0x70, 0x50, 0xc9, 0xe0, 0xf4, 0xca, 0x76, 0x9c, 0x69, 0xbd, 0x3a, 0x8e, 0xf7, 0x40, 0xbc, 0x37,
+    0x93, 0x4f, 0x8e, 0x2c, 0x03, 0x6e, 0x5a, 0x72, 0x3f, 0xd8, 0xee, 0x04, 0x8e, 0xd3, 0xf8, 0xc3,
+]);
+
+/// Storage slots a proxy may keep its implementation address in, in the
+/// order to read them.
+///
+/// A beacon proxy keeps the address of its beacon in
+/// [`EIP1967_BEACON_SLOT`] instead, and the beacon returns the
+/// implementation from `implementation()`.
+pub const IMPLEMENTATION_SLOTS: [H256; 3] = [
+    EIP1967_IMPLEMENTATION_SLOT,
+    EIP1822_PROXIABLE_SLOT,
+    ZEPPELINOS_IMPLEMENTATION_SLOT,
+];
+
+// Runtime code of an EIP-1167 minimal proxy, around the implementation
+// address, and of its ERC-7511 variant using PUSH0.
+const EIP1167_PREFIX: [u8; 10] = [0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73];
+const EIP1167_SUFFIX: [u8; 15] = [
+    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+];
+const EIP1167_PUSH0_PREFIX: [u8; 9] = [0x36, 0x5f, 0x5f, 0x37, 0x5f, 0x5f, 0x36, 0x5f, 0x73];
+const EIP1167_PUSH0_SUFFIX: [u8; 16] = [
+    0x5a, 0xf4, 0x3d, 0x5f, 0x5f, 0x3e, 0x60, 0x29, 0x57, 0x3d, 0x5f, 0xfd, 0x5b, 0x3d, 0x5f, 0xf3,
+];
+
+/// Reads the address stored in a proxy slot, as returned by
+/// `eth_getStorageAt`.
+///
+/// Returns `None` for an unset slot, or one holding more than an address.
+pub fn slot_address(value: &H256) -> Option<H160> {
+    let (padding, address) = value.as_bytes().split_at(12);
+
+    if padding.iter().any(|b| *b != 0) || address.iter().all(|b| *b == 0) {
+        return None;
+    }
+
+    Some(H160::from_slice(address))
+}
+
+/// Returns the implementation an EIP-1167 minimal proxy, or its ERC-7511
+/// variant, forwards every call to, given the proxy's runtime bytecode.
+pub fn minimal_proxy_implementation(bytecode: &[u8]) -> Option<H160> {
+    let forwards_to = |prefix: &[u8], suffix: &[u8]| {
+        let address = bytecode.strip_prefix(prefix)?.strip_suffix(suffix)?;
+
+        (address.len() == 20).then(|| H160::from_slice(address))
+    };
+
+    forwards_to(&EIP1167_PREFIX, &EIP1167_SUFFIX)
+        .or_else(|| forwards_to(&EIP1167_PUSH0_PREFIX, &EIP1167_PUSH0_SUFFIX))
+}
+
+/// Guesses from its runtime bytecode whether a contract is a proxy: either
+/// a minimal proxy, or a contract that delegates calls and refers to one of
+/// the [implementation slots](IMPLEMENTATION_SLOTS) or to the beacon slot.
+///
+/// Only a guess: UUPS implementations, which write the implementation slot
+/// when upgrading, are taken for proxies, and proxies computing their slot
+/// at runtime are missed.
+pub fn is_probably_proxy(bytecode: &[u8]) -> bool {
+    const PUSH1: u8 = 0x60;
+    const PUSH32: u8 = 0x7f;
+    const DELEGATECALL: u8 = 0xf4;
+
+    if minimal_proxy_implementation(bytecode).is_some() {
+        return true;
+    }
+
+    let mut delegates = false;
+    let mut refers_to_slot = false;
+
+    let mut i = 0;
+    while i < bytecode.len() {
+        let op = bytecode[i];
+
+        if op == DELEGATECALL {
+            delegates = true;
+        } else if op == PUSH32 {
+            if let Some(value) = bytecode.get(i + 1..i + 33) {
+                refers_to_slot |= IMPLEMENTATION_SLOTS
+                    .iter()
+                    .chain(Some(&EIP1967_BEACON_SLOT))
+                    .any(|slot| slot.as_bytes() == value);
+            }
+        }
+
+        // Pushed values are data, not opcodes.
+        i += 1;
+        if (PUSH1..=PUSH32).contains(&op) {
+            i += (op - PUSH1 + 1) as usize;
+        }
+    }
+
+    delegates && refers_to_slot
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keccak::keccak256;
+    use ethereum_types::U256;
+    use pretty_assertions::assert_eq;
+
+    fn slot(id: &str) -> H256 {
+        let hash = keccak256(id.as_bytes());
+
+        let mut slot = H256::zero();
+        (U256::from_big_endian(&hash) - 1).to_big_endian(slot.as_bytes_mut());
+
+        slot
+    }
+
+    #[test]
+    fn eip1967_slots() {
+        assert_eq!(
+            EIP1967_IMPLEMENTATION_SLOT,
+            slot("eip1967.proxy.implementation")
+        );
+        assert_eq!(EIP1967_BEACON_SLOT, slot("eip1967.proxy.beacon"));
+        assert_eq!(EIP1967_ADMIN_SLOT, slot("eip1967.proxy.admin"));
+        assert_eq!(EIP1822_PROXIABLE_SLOT, H256(keccak256(b"PROXIABLE")));
+        assert_eq!(
+            ZEPPELINOS_IMPLEMENTATION_SLOT,
+            H256(keccak256(b"org.zeppelinos.proxy.implementation"))
+        );
+
+        let address = H160::repeat_byte(0x11);
+
+        assert_eq!(slot_address(&address.into()), Some(address));
+        assert_eq!(slot_address(&H256::zero()), None);
+        assert_eq!(slot_address(&H256::repeat_byte(0x11)), None);
+    }
+
+    #[test]
+    fn detect_proxies() {
+        let implementation = H160::repeat_byte(0xbe);
+
+        let minimal = hex::decode(
+            "363d3d373d3d3d363d73bebebebebebebebebebebebebebebebebebebebe5af43d82803e903d91602b57fd5bf3",
+        )
+        .unwrap();
+        let push0 = hex::decode(
+            "365f5f375f5f365f73bebebebebebebebebebebebebebebebebebebebe5af43d5f5f3e6029573d5ffd5b3d5ff3",
+        )
+        .unwrap();
+
+        assert_eq!(minimal_proxy_implementation(&minimal), Some(implementation));
+        assert_eq!(minimal_proxy_implementation(&push0), Some(implementation));
+        assert_eq!(minimal_proxy_implementation(&minimal[1..]), None);
+        assert!(is_probably_proxy(&minimal));
+        assert!(is_probably_proxy(&push0));
+
+        // PUSH32 <slot> SLOAD ... DELEGATECALL
+        let mut delegating = vec![0x7f];
+        delegating.extend_from_slice(EIP1967_IMPLEMENTATION_SLOT.as_bytes());
+        delegating.extend_from_slice(&[0x54, 0x5a, 0xf4]);
+        assert!(is_probably_proxy(&delegating));
+
+        // Without DELEGATECALL.
+        assert!(!is_probably_proxy(&delegating[..34]));
+
+        // The slot as data of an earlier push is not a PUSH32.
+        let mut hidden = vec![0x61, 0x7f, 0x00];
+        hidden.extend_from_slice(&EIP1967_IMPLEMENTATION_SLOT.as_bytes()[1..]);
+        hidden.push(0xf4);
+        assert!(!is_probably_proxy(&hidden));
+
+        assert!(!is_probably_proxy(&[]));
+    }
+}
@@ -0,0 +1,402 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::{Bloom, H160, H256, U256};
+use serde::Deserialize;
+use std::convert::TryFrom;
+
+use crate::{address::parse_address, decode_hex, Abi, DecodedParams, Event};
+
+/// Log entry as returned by `eth_getLogs` and in transaction receipts.
+///
+/// Deserializes from the JSON-RPC wire format, where hashes, data and
+/// quantities are `0x` prefixed hex strings. Block and transaction fields
+/// are missing from pending logs.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "LogEntry")]
+pub struct Log {
+    /// Address of the contract that emitted the log.
+    pub address: H160,
+    /// Log topics, the first one being the event topic unless anonymous.
+    pub topics: Vec<H256>,
+    /// Non-indexed event data.
+    pub data: Vec<u8>,
+    /// Number of the block the log is in.
+    pub block_number: Option<u64>,
+    /// Hash of the block the log is in.
+    pub block_hash: Option<H256>,
+    /// Hash of the transaction that emitted the log.
+    pub transaction_hash: Option<H256>,
+    /// Position of the transaction in its block.
+    pub transaction_index: Option<u64>,
+    /// Position of the log in its block.
+    pub log_index: Option<u64>,
+    /// Whether the log was removed by a chain reorganization.
+    pub removed: bool,
+}
+
+/// Transaction as returned by `eth_getTransactionByHash`.
+///
+/// Only the fields common to all transaction types are kept.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "TransactionEntry")]
+pub struct Transaction {
+    /// Transaction hash.
+    pub hash: H256,
+    /// Sender nonce.
+    pub nonce: u64,
+    /// Sender address.
+    pub from: H160,
+    /// Recipient address, missing for contract creations.
+    pub to: Option<H160>,
+    /// Wei sent along.
+    pub value: U256,
+    /// Calldata, or init code for contract creations.
+    pub input: Vec<u8>,
+    /// Hash of the block the transaction is in, missing while pending.
+    pub block_hash: Option<H256>,
+    /// Number of the block the transaction is in, missing while pending.
+    pub block_number: Option<u64>,
+    /// Position of the transaction in its block, missing while pending.
+    pub transaction_index: Option<u64>,
+}
+
+/// Transaction receipt as returned by `eth_getTransactionReceipt`.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "ReceiptEntry")]
+pub struct Receipt {
+    /// Transaction hash.
+    pub transaction_hash: H256,
+    /// Position of the transaction in its block.
+    pub transaction_index: u64,
+    /// Hash of the block the transaction is in.
+    pub block_hash: Option<H256>,
+    /// Number of the block the transaction is in.
+    pub block_number: Option<u64>,
+    /// Sender address.
+    pub from: H160,
+    /// Recipient address, missing for contract creations.
+    pub to: Option<H160>,
+    /// Address of the contract created by the transaction, if any.
+    pub contract_address: Option<H160>,
+    /// Gas used by the transaction.
+    pub gas_used: U256,
+    /// Whether the transaction succeeded. Missing from pre-Byzantium
+    /// receipts.
+    pub status: Option<bool>,
+    /// Logs emitted by the transaction.
+    pub logs: Vec<Log>,
+    /// Bloom filter of the logs' addresses and topics.
+    pub logs_bloom: Bloom,
+}
+
+impl Receipt {
+    /// Returns the logs emitted by the contract at the given address.
+    pub fn logs_from<'a>(&'a self, address: &'a H160) -> impl Iterator<Item = &'a Log> {
+        self.logs.iter().filter(move |log| log.address == *address)
+    }
+}
+
+/// Log whose topics and data can be decoded, such as [`Log`] or the log
+/// types of other Ethereum libraries.
+pub trait EventLog {
+    /// Returns the log topics.
+    fn topics(&self) -> &[H256];
+    /// Returns the non-indexed event data.
+    fn data(&self) -> &[u8];
+}
+
+impl EventLog for Log {
+    fn topics(&self) -> &[H256] {
+        &self.topics
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl Abi {
+    /// Decode a log as returned by the JSON-RPC API.
+    pub fn decode_log<L: EventLog + ?Sized>(&self, log: &L) -> Result<(&Event, DecodedParams)> {
+        self.decode_log_from_slice(log.topics(), log.data())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogEntry {
+    address: String,
+    topics: Vec<String>,
+    data: String,
+    block_number: Option<String>,
+    block_hash: Option<String>,
+    transaction_hash: Option<String>,
+    transaction_index: Option<String>,
+    log_index: Option<String>,
+    #[serde(default)]
+    removed: bool,
+}
+
+impl TryFrom<LogEntry> for Log {
+    type Error = anyhow::Error;
+
+    fn try_from(entry: LogEntry) -> Result<Self> {
+        Ok(Log {
+            address: parse_address(&entry.address)?,
+            topics: entry
+                .topics
+                .iter()
+                .map(|topic| parse_hash(topic))
+                .collect::<Result<_>>()?,
+            data: parse_data(&entry.data)?,
+            block_number: entry
+                .block_number
+                .as_deref()
+                .map(parse_quantity)
+                .transpose()?,
+            block_hash: entry.block_hash.as_deref().map(parse_hash).transpose()?,
+            transaction_hash: entry
+                .transaction_hash
+                .as_deref()
+                .map(parse_hash)
+                .transpose()?,
+            transaction_index: entry
+                .transaction_index
+                .as_deref()
+                .map(parse_quantity)
+                .transpose()?,
+            log_index: entry.log_index.as_deref().map(parse_quantity).transpose()?,
+            removed: entry.removed,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionEntry {
+    hash: String,
+    nonce: String,
+    from: String,
+    to: Option<String>,
+    value: String,
+    input: String,
+    block_hash: Option<String>,
+    block_number: Option<String>,
+    transaction_index: Option<String>,
+}
+
+impl TryFrom<TransactionEntry> for Transaction {
+    type Error = anyhow::Error;
+
+    fn try_from(entry: TransactionEntry) -> Result<Self> {
+        Ok(Transaction {
+            hash: parse_hash(&entry.hash)?,
+            nonce: parse_quantity(&entry.nonce)?,
+            from: parse_address(&entry.from)?,
+            to: entry.to.as_deref().map(parse_address).transpose()?,
+            value: parse_u256(&entry.value)?,
+            input: parse_data(&entry.input)?,
+            block_hash: entry.block_hash.as_deref().map(parse_hash).transpose()?,
+            block_number: entry
+                .block_number
+                .as_deref()
+                .map(parse_quantity)
+                .transpose()?,
+            transaction_index: entry
+                .transaction_index
+                .as_deref()
+                .map(parse_quantity)
+                .transpose()?,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReceiptEntry {
+    transaction_hash: String,
+    transaction_index: String,
+    block_hash: Option<String>,
+    block_number: Option<String>,
+    from: String,
+    to: Option<String>,
+    contract_address: Option<String>,
+    gas_used: String,
+    status: Option<String>,
+    logs: Vec<Log>,
+    logs_bloom: String,
+}
+
+impl TryFrom<ReceiptEntry> for Receipt {
+    type Error = anyhow::Error;
+
+    fn try_from(entry: ReceiptEntry) -> Result<Self> {
+        let logs_bloom = parse_data(&entry.logs_bloom)?;
+        if logs_bloom.len() != 256 {
+            return Err(anyhow!(
+                "logs bloom must be 256 bytes, got {}",
+                logs_bloom.len()
+            ));
+        }
+
+        Ok(Receipt {
+            transaction_hash: parse_hash(&entry.transaction_hash)?,
+            transaction_index: parse_quantity(&entry.transaction_index)?,
+            block_hash: entry.block_hash.as_deref().map(parse_hash).transpose()?,
+            block_number: entry
+                .block_number
+                .as_deref()
+                .map(parse_quantity)
+                .transpose()?,
+            from: parse_address(&entry.from)?,
+            to: entry.to.as_deref().map(parse_address).transpose()?,
+            contract_address: entry
+                .contract_address
+                .as_deref()
+                .map(parse_address)
+                .transpose()?,
+            gas_used: parse_u256(&entry.gas_used)?,
+            status: entry
+                .status
+                .as_deref()
+                .map(|status| parse_quantity(status).map(|status| status == 1))
+                .transpose()?,
+            logs: entry.logs,
+            logs_bloom: Bloom::from_slice(&logs_bloom),
+        })
+    }
+}
+
+fn strip_0x(s: &str) -> &str {
+    s.strip_prefix("0x").unwrap_or(s)
+}
+
+pub(crate) fn parse_hash(s: &str) -> Result<H256> {
+    let bytes = parse_data(s)?;
+
+    if bytes.len() != 32 {
+        return Err(anyhow!("hash must be 32 bytes, got {}", bytes.len()));
+    }
+
+    Ok(H256::from_slice(&bytes))
+}
+
+pub(crate) fn parse_data(s: &str) -> Result<Vec<u8>> {
+    decode_hex(s).map_err(|err| anyhow!("invalid hex data {:?}: {}", s, err))
+}
+
+pub(crate) fn parse_quantity(s: &str) -> Result<u64> {
+    u64::from_str_radix(strip_0x(s), 16).map_err(|_| anyhow!("invalid quantity: {}", s))
+}
+
+pub(crate) fn parse_u256(s: &str) -> Result<U256> {
+    U256::from_str_radix(strip_0x(s), 16).map_err(|_| anyhow!("invalid quantity: {}", s))
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::BloomInput;
+    use pretty_assertions::assert_eq;
+
+    use crate::{standards::ERC20, Value};
+
+    use super::*;
+
+    const TRANSFER_LOG: &str = r#"{
+        "address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+        "topics": [
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+            "0x0000000000000000000000001111111111111111111111111111111111111111",
+            "0x0000000000000000000000002222222222222222222222222222222222222222"
+        ],
+        "data": "0x00000000000000000000000000000000000000000000000000000000000f4240",
+        "blockNumber": "0x10d4f",
+        "blockHash": "0x3333333333333333333333333333333333333333333333333333333333333333",
+        "transactionHash": "0x4444444444444444444444444444444444444444444444444444444444444444",
+        "transactionIndex": "0x1",
+        "logIndex": "0x2",
+        "removed": false
+    }"#;
+
+    #[test]
+    fn decode_rpc_log() {
+        let log: Log = serde_json::from_str(TRANSFER_LOG).unwrap();
+
+        assert_eq!(log.block_number, Some(0x10d4f));
+        assert_eq!(log.log_index, Some(2));
+
+        let (event, params) = ERC20.decode_log(&log).unwrap();
+
+        assert_eq!(event.name, "Transfer");
+        assert_eq!(params[1], Value::Address(H160::repeat_byte(0x22)));
+        assert_eq!(params[2], Value::Uint(U256::from(1_000_000), 256));
+
+        let pending: Log = serde_json::from_str(
+            r#"{"address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "topics": [], "data": "0x"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(pending.block_hash, None);
+        assert_eq!(pending.data, Vec::<u8>::new());
+
+        assert!(serde_json::from_str::<Log>(&TRANSFER_LOG.replace("0x10d4f", "block")).is_err());
+    }
+
+    #[test]
+    fn decode_rpc_receipt() {
+        let mut bloom = Bloom::default();
+        bloom.accrue(BloomInput::Raw(ERC20.events[0].topic().as_bytes()));
+
+        let receipt: Receipt = serde_json::from_str(&format!(
+            r#"{{
+                "transactionHash": "0x4444444444444444444444444444444444444444444444444444444444444444",
+                "transactionIndex": "0x1",
+                "blockHash": "0x3333333333333333333333333333333333333333333333333333333333333333",
+                "blockNumber": "0x10d4f",
+                "from": "0x1111111111111111111111111111111111111111",
+                "to": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "contractAddress": null,
+                "gasUsed": "0xb411",
+                "cumulativeGasUsed": "0x1b411",
+                "status": "0x1",
+                "logs": [{}],
+                "logsBloom": "0x{}"
+            }}"#,
+            TRANSFER_LOG,
+            hex::encode(bloom.as_bytes())
+        ))
+        .unwrap();
+
+        assert_eq!(receipt.status, Some(true));
+        assert_eq!(receipt.gas_used, U256::from(0xb411));
+        assert_eq!(receipt.logs_bloom, bloom);
+
+        let usdc = receipt.to.unwrap();
+        assert_eq!(receipt.logs_from(&usdc).count(), 1);
+        assert_eq!(receipt.logs_from(&H160::zero()).count(), 0);
+    }
+
+    #[test]
+    fn decode_rpc_transaction() {
+        let tx: Transaction = serde_json::from_str(
+            r#"{
+                "hash": "0x4444444444444444444444444444444444444444444444444444444444444444",
+                "nonce": "0x2a",
+                "from": "0x1111111111111111111111111111111111111111",
+                "to": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "value": "0xde0b6b3a7640000",
+                "input": "0x18160ddd",
+                "gas": "0x5208",
+                "type": "0x2",
+                "blockHash": null,
+                "blockNumber": null,
+                "transactionIndex": null
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(tx.nonce, 42);
+        assert_eq!(tx.value, U256::exp10(18));
+        assert_eq!(tx.input, vec![0x18, 0x16, 0x0d, 0xdd]);
+        assert_eq!(tx.block_number, None);
+    }
+}
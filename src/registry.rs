@@ -0,0 +1,529 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::{H160, H256};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Arc,
+};
+
+use crate::{Abi, AbiProvider, Artifact, DecodedParams, Event, Function};
+
+/// Collection of contract ABIs keyed by contract address.
+///
+/// Routes calls and logs to the ABI registered for the contract they target,
+/// which is what most indexers need when following more than one contract.
+/// An ABI registered at several addresses, such as a token deployed many
+/// times, is stored once.
+///
+/// ```no_run
+/// use ethereum_abi::{Abi, AbiRegistry};
+/// use ethereum_types::H160;
+///
+/// let abi: Abi = serde_json::from_str("[]").unwrap();
+///
+/// let mut registry = AbiRegistry::new();
+/// registry.register(H160::zero(), abi);
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct AbiRegistry {
+    abis: HashMap<H160, Arc<Abi>>,
+    proxies: HashMap<H160, H160>,
+    fallback: Option<Fallback>,
+}
+
+impl AbiRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the provider [`resolve`](Self::resolve) fetches the ABIs of
+    /// unknown contracts on the given chain from.
+    pub fn with_provider(
+        mut self,
+        chain_id: u64,
+        provider: impl AbiProvider + Send + Sync + 'static,
+    ) -> Self {
+        self.fallback = Some(Fallback {
+            chain_id,
+            provider: Arc::new(provider),
+            missing: HashSet::new(),
+        });
+        self
+    }
+
+    /// Registers the ABI of the contract deployed at the given address.
+    ///
+    /// Returns the ABI previously registered for that address, if any.
+    pub fn register(&mut self, address: H160, abi: Abi) -> Option<Abi> {
+        let abi = self.share(abi);
+
+        self.abis.insert(address, abi).map(unshare)
+    }
+
+    /// Registers the ABI of an artifact at the address it is deployed at on
+    /// the given chain.
+    ///
+    /// Returns the address used, or `None` if the artifact has no deployment
+    /// on that chain.
+    pub fn register_artifact(&mut self, artifact: &Artifact, chain_id: u64) -> Option<H160> {
+        let address = artifact.address(chain_id)?;
+
+        self.register(address, artifact.abi.clone());
+
+        Some(address)
+    }
+
+    /// Registers the contract at `proxy` as a proxy delegating to the
+    /// contract at `implementation`.
+    ///
+    /// Calls and logs of the proxy are then decoded with the ABI registered
+    /// for the implementation. To use an implementation ABI without
+    /// registering its address, [`register`](Self::register) it at the
+    /// proxy address instead.
+    ///
+    /// The implementation is found in one of the proxy's
+    /// [`IMPLEMENTATION_SLOTS`](crate::IMPLEMENTATION_SLOTS), or is the one
+    /// a minimal proxy's code forwards to, see
+    /// [`minimal_proxy_implementation`](crate::minimal_proxy_implementation).
+    ///
+    /// Returns the implementation previously registered for that proxy, if
+    /// any.
+    ///
+    /// ```
+    /// use ethereum_abi::{slot_address, AbiRegistry};
+    /// use ethereum_types::{H160, H256};
+    ///
+    /// let proxy = H160::repeat_byte(1);
+    /// // Value of one of `IMPLEMENTATION_SLOTS` read with eth_getStorageAt.
+    /// let slot = H256::from(H160::repeat_byte(2));
+    ///
+    /// let mut registry = AbiRegistry::new();
+    /// registry.register_proxy(proxy, slot_address(&slot).unwrap());
+    /// ```
+    pub fn register_proxy(&mut self, proxy: H160, implementation: H160) -> Option<H160> {
+        self.proxies.insert(proxy, implementation)
+    }
+
+    /// Removes the implementation registered for the given proxy.
+    pub fn unregister_proxy(&mut self, proxy: &H160) -> Option<H160> {
+        self.proxies.remove(proxy)
+    }
+
+    /// Returns the implementation registered for the given proxy.
+    pub fn implementation(&self, proxy: &H160) -> Option<H160> {
+        self.proxies.get(proxy).copied()
+    }
+
+    /// Removes the ABI registered for the given address.
+    pub fn unregister(&mut self, address: &H160) -> Option<Abi> {
+        self.abis.remove(address).map(unshare)
+    }
+
+    /// Returns the ABI used for the given address.
+    ///
+    /// For proxies this is the ABI of their implementation, following
+    /// proxies of proxies, or the ABI registered for the proxy itself if
+    /// there is none.
+    pub fn get(&self, address: &H160) -> Option<&Abi> {
+        self.proxy_chain(address)
+            .into_iter()
+            .rev()
+            .find_map(|address| self.abis.get(address))
+            .map(|abi| &**abi)
+    }
+
+    /// Returns the ABI used for the given address, fetching it from the
+    /// provider if there is none.
+    ///
+    /// For proxies the ABI of their last implementation is fetched. Fetched
+    /// ABIs are registered, and addresses the provider has no ABI for are
+    /// not looked up again.
+    ///
+    /// ```no_run
+    /// use ethereum_abi::{AbiRegistry, DirectoryProvider};
+    /// use ethereum_types::H160;
+    ///
+    /// let mut registry = AbiRegistry::new().with_provider(1, DirectoryProvider::new("abis"));
+    ///
+    /// if let Some(abi) = registry.resolve(&H160::zero()).unwrap() {
+    ///     println!("{} functions", abi.functions.len());
+    /// }
+    /// ```
+    pub fn resolve(&mut self, address: &H160) -> Result<Option<&Abi>> {
+        if self.contains(address) {
+            return Ok(self.get(address));
+        }
+
+        let target = **self.proxy_chain(address).last().unwrap();
+
+        let fallback = match &mut self.fallback {
+            Some(fallback) if !fallback.missing.contains(&target) => fallback,
+            _ => return Ok(None),
+        };
+
+        match fallback.provider.get_abi(fallback.chain_id, &target)? {
+            Some(abi) => {
+                self.register(target, abi);
+            }
+            None => {
+                fallback.missing.insert(target);
+            }
+        }
+
+        Ok(self.get(address))
+    }
+
+    /// Returns whether an ABI is used for the given address.
+    pub fn contains(&self, address: &H160) -> bool {
+        self.get(address).is_some()
+    }
+
+    /// Returns the number of registered ABIs.
+    pub fn len(&self) -> usize {
+        self.abis.len()
+    }
+
+    /// Returns whether the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.abis.is_empty()
+    }
+
+    /// Iterates over all registered (address, ABI) pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&H160, &Abi)> {
+        self.abis.iter().map(|(address, abi)| (address, &**abi))
+    }
+
+    /// Decode the input of a call made to the contract at address `to`.
+    ///
+    /// The provider is not consulted here; [`resolve`](Self::resolve) the
+    /// address first to fetch its ABI.
+    pub fn decode_call<'a>(
+        &'a self,
+        to: &H160,
+        input: &[u8],
+    ) -> Result<(&'a Function, DecodedParams)> {
+        self.abi_for(to)?.decode_input_from_slice(input)
+    }
+
+    /// Decode a log emitted by the contract at the given address.
+    pub fn decode_log<'a>(
+        &'a self,
+        address: &H160,
+        topics: &[H256],
+        data: &[u8],
+    ) -> Result<(&'a Event, DecodedParams)> {
+        self.abi_for(address)?.decode_log_from_slice(topics, data)
+    }
+
+    /// Returns the address followed by its chain of implementations.
+    fn proxy_chain<'a>(&'a self, address: &'a H160) -> Vec<&'a H160> {
+        let mut chain = vec![address];
+
+        while let Some(implementation) = self.proxies.get(chain[chain.len() - 1]) {
+            if chain.contains(&implementation) {
+                break;
+            }
+
+            chain.push(implementation);
+        }
+
+        chain
+    }
+
+    /// Returns the copy of `abi` already registered at another address, if
+    /// any, so identical ABIs share their functions, events and names.
+    fn share(&self, abi: Abi) -> Arc<Abi> {
+        self.abis
+            .values()
+            .find(|known| ***known == abi)
+            .cloned()
+            .unwrap_or_else(|| Arc::new(abi))
+    }
+
+    fn abi_for(&self, address: &H160) -> Result<&Abi> {
+        self.get(address)
+            .ok_or_else(|| anyhow!("no ABI registered for address {:?}", address))
+    }
+}
+
+impl std::iter::FromIterator<(H160, Abi)> for AbiRegistry {
+    fn from_iter<I: IntoIterator<Item = (H160, Abi)>>(iter: I) -> Self {
+        let mut registry = Self::new();
+        registry.extend(iter);
+
+        registry
+    }
+}
+
+impl Extend<(H160, Abi)> for AbiRegistry {
+    fn extend<I: IntoIterator<Item = (H160, Abi)>>(&mut self, iter: I) {
+        for (address, abi) in iter {
+            self.register(address, abi);
+        }
+    }
+}
+
+fn unshare(abi: Arc<Abi>) -> Abi {
+    Arc::try_unwrap(abi).unwrap_or_else(|abi| (*abi).clone())
+}
+
+/// Provider consulted by [`AbiRegistry::resolve`].
+#[derive(Clone)]
+struct Fallback {
+    chain_id: u64,
+    provider: Arc<dyn AbiProvider + Send + Sync>,
+    /// Addresses the provider has no ABI for.
+    missing: HashSet<H160>,
+}
+
+impl fmt::Debug for Fallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fallback")
+            .field("chain_id", &self.chain_id)
+            .field("missing", &self.missing)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Fallback {
+    fn eq(&self, other: &Self) -> bool {
+        self.chain_id == other.chain_id
+            && Arc::ptr_eq(&self.provider, &other.provider)
+            && self.missing == other.missing
+    }
+}
+
+impl Eq for Fallback {}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::U256;
+    use pretty_assertions::assert_eq;
+
+    use crate::{Param, StateMutability, Type, Value};
+
+    use super::*;
+
+    fn test_abi(fn_name: &str, evt_name: &str) -> Abi {
+        Abi {
+            constructor: None,
+            functions: vec![Function::new(
+                fn_name.to_string(),
+                vec![Param {
+                    name: "x".into(),
+                    type_: Type::Uint(256),
+                    indexed: None,
+                    internal_type: None,
+                }],
+                vec![],
+                StateMutability::NonPayable,
+            )],
+            events: vec![Event::new(
+                evt_name.to_string(),
+                vec![Param {
+                    name: "x".into(),
+                    type_: Type::Uint(256),
+                    indexed: Some(false),
+                    internal_type: None,
+                }],
+                false,
+            )],
+            errors: vec![],
+            receive: None,
+            fallback: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decode_call_routes_by_address() {
+        let addr_a = H160::random();
+        let addr_b = H160::random();
+
+        let registry: AbiRegistry =
+            vec![(addr_a, test_abi("f", "E")), (addr_b, test_abi("g", "F"))]
+                .into_iter()
+                .collect();
+
+        let args = Value::encode(&[Value::Uint(U256::from(7), 256)]);
+
+        let mut input = registry.get(&addr_b).unwrap().functions[0]
+            .method_id()
+            .to_vec();
+        input.extend(&args);
+
+        let (f, params) = registry
+            .decode_call(&addr_b, &input)
+            .expect("decode_call failed");
+
+        assert_eq!(f.name, "g");
+        assert_eq!(params[0], Value::Uint(U256::from(7), 256));
+
+        // The same selector is unknown to the other contract.
+        assert!(registry.decode_call(&addr_a, &input).is_err());
+    }
+
+    #[test]
+    fn decode_log_routes_by_address() {
+        let addr = H160::random();
+
+        let mut registry = AbiRegistry::new();
+        registry.register(addr, test_abi("f", "E"));
+
+        let topic = registry.get(&addr).unwrap().events[0].topic();
+        let data = Value::encode(&[Value::Uint(U256::from(3), 256)]);
+
+        let (evt, params) = registry
+            .decode_log(&addr, &[topic], &data)
+            .expect("decode_log failed");
+
+        assert_eq!(evt.name, "E");
+        assert_eq!(params[0], Value::Uint(U256::from(3), 256));
+    }
+
+    #[test]
+    fn identical_abis_stored_once() {
+        let (a, b, c) = (
+            H160::repeat_byte(1),
+            H160::repeat_byte(2),
+            H160::repeat_byte(3),
+        );
+
+        let mut registry = AbiRegistry::new();
+        registry.register(a, test_abi("f", "E"));
+        registry.register(b, test_abi("f", "E"));
+        registry.register(c, test_abi("g", "E"));
+
+        assert!(std::ptr::eq(
+            registry.get(&a).unwrap(),
+            registry.get(&b).unwrap()
+        ));
+        assert!(!std::ptr::eq(
+            registry.get(&a).unwrap(),
+            registry.get(&c).unwrap()
+        ));
+
+        assert_eq!(registry.unregister(&a), Some(test_abi("f", "E")));
+        assert_eq!(registry.get(&b), Some(&test_abi("f", "E")));
+    }
+
+    #[test]
+    fn register_deployed_artifact() {
+        let address = H160::random();
+
+        let artifact = Artifact {
+            contract_name: Some("C".to_string()),
+            source_name: None,
+            abi: test_abi("f", "E"),
+            bytecode: None,
+            deployed_bytecode: None,
+            method_identifiers: Default::default(),
+            networks: vec![(1, address)].into_iter().collect(),
+            storage_layout: None,
+            userdoc: None,
+            devdoc: None,
+            compiler_version: None,
+            compiler_settings: None,
+        };
+
+        let mut registry = AbiRegistry::new();
+
+        assert_eq!(registry.register_artifact(&artifact, 5), None);
+        assert_eq!(registry.register_artifact(&artifact, 1), Some(address));
+        assert_eq!(registry.get(&address), Some(&artifact.abi));
+    }
+
+    #[test]
+    fn decode_through_proxy() {
+        let proxy = H160::repeat_byte(1);
+        let implementation = H160::repeat_byte(2);
+        let upgraded = H160::repeat_byte(3);
+
+        let mut registry = AbiRegistry::new();
+        registry.register(proxy, test_abi("upgradeTo", "Upgraded"));
+        registry.register(implementation, test_abi("f", "E"));
+
+        let mut input = registry.get(&implementation).unwrap().functions[0]
+            .method_id()
+            .to_vec();
+        input.extend(Value::encode(&[Value::Uint(U256::from(7), 256)]));
+
+        assert!(registry.decode_call(&proxy, &input).is_err());
+
+        registry.register_proxy(proxy, implementation);
+        assert_eq!(registry.implementation(&proxy), Some(implementation));
+
+        let (f, _) = registry.decode_call(&proxy, &input).unwrap();
+        assert_eq!(f.name, "f");
+
+        let topic = registry.get(&implementation).unwrap().events[0].topic();
+        let data = Value::encode(&[Value::Uint(U256::from(3), 256)]);
+        let (evt, _) = registry.decode_log(&proxy, &[topic], &data).unwrap();
+        assert_eq!(evt.name, "E");
+
+        // Proxies of proxies resolve to the last implementation with an ABI,
+        // and cycles do not hang.
+        registry.register_proxy(implementation, upgraded);
+        registry.register_proxy(upgraded, proxy);
+        assert_eq!(registry.get(&proxy).unwrap().functions[0].name, "f");
+
+        registry.unregister_proxy(&proxy);
+        assert_eq!(registry.get(&proxy).unwrap().functions[0].name, "upgradeTo");
+    }
+
+    #[test]
+    fn unknown_address() {
+        let registry = AbiRegistry::new();
+
+        assert!(registry.decode_call(&H160::zero(), &[0; 4]).is_err());
+        assert!(registry.decode_log(&H160::zero(), &[], &[]).is_err());
+    }
+
+    #[test]
+    fn resolve_from_provider() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Provider {
+            known: H160,
+            lookups: Arc<AtomicUsize>,
+        }
+
+        impl AbiProvider for Provider {
+            fn get_abi(&self, chain_id: u64, address: &H160) -> Result<Option<Abi>> {
+                assert_eq!(chain_id, 10);
+                self.lookups.fetch_add(1, Ordering::SeqCst);
+
+                Ok(Some(test_abi("f", "E")).filter(|_| *address == self.known))
+            }
+        }
+
+        let known = H160::repeat_byte(1);
+        let unknown = H160::repeat_byte(2);
+        let proxy = H160::repeat_byte(3);
+        let lookups = Arc::new(AtomicUsize::new(0));
+
+        let mut registry = AbiRegistry::new().with_provider(
+            10,
+            Provider {
+                known,
+                lookups: lookups.clone(),
+            },
+        );
+        registry.register_proxy(proxy, known);
+
+        assert_eq!(registry.get(&known), None);
+        assert_eq!(
+            registry.resolve(&proxy).unwrap().unwrap().functions[0].name,
+            "f"
+        );
+        assert_eq!(registry.get(&known).unwrap().functions[0].name, "f");
+
+        assert_eq!(registry.resolve(&unknown).unwrap(), None);
+        assert_eq!(registry.resolve(&unknown).unwrap(), None);
+        assert!(registry.resolve(&known).unwrap().is_some());
+        assert_eq!(lookups.load(Ordering::SeqCst), 2);
+
+        assert_eq!(AbiRegistry::new().resolve(&known).unwrap(), None);
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::U256;
+use std::convert::TryInto;
+
+use crate::{encode_hex, Abi, DecodedParams, Error, Type, Value};
+
+/// Selector of `Error(string)`, used by `require` and `revert` with a message.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// Selector of `Panic(uint256)`, used by failed assertions and checked
+/// arithmetic.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decoded reason a call reverted.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Revert<'a> {
+    /// Revert without data, e.g. `revert()` or `require(cond)`.
+    Empty,
+    /// `Error(string)` revert message.
+    Reason(String),
+    /// `Panic(uint256)` with its panic code.
+    Panic(U256),
+    /// Custom error defined in the ABI.
+    Custom(&'a Error, DecodedParams),
+    /// Revert data matching no known error.
+    Unknown(Vec<u8>),
+}
+
+impl Revert<'_> {
+    /// Returns what a Solidity panic code means, if it is a known one.
+    pub fn panic_description(code: U256) -> Option<&'static str> {
+        if code.bits() > 8 {
+            return None;
+        }
+
+        Some(match code.low_u32() {
+            0x00 => "generic compiler panic",
+            0x01 => "assertion failed",
+            0x11 => "arithmetic overflow or underflow",
+            0x12 => "division or modulo by zero",
+            0x21 => "invalid enum value",
+            0x22 => "invalid storage byte array encoding",
+            0x31 => "pop on empty array",
+            0x32 => "array index out of bounds",
+            0x41 => "out of memory",
+            0x51 => "call to invalid internal function",
+            _ => return None,
+        })
+    }
+}
+
+impl std::fmt::Display for Revert<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Revert::Empty => write!(f, "reverted"),
+            Revert::Reason(reason) => write!(f, "reverted: {}", reason),
+            Revert::Panic(code) => match Revert::panic_description(*code) {
+                Some(description) => write!(f, "panic: {} ({:#x})", description, code),
+                None => write!(f, "panic: {:#x}", code),
+            },
+            Revert::Custom(error, params) => write!(f, "{}({})", error.name, params),
+            Revert::Unknown(data) => write!(f, "reverted: {}", encode_hex(data)),
+        }
+    }
+}
+
+impl Abi {
+    /// Decodes the data a reverted call returned.
+    ///
+    /// Custom errors defined in the ABI are recognized along with the
+    /// builtin `Error(string)` and `Panic(uint256)`. Fails only if the data
+    /// has a known selector but cannot be decoded.
+    pub fn decode_revert(&self, data: &[u8]) -> Result<Revert<'_>> {
+        decode_revert(Some(self), data)
+    }
+}
+
+/// Decodes revert data against the given ABI, or the builtin errors only.
+pub(crate) fn decode_revert<'a>(abi: Option<&'a Abi>, data: &[u8]) -> Result<Revert<'a>> {
+    if data.is_empty() {
+        return Ok(Revert::Empty);
+    }
+
+    let selector: [u8; 4] = match data.get(0..4) {
+        Some(selector) => selector.try_into()?,
+        None => return Ok(Revert::Unknown(data.to_vec())),
+    };
+
+    let args = &data[4..];
+
+    if selector == ERROR_SELECTOR {
+        match Value::decode_from_slice(args, &[Type::String])?.pop() {
+            Some(Value::String(reason)) => return Ok(Revert::Reason(reason)),
+            _ => return Err(anyhow!("invalid Error(string) revert data")),
+        }
+    }
+
+    if selector == PANIC_SELECTOR {
+        match Value::decode_from_slice(args, &[Type::Uint(256)])?.pop() {
+            Some(Value::Uint(code, _)) => return Ok(Revert::Panic(code)),
+            _ => return Err(anyhow!("invalid Panic(uint256) revert data")),
+        }
+    }
+
+    match abi.and_then(|abi| abi.error_by_selector(selector)) {
+        Some(error) => Ok(Revert::Custom(error, error.decode_data_from_slice(args)?)),
+        None => Ok(Revert::Unknown(data.to_vec())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn decode_reverts() {
+        let abi = Abi::builder()
+            .error("InsufficientBalance", &[("available", "uint256")])
+            .build()
+            .unwrap();
+
+        let mut data = ERROR_SELECTOR.to_vec();
+        data.extend(Value::encode(&[Value::String("not owner".to_string())]));
+
+        let revert = abi.decode_revert(&data).unwrap();
+        assert_eq!(revert, Revert::Reason("not owner".to_string()));
+        assert_eq!(revert.to_string(), "reverted: not owner");
+
+        let mut data = PANIC_SELECTOR.to_vec();
+        data.extend(Value::encode(&[Value::Uint(U256::from(0x11), 256)]));
+
+        assert_eq!(
+            abi.decode_revert(&data).unwrap().to_string(),
+            "panic: arithmetic overflow or underflow (0x11)"
+        );
+
+        let mut data = abi.errors[0].selector().to_vec();
+        data.extend(Value::encode(&[Value::Uint(U256::from(3), 256)]));
+
+        assert_eq!(
+            abi.decode_revert(&data).unwrap().to_string(),
+            "InsufficientBalance(available: 3)"
+        );
+
+        assert_eq!(abi.decode_revert(&[]).unwrap(), Revert::Empty);
+        assert_eq!(
+            abi.decode_revert(&[1, 2, 3, 4]).unwrap(),
+            Revert::Unknown(vec![1, 2, 3, 4])
+        );
+        assert!(abi.decode_revert(&ERROR_SELECTOR).is_err());
+    }
+}
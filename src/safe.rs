@@ -0,0 +1,252 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::{H160, U256};
+use std::convert::TryInto;
+
+use crate::{
+    standards::{MULTI_SEND, SAFE},
+    AbiRegistry, DecodedParams, Function, Value,
+};
+
+/// How a Safe executes a transaction.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Operation {
+    /// Regular call.
+    Call,
+    /// Delegate call, running the target's code in the Safe's context.
+    DelegateCall,
+}
+
+/// Transaction executed by a Safe, either directly through
+/// `execTransaction` or batched through `multiSend`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SafeTransaction {
+    /// Call or delegate call.
+    pub operation: Operation,
+    /// Target address.
+    pub to: H160,
+    /// Wei sent along.
+    pub value: U256,
+    /// Calldata.
+    pub data: Vec<u8>,
+}
+
+/// Safe transaction along with its calldata decoded against the ABI
+/// registered for its target, if any.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DecodedSafeTransaction<'a> {
+    /// The transaction.
+    pub transaction: SafeTransaction,
+    /// Called function and its decoded arguments.
+    pub call: Option<(&'a Function, DecodedParams)>,
+}
+
+impl SafeTransaction {
+    /// Unpacks the `transactions` argument of a `multiSend` call.
+    ///
+    /// Each transaction is packed as its operation (1 byte), target (20
+    /// bytes), value (32 bytes), data length (32 bytes) and data.
+    pub fn unpack_multi_send(mut packed: &[u8]) -> Result<Vec<SafeTransaction>> {
+        let mut transactions = vec![];
+
+        while !packed.is_empty() {
+            let header = packed
+                .get(0..85)
+                .ok_or_else(|| anyhow!("truncated multiSend transaction header"))?;
+
+            let operation = match header[0] {
+                0 => Operation::Call,
+                1 => Operation::DelegateCall,
+                op => return Err(anyhow!("invalid multiSend operation {}", op)),
+            };
+
+            let data_len = U256::from_big_endian(&header[53..85]);
+            if data_len > U256::from(packed.len() - 85) {
+                return Err(anyhow!("truncated multiSend transaction data"));
+            }
+
+            let data_end = 85 + data_len.as_usize();
+
+            transactions.push(SafeTransaction {
+                operation,
+                to: H160::from_slice(&header[1..21]),
+                value: U256::from_big_endian(&header[21..53]),
+                data: packed[85..data_end].to_vec(),
+            });
+
+            packed = &packed[data_end..];
+        }
+
+        Ok(transactions)
+    }
+
+    /// Packs transactions into the `transactions` argument of a `multiSend`
+    /// call.
+    pub fn pack_multi_send(transactions: &[SafeTransaction]) -> Vec<u8> {
+        let mut packed = vec![];
+
+        for tx in transactions {
+            let mut word = [0u8; 32];
+
+            packed.push(match tx.operation {
+                Operation::Call => 0,
+                Operation::DelegateCall => 1,
+            });
+            packed.extend_from_slice(tx.to.as_bytes());
+            tx.value.to_big_endian(&mut word);
+            packed.extend_from_slice(&word);
+            U256::from(tx.data.len()).to_big_endian(&mut word);
+            packed.extend_from_slice(&word);
+            packed.extend_from_slice(&tx.data);
+        }
+
+        packed
+    }
+}
+
+impl AbiRegistry {
+    /// Decodes the transactions a Safe call executes.
+    ///
+    /// `input` is either `execTransaction` or `multiSend` calldata. Batches
+    /// executed through `multiSend` are unpacked into their transactions.
+    /// Each transaction's calldata is decoded against the ABI registered for
+    /// its target, and left undecoded if there is none or it does not match.
+    pub fn decode_safe_transaction(&self, input: &[u8]) -> Result<Vec<DecodedSafeTransaction<'_>>> {
+        let selector: [u8; 4] = input
+            .get(0..4)
+            .ok_or_else(|| anyhow!("missing function selector"))?
+            .try_into()?;
+
+        let transactions = if let Some(f) = SAFE.function_by_selector(selector) {
+            let params = f.decode_input_from_slice(&input[4..])?;
+
+            let operation = match params["operation"].as_uint() {
+                Some(op) if op.is_zero() => Operation::Call,
+                Some(op) if op == U256::one() => Operation::DelegateCall,
+                _ => return Err(anyhow!("invalid Safe operation {}", params["operation"])),
+            };
+
+            let tx = SafeTransaction {
+                operation,
+                to: params["to"].as_address().unwrap_or_default(),
+                value: params["value"].as_uint().unwrap_or_default(),
+                data: params["data"].as_bytes().unwrap_or_default().to_vec(),
+            };
+
+            match multi_send_transactions(&tx.data)? {
+                Some(transactions) => transactions,
+                None => vec![tx],
+            }
+        } else {
+            multi_send_transactions(input)?
+                .ok_or_else(|| anyhow!("not an execTransaction or multiSend call"))?
+        };
+
+        Ok(transactions
+            .into_iter()
+            .map(|transaction| {
+                let call = self
+                    .get(&transaction.to)
+                    .and_then(|abi| abi.decode_input_from_slice(&transaction.data).ok());
+
+                DecodedSafeTransaction { transaction, call }
+            })
+            .collect())
+    }
+}
+
+// Unpacks `multiSend` calldata, or returns `None` for other calls.
+fn multi_send_transactions(input: &[u8]) -> Result<Option<Vec<SafeTransaction>>> {
+    let multi_send = MULTI_SEND
+        .function("multiSend")
+        .expect("MultiSend ABI has multiSend");
+
+    if input.get(0..4) != Some(&multi_send.method_id()[..]) {
+        return Ok(None);
+    }
+
+    let params = multi_send.decode_input_from_slice(&input[4..])?;
+
+    match &params[0] {
+        Value::Bytes(packed) => SafeTransaction::unpack_multi_send(packed).map(Some),
+        _ => Err(anyhow!("invalid multiSend calldata")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::standards::ERC20;
+
+    use super::*;
+
+    #[test]
+    fn decode_safe_multi_send() {
+        let token = H160::repeat_byte(0xaa);
+        let to = H160::repeat_byte(0x22);
+
+        let transfer = ERC20
+            .encode_call(
+                "transfer",
+                &[Value::Address(to), Value::Uint(U256::from(5), 256)],
+            )
+            .unwrap();
+
+        let batch = vec![
+            SafeTransaction {
+                operation: Operation::Call,
+                to: token,
+                value: U256::zero(),
+                data: transfer,
+            },
+            SafeTransaction {
+                operation: Operation::Call,
+                to,
+                value: U256::exp10(18),
+                data: vec![],
+            },
+        ];
+
+        let packed = SafeTransaction::pack_multi_send(&batch);
+        assert_eq!(SafeTransaction::unpack_multi_send(&packed).unwrap(), batch);
+        assert!(SafeTransaction::unpack_multi_send(&packed[..100]).is_err());
+
+        let multi_send = MULTI_SEND
+            .encode_call("multiSend", &[Value::Bytes(packed.into())])
+            .unwrap();
+
+        let input = SAFE
+            .encode_call(
+                "execTransaction",
+                &[
+                    Value::Address(H160::repeat_byte(0x40)),
+                    Value::Uint(U256::zero(), 256),
+                    Value::Bytes(multi_send.into()),
+                    Value::Uint(U256::one(), 8),
+                    Value::Uint(U256::zero(), 256),
+                    Value::Uint(U256::zero(), 256),
+                    Value::Uint(U256::zero(), 256),
+                    Value::Address(H160::zero()),
+                    Value::Address(H160::zero()),
+                    Value::Bytes(vec![0; 65].into()),
+                ],
+            )
+            .unwrap();
+
+        let mut registry = AbiRegistry::new();
+        registry.register(token, ERC20.clone());
+
+        let decoded = registry.decode_safe_transaction(&input).unwrap();
+
+        assert_eq!(decoded.len(), 2);
+
+        let (function, params) = decoded[0].call.as_ref().unwrap();
+        assert_eq!(function.name, "transfer");
+        assert_eq!(params[0], Value::Address(to));
+
+        assert_eq!(decoded[1].call, None);
+        assert_eq!(decoded[1].transaction.value, U256::exp10(18));
+
+        assert!(registry.decode_safe_transaction(&[1, 2, 3, 4]).is_err());
+    }
+}
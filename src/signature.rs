@@ -0,0 +1,388 @@
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+
+use crate::{params::parse_type_str, Abi, Error, Event, Function, Param, StateMutability, Type};
+
+/// Splits a text signature such as `transfer(address,uint256)` or
+/// `Transfer(address indexed from, address indexed to, uint256 value)` into
+/// its name and parameters.
+///
+/// Parameter types may be followed by a name, and by `indexed` first for
+/// event parameters, whose `indexed` flag is set either way.
+pub(crate) fn parse_signature(signature: &str, event: bool) -> Result<(String, Vec<Param>)> {
+    let signature = signature.trim();
+
+    let open = signature
+        .find('(')
+        .ok_or_else(|| anyhow!("invalid signature: {}", signature))?;
+
+    let (name, params) = signature.split_at(open);
+
+    if !is_identifier(name) {
+        return Err(anyhow!("invalid signature name: {}", name));
+    }
+
+    match parse_param_list(params, event)? {
+        (params, "") => Ok((name.to_string(), params)),
+        _ => Err(anyhow!("invalid signature parameters: {}", params)),
+    }
+}
+
+// Reads a parenthesized parameter list from the start of `input`, returning
+// the parameters and what follows the list.
+fn parse_param_list(input: &str, event: bool) -> Result<(Vec<Param>, &str)> {
+    let close = matching_paren(input)?;
+    let list = &input[1..close];
+
+    let mut params = vec![];
+    if !list.trim().is_empty() {
+        let mut depth = 0;
+        let mut start = 0;
+
+        for (i, c) in list.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    params.push(parse_param(&list[start..i], event)?);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        params.push(parse_param(&list[start..], event)?);
+    }
+
+    Ok((params, &input[close + 1..]))
+}
+
+// Reads a parameter such as `(uint256 amount, address to)[] indexed orders`.
+fn parse_param(input: &str, event: bool) -> Result<Param> {
+    let input = input.trim();
+    let invalid = || anyhow!("invalid parameter: {}", input);
+
+    let (mut type_, rest) = match input.strip_prefix("tuple").unwrap_or(input) {
+        tuple if tuple.starts_with('(') => {
+            let (fields, rest) = parse_param_list(tuple, false)?;
+
+            let fields = fields
+                .into_iter()
+                .map(|field| (field.name, field.type_))
+                .collect();
+
+            (Type::Tuple(fields), rest)
+        }
+        _ => {
+            let end = input.find(char::is_whitespace).unwrap_or(input.len());
+
+            (parse_type_str(&input[..end])?, &input[end..])
+        }
+    };
+
+    // Array suffixes of tuple types.
+    let mut rest = rest;
+    while let Some(suffix) = rest.strip_prefix('[') {
+        let close = suffix.find(']').ok_or_else(invalid)?;
+
+        type_ = match suffix[..close].trim() {
+            "" => Type::Array(Box::new(type_)),
+            size => Type::FixedArray(Box::new(type_), size.parse().map_err(|_| invalid())?),
+        };
+        rest = &suffix[close + 1..];
+    }
+
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return Err(invalid());
+    }
+
+    let mut words = rest.split_whitespace().peekable();
+
+    let indexed = event && words.next_if_eq(&"indexed").is_some();
+    let name = match words.next() {
+        Some(name) if is_identifier(name) => name,
+        None => "",
+        Some(_) => return Err(invalid()),
+    };
+
+    if words.next().is_some() {
+        return Err(invalid());
+    }
+
+    Ok(Param {
+        name: name.into(),
+        type_,
+        indexed: if event { Some(indexed) } else { None },
+        internal_type: None,
+    })
+}
+
+// Parses a type whose tuple components may be named, such as
+// `(address target,bytes callData)[]`.
+pub(crate) fn parse_named_type(ty: &str) -> Result<Type> {
+    match parse_param(ty, false)? {
+        Param { name, type_, .. } if name.is_empty() => Ok(type_),
+        _ => Err(anyhow!("invalid type: {}", ty)),
+    }
+}
+
+// Returns the position of the parenthesis closing the one `input` starts with.
+fn matching_paren(input: &str) -> Result<usize> {
+    let mut depth = 0;
+
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 1 => return Ok(i),
+            ')' => depth -= 1,
+            _ if depth == 0 => break,
+            _ => {}
+        }
+    }
+
+    Err(anyhow!("invalid parameter list: {}", input))
+}
+
+impl Function {
+    /// Builds a function definition from a text signature such as
+    /// `transfer(address,uint256)`, where parameters may be named as in
+    /// `transfer(address to, uint256 amount)`.
+    ///
+    /// Text signatures carry neither outputs nor state mutability, so outputs
+    /// are empty and the function is assumed to be non-payable.
+    pub fn parse(signature: &str) -> Result<Function> {
+        let (name, inputs) = parse_signature(signature, false)?;
+
+        Ok(Function::new(
+            name,
+            inputs,
+            vec![],
+            StateMutability::NonPayable,
+        ))
+    }
+}
+
+impl Event {
+    /// Builds an event definition from a text signature such as
+    /// `Transfer(address indexed from, address indexed to, uint256 value)`.
+    ///
+    /// Only inputs marked `indexed` are indexed, so canonical signatures such
+    /// as `Transfer(address,address,uint256)` have every input non-indexed.
+    pub fn parse(signature: &str) -> Result<Event> {
+        let (name, inputs) = parse_signature(signature, true)?;
+
+        Ok(Event::new(name, inputs, false))
+    }
+}
+
+impl Error {
+    /// Builds a custom error definition from a text signature such as
+    /// `InsufficientBalance(uint256,uint256)`, where parameters may be named.
+    pub fn parse(signature: &str) -> Result<Error> {
+        let (name, inputs) = parse_signature(signature, false)?;
+
+        Ok(Error::new(name, inputs))
+    }
+}
+
+impl Abi {
+    /// Builds an ABI from text signatures, each optionally prefixed with its
+    /// kind: `function transfer(address,uint256)`, `event
+    /// Transfer(address indexed,address indexed,uint256)` or `error
+    /// Unauthorized(address)`. Parameters may be named, see [`Event::parse`].
+    ///
+    /// Unprefixed signatures are read as functions, so a dump of selector
+    /// signatures can be used as is. Repeated signatures are kept once.
+    pub fn from_signatures<S: AsRef<str>>(signatures: &[S]) -> Result<Abi> {
+        let mut abi = Abi::builder().build()?;
+        let mut seen = HashSet::new();
+
+        for signature in signatures {
+            let signature = signature.as_ref().trim();
+
+            let (kind, rest) = match signature.split_once(char::is_whitespace) {
+                Some((kind, rest)) if !kind.contains('(') => (kind, rest.trim_start()),
+                _ => ("function", signature),
+            };
+
+            match kind {
+                "function" => {
+                    let f = Function::parse(rest)?;
+                    if seen.insert(("function", f.signature())) {
+                        abi.functions.push(f);
+                    }
+                }
+                "event" => {
+                    let e = Event::parse(rest)?;
+                    if seen.insert(("event", e.signature())) {
+                        abi.events.push(e);
+                    }
+                }
+                "error" => {
+                    let e = Error::parse(rest)?;
+                    if seen.insert(("error", e.signature())) {
+                        abi.errors.push(e);
+                    }
+                }
+                _ => return Err(anyhow!("invalid signature kind: {}", kind)),
+            }
+        }
+
+        Ok(abi)
+    }
+}
+
+pub(crate) fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$' => {}
+        _ => return false,
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use ethereum_types::{H160, H256, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::Value;
+
+    fn types(params: Vec<Param>) -> Vec<Type> {
+        params.into_iter().map(|param| param.type_).collect()
+    }
+
+    #[test]
+    fn parse_simple_signature() {
+        let (name, params) = parse_signature("transfer(address,uint256)", false).unwrap();
+
+        assert_eq!(name, "transfer");
+        assert_eq!(types(params), vec![Type::Address, Type::Uint(256)]);
+    }
+
+    #[test]
+    fn parse_tuple_signature() {
+        let (name, params) = parse_signature("f((uint256,bytes)[],())", false).unwrap();
+
+        assert_eq!(name, "f");
+        assert_eq!(
+            types(params),
+            vec![
+                Type::Array(Box::new(Type::Tuple(vec![
+                    ("".into(), Type::Uint(256)),
+                    ("".into(), Type::Bytes),
+                ]))),
+                Type::Tuple(vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn function_signature_round_trip() {
+        let f = Function::parse("swap(uint256,(address,address)[],bytes)").unwrap();
+
+        assert_eq!(f.signature(), "swap(uint256,(address,address)[],bytes)");
+    }
+
+    #[test]
+    fn parse_named_signature() {
+        let (_, params) = parse_signature(
+            "f(address to, (uint256 amount, bool[2] flags)[] orders, bytes)",
+            false,
+        )
+        .unwrap();
+
+        let names: Vec<_> = params.iter().map(|param| param.name.as_str()).collect();
+        assert_eq!(names, vec!["to", "orders", ""]);
+        assert_eq!(
+            params[1].type_,
+            Type::Array(Box::new(Type::Tuple(vec![
+                ("amount".into(), Type::Uint(256)),
+                ("flags".into(), Type::FixedArray(Box::new(Type::Bool), 2)),
+            ])))
+        );
+        assert_eq!(params[1].indexed, None);
+    }
+
+    #[test]
+    fn invalid_signatures() {
+        assert!(parse_signature("transfer", false).is_err());
+        assert!(parse_signature("(address)", false).is_err());
+        assert!(parse_signature("1f(address)", false).is_err());
+        assert!(parse_signature("f(address", false).is_err());
+        assert!(parse_signature("f(uint7)", false).is_err());
+        assert!(parse_signature("f(address) x", false).is_err());
+        assert!(parse_signature("f(address indexed to)", false).is_err());
+        assert!(parse_signature("f(address to from)", false).is_err());
+        assert!(parse_signature("f(address,)", false).is_err());
+        assert!(parse_signature("f((address)x)", false).is_err());
+    }
+
+    #[test]
+    fn abi_from_signatures() {
+        let abi = Abi::from_signatures(&[
+            "function transfer(address,uint256)",
+            "approve(address,uint256)",
+            "transfer(address,uint256)",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "error  Unauthorized(address)",
+        ])
+        .unwrap();
+
+        assert_eq!(abi.functions.len(), 2);
+        assert_eq!(abi.functions[1].signature(), "approve(address,uint256)");
+        assert_eq!(abi.events[0].name, "Transfer");
+        assert_eq!(abi.errors[0].signature(), "Unauthorized(address)");
+
+        let input = hex::decode(
+            "a9059cbb\
+             000000000000000000000000000000000000000000000000000000000000dead\
+             0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        let (f, _) = abi.decode_input_from_slice(&input).unwrap();
+
+        assert_eq!(f.name, "transfer");
+
+        let topics = [
+            abi.events[0].topic(),
+            H256::from(H160::repeat_byte(1)),
+            H256::from(H160::repeat_byte(2)),
+        ];
+        let (_, params) = abi.decode_log_from_slice(&topics, &[0xff; 32]).unwrap();
+
+        assert_eq!(params["to"], Value::Address(H160::repeat_byte(2)));
+        assert_eq!(params["value"], Value::Uint(U256::MAX, 256));
+
+        assert!(Abi::from_signatures(&["modifier onlyOwner()"]).is_err());
+        assert!(Abi::from_signatures(&["event Transfer(address"]).is_err());
+    }
+
+    #[test]
+    fn parse_event_and_error() {
+        let e = Event::parse("Transfer(address,address,uint256)").unwrap();
+
+        assert_eq!(e.inputs.len(), 3);
+        assert_eq!(e.inputs[2].indexed, Some(false));
+        assert_eq!(
+            format!("{:x}", e.topic()),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+
+        let e =
+            Event::parse("Transfer(address indexed from, address indexed, uint256 value)").unwrap();
+
+        let indexed: Vec<_> = e.inputs.iter().map(|input| input.indexed).collect();
+        assert_eq!(indexed, vec![Some(true), Some(true), Some(false)]);
+        assert_eq!(e.inputs[0].name, "from");
+        assert_eq!(e.inputs[1].name, "");
+        assert_eq!(e.signature(), "Transfer(address,address,uint256)");
+
+        let e = Error::parse("InsufficientBalance(uint256,uint256)").unwrap();
+
+        assert_eq!(e.signature(), "InsufficientBalance(uint256,uint256)");
+    }
+}
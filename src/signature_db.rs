@@ -0,0 +1,345 @@
+use anyhow::{anyhow, Context, Result};
+use ethereum_types::H256;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::Write,
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{decode_hex, encode_hex, Event, Function};
+
+/// Offline selector and topic database.
+///
+/// Maps function selectors and event topics to their known text signatures, so
+/// unknown calldata and logs can be labeled without network access.
+///
+/// Two on-disk formats are accepted:
+///
+/// - a line-oriented text format, where each line holds an optional 0x-prefixed
+///   selector (4 bytes) or topic (32 bytes) followed by the text signature.
+///   Lines holding only a signature are hashed on load and registered both as a
+///   function and as an event. Empty lines and lines starting with `#` are
+///   ignored:
+///
+///   ```text
+///   # ERC-20
+///   0xa9059cbb transfer(address,uint256)
+///   0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef Transfer(address,address,uint256)
+///   approve(address,uint256)
+///   ```
+///
+/// - a JSON object mapping hashes to a signature or a list of signatures, as
+///   exported by the public signature directories:
+///
+///   ```json
+///   {"0xa9059cbb": ["transfer(address,uint256)"]}
+///   ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct SignatureDb {
+    functions: HashMap<[u8; 4], Vec<String>>,
+    events: HashMap<H256, Vec<String>>,
+}
+
+impl SignatureDb {
+    /// Creates an empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a database from a file in either of the supported formats.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read signature database {}", path.display()))?;
+
+        content.parse()
+    }
+
+    /// Saves the database to a file in the text format.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut file = fs::File::create(path)?;
+        self.write(&mut file)
+    }
+
+    /// Writes the database in the text format.
+    ///
+    /// Entries are sorted by hash so the output is deterministic.
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<()> {
+        let functions: BTreeMap<_, _> = self.functions.iter().collect();
+        for (selector, sigs) in functions {
+            for sig in sigs {
+                writeln!(writer, "{} {}", encode_hex(selector), sig)?;
+            }
+        }
+
+        let events: BTreeMap<_, _> = self.events.iter().collect();
+        for (topic, sigs) in events {
+            for sig in sigs {
+                writeln!(writer, "{} {}", encode_hex(topic), sig)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds a function signature, returning its selector.
+    pub fn insert_function(&mut self, signature: &str) -> Result<[u8; 4]> {
+        let selector = Function::parse(signature)?.method_id();
+
+        Self::insert_unique(self.functions.entry(selector).or_default(), signature);
+
+        Ok(selector)
+    }
+
+    /// Adds an event signature, returning its topic.
+    pub fn insert_event(&mut self, signature: &str) -> Result<H256> {
+        let topic = Event::parse(signature)?.topic();
+
+        Self::insert_unique(self.events.entry(topic).or_default(), signature);
+
+        Ok(topic)
+    }
+
+    /// Returns the text signatures known for a function selector.
+    pub fn function_signatures(&self, selector: [u8; 4]) -> &[String] {
+        self.functions
+            .get(&selector)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns the text signatures known for an event topic.
+    pub fn event_signatures(&self, topic: H256) -> &[String] {
+        self.events
+            .get(&topic)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Resolves a function selector into candidate function definitions.
+    ///
+    /// Definitions built from text signatures have unnamed inputs and no outputs.
+    pub fn resolve_selector(&self, selector: [u8; 4]) -> Vec<Function> {
+        self.function_signatures(selector)
+            .iter()
+            .filter_map(|sig| Function::parse(sig).ok())
+            .collect()
+    }
+
+    /// Resolves an event topic into candidate event definitions.
+    ///
+    /// Text signatures do not carry `indexed` markers, so every input of the
+    /// returned events is non-indexed.
+    pub fn resolve_topic(&self, topic: H256) -> Vec<Event> {
+        self.event_signatures(topic)
+            .iter()
+            .filter_map(|sig| Event::parse(sig).ok())
+            .collect()
+    }
+
+    /// Returns the number of distinct selectors and topics in the database.
+    pub fn len(&self) -> usize {
+        self.functions.len() + self.events.len()
+    }
+
+    /// Returns whether the database is empty.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty() && self.events.is_empty()
+    }
+
+    fn insert_hashed(&mut self, hash: &[u8], signature: &str) -> Result<()> {
+        match hash.len() {
+            4 => {
+                let selector = self.insert_function(signature)?;
+                if selector != hash {
+                    return Err(anyhow!(
+                        "selector {} does not match signature {}",
+                        encode_hex(hash),
+                        signature
+                    ));
+                }
+            }
+
+            32 => {
+                let topic = self.insert_event(signature)?;
+                if topic.as_bytes() != hash {
+                    return Err(anyhow!(
+                        "topic {} does not match signature {}",
+                        encode_hex(hash),
+                        signature
+                    ));
+                }
+            }
+
+            len => return Err(anyhow!("invalid hash length: {} bytes", len)),
+        }
+
+        Ok(())
+    }
+
+    fn insert_unique(sigs: &mut Vec<String>, signature: &str) {
+        if !sigs.iter().any(|sig| sig == signature) {
+            sigs.push(signature.to_string());
+        }
+    }
+
+    fn parse_text(s: &str) -> Result<Self> {
+        let mut db = Self::new();
+
+        for (line_no, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let hashed = line
+                .split_once(|c: char| c.is_whitespace() || c == ',' || c == ':')
+                .and_then(|(hash, sig)| Some((parse_hash(hash)?, sig.trim())));
+
+            let res = match hashed {
+                Some((hash, sig)) => db.insert_hashed(&hash, sig),
+                None => db
+                    .insert_function(line)
+                    .and_then(|_| db.insert_event(line))
+                    .map(|_| ()),
+            };
+
+            res.with_context(|| {
+                format!("invalid signature database entry at line {}", line_no + 1)
+            })?;
+        }
+
+        Ok(db)
+    }
+
+    fn parse_json(s: &str) -> Result<Self> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Signatures {
+            One(String),
+            Many(Vec<String>),
+        }
+
+        let entries: BTreeMap<String, Signatures> = serde_json::from_str(s)?;
+
+        let mut db = Self::new();
+
+        for (hash, sigs) in entries {
+            let bytes = parse_hash(&hash).ok_or_else(|| anyhow!("invalid hash: {}", hash))?;
+
+            let sigs = match sigs {
+                Signatures::One(sig) => vec![sig],
+                Signatures::Many(sigs) => sigs,
+            };
+
+            for sig in sigs {
+                db.insert_hashed(&bytes, &sig)?;
+            }
+        }
+
+        Ok(db)
+    }
+}
+
+impl FromStr for SignatureDb {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.trim_start().starts_with('{') {
+            Self::parse_json(s)
+        } else {
+            Self::parse_text(s)
+        }
+    }
+}
+
+fn parse_hash(s: &str) -> Option<Vec<u8>> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+
+    if s.len() != 8 && s.len() != 64 {
+        return None;
+    }
+
+    decode_hex(s).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    const TRANSFER_TOPIC: &str = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+    #[test]
+    fn parse_text_format() {
+        let db: SignatureDb = format!(
+            "# ERC-20\n\n0xa9059cbb transfer(address,uint256)\n0x{} Transfer(address,address,uint256)\napprove(address,uint256)\n",
+            TRANSFER_TOPIC
+        )
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            db.function_signatures([0xa9, 0x05, 0x9c, 0xbb]),
+            ["transfer(address,uint256)".to_string()]
+        );
+        assert_eq!(
+            db.function_signatures([0x09, 0x5e, 0xa7, 0xb3]),
+            ["approve(address,uint256)".to_string()]
+        );
+
+        let events = db.resolve_topic(H256::from_str(TRANSFER_TOPIC).unwrap());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].signature(), "Transfer(address,address,uint256)");
+    }
+
+    #[test]
+    fn parse_json_format() {
+        let db: SignatureDb = serde_json::json!({
+            "0xa9059cbb": ["transfer(address,uint256)", "transfer(bytes4[9],bytes5[6],int48[11])"],
+            "0x095ea7b3": "approve(address,uint256)",
+        })
+        .to_string()
+        .parse()
+        .unwrap();
+
+        let functions = db.resolve_selector([0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].name, "transfer");
+        assert_eq!(db.len(), 2);
+    }
+
+    #[test]
+    fn mismatched_hash() {
+        let res = "0xdeadbeef transfer(address,uint256)".parse::<SignatureDb>();
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn write_and_load() {
+        let mut db = SignatureDb::new();
+        db.insert_function("transfer(address,uint256)").unwrap();
+        db.insert_event("Transfer(address,address,uint256)")
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!("ethereum_abi_sigdb_{}", std::process::id()));
+        db.save(&path).unwrap();
+        let loaded = SignatureDb::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, db);
+    }
+
+    #[test]
+    fn unknown_selector() {
+        let db = SignatureDb::new();
+
+        assert!(db.is_empty());
+        assert!(db.resolve_selector([0, 0, 0, 0]).is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::H160;
+use serde::Deserialize;
+
+use crate::{
+    address::to_checksum,
+    artifact::{normalize_bytecode, SolcMetadata},
+    unblock::unblock,
+    Abi, AbiProvider, Artifact,
+};
+
+#[cfg(feature = "async")]
+use crate::{AbiFuture, AsyncAbiProvider};
+
+const SOURCIFY_URL: &str = "https://sourcify.dev/server";
+
+/// Client fetching verified contracts from Sourcify.
+///
+/// ```no_run
+/// use ethereum_abi::Sourcify;
+/// use ethereum_types::H160;
+///
+/// let artifact = Sourcify::new().fetch_artifact_blocking(1, &H160::zero()).unwrap();
+/// println!("{:?} {:?}", artifact.contract_name, artifact.compiler_version);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sourcify {
+    agent: ureq::Agent,
+    url: String,
+}
+
+impl Default for Sourcify {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sourcify {
+    /// Creates a client using the public Sourcify server.
+    pub fn new() -> Self {
+        Self {
+            agent: ureq::Agent::new_with_defaults(),
+            url: SOURCIFY_URL.to_string(),
+        }
+    }
+
+    /// Overrides the server URL, e.g. for a self-hosted instance.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Fetches the verified contract at the given address.
+    ///
+    /// The returned artifact holds the ABI, NatSpec documentation, compiler
+    /// version and settings from the contract's metadata, along with its
+    /// on-chain bytecode and address. Fails if the contract is not verified
+    /// on that chain.
+    pub fn fetch_artifact_blocking(&self, chain_id: u64, address: &H160) -> Result<Artifact> {
+        self.find_artifact(chain_id, address)?.ok_or_else(|| {
+            anyhow!(
+                "contract {} is not verified on chain {}",
+                to_checksum(address),
+                chain_id
+            )
+        })
+    }
+
+    /// Fetches the verified contract at the given address, without blocking
+    /// the calling task.
+    ///
+    /// The request runs on a thread spawned for it.
+    pub async fn fetch_artifact(&self, chain_id: u64, address: &H160) -> Result<Artifact> {
+        let client = self.clone();
+        let address = *address;
+
+        unblock(move || client.fetch_artifact_blocking(chain_id, &address)).await
+    }
+
+    fn find_artifact(&self, chain_id: u64, address: &H160) -> Result<Option<Artifact>> {
+        let response = self
+            .agent
+            .get(&format!(
+                "{}/v2/contract/{}/{}",
+                self.url,
+                chain_id,
+                to_checksum(address)
+            ))
+            .query("fields", "metadata,creationBytecode,runtimeBytecode")
+            .call();
+
+        let body: SourcifyContract = match response {
+            Err(ureq::Error::StatusCode(404)) => return Ok(None),
+            response => response
+                .map_err(|err| anyhow!("sourcify request failed: {}", err))?
+                .body_mut()
+                .read_json()?,
+        };
+
+        parse_sourcify_contract(body, chain_id, address).map(Some)
+    }
+}
+
+impl AbiProvider for Sourcify {
+    fn get_abi(&self, chain_id: u64, address: &H160) -> Result<Option<Abi>> {
+        Ok(self
+            .find_artifact(chain_id, address)?
+            .map(|artifact| artifact.abi))
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncAbiProvider for Sourcify {
+    fn get_abi_async<'a>(&'a self, chain_id: u64, address: &'a H160) -> AbiFuture<'a> {
+        let client = self.clone();
+        let address = *address;
+
+        Box::pin(unblock(move || client.get_abi(chain_id, &address)))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SourcifyContract {
+    metadata: Option<SolcMetadata>,
+    #[serde(default)]
+    creation_bytecode: Option<SourcifyBytecode>,
+    #[serde(default)]
+    runtime_bytecode: Option<SourcifyBytecode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SourcifyBytecode {
+    #[serde(default)]
+    onchain_bytecode: Option<String>,
+}
+
+fn parse_sourcify_contract(
+    contract: SourcifyContract,
+    chain_id: u64,
+    address: &H160,
+) -> Result<Artifact> {
+    let metadata = contract
+        .metadata
+        .ok_or_else(|| anyhow!("sourcify returned no metadata"))?;
+
+    let mut artifact = Artifact::from_solc_metadata(metadata)?;
+
+    artifact.bytecode = normalize_bytecode(
+        contract
+            .creation_bytecode
+            .and_then(|code| code.onchain_bytecode),
+    );
+    artifact.deployed_bytecode = normalize_bytecode(
+        contract
+            .runtime_bytecode
+            .and_then(|code| code.onchain_bytecode),
+    );
+    artifact.networks.insert(chain_id, *address);
+
+    Ok(artifact)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sourcify_contract() {
+        let body = serde_json::json!({
+            "match": "exact_match",
+            "chainId": "1",
+            "address": "0x1111111111111111111111111111111111111111",
+            "metadata": {
+                "compiler": {"version": "0.8.19+commit.7dd6d404"},
+                "output": {
+                    "abi": [{"type": "function", "name": "increment", "inputs": [], "outputs": [], "stateMutability": "nonpayable"}]
+                },
+                "settings": {"compilationTarget": {"src/Counter.sol": "Counter"}}
+            },
+            "creationBytecode": {"onchainBytecode": "0x6080604052"},
+            "runtimeBytecode": {"onchainBytecode": "0x60806040"}
+        });
+
+        let address = H160::repeat_byte(0x11);
+        let artifact =
+            parse_sourcify_contract(serde_json::from_value(body).unwrap(), 1, &address).unwrap();
+
+        assert_eq!(artifact.contract_name.as_deref(), Some("Counter"));
+        assert_eq!(
+            artifact.compiler_version.as_deref(),
+            Some("0.8.19+commit.7dd6d404")
+        );
+        assert_eq!(artifact.bytecode.as_deref(), Some("6080604052"));
+        assert_eq!(artifact.address(1), Some(address));
+
+        let body = serde_json::json!({"match": null});
+        assert!(
+            parse_sourcify_contract(serde_json::from_value(body).unwrap(), 1, &address).is_err()
+        );
+    }
+}
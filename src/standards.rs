@@ -0,0 +1,458 @@
+//! Ready-made ABIs for common token standards and widely deployed contracts.
+//!
+//! Each ABI is built the first time it is used.
+//!
+//! ```
+//! use ethereum_abi::standards::ERC20;
+//!
+//! assert_eq!(ERC20.functions.len(), 9);
+//! ```
+
+use std::sync::LazyLock;
+
+use crate::{Abi, StateMutability};
+
+use StateMutability::{NonPayable, Payable, View};
+
+/// ERC-20 token, including the optional `name`, `symbol` and `decimals`.
+pub static ERC20: LazyLock<Abi> = LazyLock::new(|| {
+    Abi::builder()
+        .function("name", &[], &[("", "string")], View)
+        .function("symbol", &[], &[("", "string")], View)
+        .function("decimals", &[], &[("", "uint8")], View)
+        .function("totalSupply", &[], &[("", "uint256")], View)
+        .function(
+            "balanceOf",
+            &[("owner", "address")],
+            &[("", "uint256")],
+            View,
+        )
+        .function(
+            "transfer",
+            &[("to", "address"), ("value", "uint256")],
+            &[("", "bool")],
+            NonPayable,
+        )
+        .function(
+            "transferFrom",
+            &[("from", "address"), ("to", "address"), ("value", "uint256")],
+            &[("", "bool")],
+            NonPayable,
+        )
+        .function(
+            "approve",
+            &[("spender", "address"), ("value", "uint256")],
+            &[("", "bool")],
+            NonPayable,
+        )
+        .function(
+            "allowance",
+            &[("owner", "address"), ("spender", "address")],
+            &[("", "uint256")],
+            View,
+        )
+        .event(
+            "Transfer",
+            &[
+                ("from", "address", true),
+                ("to", "address", true),
+                ("value", "uint256", false),
+            ],
+        )
+        .event(
+            "Approval",
+            &[
+                ("owner", "address", true),
+                ("spender", "address", true),
+                ("value", "uint256", false),
+            ],
+        )
+        .build()
+        .expect("valid ERC-20 ABI")
+});
+
+/// ERC-721 token, including the metadata extension and ERC-165.
+pub static ERC721: LazyLock<Abi> = LazyLock::new(|| {
+    Abi::builder()
+        .function("name", &[], &[("", "string")], View)
+        .function("symbol", &[], &[("", "string")], View)
+        .function(
+            "tokenURI",
+            &[("tokenId", "uint256")],
+            &[("", "string")],
+            View,
+        )
+        .function(
+            "supportsInterface",
+            &[("interfaceId", "bytes4")],
+            &[("", "bool")],
+            View,
+        )
+        .function(
+            "balanceOf",
+            &[("owner", "address")],
+            &[("", "uint256")],
+            View,
+        )
+        .function(
+            "ownerOf",
+            &[("tokenId", "uint256")],
+            &[("", "address")],
+            View,
+        )
+        .function(
+            "safeTransferFrom",
+            &[
+                ("from", "address"),
+                ("to", "address"),
+                ("tokenId", "uint256"),
+                ("data", "bytes"),
+            ],
+            &[],
+            Payable,
+        )
+        .function(
+            "safeTransferFrom",
+            &[
+                ("from", "address"),
+                ("to", "address"),
+                ("tokenId", "uint256"),
+            ],
+            &[],
+            Payable,
+        )
+        .function(
+            "transferFrom",
+            &[
+                ("from", "address"),
+                ("to", "address"),
+                ("tokenId", "uint256"),
+            ],
+            &[],
+            Payable,
+        )
+        .function(
+            "approve",
+            &[("approved", "address"), ("tokenId", "uint256")],
+            &[],
+            Payable,
+        )
+        .function(
+            "setApprovalForAll",
+            &[("operator", "address"), ("approved", "bool")],
+            &[],
+            NonPayable,
+        )
+        .function(
+            "getApproved",
+            &[("tokenId", "uint256")],
+            &[("", "address")],
+            View,
+        )
+        .function(
+            "isApprovedForAll",
+            &[("owner", "address"), ("operator", "address")],
+            &[("", "bool")],
+            View,
+        )
+        .event(
+            "Transfer",
+            &[
+                ("from", "address", true),
+                ("to", "address", true),
+                ("tokenId", "uint256", true),
+            ],
+        )
+        .event(
+            "Approval",
+            &[
+                ("owner", "address", true),
+                ("approved", "address", true),
+                ("tokenId", "uint256", true),
+            ],
+        )
+        .event(
+            "ApprovalForAll",
+            &[
+                ("owner", "address", true),
+                ("operator", "address", true),
+                ("approved", "bool", false),
+            ],
+        )
+        .build()
+        .expect("valid ERC-721 ABI")
+});
+
+/// ERC-1155 multi token, including the metadata URI extension and ERC-165.
+pub static ERC1155: LazyLock<Abi> = LazyLock::new(|| {
+    Abi::builder()
+        .function("uri", &[("id", "uint256")], &[("", "string")], View)
+        .function(
+            "supportsInterface",
+            &[("interfaceId", "bytes4")],
+            &[("", "bool")],
+            View,
+        )
+        .function(
+            "safeTransferFrom",
+            &[
+                ("from", "address"),
+                ("to", "address"),
+                ("id", "uint256"),
+                ("value", "uint256"),
+                ("data", "bytes"),
+            ],
+            &[],
+            NonPayable,
+        )
+        .function(
+            "safeBatchTransferFrom",
+            &[
+                ("from", "address"),
+                ("to", "address"),
+                ("ids", "uint256[]"),
+                ("values", "uint256[]"),
+                ("data", "bytes"),
+            ],
+            &[],
+            NonPayable,
+        )
+        .function(
+            "balanceOf",
+            &[("account", "address"), ("id", "uint256")],
+            &[("", "uint256")],
+            View,
+        )
+        .function(
+            "balanceOfBatch",
+            &[("accounts", "address[]"), ("ids", "uint256[]")],
+            &[("", "uint256[]")],
+            View,
+        )
+        .function(
+            "setApprovalForAll",
+            &[("operator", "address"), ("approved", "bool")],
+            &[],
+            NonPayable,
+        )
+        .function(
+            "isApprovedForAll",
+            &[("account", "address"), ("operator", "address")],
+            &[("", "bool")],
+            View,
+        )
+        .event(
+            "TransferSingle",
+            &[
+                ("operator", "address", true),
+                ("from", "address", true),
+                ("to", "address", true),
+                ("id", "uint256", false),
+                ("value", "uint256", false),
+            ],
+        )
+        .event(
+            "TransferBatch",
+            &[
+                ("operator", "address", true),
+                ("from", "address", true),
+                ("to", "address", true),
+                ("ids", "uint256[]", false),
+                ("values", "uint256[]", false),
+            ],
+        )
+        .event(
+            "ApprovalForAll",
+            &[
+                ("account", "address", true),
+                ("operator", "address", true),
+                ("approved", "bool", false),
+            ],
+        )
+        .event(
+            "URI",
+            &[("value", "string", false), ("id", "uint256", true)],
+        )
+        .build()
+        .expect("valid ERC-1155 ABI")
+});
+
+/// Wrapped Ether (WETH9), using its original parameter names.
+pub static WETH: LazyLock<Abi> = LazyLock::new(|| {
+    Abi::builder()
+        .function("name", &[], &[("", "string")], View)
+        .function("symbol", &[], &[("", "string")], View)
+        .function("decimals", &[], &[("", "uint8")], View)
+        .function("totalSupply", &[], &[("", "uint256")], View)
+        .function("balanceOf", &[("", "address")], &[("", "uint256")], View)
+        .function(
+            "allowance",
+            &[("", "address"), ("", "address")],
+            &[("", "uint256")],
+            View,
+        )
+        .function("deposit", &[], &[], Payable)
+        .function("withdraw", &[("wad", "uint256")], &[], NonPayable)
+        .function(
+            "approve",
+            &[("guy", "address"), ("wad", "uint256")],
+            &[("", "bool")],
+            NonPayable,
+        )
+        .function(
+            "transfer",
+            &[("dst", "address"), ("wad", "uint256")],
+            &[("", "bool")],
+            NonPayable,
+        )
+        .function(
+            "transferFrom",
+            &[("src", "address"), ("dst", "address"), ("wad", "uint256")],
+            &[("", "bool")],
+            NonPayable,
+        )
+        .event(
+            "Approval",
+            &[
+                ("src", "address", true),
+                ("guy", "address", true),
+                ("wad", "uint256", false),
+            ],
+        )
+        .event(
+            "Transfer",
+            &[
+                ("src", "address", true),
+                ("dst", "address", true),
+                ("wad", "uint256", false),
+            ],
+        )
+        .event(
+            "Deposit",
+            &[("dst", "address", true), ("wad", "uint256", false)],
+        )
+        .event(
+            "Withdrawal",
+            &[("src", "address", true), ("wad", "uint256", false)],
+        )
+        .fallback(StateMutability::Payable)
+        .build()
+        .expect("valid WETH ABI")
+});
+
+/// Multicall3, deployed at [`MULTICALL3_ADDRESS`](crate::MULTICALL3_ADDRESS)
+/// on most chains. Only the call batching functions are included.
+pub static MULTICALL3: LazyLock<Abi> = LazyLock::new(|| {
+    Abi::builder()
+        .function(
+            "aggregate",
+            &[("calls", "(address target,bytes callData)[]")],
+            &[("blockNumber", "uint256"), ("returnData", "bytes[]")],
+            Payable,
+        )
+        .function(
+            "tryAggregate",
+            &[
+                ("requireSuccess", "bool"),
+                ("calls", "(address target,bytes callData)[]"),
+            ],
+            &[("returnData", "(bool success,bytes returnData)[]")],
+            Payable,
+        )
+        .function(
+            "aggregate3",
+            &[(
+                "calls",
+                "(address target,bool allowFailure,bytes callData)[]",
+            )],
+            &[("returnData", "(bool success,bytes returnData)[]")],
+            Payable,
+        )
+        .function(
+            "aggregate3Value",
+            &[(
+                "calls",
+                "(address target,bool allowFailure,uint256 value,bytes callData)[]",
+            )],
+            &[("returnData", "(bool success,bytes returnData)[]")],
+            Payable,
+        )
+        .build()
+        .expect("valid Multicall3 ABI")
+});
+
+/// Safe (formerly Gnosis Safe) smart account. Only the transaction
+/// execution entry point is included.
+pub static SAFE: LazyLock<Abi> = LazyLock::new(|| {
+    Abi::builder()
+        .function(
+            "execTransaction",
+            &[
+                ("to", "address"),
+                ("value", "uint256"),
+                ("data", "bytes"),
+                ("operation", "uint8"),
+                ("safeTxGas", "uint256"),
+                ("baseGas", "uint256"),
+                ("gasPrice", "uint256"),
+                ("gasToken", "address"),
+                ("refundReceiver", "address"),
+                ("signatures", "bytes"),
+            ],
+            &[("success", "bool")],
+            Payable,
+        )
+        .build()
+        .expect("valid Safe ABI")
+});
+
+/// Safe `MultiSend` and `MultiSendCallOnly` libraries, which batch
+/// transactions packed into a single `bytes` argument.
+pub static MULTI_SEND: LazyLock<Abi> = LazyLock::new(|| {
+    Abi::builder()
+        .function("multiSend", &[("transactions", "bytes")], &[], Payable)
+        .build()
+        .expect("valid MultiSend ABI")
+});
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::{H160, H256, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::{Standard, Value};
+
+    use super::*;
+
+    #[test]
+    fn standards_detected() {
+        assert_eq!(ERC20.detect_standards(), vec![Standard::Erc20]);
+        assert_eq!(
+            ERC721.detect_standards(),
+            vec![Standard::Erc165, Standard::Erc721, Standard::Erc721Metadata]
+        );
+        assert_eq!(
+            ERC1155.detect_standards(),
+            vec![Standard::Erc165, Standard::Erc1155]
+        );
+        assert_eq!(WETH.detect_standards(), vec![Standard::Erc20]);
+    }
+
+    #[test]
+    fn decode_erc20_transfer_log() {
+        let topics = vec![
+            ERC20.events[0].topic(),
+            H256::from(H160::repeat_byte(0x11)),
+            H256::from(H160::repeat_byte(0x22)),
+        ];
+        let data = Value::encode(&[Value::Uint(U256::from(1000), 256)]);
+
+        let (event, params) = ERC20.decode_log_from_slice(&topics, &data).unwrap();
+
+        assert_eq!(event.name, "Transfer");
+        assert_eq!(
+            params.reader().by_name["value"].value,
+            Value::Uint(U256::from(1000), 256)
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::{H160, U256};
+use std::sync::Arc;
+
+use crate::{
+    address::parse_address, decode_hex, json::to_signed, units::parse_number, Type, Value,
+};
+
+impl Value {
+    /// Parses a value of the given type from human friendly text, in the
+    /// style of command line tools:
+    ///
+    /// - integers in decimal or `0x` hex, optionally with an exponent and an
+    ///   ether unit: `1000`, `1e18`, `1.5 ether`, `30 gwei`;
+    /// - addresses, bytes and functions as hex, with or without `0x`;
+    /// - `true` and `false`;
+    /// - strings as is or between double quotes;
+    /// - arrays as `[1, 2, 3]` and tuples as `(1, foo)`.
+    ///
+    /// ```
+    /// use ethereum_abi::{Type, Value};
+    /// use ethereum_types::U256;
+    ///
+    /// let value = Value::parse(&Type::Uint(256), "1.5 ether").unwrap();
+    ///
+    /// assert_eq!(value, Value::Uint(U256::exp10(17) * 15, 256));
+    /// ```
+    pub fn parse(ty: &Type, s: &str) -> Result<Value> {
+        let s = s.trim();
+
+        parse_value(ty, s).map_err(|err| anyhow!("invalid {} {:?}: {}", ty, s, err))
+    }
+}
+
+fn parse_value(ty: &Type, s: &str) -> Result<Value> {
+    match ty {
+        Type::Uint(size) => {
+            let (negative, v) = parse_amount(s, 0)?;
+
+            if negative && !v.is_zero() {
+                return Err(anyhow!("cannot be negative"));
+            }
+            if v.bits() > *size {
+                return Err(anyhow!("out of range"));
+            }
+
+            Ok(Value::Uint(v, *size))
+        }
+
+        Type::Int(size) => {
+            let (negative, v) = parse_amount(s, 0)?;
+
+            Ok(Value::Int(to_signed(negative, v, *size, ty)?, *size))
+        }
+
+        Type::Fixed(size, decimals) => {
+            let (negative, v) = parse_amount(s, *decimals)?;
+
+            Ok(Value::Fixed(
+                to_signed(negative, v, *size, ty)?,
+                *size,
+                *decimals,
+            ))
+        }
+
+        Type::Ufixed(size, decimals) => {
+            let (negative, v) = parse_amount(s, *decimals)?;
+
+            if negative && !v.is_zero() {
+                return Err(anyhow!("cannot be negative"));
+            }
+            if v.bits() > *size {
+                return Err(anyhow!("out of range"));
+            }
+
+            Ok(Value::Ufixed(v, *size, *decimals))
+        }
+
+        Type::Address => Ok(Value::Address(parse_address(s)?)),
+
+        Type::Bool => match s {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            _ => Err(anyhow!("expected true or false")),
+        },
+
+        Type::Function => {
+            let bytes = decode_hex(s)?;
+
+            if bytes.len() != 24 {
+                return Err(anyhow!("expected 24 bytes, got {}", bytes.len()));
+            }
+
+            let mut selector = [0u8; 4];
+            selector.copy_from_slice(&bytes[20..]);
+
+            Ok(Value::Function(H160::from_slice(&bytes[..20]), selector))
+        }
+
+        Type::FixedBytes(size) => {
+            let bytes = decode_hex(s)?;
+
+            if bytes.len() != *size {
+                return Err(anyhow!("expected {} bytes, got {}", size, bytes.len()));
+            }
+
+            Ok(Value::FixedBytes(bytes))
+        }
+
+        Type::Bytes => Ok(Value::Bytes(decode_hex(s)?.into())),
+
+        Type::String => Ok(Value::String(unquote(s))),
+
+        Type::FixedArray(item_ty, size) => {
+            let items = split_list(s, '[', ']')?;
+
+            if items.len() != *size {
+                return Err(anyhow!("expected {} elements, got {}", size, items.len()));
+            }
+
+            Ok(Value::FixedArray(
+                parse_items(item_ty, &items)?,
+                Arc::new((**item_ty).clone()),
+            ))
+        }
+
+        Type::Array(item_ty) => {
+            let items = split_list(s, '[', ']')?;
+
+            Ok(Value::Array(
+                parse_items(item_ty, &items)?,
+                Arc::new((**item_ty).clone()),
+            ))
+        }
+
+        Type::Tuple(fields) | Type::Struct(_, fields) => {
+            let items = split_list(s, '(', ')')?;
+
+            if items.len() != fields.len() {
+                return Err(anyhow!(
+                    "expected {} fields, got {}",
+                    fields.len(),
+                    items.len()
+                ));
+            }
+
+            let values = fields
+                .iter()
+                .zip(&items)
+                .map(|((name, ty), item)| Ok((name.clone(), Value::parse(ty, item)?)))
+                .collect::<Result<_>>()?;
+
+            Ok(Value::Tuple(values))
+        }
+    }
+}
+
+fn parse_items(ty: &Type, items: &[&str]) -> Result<Vec<Value>> {
+    items.iter().map(|item| Value::parse(ty, item)).collect()
+}
+
+// Parses `<number>[e<exponent>] [unit]` scaled by `10^decimals`.
+fn parse_amount(s: &str, decimals: usize) -> Result<(bool, U256)> {
+    let (number, unit) = match s.split_once(char::is_whitespace) {
+        Some((number, unit)) => (number, unit.trim()),
+        None => (s, ""),
+    };
+
+    let unit_decimals = match unit {
+        "" | "wei" => 0,
+        "gwei" => 9,
+        "ether" => 18,
+        _ => return Err(anyhow!("unknown unit {:?}", unit)),
+    };
+
+    let (number, exponent) = match number.split_once(['e', 'E']) {
+        Some((number, exponent)) if !number.contains("0x") => (
+            number,
+            exponent
+                .parse::<usize>()
+                .map_err(|_| anyhow!("invalid exponent {:?}", exponent))?,
+        ),
+        _ => (number, 0),
+    };
+
+    // 10^77 is the largest power of ten a U256 holds.
+    let total = decimals
+        .checked_add(unit_decimals)
+        .and_then(|total| total.checked_add(exponent))
+        .filter(|total| exponent == 0 || *total <= 77)
+        .ok_or_else(|| anyhow!("exponent too large: {}", s))?;
+
+    parse_number(number, total)
+}
+
+fn unquote(s: &str) -> String {
+    match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(inner) => inner.replace("\\\"", "\"").replace("\\\\", "\\"),
+        None => s.to_string(),
+    }
+}
+
+// Splits `[a, b, (c, d)]` into its top level items, leaving nested lists and
+// quoted strings intact.
+fn split_list(s: &str, open: char, close: char) -> Result<Vec<&str>> {
+    let inner = s
+        .strip_prefix(open)
+        .and_then(|s| s.strip_suffix(close))
+        .ok_or_else(|| anyhow!("expected {}...{}", open, close))?
+        .trim();
+
+    if inner.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut items = vec![];
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (i, c) in inner.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            _ if quoted => {}
+            '[' | '(' => depth += 1,
+            ']' | ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| anyhow!("unbalanced {:?}", c))?
+            }
+            ',' if depth == 0 => {
+                items.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 || quoted {
+        return Err(anyhow!("unterminated list"));
+    }
+
+    items.push(inner[start..].trim());
+
+    Ok(items)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::params::parse_type_str;
+
+    use super::*;
+
+    #[test]
+    fn parse_amounts() {
+        let uint = Type::Uint(256);
+
+        assert_eq!(
+            Value::parse(&uint, "1000").unwrap(),
+            Value::Uint(U256::from(1000), 256)
+        );
+        assert_eq!(
+            Value::parse(&uint, "0xff").unwrap(),
+            Value::Uint(U256::from(255), 256)
+        );
+        assert_eq!(
+            Value::parse(&uint, "1e18").unwrap(),
+            Value::Uint(U256::exp10(18), 256)
+        );
+        assert_eq!(
+            Value::parse(&uint, "30 gwei").unwrap(),
+            Value::Uint(U256::from(30_000_000_000u64), 256)
+        );
+        assert_eq!(
+            Value::parse(&Type::Int(8), "-1").unwrap(),
+            Value::Int(U256::MAX, 8)
+        );
+        assert_eq!(
+            Value::parse(&Type::Ufixed(128, 2), "1.25").unwrap(),
+            Value::Ufixed(U256::from(125), 128, 2)
+        );
+
+        assert!(Value::parse(&uint, "1.5").is_err());
+        assert!(Value::parse(&uint, "1 wei1").is_err());
+        assert!(Value::parse(&uint, "0.1 gwei").is_ok());
+        assert!(Value::parse(&uint, "0.0000000001 gwei").is_err());
+        assert!(Value::parse(&Type::Uint(8), "256").is_err());
+
+        assert_eq!(
+            Value::parse(&uint, "1e77").unwrap(),
+            Value::Uint(U256::exp10(77), 256)
+        );
+        assert!(Value::parse(&uint, "1e78").is_err());
+        assert!(Value::parse(&uint, "1e4000000000").is_err());
+        assert!(Value::parse(&uint, "1e18446744073709551615").is_err());
+        assert!(Value::parse(&uint, "1e60 ether").is_err());
+        assert!(Value::parse(&Type::Ufixed(256, 80), "0x1").is_err());
+        assert!(Value::parse(&Type::Ufixed(256, 80), "1").is_err());
+    }
+
+    #[test]
+    fn parse_compound_values() {
+        let ty = parse_type_str("(address,string,bytes2[],bool)").unwrap();
+
+        let value = Value::parse(
+            &ty,
+            r#"(0x000000000000000000000000000000000000dEaD, "a, (b)", [0xbeef, cafe], true)"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            value,
+            Value::Tuple(vec![
+                ("".into(), Value::Address(H160::from_low_u64_be(0xdead))),
+                ("".into(), Value::String("a, (b)".to_string())),
+                (
+                    "".into(),
+                    Value::Array(
+                        vec![
+                            Value::FixedBytes(vec![0xbe, 0xef]),
+                            Value::FixedBytes(vec![0xca, 0xfe])
+                        ],
+                        Type::FixedBytes(2).into()
+                    )
+                ),
+                ("".into(), Value::Bool(true)),
+            ])
+        );
+
+        let ty = parse_type_str("uint8[2][]").unwrap();
+        let value = Value::parse(&ty, "[[1, 2], [3, 4]]").unwrap();
+
+        assert!(value.matches(&ty));
+        assert!(Value::parse(&ty, "[[1, 2], [3]]").is_err());
+        assert!(Value::parse(&ty, "[[1, 2]").is_err());
+        assert!(
+            Value::parse(&Type::Address, "0x000000000000000000000000000000000000DEAd").is_err()
+        );
+        assert_eq!(
+            Value::parse(&ty, "[]").unwrap(),
+            Value::Array(vec![], Type::FixedArray(Box::new(Type::Uint(8)), 2).into())
+        );
+    }
+}
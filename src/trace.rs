@@ -0,0 +1,311 @@
+use anyhow::Result;
+use ethereum_types::{H160, U256};
+use serde::Deserialize;
+use std::convert::{TryFrom, TryInto};
+
+use crate::{
+    address::{parse_address, to_checksum},
+    encode_hex,
+    receipt::{parse_data, parse_quantity, parse_u256},
+    revert::decode_revert,
+    AbiRegistry, DecodedParams, Function, Revert,
+};
+
+/// Call frame as produced by geth's `callTracer` for
+/// `debug_traceTransaction` and `debug_traceCall`.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "CallFrameEntry")]
+pub struct CallFrame {
+    /// Frame type: `CALL`, `STATICCALL`, `DELEGATECALL`, `CREATE`, ...
+    pub call_type: String,
+    /// Caller address.
+    pub from: H160,
+    /// Callee address, or the created contract for `CREATE` frames.
+    pub to: Option<H160>,
+    /// Wei sent along with the call.
+    pub value: Option<U256>,
+    /// Gas made available to the call.
+    pub gas: Option<u64>,
+    /// Gas used by the call.
+    pub gas_used: Option<u64>,
+    /// Calldata, or init code for `CREATE` frames.
+    pub input: Vec<u8>,
+    /// Returned data, which is the revert data for failed calls.
+    pub output: Vec<u8>,
+    /// Error message if the call failed, e.g. `execution reverted`.
+    pub error: Option<String>,
+    /// Sub-calls made by this call, in execution order.
+    pub calls: Vec<CallFrame>,
+}
+
+/// Call frame decoded against the ABIs of an [`AbiRegistry`].
+///
+/// Decoding is best effort: calls to contracts without a registered ABI, or
+/// whose data does not match it, are left undecoded.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DecodedCall<'a> {
+    /// The raw call frame.
+    pub frame: &'a CallFrame,
+    /// Called function, if known.
+    pub function: Option<&'a Function>,
+    /// Decoded call arguments.
+    pub inputs: Option<DecodedParams>,
+    /// Decoded return values, for successful calls.
+    pub outputs: Option<DecodedParams>,
+    /// Decoded revert reason, for failed calls.
+    pub revert: Option<Revert<'a>>,
+    /// Decoded sub-calls.
+    pub calls: Vec<DecodedCall<'a>>,
+}
+
+impl AbiRegistry {
+    /// Decodes a `callTracer` call tree, using the ABI registered for each
+    /// frame's callee.
+    ///
+    /// Revert data is decoded as the callee's custom errors, falling back to
+    /// `Error(string)` and `Panic(uint256)`.
+    pub fn decode_trace<'a>(&'a self, frame: &'a CallFrame) -> DecodedCall<'a> {
+        let abi = frame.to.as_ref().and_then(|to| self.get(to));
+
+        let function = abi.and_then(|abi| {
+            let selector = frame.input.get(0..4)?.try_into().ok()?;
+            abi.function_by_selector(selector)
+        });
+
+        let inputs = function.and_then(|f| f.decode_input_from_slice(&frame.input[4..]).ok());
+
+        let (outputs, revert) = if frame.error.is_some() {
+            (None, decode_revert(abi, &frame.output).ok())
+        } else {
+            (
+                function.and_then(|f| f.decode_output_from_slice(&frame.output).ok()),
+                None,
+            )
+        };
+
+        DecodedCall {
+            frame,
+            function,
+            inputs,
+            outputs,
+            revert,
+            calls: frame
+                .calls
+                .iter()
+                .map(|call| self.decode_trace(call))
+                .collect(),
+        }
+    }
+}
+
+impl DecodedCall<'_> {
+    /// Returns whether the call failed.
+    pub fn is_error(&self) -> bool {
+        self.frame.error.is_some()
+    }
+
+    /// Iterates over this call and all its sub-calls, depth first, along
+    /// with their depth in the tree.
+    pub fn walk(&self) -> impl Iterator<Item = (usize, &DecodedCall<'_>)> {
+        let mut stack = vec![(0, self)];
+
+        std::iter::from_fn(move || {
+            let (depth, call) = stack.pop()?;
+
+            stack.extend(call.calls.iter().rev().map(|sub| (depth + 1, sub)));
+
+            Some((depth, call))
+        })
+    }
+}
+
+impl std::fmt::Display for DecodedCall<'_> {
+    /// Formats the call tree one call per line, sub-calls indented under
+    /// their caller.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (depth, call) in self.walk() {
+            let frame = call.frame;
+
+            write!(f, "{}{} ", "  ".repeat(depth), frame.call_type)?;
+
+            match &frame.to {
+                Some(to) => write!(f, "{}", to_checksum(to))?,
+                None => write!(f, "?")?,
+            }
+
+            match (call.function, &call.inputs) {
+                (Some(function), Some(inputs)) => write!(f, "::{}({})", function.name, inputs)?,
+                (Some(function), None) => write!(f, "::{}(?)", function.name)?,
+                (None, _) if frame.input.is_empty() => {}
+                (None, _) => write!(f, " {}", encode_hex(&frame.input))?,
+            }
+
+            if let Some(outputs) = &call.outputs {
+                if !outputs.is_empty() {
+                    write!(f, " -> ({})", outputs)?;
+                }
+            }
+
+            match (&call.revert, &frame.error) {
+                (Some(revert), _) => write!(f, " [{}]", revert)?,
+                (None, Some(error)) => write!(f, " [{}]", error)?,
+                (None, None) => {}
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CallFrameEntry {
+    #[serde(rename = "type")]
+    type_: String,
+    from: String,
+    to: Option<String>,
+    value: Option<String>,
+    gas: Option<String>,
+    gas_used: Option<String>,
+    #[serde(default)]
+    input: String,
+    #[serde(default)]
+    output: String,
+    error: Option<String>,
+    #[serde(default)]
+    calls: Vec<CallFrame>,
+}
+
+impl TryFrom<CallFrameEntry> for CallFrame {
+    type Error = anyhow::Error;
+
+    fn try_from(entry: CallFrameEntry) -> Result<Self> {
+        Ok(CallFrame {
+            call_type: entry.type_,
+            from: parse_address(&entry.from)?,
+            to: entry.to.as_deref().map(parse_address).transpose()?,
+            value: entry.value.as_deref().map(parse_u256).transpose()?,
+            gas: entry.gas.as_deref().map(parse_quantity).transpose()?,
+            gas_used: entry.gas_used.as_deref().map(parse_quantity).transpose()?,
+            input: parse_data(&entry.input)?,
+            output: parse_data(&entry.output)?,
+            error: entry.error,
+            calls: entry.calls,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{Abi, StateMutability, Value};
+
+    use super::*;
+
+    fn token_abi() -> Abi {
+        Abi::builder()
+            .function(
+                "transfer",
+                &[("to", "address"), ("amount", "uint256")],
+                &[("", "bool")],
+                StateMutability::NonPayable,
+            )
+            .error("InsufficientBalance", &[("available", "uint256")])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn decode_call_trace() {
+        let abi = token_abi();
+        let token = H160::repeat_byte(0xaa);
+
+        let transfer = abi
+            .encode_call(
+                "transfer",
+                &[
+                    Value::Address(H160::repeat_byte(0x22)),
+                    Value::Uint(U256::from(5), 256),
+                ],
+            )
+            .unwrap();
+
+        let mut revert = abi.errors[0].selector().to_vec();
+        revert.extend(Value::encode(&[Value::Uint(U256::from(1), 256)]));
+
+        let trace = format!(
+            r#"{{
+                "type": "CALL",
+                "from": "0x1111111111111111111111111111111111111111",
+                "to": "0x3333333333333333333333333333333333333333",
+                "value": "0x0",
+                "gas": "0x7a120",
+                "gasUsed": "0x5208",
+                "input": "0xdeadbeef",
+                "output": "0x",
+                "calls": [
+                    {{
+                        "type": "CALL",
+                        "from": "0x3333333333333333333333333333333333333333",
+                        "to": "{token:?}",
+                        "input": "0x{input}",
+                        "output": "0x{ok}"
+                    }},
+                    {{
+                        "type": "CALL",
+                        "from": "0x3333333333333333333333333333333333333333",
+                        "to": "{token:?}",
+                        "input": "0x{input}",
+                        "output": "0x{revert}",
+                        "error": "execution reverted"
+                    }}
+                ]
+            }}"#,
+            token = token,
+            input = hex::encode(&transfer),
+            ok = hex::encode(Value::encode(&[Value::Bool(true)])),
+            revert = hex::encode(&revert),
+        );
+
+        let frame: CallFrame = serde_json::from_str(&trace).unwrap();
+        assert_eq!(frame.gas_used, Some(21000));
+
+        let mut registry = AbiRegistry::new();
+        registry.register(token, abi);
+
+        let decoded = registry.decode_trace(&frame);
+
+        assert_eq!(decoded.function, None);
+        assert_eq!(decoded.calls.len(), 2);
+
+        let ok = &decoded.calls[0];
+        assert_eq!(ok.function.map(|f| f.name.as_str()), Some("transfer"));
+        assert_eq!(ok.outputs.as_ref().unwrap()[0], Value::Bool(true));
+        assert!(!ok.is_error());
+
+        let failed = &decoded.calls[1];
+        assert!(failed.is_error());
+        assert_eq!(failed.outputs, None);
+        assert_eq!(
+            failed.revert.as_ref().unwrap().to_string(),
+            "InsufficientBalance(available: 1)"
+        );
+
+        assert_eq!(
+            decoded.walk().map(|(depth, _)| depth).collect::<Vec<_>>(),
+            vec![0, 1, 1]
+        );
+        assert_eq!(
+            decoded.to_string(),
+            format!(
+                "CALL 0x3333333333333333333333333333333333333333 0xdeadbeef\n  \
+                 CALL {token}::transfer(to: 0x2222222222222222222222222222222222222222, amount: 5) -> (true)\n  \
+                 CALL {token}::transfer(to: 0x2222222222222222222222222222222222222222, amount: 5) [InsufficientBalance(available: 1)]\n",
+                token = to_checksum(&token)
+            )
+        );
+    }
+}
@@ -1,14 +1,28 @@
+use anyhow::{anyhow, Result};
+
+use crate::params::parse_type_str;
+
 /// Available ABI types.
+///
+/// `==` is nominal: tuples and structs only compare equal with the same
+/// struct and field names. Use [`Type::abi_eq`] to compare encodings.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Type {
     /// Unsigned int type (uint<M>).
     Uint(usize),
     /// Signed int type (int<M>).
     Int(usize),
+    /// Signed fixed point decimal type (fixed<M>x<N>).
+    Fixed(usize, usize),
+    /// Unsigned fixed point decimal type (ufixed<M>x<N>).
+    Ufixed(usize, usize),
     /// Address type (address).
     Address,
     /// Bool type (bool).
     Bool,
+    /// External function type (function): an address followed by a function
+    /// selector, encoded as `bytes24`.
+    Function,
     /// Fixed size bytes type (bytes<M>).
     FixedBytes(usize),
     /// Fixed size array type (T\[k\])
@@ -21,6 +35,11 @@ pub enum Type {
     Array(Box<Type>),
     /// Tuple type (tuple(T1, T2, ..., Tn))
     Tuple(Vec<(String, Type)>),
+    /// Named Solidity struct, encoded as a tuple.
+    ///
+    /// Built from a tuple parameter whose `internalType` is `struct <Name>`.
+    /// The name keeps its contract qualifier, e.g. `Exchange.Order`.
+    Struct(String, Vec<(String, Type)>),
 }
 
 impl Type {
@@ -29,31 +48,99 @@ impl Type {
         match self {
             Type::Uint(_) => false,
             Type::Int(_) => false,
+            Type::Fixed(_, _) => false,
+            Type::Ufixed(_, _) => false,
             Type::Address => false,
             Type::Bool => false,
+            Type::Function => false,
             Type::FixedBytes(_) => false,
             Type::FixedArray(ty, _) => ty.is_dynamic(),
             Type::String => true,
             Type::Bytes => true,
             Type::Array(_) => true,
-            Type::Tuple(tys) => tys.iter().any(|(_, ty)| ty.is_dynamic()),
+            Type::Tuple(tys) | Type::Struct(_, tys) => tys.iter().any(|(_, ty)| ty.is_dynamic()),
+        }
+    }
+
+    /// Returns the number of bytes a value of this type takes in the head of
+    /// an encoding: its full size for static types, an offset for dynamic
+    /// ones.
+    ///
+    /// Fails if the size does not fit in a `usize`.
+    pub(crate) fn head_size(&self) -> Result<usize> {
+        let too_large = || anyhow!("{} is too large to encode", self);
+
+        match self {
+            _ if self.is_dynamic() => Ok(32),
+            Type::FixedArray(ty, size) => ty.head_size()?.checked_mul(*size).ok_or_else(too_large),
+            Type::Tuple(tys) | Type::Struct(_, tys) => {
+                tys.iter().try_fold(0usize, |total, (_, ty)| {
+                    total.checked_add(ty.head_size()?).ok_or_else(too_large)
+                })
+            }
+            _ => Ok(32),
+        }
+    }
+
+    /// Returns whether both types have the same canonical form, ignoring
+    /// struct and field names.
+    pub fn abi_eq(&self, other: &Type) -> bool {
+        match (self, other) {
+            (Type::FixedArray(a, n), Type::FixedArray(b, m)) => n == m && a.abi_eq(b),
+            (Type::Array(a), Type::Array(b)) => a.abi_eq(b),
+            (Type::Tuple(a) | Type::Struct(_, a), Type::Tuple(b) | Type::Struct(_, b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|((_, a), (_, b))| a.abi_eq(b))
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// Returns the struct name if this is a struct type.
+    pub fn struct_name(&self) -> Option<&str> {
+        match self {
+            Type::Struct(name, _) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the (name, type) fields if this is a tuple or struct type.
+    pub fn tuple_fields(&self) -> Option<&[(String, Type)]> {
+        match self {
+            Type::Tuple(tys) | Type::Struct(_, tys) => Some(tys),
+            _ => None,
         }
     }
 }
 
+impl std::str::FromStr for Type {
+    type Err = anyhow::Error;
+
+    /// Parses a canonical type string such as `uint256`, `bytes32[2][]` or
+    /// `(address,(uint8,string)[])`.
+    ///
+    /// Tuples carry no component names, so they parse as unnamed
+    /// [`Type::Tuple`]s. The `tuple(...)` spelling is accepted as well.
+    fn from_str(s: &str) -> Result<Self> {
+        parse_type_str(s)
+    }
+}
+
 impl std::fmt::Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Type::Uint(size) => write!(f, "uint{}", size),
             Type::Int(size) => write!(f, "int{}", size),
+            Type::Fixed(size, decimals) => write!(f, "fixed{}x{}", size, decimals),
+            Type::Ufixed(size, decimals) => write!(f, "ufixed{}x{}", size, decimals),
             Type::Address => write!(f, "address"),
             Type::Bool => write!(f, "bool"),
+            Type::Function => write!(f, "function"),
             Type::String => write!(f, "string"),
             Type::FixedBytes(size) => write!(f, "bytes{}", size),
             Type::Bytes => write!(f, "bytes"),
             Type::FixedArray(ty, size) => write!(f, "{}[{}]", ty, size),
             Type::Array(ty) => write!(f, "{}[]", ty),
-            Type::Tuple(tys) => write!(
+            Type::Tuple(tys) | Type::Struct(_, tys) => write!(
                 f,
                 "({})",
                 tys.iter()
@@ -64,3 +151,74 @@ impl std::fmt::Display for Type {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn parse_type_strings() {
+        assert_eq!("uint256".parse::<Type>().unwrap(), Type::Uint(256));
+        assert_eq!(
+            "bytes32[2][]".parse::<Type>().unwrap(),
+            Type::Array(Box::new(Type::FixedArray(
+                Box::new(Type::FixedBytes(32)),
+                2
+            )))
+        );
+
+        let ty: Type = "(address,(uint8,string)[])".parse().unwrap();
+        assert_eq!(
+            ty,
+            Type::Tuple(vec![
+                ("".into(), Type::Address),
+                (
+                    "".into(),
+                    Type::Array(Box::new(Type::Tuple(vec![
+                        ("".into(), Type::Uint(8)),
+                        ("".into(), Type::String),
+                    ])))
+                ),
+            ])
+        );
+        assert_eq!(
+            "tuple(address,(uint8,string)[])".parse::<Type>().unwrap(),
+            ty
+        );
+        assert_eq!("()".parse::<Type>().unwrap(), Type::Tuple(vec![]));
+
+        assert!("uint7".parse::<Type>().is_err());
+        assert!("tuple".parse::<Type>().is_err());
+        assert!("(uint256,string".parse::<Type>().is_err());
+        assert!("uint256 ".parse::<Type>().is_err());
+    }
+
+    #[test]
+    fn display_canonical_types() {
+        let ty = Type::Tuple(vec![
+            ("amount".into(), Type::Uint(256)),
+            (
+                "legs".into(),
+                Type::Array(Box::new(Type::Struct(
+                    "Leg".into(),
+                    vec![("to".into(), Type::Address), ("data".into(), Type::Bytes)],
+                ))),
+            ),
+        ]);
+
+        assert_eq!(ty.to_string(), "(uint256,(address,bytes)[])");
+        assert_eq!(
+            Type::FixedArray(Box::new(ty.clone()), 2).to_string(),
+            "(uint256,(address,bytes)[])[2]"
+        );
+        assert_eq!(Type::Ufixed(128, 18).to_string(), "ufixed128x18");
+
+        // Rendering and parsing agree, up to component names.
+        assert_eq!(
+            ty.to_string().parse::<Type>().unwrap().to_string(),
+            ty.to_string()
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+/// Runs blocking work on a background thread, as a future any executor can
+/// await.
+///
+/// Each call spawns its own OS thread. If `f` panics, the panic is resumed
+/// when the future is polled.
+pub(crate) fn unblock<T, F>(f: F) -> Unblock<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let shared = Arc::new(Mutex::new(Shared {
+        result: None,
+        waker: None,
+    }));
+
+    let thread_shared = shared.clone();
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+
+        let mut shared = thread_shared.lock().unwrap();
+        shared.result = Some(result);
+
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    });
+
+    Unblock { shared }
+}
+
+/// Future returned by [`unblock`].
+pub(crate) struct Unblock<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+struct Shared<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+impl<T> Future for Unblock<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.shared.lock().unwrap();
+
+        match shared.result.take() {
+            Some(Ok(result)) => Poll::Ready(result),
+            Some(Err(panic)) => {
+                drop(shared);
+                panic::resume_unwind(panic)
+            }
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    use std::task::Wake;
+
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unblock_runs_in_background() {
+        let future = unblock(|| {
+            thread::sleep(std::time::Duration::from_millis(10));
+            thread::current().id()
+        });
+
+        assert_ne!(block_on(future), thread::current().id());
+    }
+
+    #[test]
+    fn unblock_resumes_panics() {
+        let future = unblock(|| -> u8 { panic!("request failed") });
+
+        let panic = panic::catch_unwind(AssertUnwindSafe(|| block_on(future))).unwrap_err();
+
+        assert_eq!(panic.downcast_ref::<&str>(), Some(&"request failed"));
+    }
+}
@@ -0,0 +1,172 @@
+use anyhow::{anyhow, Result};
+use ethereum_types::U256;
+
+use crate::{Value, I256};
+
+/// Converts a decimal amount such as `1.23` into the integer it represents
+/// with `decimals` decimal places, e.g. token base units or wei.
+///
+/// Fails if the amount is negative, has more than `decimals` decimal
+/// places, or does not fit in a `U256`.
+///
+/// ```
+/// use ethereum_abi::parse_units;
+/// use ethereum_types::U256;
+///
+/// assert_eq!(parse_units("1.5", 6).unwrap(), U256::from(1_500_000));
+/// ```
+pub fn parse_units(amount: &str, decimals: usize) -> Result<U256> {
+    match parse_number(amount, decimals)? {
+        (true, v) if !v.is_zero() => Err(anyhow!("amount cannot be negative: {}", amount)),
+        (_, v) => Ok(v),
+    }
+}
+
+/// Formats an integer amount with `decimals` decimal places as a decimal
+/// string, e.g. `1500000` with 6 decimals as `1.5`.
+pub fn format_units(amount: U256, decimals: usize) -> String {
+    format_scaled(&amount, false, decimals)
+}
+
+impl Value {
+    /// Formats a `uint` or `int` value as a decimal amount with `decimals`
+    /// decimal places, e.g. an ERC-20 balance given its token's decimals.
+    pub fn format_units(&self, decimals: usize) -> Result<String> {
+        match self {
+            Value::Uint(v, _) => Ok(format_units(*v, decimals)),
+            Value::Int(v, size) => Ok(format_scaled(
+                &I256::sign_extend(*v, *size).into_raw(),
+                true,
+                decimals,
+            )),
+            _ => Err(anyhow!("expected integer, got {}", self.type_of())),
+        }
+    }
+}
+
+/// Parses an optionally signed decimal (`-1.5`) or `0x` hex number into its
+/// sign and magnitude scaled by `10^decimals`.
+///
+/// Fails if the number has more fractional digits than `decimals`.
+pub(crate) fn parse_number(s: &str, decimals: usize) -> Result<(bool, U256)> {
+    let s = s.trim();
+
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+
+    if let Some(hex) = digits.strip_prefix("0x") {
+        let v = U256::from_str_radix(hex, 16).map_err(|_| anyhow!("invalid hex number {:?}", s))?;
+
+        return scale(v, decimals)
+            .map(|v| (negative, v))
+            .ok_or_else(|| anyhow!("number out of range: {}", s));
+    }
+
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+    if integer.is_empty() && fraction.is_empty()
+        || !integer
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(anyhow!("invalid number {:?}", s));
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.len() > decimals {
+        return Err(anyhow!("{} has more than {} decimal places", s, decimals));
+    }
+
+    let digits = format!("{}{}", integer, fraction);
+    let digits = digits.trim_start_matches('0');
+
+    if digits.is_empty() {
+        return Ok((negative, U256::zero()));
+    }
+
+    U256::from_dec_str(digits)
+        .ok()
+        .and_then(|v| scale(v, decimals - fraction.len()))
+        .map(|v| (negative, v))
+        .ok_or_else(|| anyhow!("number out of range: {}", s))
+}
+
+// Multiplies `v` by `10^exponent`, or returns `None` if the result overflows.
+fn scale(v: U256, exponent: usize) -> Option<U256> {
+    if v.is_zero() {
+        return Some(v);
+    }
+
+    U256::from(10)
+        .checked_pow(U256::from(exponent))?
+        .checked_mul(v)
+}
+
+/// Formats an integer scaled by `10^decimals` as a decimal number, reading it
+/// as two's complement if `signed`. Trailing fractional zeros are dropped.
+pub(crate) fn format_scaled(v: &U256, signed: bool, decimals: usize) -> String {
+    let negative = signed && v.bit(255);
+    let magnitude = if signed {
+        I256::from_raw(*v).unsigned_abs()
+    } else {
+        *v
+    };
+
+    let digits = magnitude.to_string();
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+
+    format!(
+        "{}{}{}{}",
+        if negative { "-" } else { "" },
+        integer,
+        if fraction.is_empty() { "" } else { "." },
+        fraction
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn units_round_trip() {
+        assert_eq!(
+            parse_units("1.23", 18).unwrap(),
+            U256::from(123) * U256::exp10(16)
+        );
+        assert_eq!(parse_units("0.000001", 6).unwrap(), U256::one());
+        assert_eq!(parse_units("42", 0).unwrap(), U256::from(42));
+        assert!(parse_units("0.0000001", 6).is_err());
+        assert!(parse_units("-1", 6).is_err());
+        assert!(parse_units("1,5", 6).is_err());
+        assert!(parse_units("0x1", 78).is_err());
+        assert!(parse_units("1", 78).is_err());
+        assert!(parse_units("1", usize::MAX).is_err());
+        assert_eq!(parse_units("0x1", 77).unwrap(), U256::exp10(77));
+        assert_eq!(parse_units("0", 78).unwrap(), U256::zero());
+
+        assert_eq!(format_units(U256::from(1_500_000), 6), "1.5");
+        assert_eq!(format_units(U256::one(), 18), "0.000000000000000001");
+        assert_eq!(format_units(U256::exp10(18), 18), "1");
+
+        assert_eq!(
+            Value::Int(U256::MAX - 249_999, 256)
+                .format_units(6)
+                .unwrap(),
+            "-0.25"
+        );
+        assert_eq!(
+            Value::Uint(U256::from(1234), 256).format_units(2).unwrap(),
+            "12.34"
+        );
+        assert!(Value::Bool(true).format_units(2).is_err());
+    }
+}
@@ -0,0 +1,357 @@
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+use crate::{abi::Def, encode_hex, Abi, Param};
+
+/// Issue found by [`Abi::validate`].
+///
+/// Each issue names the offending entry by its index in the ABI's JSON, as
+/// it was parsed or as it serializes.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ValidationIssue {
+    /// Function defined more than once with the same signature.
+    DuplicateFunction { entry: usize, signature: String },
+    /// Function sharing the selector of an earlier one with a different
+    /// signature.
+    SelectorCollision {
+        entry: usize,
+        selector: [u8; 4],
+        existing: String,
+        signature: String,
+    },
+    /// Event defined more than once with the same signature.
+    DuplicateEvent { entry: usize, signature: String },
+    /// Error defined more than once with the same signature.
+    DuplicateError { entry: usize, signature: String },
+    /// Error sharing the selector of an earlier one with a different
+    /// signature.
+    ErrorSelectorCollision {
+        entry: usize,
+        selector: [u8; 4],
+        existing: String,
+        signature: String,
+    },
+    /// Event with more indexed inputs than there are topics for: 3, or 4 if
+    /// anonymous.
+    TooManyIndexed {
+        entry: usize,
+        signature: String,
+        indexed: usize,
+        max: usize,
+    },
+    /// Param marked indexed outside of an event.
+    IndexedOutsideEvent { entry: usize, param: String },
+}
+
+impl ValidationIssue {
+    /// Returns the index of the offending entry.
+    pub fn entry(&self) -> usize {
+        match self {
+            ValidationIssue::DuplicateFunction { entry, .. }
+            | ValidationIssue::SelectorCollision { entry, .. }
+            | ValidationIssue::DuplicateEvent { entry, .. }
+            | ValidationIssue::DuplicateError { entry, .. }
+            | ValidationIssue::ErrorSelectorCollision { entry, .. }
+            | ValidationIssue::TooManyIndexed { entry, .. }
+            | ValidationIssue::IndexedOutsideEvent { entry, .. } => *entry,
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "entry {}: ", self.entry())?;
+
+        match self {
+            ValidationIssue::DuplicateFunction { signature, .. } => {
+                write!(f, "function {} defined more than once", signature)
+            }
+            ValidationIssue::SelectorCollision {
+                selector,
+                existing,
+                signature,
+                ..
+            } => write!(
+                f,
+                "functions {} and {} share selector {}",
+                existing,
+                signature,
+                encode_hex(selector)
+            ),
+            ValidationIssue::DuplicateEvent { signature, .. } => {
+                write!(f, "event {} defined more than once", signature)
+            }
+            ValidationIssue::DuplicateError { signature, .. } => {
+                write!(f, "error {} defined more than once", signature)
+            }
+            ValidationIssue::ErrorSelectorCollision {
+                selector,
+                existing,
+                signature,
+                ..
+            } => write!(
+                f,
+                "errors {} and {} share selector {}",
+                existing,
+                signature,
+                encode_hex(selector)
+            ),
+            ValidationIssue::TooManyIndexed {
+                signature,
+                indexed,
+                max,
+                ..
+            } => write!(
+                f,
+                "event {} has {} indexed inputs, at most {} allowed",
+                signature, indexed, max
+            ),
+            ValidationIssue::IndexedOutsideEvent { param, .. } => {
+                write!(f, "param {:?} is indexed outside of an event", param)
+            }
+        }
+    }
+}
+
+/// Error returned by [`Abi::validate`], listing every issue found.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ValidationError {
+    /// Issues found, in the order of the entries.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid ABI: ")?;
+
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", issue)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl Abi {
+    /// Checks the ABI for definitions no compiler would emit together:
+    /// functions, events or errors defined twice, functions or errors whose
+    /// selectors collide, events with too many indexed inputs and indexed
+    /// params outside of events.
+    ///
+    /// Fails with a [`ValidationError`] listing every issue found.
+    ///
+    /// ```
+    /// use ethereum_abi::{Abi, ValidationError, ValidationIssue};
+    ///
+    /// let abi: Abi = serde_json::from_str(r#"[
+    ///     {"type": "function", "name": "f", "inputs": [], "outputs": [], "stateMutability": "view"},
+    ///     {"type": "event", "name": "E", "inputs": [], "anonymous": false},
+    ///     {"type": "function", "name": "f", "inputs": [], "outputs": [], "stateMutability": "pure"}
+    /// ]"#).unwrap();
+    ///
+    /// let err = abi.validate().unwrap_err();
+    /// let issues = &err.downcast_ref::<ValidationError>().unwrap().issues;
+    ///
+    /// assert_eq!(issues[0].entry(), 2);
+    /// assert_eq!(issues[0].to_string(), "entry 2: function f() defined more than once");
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        let issues = self.validation_issues();
+
+        if !issues.is_empty() {
+            return Err(ValidationError { issues }.into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the issues [`Abi::validate`] would report.
+    pub fn validation_issues(&self) -> Vec<ValidationIssue> {
+        let mut issues = vec![];
+
+        let mut functions = HashMap::new();
+        let mut events = HashSet::new();
+        let mut errors = HashMap::new();
+
+        for (entry, (def, _)) in self.entry_order().into_iter().enumerate() {
+            match def {
+                Def::Constructor => {
+                    if let Some(c) = &self.constructor {
+                        check_not_indexed(entry, &c.inputs, &mut issues);
+                    }
+                }
+
+                Def::Function(i) => {
+                    let f = &self.functions[i];
+                    let signature = f.signature();
+
+                    match functions.get(&f.method_id()) {
+                        Some(existing) if *existing == signature => {
+                            issues.push(ValidationIssue::DuplicateFunction { entry, signature })
+                        }
+                        Some(existing) => issues.push(ValidationIssue::SelectorCollision {
+                            entry,
+                            selector: f.method_id(),
+                            existing: String::clone(existing),
+                            signature,
+                        }),
+                        None => {
+                            functions.insert(f.method_id(), signature);
+                        }
+                    }
+
+                    check_not_indexed(entry, &f.inputs, &mut issues);
+                    check_not_indexed(entry, &f.outputs, &mut issues);
+                }
+
+                Def::Event(i) => {
+                    let e = &self.events[i];
+                    let signature = e.signature();
+
+                    let indexed = e.inputs.iter().filter(|p| p.indexed == Some(true)).count();
+                    let max = if e.anonymous { 4 } else { 3 };
+
+                    if indexed > max {
+                        issues.push(ValidationIssue::TooManyIndexed {
+                            entry,
+                            signature: signature.clone(),
+                            indexed,
+                            max,
+                        });
+                    }
+
+                    if !events.insert(signature.clone()) {
+                        issues.push(ValidationIssue::DuplicateEvent { entry, signature });
+                    }
+                }
+
+                Def::Error(i) => {
+                    let e = &self.errors[i];
+                    let signature = e.signature();
+
+                    match errors.get(&e.selector()) {
+                        Some(existing) if *existing == signature => {
+                            issues.push(ValidationIssue::DuplicateError { entry, signature })
+                        }
+                        Some(existing) => issues.push(ValidationIssue::ErrorSelectorCollision {
+                            entry,
+                            selector: e.selector(),
+                            existing: String::clone(existing),
+                            signature,
+                        }),
+                        None => {
+                            errors.insert(e.selector(), signature);
+                        }
+                    }
+
+                    check_not_indexed(entry, &e.inputs, &mut issues);
+                }
+
+                Def::Receive | Def::Fallback => {}
+            }
+        }
+
+        issues
+    }
+}
+
+fn check_not_indexed(entry: usize, params: &[Param], issues: &mut Vec<ValidationIssue>) {
+    for param in params {
+        if param.indexed == Some(true) {
+            issues.push(ValidationIssue::IndexedOutsideEvent {
+                entry,
+                param: param.name.to_string(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn valid_abi() {
+        let abi: Abi =
+            serde_json::from_str(include_str!("../testdata/vyper_0_2_pool.json")).unwrap();
+
+        assert_eq!(abi.validation_issues(), vec![]);
+        assert!(abi.validate().is_ok());
+    }
+
+    #[test]
+    fn report_issues() {
+        // `burn(uint256)` and `collate_propagate_storage(bytes16)` share
+        // selector 0x42966c68.
+        let abi: Abi = serde_json::from_str(
+            r#"[
+                {"type": "function", "name": "transfer", "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}], "outputs": [], "stateMutability": "nonpayable"},
+                {"type": "function", "name": "burn", "inputs": [{"name": "amount", "type": "uint256"}], "outputs": [], "stateMutability": "nonpayable"},
+                {"type": "function", "name": "collate_propagate_storage", "inputs": [{"name": "", "type": "bytes16"}], "outputs": [], "stateMutability": "nonpayable"},
+                {"type": "event", "name": "E", "inputs": [], "anonymous": false},
+                {"type": "error", "name": "Err", "inputs": [{"name": "code", "type": "uint8", "indexed": true}]},
+                {"type": "event", "name": "E", "inputs": [], "anonymous": true},
+                {"type": "event", "name": "Many", "inputs": [
+                    {"name": "a", "type": "uint8", "indexed": true},
+                    {"name": "b", "type": "uint8", "indexed": true},
+                    {"name": "c", "type": "uint8", "indexed": true},
+                    {"name": "d", "type": "uint8", "indexed": true}
+                ], "anonymous": false},
+                {"type": "error", "name": "Err", "inputs": [{"name": "code", "type": "uint8"}]},
+                {"type": "function", "name": "transfer", "inputs": [{"name": "to", "type": "address"}, {"name": "value", "type": "uint256"}], "outputs": [], "stateMutability": "nonpayable"}
+            ]"#,
+        )
+        .unwrap();
+
+        let issues = abi.validation_issues();
+
+        assert_eq!(
+            issues,
+            vec![
+                ValidationIssue::SelectorCollision {
+                    entry: 2,
+                    selector: [0x42, 0x96, 0x6c, 0x68],
+                    existing: "burn(uint256)".to_string(),
+                    signature: "collate_propagate_storage(bytes16)".to_string(),
+                },
+                ValidationIssue::IndexedOutsideEvent {
+                    entry: 4,
+                    param: "code".to_string(),
+                },
+                ValidationIssue::DuplicateEvent {
+                    entry: 5,
+                    signature: "E()".to_string(),
+                },
+                ValidationIssue::TooManyIndexed {
+                    entry: 6,
+                    signature: "Many(uint8,uint8,uint8,uint8)".to_string(),
+                    indexed: 4,
+                    max: 3,
+                },
+                ValidationIssue::DuplicateError {
+                    entry: 7,
+                    signature: "Err(uint8)".to_string(),
+                },
+                ValidationIssue::DuplicateFunction {
+                    entry: 8,
+                    signature: "transfer(address,uint256)".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "entry 2: functions burn(uint256) and collate_propagate_storage(bytes16) share selector 0x42966c68"
+        );
+
+        let err = abi.validate().unwrap_err();
+        let err = err.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(err.issues, issues);
+    }
+}
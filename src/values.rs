@@ -1,7 +1,10 @@
 use anyhow::{anyhow, Result};
-use ethereum_types::{H160, U256};
+use bytes::Bytes;
+use ethereum_types::{H160, H256, U256};
+use serde::Serialize;
+use std::{ops::Range, sync::Arc};
 
-use crate::types::Type;
+use crate::{address::to_checksum, encode_hex, types::Type, units::format_scaled, I256};
 
 /// ABI decoded value.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -10,37 +13,89 @@ pub enum Value {
     Uint(U256, usize),
     /// Signed int value (int<M>).
     Int(U256, usize),
+    /// Signed fixed point decimal value (fixed<M>x<N>).
+    ///
+    /// Stored as the scaled integer `v * 10^N`, in two's complement like
+    /// [`Value::Int`].
+    Fixed(U256, usize, usize),
+    /// Unsigned fixed point decimal value (ufixed<M>x<N>), stored as the
+    /// scaled integer `v * 10^N`.
+    Ufixed(U256, usize, usize),
     /// Address value (address).
     Address(H160),
     /// Bool value (bool).
     Bool(bool),
+    /// External function value (function): contract address and function
+    /// selector.
+    Function(H160, [u8; 4]),
     /// Fixed size bytes value (bytes<M>).
     FixedBytes(Vec<u8>),
-    /// Fixed size array value (T\[k\]).
-    FixedArray(Vec<Value>, Type),
+    /// Fixed size array value (T\[k\]), with its element type.
+    FixedArray(Vec<Value>, Arc<Type>),
     /// UTF-8 string value (string).
     String(String),
     /// Dynamic size bytes value (bytes).
-    Bytes(Vec<u8>),
-    /// Dynamic size array value (T[]).
-    Array(Vec<Value>, Type),
+    ///
+    /// Cloning only bumps a reference count, and values decoded with
+    /// [`Value::decode_from_bytes`] share the input buffer.
+    Bytes(Bytes),
+    /// Dynamic size array value (T[]), with its element type.
+    Array(Vec<Value>, Arc<Type>),
     /// Tuple value (tuple(T1, T2, ..., Tn)).
     ///
     /// This variant's vector items have the form (name, value).
     Tuple(Vec<(String, Value)>),
+    /// Keccak hash standing in for an indexed event param of a dynamic type
+    /// (`string`, `bytes`, arrays and tuples), whose value is not recoverable
+    /// from the log.
+    ///
+    /// Encoded and typed as `bytes32`.
+    Hash(H256),
 }
 
 impl Value {
     /// Decodes values from bytes using the given type hint.
     pub fn decode_from_slice(bs: &[u8], tys: &[Type]) -> Result<Vec<Value>> {
-        tys.iter()
-            .try_fold((vec![], 0), |(mut values, at), ty| {
-                let (value, consumed) = Self::decode(bs, ty, 0, at)?;
-                values.push(value);
+        Self::decode_all(bs, tys.iter())
+    }
 
-                Ok((values, at + consumed))
-            })
-            .map(|(values, _)| values)
+    /// Decodes values from a shared buffer using the given type hint.
+    ///
+    /// Unlike [`Value::decode_from_slice`], `bytes` values are not copied but
+    /// point into `bs`, so large payloads are cheap to decode and to hand to
+    /// several consumers.
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use ethereum_abi::{Type, Value};
+    ///
+    /// let payload = Bytes::from(vec![0xab; 4096]);
+    /// let input = Bytes::from(Value::encode(&[Value::Bytes(payload.clone())]));
+    ///
+    /// let values = Value::decode_from_bytes(&input, &[Type::Bytes]).unwrap();
+    /// assert_eq!(values, vec![Value::Bytes(payload)]);
+    /// ```
+    pub fn decode_from_bytes(bs: &Bytes, tys: &[Type]) -> Result<Vec<Value>> {
+        Self::decode_all(bs, tys.iter())
+    }
+
+    /// Decodes values from bytes given an iterator over their types, such as
+    /// the types of a parameter list, sparing a vector of cloned types.
+    pub(crate) fn decode_all<'a, I: Input + ?Sized>(
+        bs: &I,
+        tys: impl IntoIterator<Item = &'a Type>,
+    ) -> Result<Vec<Value>> {
+        let tys = tys.into_iter();
+        let mut values = Vec::with_capacity(tys.size_hint().0);
+        let mut at = 0;
+
+        for ty in tys {
+            let (value, consumed) = Self::decode(bs, ty, 0, at)?;
+            values.push(value);
+            at += consumed;
+        }
+
+        Ok(values)
     }
 
     /// Encodes values into bytes.
@@ -50,7 +105,10 @@ impl Value {
 
         for value in values {
             match value {
-                Value::Uint(i, _) | Value::Int(i, _) => {
+                Value::Uint(i, _)
+                | Value::Int(i, _)
+                | Value::Fixed(i, _, _)
+                | Value::Ufixed(i, _, _) => {
                     let start = buf.len();
                     buf.resize(buf.len() + 32, 0);
 
@@ -74,6 +132,15 @@ impl Value {
                     }
                 }
 
+                Value::Function(addr, selector) => {
+                    let start = buf.len();
+                    buf.resize(buf.len() + 32, 0);
+
+                    // left-aligned, as if it were a bytes24.
+                    buf[start..(start + 20)].copy_from_slice(addr.as_fixed_bytes());
+                    buf[(start + 20)..(start + 24)].copy_from_slice(selector);
+                }
+
                 Value::FixedBytes(bytes) => {
                     let start = buf.len();
                     buf.resize(buf.len() + 32, 0);
@@ -81,6 +148,8 @@ impl Value {
                     buf[start..(start + bytes.len())].copy_from_slice(bytes);
                 }
 
+                Value::Hash(hash) => buf.extend_from_slice(hash.as_bytes()),
+
                 Value::FixedArray(values, _) => {
                     if value.type_of().is_dynamic() {
                         alloc_queue.push_back((buf.len(), value));
@@ -165,13 +234,19 @@ impl Value {
         match self {
             Value::Uint(_, size) => Type::Uint(*size),
             Value::Int(_, size) => Type::Int(*size),
+            Value::Fixed(_, size, decimals) => Type::Fixed(*size, *decimals),
+            Value::Ufixed(_, size, decimals) => Type::Ufixed(*size, *decimals),
             Value::Address(_) => Type::Address,
             Value::Bool(_) => Type::Bool,
+            Value::Function(_, _) => Type::Function,
             Value::FixedBytes(bytes) => Type::FixedBytes(bytes.len()),
-            Value::FixedArray(values, ty) => Type::FixedArray(Box::new(ty.clone()), values.len()),
+            Value::FixedArray(values, ty) => {
+                Type::FixedArray(Box::new((**ty).clone()), values.len())
+            }
             Value::String(_) => Type::String,
             Value::Bytes(_) => Type::Bytes,
-            Value::Array(_, ty) => Type::Array(Box::new(ty.clone())),
+            Value::Array(_, ty) => Type::Array(Box::new((**ty).clone())),
+            Value::Hash(_) => Type::FixedBytes(32),
             Value::Tuple(values) => Type::Tuple(
                 values
                     .iter()
@@ -181,7 +256,96 @@ impl Value {
         }
     }
 
-    fn decode(bs: &[u8], ty: &Type, base_addr: usize, at: usize) -> Result<(Value, usize)> {
+    /// Returns whether the value can be encoded as the given type.
+    ///
+    /// Integers match any integer type of the same signedness wide enough to
+    /// hold them. Array elements, fixed array lengths, tuple arity and tuple
+    /// fields are checked recursively; the element type recorded in an array
+    /// value is not, so empty arrays match any array type.
+    ///
+    /// ```
+    /// use ethereum_abi::{Type, Value};
+    /// use ethereum_types::U256;
+    ///
+    /// let value = Value::Array(vec![Value::Uint(U256::from(300), 256)], Type::Uint(256).into());
+    ///
+    /// assert!(value.matches(&Type::Array(Box::new(Type::Uint(16)))));
+    /// assert!(!value.matches(&Type::Array(Box::new(Type::Uint(8)))));
+    /// assert!(!value.matches(&Type::FixedArray(Box::new(Type::Uint(16)), 2)));
+    /// ```
+    pub fn matches(&self, ty: &Type) -> bool {
+        match (self, ty) {
+            (Value::Uint(v, _), Type::Uint(size)) => v.bits() <= *size,
+            (Value::Int(v, v_size), Type::Int(size)) => I256::sign_extend(*v, *v_size).fits(*size),
+            (Value::Fixed(_, size, decimals), Type::Fixed(ty_size, ty_decimals))
+            | (Value::Ufixed(_, size, decimals), Type::Ufixed(ty_size, ty_decimals)) => {
+                size == ty_size && decimals == ty_decimals
+            }
+            (Value::Address(_), Type::Address)
+            | (Value::Bool(_), Type::Bool)
+            | (Value::Function(_, _), Type::Function)
+            | (Value::String(_), Type::String)
+            | (Value::Bytes(_), Type::Bytes) => true,
+            (Value::FixedBytes(bytes), Type::FixedBytes(size)) => bytes.len() == *size,
+            (Value::Hash(_), Type::FixedBytes(32)) => true,
+            (Value::FixedArray(values, _), Type::FixedArray(ty, size)) => {
+                values.len() == *size && values.iter().all(|v| v.matches(ty))
+            }
+            (Value::Array(values, _), Type::Array(ty)) => values.iter().all(|v| v.matches(ty)),
+            (Value::Tuple(values), Type::Tuple(tys))
+            | (Value::Tuple(values), Type::Struct(_, tys)) => {
+                values.len() == tys.len()
+                    && values
+                        .iter()
+                        .zip(tys)
+                        .all(|((_, v), (_, ty))| v.matches(ty))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether each value matches the type at the same position, and
+    /// there are as many values as types.
+    pub fn matches_all(values: &[Value], tys: &[Type]) -> bool {
+        values.len() == tys.len() && values.iter().zip(tys).all(|(v, ty)| v.matches(ty))
+    }
+
+    /// Relabels a value that [`Value::matches`] the given type with the
+    /// type's sizes, element types and tuple field names.
+    pub(crate) fn normalize(self, ty: &Type) -> Value {
+        match (self, ty) {
+            (Value::Uint(v, _), Type::Uint(size)) => Value::Uint(v, *size),
+            (Value::Int(v, v_size), Type::Int(size)) => {
+                Value::Int(I256::sign_extend(v, v_size).into_raw(), *size)
+            }
+            (Value::FixedArray(values, _), Type::FixedArray(ty, _)) => Value::FixedArray(
+                values.into_iter().map(|v| v.normalize(ty)).collect(),
+                Arc::new((**ty).clone()),
+            ),
+            (Value::Array(values, _), Type::Array(ty)) => Value::Array(
+                values.into_iter().map(|v| v.normalize(ty)).collect(),
+                Arc::new((**ty).clone()),
+            ),
+            (Value::Tuple(values), Type::Tuple(tys))
+            | (Value::Tuple(values), Type::Struct(_, tys)) => Value::Tuple(
+                values
+                    .into_iter()
+                    .zip(tys)
+                    .map(|((_, v), (name, ty))| (name.clone(), v.normalize(ty)))
+                    .collect(),
+            ),
+            (value, _) => value,
+        }
+    }
+
+    pub(crate) fn decode<I: Input + ?Sized>(
+        input: &I,
+        ty: &Type,
+        base_addr: usize,
+        at: usize,
+    ) -> Result<(Value, usize)> {
+        let bs = input.as_ref();
+
         match ty {
             Type::Uint(size) => {
                 let at = base_addr + at;
@@ -205,6 +369,22 @@ impl Value {
                 Ok((Value::Int(uint, *size), 32))
             }
 
+            Type::Fixed(size, decimals) | Type::Ufixed(size, decimals) => {
+                let at = base_addr + at;
+                let slice = bs
+                    .get(at..(at + 32))
+                    .ok_or_else(|| anyhow!("reached end of input while decoding {}", ty))?;
+
+                let uint = U256::from_big_endian(slice);
+
+                let value = match ty {
+                    Type::Fixed(_, _) => Value::Fixed(uint, *size, *decimals),
+                    _ => Value::Ufixed(uint, *size, *decimals),
+                };
+
+                Ok((value, 32))
+            }
+
             Type::Address => {
                 let at = base_addr + at;
                 let slice = bs
@@ -228,6 +408,21 @@ impl Value {
                 Ok((Value::Bool(b), 32))
             }
 
+            Type::Function => {
+                let at = base_addr + at;
+                let slice = bs
+                    .get(at..(at + 24))
+                    .ok_or_else(|| anyhow!("reached end of input while decoding function"))?;
+
+                let mut selector = [0u8; 4];
+                selector.copy_from_slice(&slice[20..24]);
+
+                Ok((
+                    Value::Function(H160::from_slice(&slice[0..20]), selector),
+                    32,
+                ))
+            }
+
             Type::FixedBytes(size) => {
                 let at = base_addr + at;
                 let bv = bs
@@ -242,9 +437,11 @@ impl Value {
                 let (base_addr, at) = if ty.is_dynamic() {
                     // For fixed arrays of types that are dynamic, we just jump
                     // to the offset location and decode from there.
-                    let slice = bs.get(at..(at + 32)).ok_or_else(|| {
-                        anyhow!("reached end of input while decoding {}[{}]", ty, size)
-                    })?;
+                    let slice =
+                        bs.get((base_addr + at)..(base_addr + at + 32))
+                            .ok_or_else(|| {
+                                anyhow!("reached end of input while decoding {}[{}]", ty, size)
+                            })?;
                     let offset = U256::from_big_endian(slice).as_usize();
 
                     (base_addr + offset, 0)
@@ -255,20 +452,29 @@ impl Value {
                     (base_addr, at)
                 };
 
-                (0..(*size))
-                    .try_fold((vec![], 0), |(mut values, total_consumed), _| {
-                        let (value, consumed) =
-                            Self::decode(bs, ty, base_addr, at + total_consumed)?;
-
-                        values.push(value);
+                // Like dynamic arrays, the size alone can't be trusted to
+                // preallocate: the input must have room for every head.
+                let capacity = (*size).min(
+                    bs.len().saturating_sub(base_addr + at)
+                        / ty.head_size().unwrap_or(usize::MAX).max(1),
+                );
+                let mut values = Vec::with_capacity(capacity);
+                let mut total_consumed = 0;
+
+                for _ in 0..*size {
+                    let (value, consumed) =
+                        Self::decode(input, ty, base_addr, at + total_consumed)?;
+
+                    values.push(value);
+                    total_consumed += consumed;
+                }
 
-                        Ok((values, total_consumed + consumed))
-                    })
-                    .map(|(values, consumed)| {
-                        let consumed = if ty.is_dynamic() { 32 } else { consumed };
+                let consumed = if ty.is_dynamic() { 32 } else { total_consumed };
 
-                        (Value::FixedArray(values, *ty.clone()), consumed)
-                    })
+                Ok((
+                    Value::FixedArray(values, Arc::new((**ty).clone())),
+                    consumed,
+                ))
             }
 
             Type::String => {
@@ -281,7 +487,7 @@ impl Value {
                     unreachable!();
                 };
 
-                let s = String::from_utf8(bytes)?;
+                let s = String::from_utf8(bytes.into())?;
 
                 Ok((Value::String(s), consumed))
             }
@@ -301,10 +507,10 @@ impl Value {
                 let bytes_len = U256::from_big_endian(slice).as_usize();
 
                 let at = at + 32;
-                let bytes = bs
-                    .get(at..(at + bytes_len))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding bytes"))?
-                    .to_vec();
+                if bs.get(at..(at + bytes_len)).is_none() {
+                    return Err(anyhow!("reached end of input while decoding bytes"));
+                }
+                let bytes = input.payload(at..(at + bytes_len));
 
                 // consumes only the first 32 bytes, i.e. the offset pointer
                 Ok((Value::Bytes(bytes), 32))
@@ -325,23 +531,31 @@ impl Value {
 
                 let at = at + 32;
 
-                (0..array_len)
-                    .try_fold((vec![], 0), |(mut values, total_consumed), _| {
-                        let (value, consumed) = Self::decode(bs, ty, at, total_consumed)?;
+                // Every element takes at least its head, so the length can't
+                // be trusted beyond what the input has room for.
+                let capacity = array_len
+                    .min(bs.len().saturating_sub(at) / ty.head_size().unwrap_or(usize::MAX).max(1));
+                let mut values = Vec::with_capacity(capacity);
+                let mut total_consumed = 0;
+
+                for _ in 0..array_len {
+                    let (value, consumed) = Self::decode(input, ty, at, total_consumed)?;
 
-                        values.push(value);
+                    values.push(value);
+                    total_consumed += consumed;
+                }
 
-                        Ok((values, total_consumed + consumed))
-                    })
-                    .map(|(values, _)| (Value::Array(values, *ty.clone()), 32))
+                Ok((Value::Array(values, Arc::new((**ty).clone())), 32))
             }
 
-            Type::Tuple(tys) => {
+            Type::Tuple(tys) | Type::Struct(_, tys) => {
                 // Tuples follow the same logic as fixed arrays.
                 let (base_addr, at) = if ty.is_dynamic() {
-                    let slice = bs.get(at..(at + 32)).ok_or_else(|| {
-                        anyhow!("reached end of input while decoding tuple offset")
-                    })?;
+                    let slice =
+                        bs.get((base_addr + at)..(base_addr + at + 32))
+                            .ok_or_else(|| {
+                                anyhow!("reached end of input while decoding tuple offset")
+                            })?;
                     let offset = U256::from_big_endian(slice).as_usize();
 
                     (base_addr + offset, 0)
@@ -349,21 +563,20 @@ impl Value {
                     (base_addr, at)
                 };
 
-                tys.iter()
-                    .cloned()
-                    .try_fold((vec![], 0), |(mut values, total_consumed), (name, ty)| {
-                        let (value, consumed) =
-                            Self::decode(bs, &ty, base_addr, at + total_consumed)?;
+                let mut values = Vec::with_capacity(tys.len());
+                let mut total_consumed = 0;
+
+                for (name, ty) in tys {
+                    let (value, consumed) =
+                        Self::decode(input, ty, base_addr, at + total_consumed)?;
 
-                        values.push((name, value));
+                    values.push((name.clone(), value));
+                    total_consumed += consumed;
+                }
 
-                        Ok((values, total_consumed + consumed))
-                    })
-                    .map(|(values, consumed)| {
-                        let consumed = if ty.is_dynamic() { 32 } else { consumed };
+                let consumed = if ty.is_dynamic() { 32 } else { total_consumed };
 
-                        (Value::Tuple(values), consumed)
-                    })
+                Ok((Value::Tuple(values), consumed))
             }
         }
     }
@@ -397,6 +610,220 @@ impl Value {
     }
 }
 
+/// Input that values are decoded from, which `bytes` payloads are copied or
+/// sliced out of.
+pub(crate) trait Input: AsRef<[u8]> {
+    fn payload(&self, range: Range<usize>) -> Bytes;
+}
+
+impl Input for [u8] {
+    fn payload(&self, range: Range<usize>) -> Bytes {
+        Bytes::copy_from_slice(&self[range])
+    }
+}
+
+impl Input for Bytes {
+    fn payload(&self, range: Range<usize>) -> Bytes {
+        self.slice(range)
+    }
+}
+
+impl Value {
+    /// Returns the value nested at `path`, a sequence of `.field` tuple
+    /// accesses and `[index]` array or tuple accesses, e.g. `[2].maker` or
+    /// `.legs[0][1]`. The leading dot may be omitted.
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        let path = path.strip_prefix('.').unwrap_or(path);
+
+        if path.is_empty() {
+            return Some(self);
+        }
+
+        parse_path(path)?
+            .into_iter()
+            .try_fold(self, |value, segment| match (segment, value) {
+                (PathSegment::Field(name), Value::Tuple(values)) => values
+                    .iter()
+                    .find(|(field, _)| field == name)
+                    .map(|(_, value)| value),
+                (PathSegment::Index(i), Value::Tuple(values)) => {
+                    values.get(i).map(|(_, value)| value)
+                }
+                (PathSegment::Index(i), Value::Array(values, _))
+                | (PathSegment::Index(i), Value::FixedArray(values, _)) => values.get(i),
+                _ => None,
+            })
+    }
+}
+
+enum PathSegment<'a> {
+    Field(&'a str),
+    Index(usize),
+}
+
+/// Splits `a.b[1][2].c` into field and index segments. Returns `None` on
+/// malformed paths.
+fn parse_path(path: &str) -> Option<Vec<PathSegment<'_>>> {
+    let mut segments = vec![];
+    let mut rest = path;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']')?;
+            segments.push(PathSegment::Index(after[..end].trim().parse().ok()?));
+            rest = &after[end + 1..];
+        } else {
+            let field = if segments.is_empty() {
+                rest
+            } else {
+                rest.strip_prefix('.')?
+            };
+            let end = field.find(['.', '[']).unwrap_or(field.len());
+
+            if end == 0 {
+                return None;
+            }
+
+            segments.push(PathSegment::Field(&field[..end]));
+            rest = &field[end..];
+        }
+    }
+
+    Some(segments)
+}
+
+impl std::fmt::Display for Value {
+    /// Formats the value for humans: integers in decimal, addresses in their
+    /// checksummed form, bytes as `0x` hex, strings quoted, arrays as `[a, b]`
+    /// and tuples as `(a, b)`.
+    ///
+    /// The alternate form (`{:#}`) spreads arrays and tuples over several
+    /// indented lines and labels named tuple fields.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+/// Something written like [`Value`]'s `Display`, at some indentation level
+/// of the alternate form.
+pub(crate) trait WriteNested {
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result;
+}
+
+impl<T: WriteNested + ?Sized> WriteNested for &T {
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        (**self).write(f, indent)
+    }
+}
+
+impl WriteNested for Value {
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        match self {
+            Value::Uint(v, _) => write!(f, "{}", v),
+            Value::Int(v, size) => write!(f, "{}", I256::sign_extend(*v, *size)),
+            Value::Fixed(v, _, decimals) => write!(f, "{}", format_scaled(v, true, *decimals)),
+            Value::Ufixed(v, _, decimals) => write!(f, "{}", format_scaled(v, false, *decimals)),
+            Value::Address(address) => write!(f, "{}", to_checksum(address)),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Function(address, selector) => write!(
+                f,
+                "{}{}",
+                encode_hex(address),
+                faster_hex::hex_string(selector)
+            ),
+            Value::FixedBytes(bytes) => write!(f, "{}", encode_hex(bytes)),
+            Value::Bytes(bytes) => write!(f, "{}", encode_hex(bytes)),
+            Value::Hash(hash) => write!(f, "{:?}", hash),
+            Value::String(s) => write!(f, "{:?}", s),
+            Value::FixedArray(values, _) | Value::Array(values, _) => {
+                write_list(f, indent, ('[', ']'), values.iter().map(|v| ("", v)))
+            }
+            Value::Tuple(values) => write_list(
+                f,
+                indent,
+                ('(', ')'),
+                values.iter().map(|(name, v)| (name.as_str(), v)),
+            ),
+        }
+    }
+}
+
+impl Serialize for Value {
+    /// Serializes integers and fixed point numbers as decimal strings,
+    /// addresses in checksummed form, bytes as `0x` hex, arrays as sequences
+    /// and tuples as maps keyed by field name, or by position for unnamed
+    /// fields.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Value::Uint(_, _)
+            | Value::Int(_, _)
+            | Value::Fixed(_, _, _)
+            | Value::Ufixed(_, _, _)
+            | Value::Address(_)
+            | Value::Function(_, _)
+            | Value::FixedBytes(_)
+            | Value::Bytes(_)
+            | Value::Hash(_) => serializer.collect_str(self),
+            Value::Bool(b) => serializer.serialize_bool(*b),
+            Value::String(s) => serializer.serialize_str(s),
+            Value::FixedArray(values, _) | Value::Array(values, _) => {
+                serializer.collect_seq(values)
+            }
+            Value::Tuple(values) => serializer.collect_map(
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (name, value))| (field_key(name, i), value)),
+            ),
+        }
+    }
+}
+
+/// Returns the key a named value is serialized under: its name, or its
+/// position if unnamed.
+pub(crate) fn field_key(name: &str, position: usize) -> String {
+    if name.is_empty() {
+        position.to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+pub(crate) fn write_list<'a, T: WriteNested>(
+    f: &mut std::fmt::Formatter<'_>,
+    indent: usize,
+    (open, close): (char, char),
+    items: impl ExactSizeIterator<Item = (&'a str, T)>,
+) -> std::fmt::Result {
+    const INDENT: &str = "    ";
+
+    write!(f, "{}", open)?;
+
+    if f.alternate() && items.len() > 0 {
+        for (name, value) in items {
+            write!(f, "\n{}", INDENT.repeat(indent + 1))?;
+            if !name.is_empty() {
+                write!(f, "{}: ", name)?;
+            }
+            value.write(f, indent + 1)?;
+            write!(f, ",")?;
+        }
+        write!(f, "\n{}", INDENT.repeat(indent))?;
+    } else {
+        for (i, (_, value)) in items.enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            value.write(f, indent)?;
+        }
+    }
+
+    write!(f, "{}", close)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -429,6 +856,83 @@ mod test {
         assert_eq!(v, vec![Value::Int(uint, 256)]);
     }
 
+    #[test]
+    fn decode_fixed() {
+        // -1.5 as fixed128x18
+        let scaled = U256::MAX - U256::exp10(18) * 3 / 2 + 1;
+
+        let bs = Value::encode(&[
+            Value::Fixed(scaled, 128, 18),
+            Value::Ufixed(U256::from(15), 8, 1),
+        ]);
+
+        let v = Value::decode_from_slice(&bs, &[Type::Fixed(128, 18), Type::Ufixed(8, 1)])
+            .expect("decode_from_slice failed");
+
+        assert_eq!(
+            v,
+            vec![
+                Value::Fixed(scaled, 128, 18),
+                Value::Ufixed(U256::from(15), 8, 1)
+            ]
+        );
+        assert_eq!(bs[0], 0xff);
+        assert_eq!(bs[63], 15);
+    }
+
+    #[test]
+    fn decode_arrays_of_dynamic_tuples() {
+        let tuple = |n: u8| {
+            Value::Tuple(vec![
+                ("".into(), Value::Uint(U256::from(n), 8)),
+                ("".into(), Value::Bytes(vec![n; n as usize].into())),
+            ])
+        };
+        let strings = |s: &str| {
+            Value::FixedArray(
+                vec![Value::String(s.to_string()), Value::String(s.repeat(40))],
+                Type::String.into(),
+            )
+        };
+
+        let tuple_ty = Type::Tuple(vec![("".into(), Type::Uint(8)), ("".into(), Type::Bytes)]);
+        let strings_ty = Type::FixedArray(Box::new(Type::String), 2);
+
+        let values = vec![
+            Value::Array(vec![tuple(1), tuple(40), tuple(3)], tuple_ty.clone().into()),
+            Value::Array(vec![strings("a"), strings("b")], strings_ty.clone().into()),
+        ];
+
+        let bs = Value::encode(&values);
+
+        assert_eq!(
+            Value::decode_from_slice(
+                &bs,
+                &[
+                    Type::Array(Box::new(tuple_ty)),
+                    Type::Array(Box::new(strings_ty))
+                ]
+            )
+            .unwrap(),
+            values
+        );
+    }
+
+    #[test]
+    fn decode_function() {
+        let addr = H160::random();
+
+        let bs = Value::encode(&[Value::Function(addr, [0xa9, 0x05, 0x9c, 0xbb])]);
+
+        assert_eq!(&bs[0..20], addr.as_bytes());
+        assert_eq!(&bs[20..24], &[0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(&bs[24..32], &[0u8; 8]);
+
+        let v = Value::decode_from_slice(&bs, &[Type::Function]).expect("decode_from_slice failed");
+
+        assert_eq!(v, vec![Value::Function(addr, [0xa9, 0x05, 0x9c, 0xbb])]);
+    }
+
     #[test]
     fn decode_address() {
         let addr = H160::random();
@@ -490,14 +994,14 @@ mod test {
                 vec![
                     Value::FixedArray(
                         vec![Value::Uint(uint1, 256), Value::Uint(uint2, 256)],
-                        Type::Uint(256)
+                        Type::Uint(256).into()
                     ),
                     Value::FixedArray(
                         vec![Value::Uint(uint3, 256), Value::Uint(uint4, 256)],
-                        Type::Uint(256)
+                        Type::Uint(256).into()
                     )
                 ],
-                uint_arr2
+                uint_arr2.into()
             )]
         );
     }
@@ -541,7 +1045,44 @@ mod test {
 
         let v = Value::decode_from_slice(&bs, &[Type::Bytes]).expect("decode_from_slice failed");
 
-        assert_eq!(v, vec![Value::Bytes(bs[64..(64 + bytes_len)].to_vec())]);
+        assert_eq!(
+            v,
+            vec![Value::Bytes(bs[64..(64 + bytes_len)].to_vec().into())]
+        );
+    }
+
+    #[test]
+    fn decode_shared_bytes() {
+        let tys = [
+            Type::Array(Box::new(Type::Bytes)),
+            Type::Tuple(vec![("".into(), Type::String), ("".into(), Type::Bytes)]),
+        ];
+        let values = vec![
+            Value::Array(
+                vec![
+                    Value::Bytes(vec![1; 100].into()),
+                    Value::Bytes(vec![2; 3].into()),
+                ],
+                Type::Bytes.into(),
+            ),
+            Value::Tuple(vec![
+                ("".into(), Value::String("gm".to_string())),
+                ("".into(), Value::Bytes(vec![3; 40].into())),
+            ]),
+        ];
+        let input = Bytes::from(Value::encode(&values));
+
+        let decoded = Value::decode_from_bytes(&input, &tys).unwrap();
+        assert_eq!(decoded, values);
+
+        // Payloads point into the input rather than being copied.
+        let payload = decoded[1]
+            .get_path("[1]")
+            .and_then(Value::as_bytes)
+            .unwrap();
+        assert!(input.as_ptr_range().contains(&payload.as_ptr()));
+
+        assert!(Value::decode_from_bytes(&input.slice(..100), &tys).is_err());
     }
 
     #[test]
@@ -572,18 +1113,40 @@ mod test {
                 vec![
                     Value::FixedArray(
                         vec![Value::Uint(uint1, 256), Value::Uint(uint2, 256)],
-                        Type::Uint(256)
+                        Type::Uint(256).into()
                     ),
                     Value::FixedArray(
                         vec![Value::Uint(uint3, 256), Value::Uint(uint4, 256)],
-                        Type::Uint(256)
+                        Type::Uint(256).into()
                     )
                 ],
-                uint_arr2
+                uint_arr2.into()
             )]
         );
     }
 
+    #[test]
+    fn decode_array_with_bogus_length() {
+        let mut bs = [0u8; 96];
+        bs[31] = 0x20;
+        U256::from(u64::MAX).to_big_endian(&mut bs[32..64]);
+
+        // The length is not trusted for preallocating, so this fails on the
+        // second element rather than aborting.
+        assert!(Value::decode_from_slice(&bs, &[Type::Array(Box::new(Type::Uint(256)))]).is_err());
+    }
+
+    #[test]
+    fn decode_fixed_array_with_bogus_size() {
+        let ty = Type::FixedArray(Box::new(Type::Uint(256)), 1 << 59);
+
+        let err = Value::decode_from_slice(&[0u8; 64], &[ty]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "reached end of input while decoding uint256"
+        );
+    }
+
     #[test]
     fn decode_fixed_tuple() {
         let mut bs = [0u8; 96];
@@ -600,9 +1163,9 @@ mod test {
         let v = Value::decode_from_slice(
             &bs,
             &[Type::Tuple(vec![
-                ("a".to_string(), Type::Uint(256)),
-                ("b".to_string(), Type::Uint(256)),
-                ("c".to_string(), Type::Address),
+                ("a".into(), Type::Uint(256)),
+                ("b".into(), Type::Uint(256)),
+                ("c".into(), Type::Address),
             ])],
         )
         .expect("decode_from_slice failed");
@@ -610,9 +1173,9 @@ mod test {
         assert_eq!(
             v,
             vec![Value::Tuple(vec![
-                ("a".to_string(), Value::Uint(uint1, 256)),
-                ("b".to_string(), Value::Uint(uint2, 256)),
-                ("c".to_string(), Value::Address(addr))
+                ("a".into(), Value::Uint(uint1, 256)),
+                ("b".into(), Value::Uint(uint2, 256)),
+                ("c".into(), Value::Address(addr))
             ])]
         );
     }
@@ -636,9 +1199,9 @@ mod test {
         let v = Value::decode_from_slice(
             &bs,
             &[Type::Tuple(vec![
-                ("a".to_string(), Type::Uint(256)),
-                ("b".to_string(), Type::String),
-                ("c".to_string(), Type::Address),
+                ("a".into(), Type::Uint(256)),
+                ("b".into(), Type::String),
+                ("c".into(), Type::Address),
             ])],
         )
         .expect("decode_from_slice failed");
@@ -646,9 +1209,9 @@ mod test {
         assert_eq!(
             v,
             vec![Value::Tuple(vec![
-                ("a".to_string(), Value::Uint(uint1, 256)),
-                ("b".to_string(), Value::String(s)),
-                ("c".to_string(), Value::Address(addr))
+                ("a".into(), Value::Uint(uint1, 256)),
+                ("b".into(), Value::String(s)),
+                ("c".into(), Value::Address(addr))
             ])]
         );
     }
@@ -681,11 +1244,11 @@ mod test {
                                 Value::Uint(U256::from(1), 32),
                                 Value::Uint(U256::from(2), 32),
                             ],
-                            Type::Uint(32)
+                            Type::Uint(32).into()
                         ),
-                        Value::Array(vec![Value::Uint(U256::from(3), 32)], Type::Uint(32)),
+                        Value::Array(vec![Value::Uint(U256::from(3), 32)], Type::Uint(32).into()),
                     ],
-                    Type::Array(Box::new(Type::Uint(32)))
+                    Type::Array(Box::new(Type::Uint(32))).into()
                 ),
             ],
         );
@@ -704,9 +1267,9 @@ mod test {
         assert_eq!(values, vec![
                 Value::Uint(U256::from_dec_str("1646668705").unwrap(), 256),
                 Value::Array(vec![
-                    Value::Bytes(hex::decode("04e45aaf000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000000000001f4000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000084b6a5c40000000000000000000000000000000000000000000000000bd373e0061c7e7f940000000000000000000000000000000000000000000000000000000000000000").unwrap()),
-                    Value::Bytes(hex::decode("49404b7c00000000000000000000000000000000000000000000000bd373e0061c7e7f9400000000000000000000000016ee789b50d3d49b8f71b5314c367e3fef24d746").unwrap()),
-                ], Type::Bytes),
+                    Value::Bytes(hex::decode("04e45aaf000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000000000001f4000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000084b6a5c40000000000000000000000000000000000000000000000000bd373e0061c7e7f940000000000000000000000000000000000000000000000000000000000000000").unwrap().into()),
+                    Value::Bytes(hex::decode("49404b7c00000000000000000000000000000000000000000000000bd373e0061c7e7f9400000000000000000000000016ee789b50d3d49b8f71b5314c367e3fef24d746").unwrap().into()),
+                ], Type::Bytes.into()),
             ]);
     }
 
@@ -776,7 +1339,7 @@ mod test {
 
         let value = Value::FixedArray(
             vec![Value::Uint(uint1, 56), Value::Uint(uint2, 56)],
-            Type::Uint(56),
+            Type::Uint(56).into(),
         );
 
         let mut expected_bytes = [0u8; 64];
@@ -812,7 +1375,7 @@ mod test {
 
         let value = Value::Array(
             vec![Value::Address(addr1), Value::Address(addr2)],
-            Type::Address,
+            Type::Address.into(),
         );
 
         let mut expected_bytes = [0u8; 128];
@@ -830,8 +1393,8 @@ mod test {
         let uint = U256::from(53);
 
         let value = Value::Tuple(vec![
-            ("a".to_string(), Value::Address(addr)),
-            ("b".to_string(), Value::Uint(uint, 256)),
+            ("a".into(), Value::Address(addr)),
+            ("b".into(), Value::Uint(uint, 256)),
         ]);
 
         let mut expected_bytes = [0u8; 64];
@@ -847,8 +1410,8 @@ mod test {
         let uint = U256::from(53);
 
         let value = Value::Tuple(vec![
-            ("a".to_string(), Value::String(s.clone())),
-            ("b".to_string(), Value::Uint(uint, 256)),
+            ("a".into(), Value::String(s.clone())),
+            ("b".into(), Value::Uint(uint, 256)),
         ]);
 
         let mut expected_bytes = [0u8; 160];
@@ -873,11 +1436,11 @@ mod test {
                             Value::Uint(U256::from(1), 32),
                             Value::Uint(U256::from(2), 32),
                         ],
-                        Type::Uint(32),
+                        Type::Uint(32).into(),
                     ),
-                    Value::Array(vec![Value::Uint(U256::from(3), 32)], Type::Uint(32)),
+                    Value::Array(vec![Value::Uint(U256::from(3), 32)], Type::Uint(32).into()),
                 ],
-                Type::Array(Box::new(Type::Uint(32))),
+                Type::Array(Box::new(Type::Uint(32))).into(),
             ),
         ];
 
@@ -886,4 +1449,148 @@ mod test {
 
         assert_eq!(encoded, expected);
     }
+
+    #[test]
+    fn value_matches_type() {
+        let minus_one = Value::Int(U256::MAX, 256);
+
+        assert!(minus_one.matches(&Type::Int(8)));
+        assert!(!Value::Int(U256::from(128), 256).matches(&Type::Int(8)));
+        assert!(Value::Int(U256::from(127), 256).matches(&Type::Int(8)));
+        assert!(!Value::Uint(U256::from(256), 256).matches(&Type::Uint(8)));
+        assert!(!minus_one.matches(&Type::Uint(256)));
+
+        let array = Value::FixedArray(
+            vec![Value::Bool(true), Value::Bool(false)],
+            Type::Bool.into(),
+        );
+
+        assert!(array.matches(&Type::FixedArray(Box::new(Type::Bool), 2)));
+        assert!(!array.matches(&Type::FixedArray(Box::new(Type::Bool), 3)));
+        assert!(!array.matches(&Type::FixedArray(Box::new(Type::Address), 2)));
+
+        let tuple = Value::Tuple(vec![
+            ("a".into(), Value::String("x".to_string())),
+            ("b".into(), Value::Bytes(vec![].into())),
+        ]);
+
+        assert!(tuple.matches(&Type::Struct(
+            "S".into(),
+            vec![("".into(), Type::String), ("".into(), Type::Bytes)]
+        )));
+        assert!(!tuple.matches(&Type::Tuple(vec![("".into(), Type::String)])));
+
+        assert!(Value::matches_all(
+            &[Value::Bool(true), Value::Uint(U256::one(), 256)],
+            &[Type::Bool, Type::Uint(8)]
+        ));
+        assert!(!Value::matches_all(
+            &[Value::Bool(true)],
+            &[Type::Bool, Type::Bool]
+        ));
+    }
+
+    #[test]
+    fn display_values() {
+        let value = Value::Tuple(vec![
+            (
+                "maker".into(),
+                Value::Address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap()),
+            ),
+            ("amount".into(), Value::Int(U256::MAX - 4, 256)),
+            ("price".into(), Value::Ufixed(U256::from(1_500_000), 128, 6)),
+            ("data".into(), Value::Bytes(vec![0xde, 0xad].into())),
+            ("memo".into(), Value::String("say \"hi\"".to_string())),
+            (
+                "ids".into(),
+                Value::Array(
+                    vec![Value::Uint(U256::from(1), 8), Value::Uint(U256::from(2), 8)],
+                    Type::Uint(8).into(),
+                ),
+            ),
+            ("empty".into(), Value::Array(vec![], Type::Bool.into())),
+        ]);
+
+        assert_eq!(
+            value.to_string(),
+            r#"(0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed, -5, 1.5, 0xdead, "say \"hi\"", [1, 2], [])"#
+        );
+
+        assert_eq!(
+            format!("{:#}", value),
+            r#"(
+    maker: 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed,
+    amount: -5,
+    price: 1.5,
+    data: 0xdead,
+    memo: "say \"hi\"",
+    ids: [
+        1,
+        2,
+    ],
+    empty: [],
+)"#
+        );
+
+        assert_eq!(Value::Fixed(U256::from(5), 128, 2).to_string(), "0.05");
+        assert_eq!(Value::Fixed(U256::MAX, 128, 2).to_string(), "-0.01");
+    }
+
+    #[test]
+    fn serialize_values() {
+        let value = Value::Tuple(vec![
+            (
+                "maker".into(),
+                Value::Address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap()),
+            ),
+            ("".into(), Value::Int(U256::MAX, 256)),
+            (
+                "amounts".into(),
+                Value::Array(vec![Value::Uint(U256::MAX, 256)], Type::Uint(256).into()),
+            ),
+            ("data".into(), Value::FixedBytes(vec![0xab, 0xcd])),
+            ("ok".into(), Value::Bool(true)),
+            ("note".into(), Value::String("hi".to_string())),
+        ]);
+
+        assert_eq!(
+            serde_json::to_value(&value).unwrap(),
+            serde_json::json!({
+                "maker": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+                "1": "-1",
+                "amounts": [
+                    "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+                ],
+                "data": "0xabcd",
+                "ok": true,
+                "note": "hi"
+            })
+        );
+    }
+
+    #[test]
+    fn value_paths() {
+        let uint = |v: u64| Value::Uint(U256::from(v), 256);
+
+        let value = Value::Array(
+            vec![Value::Tuple(vec![
+                ("maker".into(), uint(1)),
+                (
+                    "legs".into(),
+                    Value::FixedArray(vec![uint(2), uint(3)], Type::Uint(256).into()),
+                ),
+                ("".into(), uint(4)),
+            ])],
+            Type::Tuple(vec![]).into(),
+        );
+
+        assert_eq!(value.get_path("[0].maker"), Some(&uint(1)));
+        assert_eq!(value.get_path("[0].legs[1]"), Some(&uint(3)));
+        assert_eq!(value.get_path("[0][2]"), Some(&uint(4)));
+        assert_eq!(value.get_path(""), Some(&value));
+        assert_eq!(value.get_path("[1].maker"), None);
+        assert_eq!(value.get_path("[0].taker"), None);
+        assert_eq!(value.get_path("[0]..maker"), None);
+        assert_eq!(value.get_path("[0].legs[x]"), None);
+    }
 }
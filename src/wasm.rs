@@ -0,0 +1,127 @@
+//! JavaScript bindings, built with `wasm-bindgen`.
+//!
+//! ABIs are passed as JSON strings and binary data as `0x` prefixed hex
+//! strings. Decoded values are returned as plain objects, with integers as
+//! decimal strings.
+
+use anyhow::Result;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{decode_hex, encode_hex, receipt::parse_hash, Abi};
+
+/// Decodes function calldata against a JSON ABI into `{ function, params }`.
+#[wasm_bindgen(js_name = decodeCalldata)]
+pub fn decode_calldata(abi_json: &str, calldata: &str) -> Result<JsValue, JsError> {
+    to_js(decode_calldata_json(abi_json, calldata))
+}
+
+/// Decodes a log against a JSON ABI into `{ event, params }`.
+#[wasm_bindgen(js_name = decodeLog)]
+pub fn decode_log(abi_json: &str, topics: Vec<String>, data: &str) -> Result<JsValue, JsError> {
+    to_js(decode_log_json(abi_json, &topics, data))
+}
+
+/// Encodes a call of the named function, with arguments given as an array
+/// or an object keyed by parameter name, into hex calldata.
+#[wasm_bindgen(js_name = encodeCall)]
+pub fn encode_call(abi_json: &str, name: &str, args: JsValue) -> Result<String, JsError> {
+    let args: serde_json::Value = serde_wasm_bindgen::from_value(args)?;
+
+    encode_call_json(abi_json, name, &args).map_err(js_error)
+}
+
+fn decode_calldata_json(abi_json: &str, calldata: &str) -> Result<serde_json::Value> {
+    let abi: Abi = serde_json::from_str(abi_json)?;
+
+    let (function, params) = abi.decode_input_from_slice(&decode_hex(calldata)?)?;
+
+    Ok(serde_json::json!({
+        "function": function.signature(),
+        "params": params,
+    }))
+}
+
+fn decode_log_json(abi_json: &str, topics: &[String], data: &str) -> Result<serde_json::Value> {
+    let abi: Abi = serde_json::from_str(abi_json)?;
+
+    let topics = topics
+        .iter()
+        .map(|topic| parse_hash(topic))
+        .collect::<Result<Vec<_>>>()?;
+
+    let (event, params) = abi.decode_log_from_slice(&topics, &decode_hex(data)?)?;
+
+    Ok(serde_json::json!({
+        "event": event.signature(),
+        "params": params,
+    }))
+}
+
+fn encode_call_json(abi_json: &str, name: &str, args: &serde_json::Value) -> Result<String> {
+    let abi: Abi = serde_json::from_str(abi_json)?;
+
+    Ok(encode_hex(abi.encode_call_from_json(name, args)?))
+}
+
+fn to_js(value: Result<serde_json::Value>) -> Result<JsValue, JsError> {
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+
+    Ok(value.map_err(js_error)?.serialize(&serializer)?)
+}
+
+fn js_error(err: anyhow::Error) -> JsError {
+    JsError::new(&format!("{:#}", err))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const ABI: &str = r#"[
+        {"type": "function", "name": "transfer", "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}], "outputs": [{"name": "", "type": "bool"}], "stateMutability": "nonpayable"},
+        {"type": "event", "name": "Transfer", "inputs": [{"name": "from", "type": "address", "indexed": true}, {"name": "to", "type": "address", "indexed": true}, {"name": "value", "type": "uint256", "indexed": false}], "anonymous": false}
+    ]"#;
+
+    #[test]
+    fn wasm_calldata() {
+        let args = serde_json::json!(["0x1111111111111111111111111111111111111111", "5"]);
+        let calldata = encode_call_json(ABI, "transfer", &args).unwrap();
+
+        assert!(calldata.starts_with("0xa9059cbb"));
+
+        let decoded = decode_calldata_json(ABI, &calldata).unwrap();
+        assert_eq!(decoded["function"], "transfer(address,uint256)");
+        assert_eq!(
+            decoded["params"],
+            serde_json::to_value(
+                serde_json::from_str::<Abi>(ABI)
+                    .unwrap()
+                    .decode_input_from_hex(&calldata)
+                    .unwrap()
+                    .1
+            )
+            .unwrap()
+        );
+
+        assert!(decode_calldata_json(ABI, "0x12345678").is_err());
+        assert!(decode_calldata_json("{", &calldata).is_err());
+    }
+
+    #[test]
+    fn wasm_log() {
+        let topics = vec![
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".to_string(),
+            format!("0x{}{}", "0".repeat(24), "11".repeat(20)),
+            format!("0x{}{}", "0".repeat(24), "22".repeat(20)),
+        ];
+        let data = format!("0x{:064x}", 7);
+
+        let decoded = decode_log_json(ABI, &topics, &data).unwrap();
+        assert_eq!(decoded["event"], "Transfer(address,address,uint256)");
+
+        assert!(decode_log_json(ABI, &topics[..1], &data).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! Adapters for the types of the `web3` crate.
+
+use anyhow::Result;
+use ethereum_types::{H160, H256};
+use web3::types::CallRequest;
+
+use crate::{Abi, EventLog, Log, Value};
+
+impl EventLog for web3::types::Log {
+    fn topics(&self) -> &[H256] {
+        &self.topics
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data.0
+    }
+}
+
+impl From<&web3::types::Log> for Log {
+    fn from(log: &web3::types::Log) -> Self {
+        Self {
+            address: log.address,
+            topics: log.topics.clone(),
+            data: log.data.0.clone(),
+            block_number: log.block_number.map(|n| n.as_u64()),
+            block_hash: log.block_hash,
+            transaction_hash: log.transaction_hash,
+            transaction_index: log.transaction_index.map(|i| i.as_u64()),
+            log_index: log.log_index.map(|i| i.low_u64()),
+            removed: log.is_removed(),
+        }
+    }
+}
+
+impl Abi {
+    /// Builds a web3 call request calling the named function of the contract
+    /// at `to`.
+    ///
+    /// Overloads are resolved as in [`Abi::resolve_function`].
+    pub fn call_request(&self, to: H160, name: &str, args: &[Value]) -> Result<CallRequest> {
+        Ok(CallRequest::builder()
+            .to(to)
+            .data(self.encode_call(name, args)?.into())
+            .build())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethereum_types::U256;
+    use pretty_assertions::assert_eq;
+    use web3::types::U64;
+
+    use crate::StateMutability;
+
+    use super::*;
+
+    #[test]
+    fn decode_web3_log() {
+        let abi = Abi::builder()
+            .event(
+                "Transfer",
+                &[("from", "address", true), ("value", "uint256", false)],
+            )
+            .build()
+            .unwrap();
+
+        let log = web3::types::Log {
+            address: H160::repeat_byte(1),
+            topics: vec![abi.events[0].topic(), H160::repeat_byte(2).into()],
+            data: Value::encode(&[Value::Uint(U256::from(7), 256)]).into(),
+            block_hash: None,
+            block_number: Some(U64::from(10)),
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+
+        let (event, params) = abi.decode_log(&log).unwrap();
+        assert_eq!(event.name, "Transfer");
+        assert_eq!(params["value"], Value::Uint(U256::from(7), 256));
+
+        let ours = Log::from(&log);
+        assert_eq!(ours.block_number, Some(10));
+        assert_eq!(abi.decode_log(&ours).unwrap().1, params);
+    }
+
+    #[test]
+    fn web3_call_request() {
+        let abi = Abi::builder()
+            .function(
+                "balanceOf",
+                &[("owner", "address")],
+                &[("", "uint256")],
+                StateMutability::View,
+            )
+            .build()
+            .unwrap();
+        let args = [Value::Address(H160::repeat_byte(2))];
+
+        let request = abi
+            .call_request(H160::repeat_byte(1), "balanceOf", &args)
+            .unwrap();
+
+        assert_eq!(request.to, Some(H160::repeat_byte(1)));
+        assert_eq!(
+            request.data.unwrap().0,
+            abi.encode_call("balanceOf", &args).unwrap()
+        );
+    }
+}
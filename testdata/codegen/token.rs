@@ -0,0 +1,147 @@
+// Generated by `ethereum_abi::codegen` from `token.json`. Do not edit.
+
+#[allow(unused_imports)]
+use ::ethereum_abi::{
+    Abi, EventLog, Value, I256,
+    __private::{
+        anyhow::{anyhow, Result},
+        ethereum_types::{H160, H256, U256},
+    },
+};
+
+/// The contract ABI, parsed on first use.
+pub static ABI: ::std::sync::LazyLock<Abi> = ::std::sync::LazyLock::new(|| {
+    ::ethereum_abi::__private::serde_json::from_str(ABI_JSON).expect("generated ABI is valid")
+});
+
+/// The contract ABI as JSON.
+pub const ABI_JSON: &str = "[{\"inputs\":[{\"name\":\"supply\",\"type\":\"uint256\"}],\"stateMutability\":\"nonpayable\",\"type\":\"constructor\"},{\"inputs\":[{\"name\":\"to\",\"type\":\"address\"},{\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"transfer\",\"outputs\":[{\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"name\":\"to\",\"type\":\"address\"},{\"internalType\":\"Amount\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"mint\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"name\":\"recipients\",\"type\":\"address[]\"},{\"internalType\":\"Amount[]\",\"name\":\"amounts\",\"type\":\"uint256[]\"},{\"name\":\"tag\",\"type\":\"bytes2\"}],\"name\":\"mint\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"info\",\"outputs\":[{\"name\":\"decimals\",\"type\":\"uint8\"},{\"name\":\"offset\",\"type\":\"int16\"}],\"stateMutability\":\"pure\",\"type\":\"function\"},{\"inputs\":[{\"components\":[{\"name\":\"cap\",\"type\":\"uint256\"},{\"name\":\"paused\",\"type\":\"bool\"}],\"name\":\"config\",\"type\":\"tuple\"}],\"name\":\"configure\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"name\":\"from\",\"type\":\"address\"},{\"indexed\":true,\"name\":\"to\",\"type\":\"address\"},{\"indexed\":false,\"internalType\":\"Amount\",\"name\":\"value\",\"type\":\"uint256\"}],\"name\":\"Transfer\",\"type\":\"event\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"name\":\"memo\",\"type\":\"string\"},{\"components\":[{\"name\":\"a\",\"type\":\"bool\"},{\"name\":\"b\",\"type\":\"string\"}],\"indexed\":false,\"name\":\"data\",\"type\":\"tuple\"}],\"name\":\"Note\",\"type\":\"event\"},{\"inputs\":[{\"name\":\"available\",\"type\":\"uint256\"},{\"name\":\"required\",\"type\":\"uint256\"}],\"name\":\"InsufficientBalance\",\"type\":\"error\"}]";
+
+/// Selector of `transfer(address,uint256)`.
+pub const TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+
+/// Encodes a call to `transfer(address,uint256)`.
+pub fn encode_transfer(to: H160, amount: U256) -> Result<Vec<u8>> {
+    ABI.functions[0].encode_input(&[
+        Value::Address(to),
+        Value::Uint(amount, 256),
+    ])
+}
+
+/// Decodes the data returned by `transfer(address,uint256)`.
+pub fn decode_transfer_output(output: &[u8]) -> Result<bool> {
+    let params = ABI.functions[0].decode_output_from_slice(output)?;
+
+    <bool as ::std::convert::TryFrom<Value>>::try_from(params[0].clone())
+}
+
+/// Selector of `mint(address,uint256)`.
+pub const MINT_40C10F19_SELECTOR: [u8; 4] = [0x40, 0xc1, 0x0f, 0x19];
+
+/// Encodes a call to `mint(address,uint256)`.
+///
+/// `amount` is declared as `Amount`.
+pub fn encode_mint_40c10f19(to: H160, amount: U256) -> Result<Vec<u8>> {
+    ABI.functions[1].encode_input(&[
+        Value::Address(to),
+        Value::Uint(amount, 256),
+    ])
+}
+
+/// Selector of `mint(address[],uint256[],bytes2)`.
+pub const MINT_5487EBBF_SELECTOR: [u8; 4] = [0x54, 0x87, 0xeb, 0xbf];
+
+/// Encodes a call to `mint(address[],uint256[],bytes2)`.
+///
+/// `amounts` is declared as `Amount[]`.
+pub fn encode_mint_5487ebbf(recipients: Vec<H160>, amounts: Vec<U256>, tag: [u8; 2]) -> Result<Vec<u8>> {
+    ABI.functions[2].encode_input(&[
+        Value::from(recipients.into_iter().map(Value::Address).collect::<Vec<Value>>()),
+        Value::from(amounts.into_iter().map(|element| Value::Uint(element, 256)).collect::<Vec<Value>>()),
+        Value::FixedBytes(tag.to_vec()),
+    ])
+}
+
+/// Selector of `info()`.
+pub const INFO_SELECTOR: [u8; 4] = [0x37, 0x01, 0x58, 0xea];
+
+/// Encodes a call to `info()`.
+pub fn encode_info() -> Result<Vec<u8>> {
+    ABI.functions[3].encode_input(&[])
+}
+
+/// Decodes the data returned by `info()`.
+pub fn decode_info_output(output: &[u8]) -> Result<(U256, I256)> {
+    let params = ABI.functions[3].decode_output_from_slice(output)?;
+
+    Ok((
+        <U256 as ::std::convert::TryFrom<Value>>::try_from(params[0].clone())?,
+        <I256 as ::std::convert::TryFrom<Value>>::try_from(params[1].clone())?,
+    ))
+}
+
+/// Selector of `configure((uint256,bool))`.
+pub const CONFIGURE_SELECTOR: [u8; 4] = [0xf3, 0x13, 0xa2, 0xfc];
+
+/// Encodes a call to `configure((uint256,bool))`.
+pub fn encode_configure(config: Value) -> Result<Vec<u8>> {
+    ABI.functions[4].encode_input(&[config])
+}
+
+/// `Transfer(address,address,uint256)` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferEvent {
+    pub from: H160,
+    pub to: H160,
+    /// Declared as `Amount`.
+    pub value: U256,
+}
+
+impl TransferEvent {
+    /// Topic of `Transfer(address,address,uint256)`.
+    pub const TOPIC: H256 = H256([0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa, 0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef]);
+
+    /// Decodes the event from a log.
+    pub fn decode<L: EventLog + ?Sized>(log: &L) -> Result<Self> {
+        if log.topics().first() != Some(&Self::TOPIC) {
+            return Err(anyhow!("not a Transfer event"));
+        }
+
+        let params = ABI.events[0].decode_data_from_slice(log.topics(), log.data())?;
+
+        Ok(Self {
+            from: <H160 as ::std::convert::TryFrom<Value>>::try_from(params[0].clone())?,
+            to: <H160 as ::std::convert::TryFrom<Value>>::try_from(params[1].clone())?,
+            value: <U256 as ::std::convert::TryFrom<Value>>::try_from(params[2].clone())?,
+        })
+    }
+}
+
+/// `Note(string,(bool,string))` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteEvent {
+    pub memo: H256,
+    pub data: Value,
+}
+
+impl NoteEvent {
+    /// Topic of `Note(string,(bool,string))`.
+    pub const TOPIC: H256 = H256([0xf1, 0xd9, 0x71, 0x90, 0x4a, 0xfa, 0xc5, 0x9b, 0x75, 0xcd, 0x4d, 0x8f, 0xd2, 0x2a, 0xd5, 0xaf, 0x8e, 0x94, 0xf4, 0x29, 0xc6, 0x1b, 0xa4, 0xd4, 0xf2, 0xa4, 0x66, 0x33, 0x3a, 0x49, 0x21, 0x25]);
+
+    /// Decodes the event from a log.
+    pub fn decode<L: EventLog + ?Sized>(log: &L) -> Result<Self> {
+        if log.topics().first() != Some(&Self::TOPIC) {
+            return Err(anyhow!("not a Note event"));
+        }
+
+        let params = ABI.events[1].decode_data_from_slice(log.topics(), log.data())?;
+
+        Ok(Self {
+            memo: <H256 as ::std::convert::TryFrom<Value>>::try_from(params[0].clone())?,
+            data: params[1].clone(),
+        })
+    }
+}
+
+/// Selector of `InsufficientBalance(uint256,uint256)`.
+pub const INSUFFICIENT_BALANCE_ERROR_SELECTOR: [u8; 4] = [0xcf, 0x47, 0x91, 0x81];